@@ -1,4 +1,11 @@
+use erm::diff::{DiagramDiff, DiffOptions};
 use erm::dtos::diagram::Diagram;
+use erm::importers::dbml::DbmlImport;
+use erm::importers::ddl::{DdlImport, Dialect};
+use erm::importers::prisma::PrismaImport;
+use erm::layout::LayoutOptions;
+use erm::naming::NamingConfig;
+use erm::validation::Diagnostic;
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -6,6 +13,68 @@ fn load_diagram(filename: &str) -> Result<Diagram, String> {
     erm::open(&filename).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+fn diff_diagrams(
+    old_filename: &str,
+    new_filename: &str,
+    include_visual: Option<bool>,
+) -> Result<DiagramDiff, String> {
+    let options = DiffOptions {
+        include_visual: include_visual.unwrap_or(false),
+    };
+    erm::diff_files(old_filename, new_filename, &options).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn import_sqlite(filename: &str) -> Result<Diagram, String> {
+    erm::importers::sqlite::import(filename).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn import_database(url: &str) -> Result<Diagram, String> {
+    erm::importers::import_database(url).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn import_ddl(filename: &str, dialect: Dialect) -> Result<DdlImport, String> {
+    erm::importers::ddl::import(filename, dialect).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn import_dbml(filename: &str) -> Result<DbmlImport, String> {
+    erm::importers::dbml::import(filename).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn import_prisma(filename: &str) -> Result<PrismaImport, String> {
+    erm::importers::prisma::import(filename).map_err(|e| e.to_string())
+}
+
+/// Validates the diagram being edited, including the naming conventions next to
+/// `filename` once the diagram has been saved. Nothing is reported while the validator
+/// is suspended in the diagram settings.
+#[tauri::command]
+fn validate_diagram(diagram: Diagram, filename: Option<&str>) -> Result<Vec<Diagnostic>, String> {
+    if diagram.diagram_settings.suspend_validator.unwrap_or(false) {
+        return Ok(Vec::new());
+    }
+    let mut diagnostics = erm::validation::validate(&diagram);
+    if let Some(filename) = filename {
+        if let Some(config) = NamingConfig::find(filename).map_err(|e| e.to_string())? {
+            diagnostics.extend(erm::naming::check(&diagram, &config));
+        }
+    }
+    Ok(diagnostics)
+}
+
+/// Arranges the tables of the diagram being edited, or of `options.selection`, and
+/// returns the rearranged diagram.
+#[tauri::command]
+fn layout_diagram(mut diagram: Diagram, options: LayoutOptions) -> Result<Diagram, String> {
+    erm::layout::layout(&mut diagram, &options).map_err(|e| e.to_string())?;
+    Ok(diagram)
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -13,7 +82,17 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_os::init())
-        .invoke_handler(tauri::generate_handler![load_diagram])
+        .invoke_handler(tauri::generate_handler![
+            load_diagram,
+            diff_diagrams,
+            import_sqlite,
+            import_database,
+            import_ddl,
+            import_dbml,
+            import_prisma,
+            validate_diagram,
+            layout_diagram
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -0,0 +1,489 @@
+use crate::dtos::column_groups;
+use crate::dtos::diagram_walkers::NormalColumn;
+
+/// Column types known to ERFlute, mirroring `ColumnType` of the frontend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColumnType {
+    Char,
+    CharN,
+    VarCharN,
+    Text,
+    TinyText,
+    MediumText,
+    LongText,
+    Int,
+    IntN,
+    TinyInt,
+    TinyIntN,
+    SmallInt,
+    SmallIntN,
+    MediumInt,
+    MediumIntN,
+    BigInt,
+    BigIntN,
+    Date,
+    Datetime,
+    Boolean,
+    Binary,
+    BinaryN,
+    VarBinaryN,
+    BitN,
+    Blob,
+    TinyBlob,
+    MediumBlob,
+    LongBlob,
+    Decimal,
+    DecimalP,
+    DecimalPS,
+    Double,
+    DoubleMD,
+    Enum,
+    Float,
+    FloatMD,
+    FloatP,
+    Geometry,
+    Json,
+    Numeric,
+    NumericP,
+    NumericPS,
+    Real,
+    RealMD,
+    Set,
+    Time,
+    Timestamp,
+    Year2,
+    Year4,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnTypeConfig {
+    pub label: &'static str,
+    pub label_without_args: &'static str,
+    pub supports_length: bool,
+    pub supports_decimal: bool,
+    pub supports_enum_args: bool,
+    pub supports_unsigned: bool,
+}
+
+impl ColumnType {
+    pub const ALL: [ColumnType; 49] = [
+        ColumnType::Char,
+        ColumnType::CharN,
+        ColumnType::VarCharN,
+        ColumnType::Text,
+        ColumnType::TinyText,
+        ColumnType::MediumText,
+        ColumnType::LongText,
+        ColumnType::Int,
+        ColumnType::IntN,
+        ColumnType::TinyInt,
+        ColumnType::TinyIntN,
+        ColumnType::SmallInt,
+        ColumnType::SmallIntN,
+        ColumnType::MediumInt,
+        ColumnType::MediumIntN,
+        ColumnType::BigInt,
+        ColumnType::BigIntN,
+        ColumnType::Date,
+        ColumnType::Datetime,
+        ColumnType::Boolean,
+        ColumnType::Binary,
+        ColumnType::BinaryN,
+        ColumnType::VarBinaryN,
+        ColumnType::BitN,
+        ColumnType::Blob,
+        ColumnType::TinyBlob,
+        ColumnType::MediumBlob,
+        ColumnType::LongBlob,
+        ColumnType::Decimal,
+        ColumnType::DecimalP,
+        ColumnType::DecimalPS,
+        ColumnType::Double,
+        ColumnType::DoubleMD,
+        ColumnType::Enum,
+        ColumnType::Float,
+        ColumnType::FloatMD,
+        ColumnType::FloatP,
+        ColumnType::Geometry,
+        ColumnType::Json,
+        ColumnType::Numeric,
+        ColumnType::NumericP,
+        ColumnType::NumericPS,
+        ColumnType::Real,
+        ColumnType::RealMD,
+        ColumnType::Set,
+        ColumnType::Time,
+        ColumnType::Timestamp,
+        ColumnType::Year2,
+        ColumnType::Year4,
+    ];
+
+    /// The value stored in the `<type>` element of an .erm file.
+    pub fn key(self) -> &'static str {
+        match self {
+            ColumnType::Char => "char",
+            ColumnType::CharN => "character(n)",
+            ColumnType::VarCharN => "varchar(n)",
+            ColumnType::Text => "text",
+            ColumnType::TinyText => "tinytext",
+            ColumnType::MediumText => "mediumtext",
+            ColumnType::LongText => "longtext",
+            ColumnType::Int => "integer",
+            ColumnType::IntN => "int(n)",
+            ColumnType::TinyInt => "tinyint",
+            ColumnType::TinyIntN => "tinyint(n)",
+            ColumnType::SmallInt => "smallint",
+            ColumnType::SmallIntN => "smallint(n)",
+            ColumnType::MediumInt => "mediumint",
+            ColumnType::MediumIntN => "mediumint(n)",
+            ColumnType::BigInt => "bigint",
+            ColumnType::BigIntN => "bigint(n)",
+            ColumnType::Date => "date",
+            ColumnType::Datetime => "datetime",
+            ColumnType::Boolean => "boolean",
+            ColumnType::Binary => "binary1",
+            ColumnType::BinaryN => "binary(n)",
+            ColumnType::VarBinaryN => "varbinary(n)",
+            ColumnType::BitN => "bit(n)",
+            ColumnType::Blob => "blob",
+            ColumnType::TinyBlob => "tinyblob",
+            ColumnType::MediumBlob => "mediumblob",
+            ColumnType::LongBlob => "longblob",
+            ColumnType::Decimal => "decimal",
+            ColumnType::DecimalP => "decimal(p)",
+            ColumnType::DecimalPS => "decimal(p,s)",
+            ColumnType::Double => "double",
+            ColumnType::DoubleMD => "double precision(m,d)",
+            ColumnType::Enum => "enum",
+            ColumnType::Float => "float",
+            ColumnType::FloatMD => "float(m,d)",
+            ColumnType::FloatP => "float(p)",
+            ColumnType::Geometry => "geometry",
+            ColumnType::Json => "json",
+            ColumnType::Numeric => "numeric",
+            ColumnType::NumericP => "numeric(p)",
+            ColumnType::NumericPS => "numeric(p,s)",
+            ColumnType::Real => "real",
+            ColumnType::RealMD => "real(m,d)",
+            ColumnType::Set => "set",
+            ColumnType::Time => "time",
+            ColumnType::Timestamp => "timestamp",
+            ColumnType::Year2 => "year(2)",
+            ColumnType::Year4 => "year(4)",
+        }
+    }
+
+    pub fn config(self) -> ColumnTypeConfig {
+        let (
+            label,
+            label_without_args,
+            supports_length,
+            supports_decimal,
+            supports_enum_args,
+            supports_unsigned,
+        ) = match self {
+            ColumnType::Char => ("char", "char", false, false, false, false),
+            ColumnType::CharN => ("char(n)", "char", true, false, false, false),
+            ColumnType::VarCharN => ("varchar(n)", "varchar", true, false, false, false),
+            ColumnType::Text => ("text", "text", false, false, false, false),
+            ColumnType::TinyText => ("tinytext", "tinytext", false, false, false, false),
+            ColumnType::MediumText => ("mediumtext", "mediumtext", false, false, false, false),
+            ColumnType::LongText => ("longtext", "longtext", false, false, false, false),
+            ColumnType::Int => ("int", "int", false, false, false, true),
+            ColumnType::IntN => ("int(n)", "int", true, false, false, true),
+            ColumnType::TinyInt => ("tinyint", "tinyint", false, false, false, true),
+            ColumnType::TinyIntN => ("tinyint(n)", "tinyint", true, false, false, true),
+            ColumnType::SmallInt => ("smallint", "smallint", false, false, false, true),
+            ColumnType::SmallIntN => ("smallint(n)", "smallint", true, false, false, true),
+            ColumnType::MediumInt => ("mediumint", "mediumint", false, false, false, true),
+            ColumnType::MediumIntN => ("mediumint(n)", "mediumint", true, false, false, true),
+            ColumnType::BigInt => ("bigint", "bigint", false, false, false, true),
+            ColumnType::BigIntN => ("bigint(n)", "bigint", true, false, false, true),
+            ColumnType::Date => ("date", "date", false, false, false, true),
+            ColumnType::Datetime => ("datetime", "datetime", false, false, false, false),
+            ColumnType::Boolean => ("boolean", "boolean", false, false, false, false),
+            ColumnType::Binary => ("binary", "binary", false, false, false, false),
+            ColumnType::BinaryN => ("binary(n)", "binary", true, false, false, false),
+            ColumnType::VarBinaryN => ("varbinary(n)", "varbinary", true, false, false, false),
+            ColumnType::BitN => ("bit(n)", "bit", true, false, false, false),
+            ColumnType::Blob => ("blob", "blob", false, false, false, false),
+            ColumnType::TinyBlob => ("tinyblob", "tinyblob", false, false, false, false),
+            ColumnType::MediumBlob => ("mediumblob", "mediumblob", false, false, false, false),
+            ColumnType::LongBlob => ("longblob", "longblob", false, false, false, false),
+            ColumnType::Decimal => ("decimal", "decimal", false, false, false, true),
+            ColumnType::DecimalP => ("decimal(p)", "decimal", true, false, false, true),
+            ColumnType::DecimalPS => ("decimal(p,s)", "decimal", true, true, false, true),
+            ColumnType::Double => ("double", "double", false, false, false, true),
+            ColumnType::DoubleMD => ("double(m,d)", "double", true, true, false, true),
+            ColumnType::Enum => ("enum", "enum", false, false, true, false),
+            ColumnType::Float => ("float", "float", false, false, false, true),
+            ColumnType::FloatMD => ("float(m,d)", "float", true, true, false, true),
+            ColumnType::FloatP => ("float(p)", "float", true, false, false, true),
+            ColumnType::Geometry => ("geometry", "geometry", false, false, false, false),
+            ColumnType::Json => ("json", "json", false, false, false, false),
+            ColumnType::Numeric => ("numeric", "numeric", false, false, false, true),
+            ColumnType::NumericP => ("numeric(p)", "numeric", true, false, false, true),
+            ColumnType::NumericPS => ("numeric(p,s)", "numeric", true, true, false, true),
+            ColumnType::Real => ("real", "real", false, false, false, true),
+            ColumnType::RealMD => ("real(m,d)", "real", true, true, false, true),
+            ColumnType::Set => ("set", "set", false, false, false, false),
+            ColumnType::Time => ("time", "time", false, false, false, false),
+            ColumnType::Timestamp => ("timestamp", "timestamp", false, false, false, false),
+            ColumnType::Year2 => ("year(2)", "year(2)", false, false, false, true),
+            ColumnType::Year4 => ("year(4)", "year(4)", false, false, false, true),
+        };
+        ColumnTypeConfig {
+            label,
+            label_without_args,
+            supports_length,
+            supports_decimal,
+            supports_enum_args,
+            supports_unsigned,
+        }
+    }
+
+    /// Parses either the key stored in .erm files or the display label, like
+    /// `parseColumnType` of the frontend.
+    pub fn parse(value: &str) -> Option<ColumnType> {
+        Self::ALL
+            .into_iter()
+            .find(|column_type| column_type.key() == value)
+            .or_else(|| {
+                Self::ALL
+                    .into_iter()
+                    .find(|column_type| column_type.config().label == value)
+            })
+    }
+}
+
+/// Splits the arguments of an `enum`, e.g. `'draft', 'published'`, into its values.
+/// Quotes are optional; a doubled quote stands for a quote within a value.
+pub fn enum_values(args: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut value = String::new();
+    let mut quoted = false;
+    let mut chars = args.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' if quoted && chars.peek() == Some(&'\'') => {
+                chars.next();
+                value.push('\'');
+            }
+            '\'' => quoted = !quoted,
+            ',' if !quoted => values.push(std::mem::take(&mut value)),
+            _ => value.push(c),
+        }
+    }
+    values.push(value);
+    values
+        .into_iter()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+fn format(
+    column_type: &str,
+    length: Option<u16>,
+    decimal: Option<u16>,
+    unsigned: Option<bool>,
+) -> String {
+    let without_unsigned = match ColumnType::parse(column_type).map(ColumnType::config) {
+        // As in the frontend, a type that supports decimal always supports length too.
+        Some(config) if config.supports_length && config.supports_decimal => format!(
+            "{}({}, {})",
+            config.label_without_args,
+            length.unwrap_or(0),
+            decimal.unwrap_or(0)
+        ),
+        Some(config) if config.supports_length => {
+            format!("{}({})", config.label_without_args, length.unwrap_or(0))
+        }
+        Some(config) => config.label.to_string(),
+        None => column_type.to_string(),
+    };
+    if unsigned.unwrap_or(false) {
+        format!("{without_unsigned} unsigned")
+    } else {
+        without_unsigned
+    }
+}
+
+/// Formats the type of a column the same way as `formatColumnType` of the frontend,
+/// e.g. `varchar(32)` or `bigint unsigned`. Types unknown to ERFlute are kept as is.
+pub fn format_column_type(column: &NormalColumn) -> String {
+    match &column.column_type {
+        Some(column_type) => format(column_type, column.length, column.decimal, column.unsigned),
+        None => String::new(),
+    }
+}
+
+/// Quotes the values of an enum, e.g. `'draft', 'published'`, as [`NormalColumn::args`]
+/// keeps them.
+pub fn quote_values(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|value| format!("'{}'", value.replace('\'', "''")))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Formats the type of a column as declared in SQL: like [`format_column_type`], with
+/// the values of an enum, e.g. `enum('draft', 'published')`.
+pub fn format_sql_type(column: &NormalColumn) -> String {
+    let config = column
+        .column_type
+        .as_deref()
+        .and_then(ColumnType::parse)
+        .map(ColumnType::config);
+    let values = column.args.as_deref().map(enum_values).unwrap_or_default();
+    match config {
+        Some(config) if config.supports_enum_args && !values.is_empty() => {
+            format!("{}({})", config.label_without_args, quote_values(&values))
+        }
+        _ => format_column_type(column),
+    }
+}
+
+pub fn format_group_column_type(column: &column_groups::NormalColumn) -> String {
+    format(
+        &column.column_type,
+        column.length,
+        column.decimal,
+        column.unsigned,
+    )
+}
+
+/// A column type declared in SQL, mapped onto the ERFlute type catalogue.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SqlType {
+    /// The ERFlute type key, or the lowercased SQL type when it has no counterpart.
+    pub column_type: Option<String>,
+    pub length: Option<u16>,
+    pub decimal: Option<u16>,
+    pub unsigned: bool,
+
+    /// Set for types that imply a sequence, such as PostgreSQL's `serial`.
+    pub auto_increment: bool,
+
+    /// The values of an enum or a set, e.g. `'draft', 'published'`.
+    pub args: Option<String>,
+}
+
+fn canonical_type_name(name: &str) -> (&str, bool) {
+    match name {
+        "integer" | "int4" => ("int", false),
+        "int2" => ("smallint", false),
+        "int8" => ("bigint", false),
+        "serial" | "serial4" => ("int", true),
+        "smallserial" | "serial2" => ("smallint", true),
+        "bigserial" | "serial8" => ("bigint", true),
+        "character varying" | "nvarchar" | "varchar2" | "nvarchar2" => ("varchar", false),
+        "character" | "nchar" | "bpchar" => ("char", false),
+        "bool" => ("boolean", false),
+        "double precision" | "float8" => ("double", false),
+        "float4" => ("real", false),
+        "dec" => ("decimal", false),
+        "jsonb" => ("json", false),
+        "bytea" => ("blob", false),
+        "clob" => ("text", false),
+        "timestamptz" | "timestamp with time zone" | "timestamp without time zone" => {
+            ("timestamp", false)
+        }
+        "timetz" | "time with time zone" | "time without time zone" => ("time", false),
+        _ => (name, false),
+    }
+}
+
+/// The values between the parentheses of `enum(...)`, as [`format_sql_type`] writes
+/// them. The values keep their case, unlike the rest of the type.
+fn enum_args(sql: &str) -> String {
+    let args = match (sql.find('('), sql.rfind(')')) {
+        (Some(start), Some(end)) if start < end => &sql[start + 1..end],
+        _ => "",
+    };
+    quote_values(&enum_values(args))
+}
+
+/// Parses a declared SQL type such as `VARCHAR(32)`, `decimal(10, 2)`,
+/// `bigint unsigned` or `ENUM('draft', 'published')`. Arguments the matching ERFlute
+/// type cannot hold, such as the precision of `datetime(6)`, are dropped.
+pub fn parse_sql_type(sql: &str) -> SqlType {
+    let normalized = sql.trim().to_lowercase();
+    let mut words: Vec<&str> = normalized.split_whitespace().collect();
+    let unsigned = words.contains(&"unsigned");
+    words.retain(|word| *word != "unsigned" && *word != "zerofill" && *word != "signed");
+    let normalized = words.join(" ");
+
+    let (name, args) = match normalized.split_once('(') {
+        Some((name, rest)) => {
+            let (args, suffix) = rest.split_once(')').unwrap_or((rest, ""));
+            (
+                format!("{} {}", name.trim(), suffix.trim()),
+                args.to_string(),
+            )
+        }
+        None => (normalized.clone(), String::new()),
+    };
+    let name = name.trim();
+    if name.is_empty() {
+        return SqlType::default();
+    }
+    let (name, auto_increment) = canonical_type_name(name);
+    let args: Vec<u16> = args
+        .split(',')
+        .filter_map(|arg| arg.trim().parse().ok())
+        .collect();
+
+    if name == "year" {
+        let column_type = match args.first() {
+            Some(2) => ColumnType::Year2,
+            _ => ColumnType::Year4,
+        };
+        return SqlType {
+            column_type: Some(column_type.key().to_string()),
+            unsigned,
+            ..Default::default()
+        };
+    }
+
+    let candidates: Vec<ColumnType> = ColumnType::ALL
+        .into_iter()
+        .filter(|column_type| column_type.config().label_without_args == name)
+        .collect();
+    let wanted_length = !args.is_empty();
+    let wanted_decimal = args.len() > 1;
+    let matched = candidates
+        .iter()
+        .find(|column_type| {
+            let config = column_type.config();
+            config.supports_length == wanted_length && config.supports_decimal == wanted_decimal
+        })
+        .or_else(|| {
+            candidates
+                .iter()
+                .find(|column_type| column_type.config().supports_length == wanted_length)
+        })
+        .or_else(|| candidates.first());
+
+    match matched {
+        Some(column_type) => {
+            let config = column_type.config();
+            SqlType {
+                column_type: Some(column_type.key().to_string()),
+                length: args.first().copied().filter(|_| config.supports_length),
+                decimal: args.get(1).copied().filter(|_| config.supports_decimal),
+                unsigned: unsigned && config.supports_unsigned,
+                auto_increment,
+                args: Some(enum_args(sql))
+                    .filter(|args| config.supports_enum_args && !args.is_empty()),
+            }
+        }
+        None => SqlType {
+            column_type: Some(normalized),
+            unsigned,
+            auto_increment,
+            ..Default::default()
+        },
+    }
+}
@@ -0,0 +1,149 @@
+//! Import, export and use of the word dictionary mapping logical names to physical
+//! names, e.g. 会員 to `MEMBER`.
+//!
+//! The dictionary is stored in the `<dictionary>` section of the .erm file, as ERFlute
+//! does, and can be exchanged as CSV with the columns `logical_name`, `physical_name`
+//! and optionally `description`.
+
+use crate::dtos::diagram::Diagram;
+use crate::dtos::diagram_walkers::Column;
+use crate::dtos::dictionary::{Dictionary, Word};
+use crate::validation::Location;
+use serde::{Deserialize, Serialize};
+
+const CSV_HEADER: [&str; 3] = ["logical_name", "physical_name", "description"];
+
+/// Reads a dictionary from CSV. The header row is optional.
+pub fn parse_csv(text: &str) -> Result<Dictionary, Box<dyn std::error::Error>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let mut dictionary = Dictionary::default();
+    for (i, record) in reader.records().enumerate() {
+        let record = record?;
+        if i == 0 && record.get(0) == Some(CSV_HEADER[0]) {
+            continue;
+        }
+        let (Some(logical_name), Some(physical_name)) = (record.get(0), record.get(1)) else {
+            return Err(format!("line {}: expected logical and physical names", i + 1).into());
+        };
+        dictionary.insert(Word {
+            physical_name: physical_name.trim().to_string(),
+            logical_name: logical_name.trim().to_string(),
+            description: record
+                .get(2)
+                .filter(|description| !description.is_empty())
+                .map(str::to_string),
+        });
+    }
+    Ok(dictionary)
+}
+
+pub fn load_csv(filename: &str) -> Result<Dictionary, Box<dyn std::error::Error>> {
+    parse_csv(&std::fs::read_to_string(filename)?)
+}
+
+pub fn to_csv(dictionary: &Dictionary) -> Result<String, Box<dyn std::error::Error>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(CSV_HEADER)?;
+    for word in &dictionary.words {
+        writer.write_record([
+            word.logical_name.as_str(),
+            word.physical_name.as_str(),
+            word.description.as_deref().unwrap_or(""),
+        ])?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+pub fn save_csv(filename: &str, dictionary: &Dictionary) -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::write(filename, to_csv(dictionary)?)?;
+    Ok(())
+}
+
+/// Reads the dictionary section of an ERFlute .erm file.
+pub fn import_erm(filename: &str) -> Result<Dictionary, Box<dyn std::error::Error>> {
+    Ok(crate::open(filename)?.dictionary)
+}
+
+/// Collects the logical and physical names of the columns of the diagram as words, the
+/// way ERFlute lists the words of a diagram. The first column wins when several
+/// columns share a logical name.
+pub fn collect_words(diagram: &Diagram) -> Dictionary {
+    let mut dictionary = Dictionary::default();
+    let mut add = |logical_name: Option<&String>, physical_name: &str| {
+        if let Some(logical_name) = logical_name
+            && dictionary.find_by_logical_name(logical_name).is_none()
+        {
+            dictionary.insert(Word {
+                physical_name: physical_name.to_string(),
+                logical_name: logical_name.clone(),
+                description: None,
+            });
+        }
+    };
+    for table in diagram.tables() {
+        for item in table.column_items() {
+            if let Column::Normal(column) = item {
+                add(column.logical_name.as_ref(), &column.physical_name);
+            }
+        }
+    }
+    for group in diagram.groups() {
+        for column in group.columns.normal_columns.iter().flatten() {
+            add(column.logical_name.as_ref(), &column.physical_name);
+        }
+    }
+    dictionary
+}
+
+/// A column whose physical name differs from the one derived from its logical name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NameMismatch {
+    pub location: Location,
+    pub logical_name: String,
+    pub physical_name: String,
+
+    /// The physical name derived from the dictionary.
+    pub expected: String,
+}
+
+/// Lists the columns whose physical name disagrees with the dictionary. Physical names
+/// are compared case-insensitively, and columns whose logical name cannot be derived
+/// from the dictionary are skipped.
+pub fn find_mismatches(diagram: &Diagram, dictionary: &Dictionary) -> Vec<NameMismatch> {
+    let mismatch = |logical_name: Option<&String>, physical_name: &str, location: Location| {
+        let logical_name = logical_name?;
+        let expected = dictionary.suggest_physical_name(logical_name)?;
+        (!expected.eq_ignore_ascii_case(physical_name)).then(|| NameMismatch {
+            location,
+            logical_name: logical_name.clone(),
+            physical_name: physical_name.to_string(),
+            expected,
+        })
+    };
+    let mut mismatches = Vec::new();
+    for table in diagram.tables() {
+        for item in table.column_items() {
+            if let Column::Normal(column) = item {
+                mismatches.extend(mismatch(
+                    column.logical_name.as_ref(),
+                    &column.physical_name,
+                    Location::column(&table.physical_name, &column.physical_name),
+                ));
+            }
+        }
+    }
+    for group in diagram.groups() {
+        for column in group.columns.normal_columns.iter().flatten() {
+            mismatches.extend(mismatch(
+                column.logical_name.as_ref(),
+                &column.physical_name,
+                Location::group_column(&group.column_group_name, &column.physical_name),
+            ));
+        }
+    }
+    mismatches
+}
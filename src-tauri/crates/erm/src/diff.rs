@@ -0,0 +1,525 @@
+use crate::dtos::column_groups::{self, ColumnGroup};
+use crate::dtos::diagram::Diagram;
+use crate::dtos::diagram_walkers::{Column, NormalColumn, Relationship, Table};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffOptions {
+    /// Also report changes of position, size, color and font, which are ignored by default.
+    pub include_visual: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    pub field: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old_value: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_value: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnDiff {
+    pub physical_name: String,
+    pub kind: ChangeKind,
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelationshipDiff {
+    pub name: String,
+    pub kind: ChangeKind,
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableDiff {
+    pub physical_name: String,
+    pub kind: ChangeKind,
+    pub changes: Vec<FieldChange>,
+    pub columns: Vec<ColumnDiff>,
+    pub relationships: Vec<RelationshipDiff>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnGroupDiff {
+    pub column_group_name: String,
+    pub kind: ChangeKind,
+    pub columns: Vec<ColumnDiff>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagramDiff {
+    pub changes: Vec<FieldChange>,
+    pub tables: Vec<TableDiff>,
+    pub column_groups: Vec<ColumnGroupDiff>,
+}
+
+impl DiagramDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.tables.is_empty() && self.column_groups.is_empty()
+    }
+
+    /// Physical names of the tables that were added, removed or modified.
+    pub fn changed_tables(&self) -> Vec<&str> {
+        self.tables
+            .iter()
+            .map(|table| table.physical_name.as_str())
+            .collect()
+    }
+}
+
+fn marker(kind: ChangeKind) -> char {
+    match kind {
+        ChangeKind::Added => '+',
+        ChangeKind::Removed => '-',
+        ChangeKind::Modified => '~',
+    }
+}
+
+fn write_changes(f: &mut fmt::Formatter<'_>, indent: &str, changes: &[FieldChange]) -> fmt::Result {
+    let value = |value: &Option<String>| value.clone().unwrap_or_else(|| "none".to_string());
+    for change in changes {
+        writeln!(
+            f,
+            "{indent}~ {}: {} -> {}",
+            change.field,
+            value(&change.old_value),
+            value(&change.new_value)
+        )?;
+    }
+    Ok(())
+}
+
+fn write_columns(f: &mut fmt::Formatter<'_>, indent: &str, columns: &[ColumnDiff]) -> fmt::Result {
+    for column in columns {
+        writeln!(
+            f,
+            "{indent}{} column {}",
+            marker(column.kind),
+            column.physical_name
+        )?;
+        write_changes(f, &format!("{indent}    "), &column.changes)?;
+    }
+    Ok(())
+}
+
+/// Formats one line per change, nested by table, e.g. `~ table MEMBERS` followed by
+/// `    + column NICKNAME`.
+impl fmt::Display for DiagramDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_changes(f, "", &self.changes)?;
+        for table in &self.tables {
+            writeln!(f, "{} table {}", marker(table.kind), table.physical_name)?;
+            write_changes(f, "    ", &table.changes)?;
+            write_columns(f, "    ", &table.columns)?;
+            for relationship in &table.relationships {
+                writeln!(
+                    f,
+                    "    {} relationship {}",
+                    marker(relationship.kind),
+                    relationship.name
+                )?;
+                write_changes(f, "        ", &relationship.changes)?;
+            }
+        }
+        for group in &self.column_groups {
+            writeln!(
+                f,
+                "{} column group {}",
+                marker(group.kind),
+                group.column_group_name
+            )?;
+            write_columns(f, "    ", &group.columns)?;
+        }
+        Ok(())
+    }
+}
+
+struct Changes(Vec<FieldChange>);
+
+impl Changes {
+    fn compare<T: PartialEq + Display>(&mut self, field: &str, old: &T, new: &T) {
+        if old != new {
+            self.0.push(FieldChange {
+                field: field.to_string(),
+                old_value: Some(old.to_string()),
+                new_value: Some(new.to_string()),
+            });
+        }
+    }
+
+    fn compare_option<T: PartialEq + Display>(
+        &mut self,
+        field: &str,
+        old: &Option<T>,
+        new: &Option<T>,
+    ) {
+        if old != new {
+            self.0.push(FieldChange {
+                field: field.to_string(),
+                old_value: old.as_ref().map(ToString::to_string),
+                new_value: new.as_ref().map(ToString::to_string),
+            });
+        }
+    }
+}
+
+/// Matches items of `old` and `new` by key, preserving the order of `old` followed by
+/// the items that only exist in `new`.
+fn pair_by_key<'a, T, K: PartialEq>(
+    old: &'a [T],
+    new: &'a [T],
+    key: impl Fn(&T) -> K,
+) -> Vec<(Option<&'a T>, Option<&'a T>)> {
+    let mut pairs: Vec<(Option<&T>, Option<&T>)> = old
+        .iter()
+        .map(|o| (Some(o), new.iter().find(|n| key(n) == key(o))))
+        .collect();
+    pairs.extend(
+        new.iter()
+            .filter(|n| !old.iter().any(|o| key(o) == key(n)))
+            .map(|n| (None, Some(n))),
+    );
+    pairs
+}
+
+fn normal_columns(table: &Table) -> Vec<&NormalColumn> {
+    table
+        .columns
+        .items
+        .iter()
+        .flatten()
+        .filter_map(|column| match column {
+            Column::Normal(column) => Some(column),
+            Column::Group(_) => None,
+        })
+        .collect()
+}
+
+fn group_references(table: &Table) -> String {
+    table
+        .columns
+        .items
+        .iter()
+        .flatten()
+        .filter_map(|column| match column {
+            Column::Group(name) => Some(name.as_str()),
+            Column::Normal(_) => None,
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Indexes and compound unique keys as `(field, description)` pairs, so that each one
+/// is reported as a single field change.
+fn keys(table: &Table) -> Vec<(String, String)> {
+    let indexes = table.index_list().iter().map(|index| {
+        let kind = if index.is_unique() { "UNIQUE " } else { "" };
+        (
+            format!("index.{}", index.name),
+            format!("{kind}({})", index.columns),
+        )
+    });
+    let unique_keys = table.compound_unique_keys().iter().map(|key| {
+        (
+            format!("compoundUniqueKey.{}", key.name),
+            format!("({})", key.columns),
+        )
+    });
+    indexes.chain(unique_keys).collect()
+}
+
+fn diff_normal_column(old: &NormalColumn, new: &NormalColumn) -> Vec<FieldChange> {
+    let mut changes = Changes(Vec::new());
+    changes.compare_option("logicalName", &old.logical_name, &new.logical_name);
+    changes.compare_option("description", &old.description, &new.description);
+    changes.compare_option("columnType", &old.column_type, &new.column_type);
+    changes.compare_option("length", &old.length, &new.length);
+    changes.compare_option("decimal", &old.decimal, &new.decimal);
+    changes.compare_option("unsigned", &old.unsigned, &new.unsigned);
+    changes.compare_option("notNull", &old.not_null, &new.not_null);
+    changes.compare_option("uniqueKey", &old.unique_key, &new.unique_key);
+    changes.compare_option("defaultValue", &old.default_value, &new.default_value);
+    changes.compare_option("primaryKey", &old.primary_key, &new.primary_key);
+    changes.compare_option("autoIncrement", &old.auto_increment, &new.auto_increment);
+    changes.compare_option("referredColumn", &old.referred_column, &new.referred_column);
+    changes.compare_option("relationship", &old.relationship, &new.relationship);
+    changes.0
+}
+
+fn diff_group_column(
+    old: &column_groups::NormalColumn,
+    new: &column_groups::NormalColumn,
+) -> Vec<FieldChange> {
+    let mut changes = Changes(Vec::new());
+    changes.compare_option("logicalName", &old.logical_name, &new.logical_name);
+    changes.compare_option("description", &old.description, &new.description);
+    changes.compare("columnType", &old.column_type, &new.column_type);
+    changes.compare_option("length", &old.length, &new.length);
+    changes.compare_option("decimal", &old.decimal, &new.decimal);
+    changes.compare_option("notNull", &old.not_null, &new.not_null);
+    changes.compare_option("uniqueKey", &old.unique_key, &new.unique_key);
+    changes.compare_option("unsigned", &old.unsigned, &new.unsigned);
+    changes.compare_option("defaultValue", &old.default_value, &new.default_value);
+    changes.0
+}
+
+fn diff_columns<T>(
+    old: &[&T],
+    new: &[&T],
+    name: impl Fn(&T) -> &str,
+    diff_fields: impl Fn(&T, &T) -> Vec<FieldChange>,
+) -> Vec<ColumnDiff> {
+    pair_by_key(old, new, |column| name(column).to_string())
+        .into_iter()
+        .filter_map(|pair| match pair {
+            (Some(old), Some(new)) => {
+                let changes = diff_fields(old, new);
+                (!changes.is_empty()).then(|| ColumnDiff {
+                    physical_name: name(old).to_string(),
+                    kind: ChangeKind::Modified,
+                    changes,
+                })
+            }
+            (Some(old), None) => Some(ColumnDiff {
+                physical_name: name(old).to_string(),
+                kind: ChangeKind::Removed,
+                changes: Vec::new(),
+            }),
+            (None, Some(new)) => Some(ColumnDiff {
+                physical_name: name(new).to_string(),
+                kind: ChangeKind::Added,
+                changes: Vec::new(),
+            }),
+            (None, None) => None,
+        })
+        .collect()
+}
+
+fn diff_relationship(old: &Relationship, new: &Relationship) -> Vec<FieldChange> {
+    let fk_columns = |relationship: &Relationship| {
+        relationship
+            .fk_columns
+            .fk_column
+            .iter()
+            .map(|column| column.fk_column_name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut changes = Changes(Vec::new());
+    changes.compare("source", &old.source, &new.source);
+    changes.compare("target", &old.target, &new.target);
+    changes.compare("fkColumns", &fk_columns(old), &fk_columns(new));
+    changes.compare(
+        "parentCardinality",
+        &old.parent_cardinality,
+        &new.parent_cardinality,
+    );
+    changes.compare(
+        "childCardinality",
+        &old.child_cardinality,
+        &new.child_cardinality,
+    );
+    changes.compare(
+        "referenceForPk",
+        &old.reference_for_pk,
+        &new.reference_for_pk,
+    );
+    changes.compare(
+        "onDeleteAction",
+        &old.on_delete_action,
+        &new.on_delete_action,
+    );
+    changes.compare(
+        "onUpdateAction",
+        &old.on_update_action,
+        &new.on_update_action,
+    );
+    changes.0
+}
+
+fn diff_relationships(old: &[Relationship], new: &[Relationship]) -> Vec<RelationshipDiff> {
+    pair_by_key(old, new, |relationship| relationship.name.clone())
+        .into_iter()
+        .filter_map(|pair| match pair {
+            (Some(old), Some(new)) => {
+                let changes = diff_relationship(old, new);
+                (!changes.is_empty()).then(|| RelationshipDiff {
+                    name: old.name.clone(),
+                    kind: ChangeKind::Modified,
+                    changes,
+                })
+            }
+            (Some(old), None) => Some(RelationshipDiff {
+                name: old.name.clone(),
+                kind: ChangeKind::Removed,
+                changes: Vec::new(),
+            }),
+            (None, Some(new)) => Some(RelationshipDiff {
+                name: new.name.clone(),
+                kind: ChangeKind::Added,
+                changes: Vec::new(),
+            }),
+            (None, None) => None,
+        })
+        .collect()
+}
+
+fn diff_table(old: &Table, new: &Table, options: &DiffOptions) -> Option<TableDiff> {
+    let mut changes = Changes(Vec::new());
+    changes.compare("logicalName", &old.logical_name, &new.logical_name);
+    changes.compare("description", &old.description, &new.description);
+    changes.compare_option(
+        "tableConstraint",
+        &old.table_constraint,
+        &new.table_constraint,
+    );
+    changes.compare_option(
+        "primaryKeyName",
+        &old.primary_key_name,
+        &new.primary_key_name,
+    );
+    changes.compare_option("option", &old.option, &new.option);
+    changes.compare(
+        "columnGroups",
+        &group_references(old),
+        &group_references(new),
+    );
+    for pair in pair_by_key(&keys(old), &keys(new), |(field, _)| field.clone()) {
+        if let Some((field, _)) = pair.0.or(pair.1) {
+            changes.compare_option(
+                field,
+                &pair.0.map(|(_, description)| description),
+                &pair.1.map(|(_, description)| description),
+            );
+        }
+    }
+    if options.include_visual {
+        let color = |table: &Table| {
+            format!(
+                "#{:02x}{:02x}{:02x}",
+                table.color.r, table.color.g, table.color.b
+            )
+        };
+        changes.compare("x", &old.x, &new.x);
+        changes.compare("y", &old.y, &new.y);
+        changes.compare("width", &old.width, &new.width);
+        changes.compare("height", &old.height, &new.height);
+        changes.compare("color", &color(old), &color(new));
+        changes.compare("fontName", &old.font_name, &new.font_name);
+        changes.compare("fontSize", &old.font_size, &new.font_size);
+    }
+
+    let columns = diff_columns(
+        &normal_columns(old),
+        &normal_columns(new),
+        |column| column.physical_name.as_str(),
+        diff_normal_column,
+    );
+    let relationships = diff_relationships(old.relationships(), new.relationships());
+
+    if changes.0.is_empty() && columns.is_empty() && relationships.is_empty() {
+        return None;
+    }
+    Some(TableDiff {
+        physical_name: old.physical_name.clone(),
+        kind: ChangeKind::Modified,
+        changes: changes.0,
+        columns,
+        relationships,
+    })
+}
+
+fn whole_table(table: &Table, kind: ChangeKind) -> TableDiff {
+    TableDiff {
+        physical_name: table.physical_name.clone(),
+        kind,
+        changes: Vec::new(),
+        columns: Vec::new(),
+        relationships: Vec::new(),
+    }
+}
+
+fn group_columns(group: &ColumnGroup) -> Vec<&column_groups::NormalColumn> {
+    group.columns.normal_columns.iter().flatten().collect()
+}
+
+fn diff_column_groups(old: &[ColumnGroup], new: &[ColumnGroup]) -> Vec<ColumnGroupDiff> {
+    pair_by_key(old, new, |group| group.column_group_name.clone())
+        .into_iter()
+        .filter_map(|pair| match pair {
+            (Some(old), Some(new)) => {
+                let columns = diff_columns(
+                    &group_columns(old),
+                    &group_columns(new),
+                    |column| column.physical_name.as_str(),
+                    diff_group_column,
+                );
+                (!columns.is_empty()).then(|| ColumnGroupDiff {
+                    column_group_name: old.column_group_name.clone(),
+                    kind: ChangeKind::Modified,
+                    columns,
+                })
+            }
+            (Some(old), None) => Some(ColumnGroupDiff {
+                column_group_name: old.column_group_name.clone(),
+                kind: ChangeKind::Removed,
+                columns: Vec::new(),
+            }),
+            (None, Some(new)) => Some(ColumnGroupDiff {
+                column_group_name: new.column_group_name.clone(),
+                kind: ChangeKind::Added,
+                columns: Vec::new(),
+            }),
+            (None, None) => None,
+        })
+        .collect()
+}
+
+/// Compares two diagrams table by table, matching tables, columns, relationships and
+/// column groups by name.
+pub fn diff(old: &Diagram, new: &Diagram, options: &DiffOptions) -> DiagramDiff {
+    let mut changes = Changes(Vec::new());
+    changes.compare(
+        "database",
+        &old.diagram_settings.database,
+        &new.diagram_settings.database,
+    );
+
+    let tables = pair_by_key(old.tables(), new.tables(), |table| {
+        table.physical_name.clone()
+    })
+    .into_iter()
+    .filter_map(|pair| match pair {
+        (Some(old), Some(new)) => diff_table(old, new, options),
+        (Some(old), None) => Some(whole_table(old, ChangeKind::Removed)),
+        (None, Some(new)) => Some(whole_table(new, ChangeKind::Added)),
+        (None, None) => None,
+    })
+    .collect();
+
+    DiagramDiff {
+        changes: changes.0,
+        tables,
+        column_groups: diff_column_groups(old.groups(), new.groups()),
+    }
+}
@@ -0,0 +1,350 @@
+//! Detection of differences between a diagram and the schema actually deployed.
+//!
+//! Unlike [`crate::diff`], which compares two versions of a diagram, this compares only
+//! what the database can tell: tables, columns, types, nullability, keys and indexes.
+//! Names are compared case-insensitively since databases fold unquoted identifiers.
+
+use crate::column_type::format_column_type;
+use crate::dtos::diagram::{Diagram, ResolvedColumn};
+use crate::dtos::diagram_walkers::Table;
+use crate::importers::{self, ddl};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DriftKind {
+    MissingTable,
+    ExtraTable,
+    MissingColumn,
+    ExtraColumn,
+    TypeMismatch,
+    NullabilityMismatch,
+    PrimaryKeyMismatch,
+    UniqueMismatch,
+    ForeignKeyMismatch,
+    MissingUniqueKey,
+    ExtraUniqueKey,
+    MissingIndex,
+    ExtraIndex,
+    IndexMismatch,
+}
+
+impl DriftKind {
+    fn message(self) -> &'static str {
+        match self {
+            DriftKind::MissingTable => "table is missing in the database",
+            DriftKind::ExtraTable => "table is not in the diagram",
+            DriftKind::MissingColumn => "column is missing in the database",
+            DriftKind::ExtraColumn => "column is not in the diagram",
+            DriftKind::TypeMismatch => "type differs",
+            DriftKind::NullabilityMismatch => "nullability differs",
+            DriftKind::PrimaryKeyMismatch => "primary key differs",
+            DriftKind::UniqueMismatch => "unique constraint differs",
+            DriftKind::ForeignKeyMismatch => "foreign key differs",
+            DriftKind::MissingUniqueKey => "unique key is missing in the database",
+            DriftKind::ExtraUniqueKey => "unique key is not in the diagram",
+            DriftKind::MissingIndex => "index is missing in the database",
+            DriftKind::ExtraIndex => "index is not in the diagram",
+            DriftKind::IndexMismatch => "index differs",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Drift {
+    pub kind: DriftKind,
+
+    /// Location of the drift, e.g. `MEMBERS`, `MEMBERS.EMAIL` or
+    /// `MEMBERS.index.IX_MEMBERS_EMAIL`.
+    pub path: String,
+
+    /// The value in the diagram.
+    pub expected: Option<String>,
+
+    /// The value in the database.
+    pub actual: Option<String>,
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.kind.message())?;
+        if self.expected.is_some() || self.actual.is_some() {
+            let value =
+                |value: &Option<String>| value.clone().unwrap_or_else(|| "none".to_string());
+            write!(
+                f,
+                " (expected {}, actual {})",
+                value(&self.expected),
+                value(&self.actual)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftReport {
+    pub drifts: Vec<Drift>,
+
+    /// Problems reading the target schema, such as DDL statements that could not be
+    /// parsed. Parts of the schema may be missing from the comparison because of them.
+    pub warnings: Vec<String>,
+}
+
+impl DriftReport {
+    pub fn has_drift(&self) -> bool {
+        !self.drifts.is_empty()
+    }
+}
+
+/// The schema to compare a diagram against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftTarget<'a> {
+    /// An SQLite database file.
+    Sqlite(&'a str),
+
+    /// A DDL script such as a schema dump.
+    Ddl(&'a str, ddl::Dialect),
+
+    /// A running database given its connection URL.
+    Database(&'a str),
+}
+
+fn same_name(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+struct Drifts(Vec<Drift>);
+
+impl Drifts {
+    fn push(&mut self, kind: DriftKind, path: String) {
+        self.0.push(Drift {
+            kind,
+            path,
+            expected: None,
+            actual: None,
+        });
+    }
+
+    fn compare(&mut self, kind: DriftKind, path: &str, expected: String, actual: String) {
+        if !same_name(&expected, &actual) {
+            self.0.push(Drift {
+                kind,
+                path: path.to_string(),
+                expected: Some(expected),
+                actual: Some(actual),
+            });
+        }
+    }
+}
+
+fn column_type(column: &ResolvedColumn) -> String {
+    format_column_type(&column.column)
+}
+
+fn nullability(column: &ResolvedColumn) -> String {
+    if column.is_not_null() {
+        "NOT NULL"
+    } else {
+        "NULL"
+    }
+    .to_string()
+}
+
+fn reference(column: &ResolvedColumn) -> String {
+    column.column.referred_table_and_column().map_or_else(
+        || "none".to_string(),
+        |(table, referred)| format!("{table}.{referred}"),
+    )
+}
+
+fn compare_columns(
+    drifts: &mut Drifts,
+    table: &str,
+    expected: &[ResolvedColumn],
+    actual: &[ResolvedColumn],
+) {
+    for column in expected {
+        let name = &column.column.physical_name;
+        let path = format!("{table}.{name}");
+        let Some(found) = actual
+            .iter()
+            .find(|found| same_name(&found.column.physical_name, name))
+        else {
+            drifts.push(DriftKind::MissingColumn, path);
+            continue;
+        };
+        drifts.compare(
+            DriftKind::TypeMismatch,
+            &path,
+            column_type(column),
+            column_type(found),
+        );
+        drifts.compare(
+            DriftKind::NullabilityMismatch,
+            &path,
+            nullability(column),
+            nullability(found),
+        );
+        drifts.compare(
+            DriftKind::PrimaryKeyMismatch,
+            &path,
+            column.is_primary_key().to_string(),
+            found.is_primary_key().to_string(),
+        );
+        drifts.compare(
+            DriftKind::UniqueMismatch,
+            &path,
+            column.is_unique().to_string(),
+            found.is_unique().to_string(),
+        );
+        drifts.compare(
+            DriftKind::ForeignKeyMismatch,
+            &path,
+            reference(column),
+            reference(found),
+        );
+    }
+    for found in actual {
+        let name = &found.column.physical_name;
+        if !expected
+            .iter()
+            .any(|column| same_name(&column.column.physical_name, name))
+        {
+            drifts.push(DriftKind::ExtraColumn, format!("{table}.{name}"));
+        }
+    }
+}
+
+fn unique_key_columns(table: &Table) -> Vec<String> {
+    table
+        .compound_unique_keys()
+        .iter()
+        .map(|key| {
+            let mut columns: Vec<String> = key
+                .column_names()
+                .iter()
+                .map(|name| name.to_uppercase())
+                .collect();
+            columns.sort();
+            columns.join(", ")
+        })
+        .collect()
+}
+
+fn compare_keys(drifts: &mut Drifts, expected: &Table, actual: &Table) {
+    let table = &expected.physical_name;
+    let expected_keys = unique_key_columns(expected);
+    let actual_keys = unique_key_columns(actual);
+    for key in &expected_keys {
+        if !actual_keys.contains(key) {
+            drifts.push(
+                DriftKind::MissingUniqueKey,
+                format!("{table}.unique({key})"),
+            );
+        }
+    }
+    for key in &actual_keys {
+        if !expected_keys.contains(key) {
+            drifts.push(DriftKind::ExtraUniqueKey, format!("{table}.unique({key})"));
+        }
+    }
+
+    for index in expected.index_list() {
+        let path = format!("{table}.index.{}", index.name);
+        let Some(found) = actual
+            .index_list()
+            .iter()
+            .find(|found| same_name(&found.name, &index.name))
+        else {
+            drifts.push(DriftKind::MissingIndex, path);
+            continue;
+        };
+        let describe = |index: &crate::dtos::diagram_walkers::Index| {
+            let kind = if index.is_unique() { "unique " } else { "" };
+            format!("{kind}({})", index.columns)
+        };
+        drifts.compare(
+            DriftKind::IndexMismatch,
+            &path,
+            describe(index),
+            describe(found),
+        );
+    }
+    for found in actual.index_list() {
+        if !expected
+            .index_list()
+            .iter()
+            .any(|index| same_name(&index.name, &found.name))
+        {
+            drifts.push(
+                DriftKind::ExtraIndex,
+                format!("{table}.index.{}", found.name),
+            );
+        }
+    }
+}
+
+/// Compares the diagram with the schema in `actual`, usually a diagram obtained by
+/// reverse engineering the database.
+pub fn check(diagram: &Diagram, actual: &Diagram) -> DriftReport {
+    let mut drifts = Drifts(Vec::new());
+    for table in diagram.tables() {
+        let name = &table.physical_name;
+        let Some(found) = actual
+            .tables()
+            .iter()
+            .find(|found| same_name(&found.physical_name, name))
+        else {
+            drifts.push(DriftKind::MissingTable, name.clone());
+            continue;
+        };
+        compare_columns(
+            &mut drifts,
+            name,
+            &diagram.resolve_columns(table),
+            &actual.resolve_columns(found),
+        );
+        compare_keys(&mut drifts, table, found);
+    }
+    for found in actual.tables() {
+        if !diagram
+            .tables()
+            .iter()
+            .any(|table| same_name(&table.physical_name, &found.physical_name))
+        {
+            drifts.push(DriftKind::ExtraTable, found.physical_name.clone());
+        }
+    }
+    DriftReport {
+        drifts: drifts.0,
+        warnings: Vec::new(),
+    }
+}
+
+/// Reverse engineers the target schema and compares the diagram with it.
+pub fn check_target(
+    diagram: &Diagram,
+    target: DriftTarget,
+) -> Result<DriftReport, Box<dyn std::error::Error>> {
+    let (actual, warnings) = match target {
+        DriftTarget::Sqlite(filename) => (importers::sqlite::import(filename)?, Vec::new()),
+        DriftTarget::Ddl(filename, dialect) => {
+            let imported = ddl::import(filename, dialect)?;
+            let warnings = imported
+                .warnings
+                .iter()
+                .map(|warning| format!("line {}: {}", warning.line, warning.message))
+                .collect();
+            (imported.diagram, warnings)
+        }
+        DriftTarget::Database(url) => (importers::import_database(url)?, Vec::new()),
+    };
+    Ok(DriftReport {
+        warnings,
+        ..check(diagram, &actual)
+    })
+}
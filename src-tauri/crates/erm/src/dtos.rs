@@ -2,3 +2,6 @@ pub mod column_groups;
 pub mod diagram;
 pub mod diagram_settings;
 pub mod diagram_walkers;
+pub mod dictionary;
+pub mod page_settings;
+pub mod vdiagrams;
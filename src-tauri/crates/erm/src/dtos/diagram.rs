@@ -1,21 +1,329 @@
-use super::column_groups::ColumnGroups;
-use super::diagram_settings::DiagramSettings;
-use super::diagram_walkers::DiagramWalkers;
+use super::column_groups::{ColumnGroup, ColumnGroups};
+use super::diagram_settings::{Category, DiagramSettings};
+use super::diagram_walkers::{
+    Column, DiagramWalkers, NormalColumn, Note, Relationship, Table, WalkerGroup, table_name_of,
+};
+use super::dictionary::Dictionary;
+use super::page_settings::PageSettings;
+use super::vdiagrams::VDiagram;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Diagram {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_settings: Option<PageSettings>,
+
     pub diagram_settings: DiagramSettings,
+
+    #[serde(default)]
+    pub dictionary: Dictionary,
+
     pub diagram_walkers: DiagramWalkers,
+
+    #[serde(default)]
+    pub vdiagrams: Vec<VDiagram>,
+
     pub column_groups: ColumnGroups,
 }
 
+/// A column of a table as it appears in the database: column group references are
+/// expanded, and foreign key columns take their type from the referred column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedColumn {
+    pub column: NormalColumn,
+
+    /// The column group the column comes from, if any.
+    pub column_group: Option<String>,
+}
+
+impl ResolvedColumn {
+    pub fn is_primary_key(&self) -> bool {
+        self.column.primary_key.unwrap_or(false)
+    }
+
+    pub fn is_foreign_key(&self) -> bool {
+        self.column.referred_column.is_some()
+    }
+
+    pub fn is_not_null(&self) -> bool {
+        self.column.not_null.unwrap_or(false) || self.is_primary_key()
+    }
+
+    pub fn is_unique(&self) -> bool {
+        self.column.unique_key.unwrap_or(false)
+    }
+}
+
+// Foreign keys may refer to foreign keys; this bounds the chain in case of cycles.
+const MAX_REFERENCE_DEPTH: usize = 16;
+
+impl Diagram {
+    pub fn tables(&self) -> &[Table] {
+        self.diagram_walkers.tables.as_deref().unwrap_or_default()
+    }
+
+    /// Finds a table by its physical name or by its id such as `table.MEMBERS`.
+    pub fn find_table(&self, name: &str) -> Option<&Table> {
+        let name = table_name_of(name);
+        self.tables()
+            .iter()
+            .find(|table| table.physical_name == name)
+    }
+
+    pub fn notes(&self) -> &[Note] {
+        self.diagram_walkers.notes.as_deref().unwrap_or_default()
+    }
+
+    /// The groups framing tables on the diagram, as opposed to column groups.
+    pub fn walker_groups(&self) -> &[WalkerGroup] {
+        self.diagram_walkers.groups.as_deref().unwrap_or_default()
+    }
+
+    pub fn groups(&self) -> &[ColumnGroup] {
+        self.column_groups
+            .column_groups
+            .as_deref()
+            .unwrap_or_default()
+    }
+
+    pub fn find_column_group(&self, name: &str) -> Option<&ColumnGroup> {
+        self.groups()
+            .iter()
+            .find(|group| group.column_group_name == name)
+    }
+
+    /// All relationships of the diagram, in the order of the tables holding them.
+    pub fn relationships(&self) -> impl Iterator<Item = &Relationship> {
+        self.tables().iter().flat_map(Table::relationships)
+    }
+
+    /// Whether the foreign key columns are part of the primary key of the child, i.e.
+    /// the child cannot be identified without the parent.
+    pub fn is_identifying(&self, relationship: &Relationship) -> bool {
+        let Some(child) = self.find_table(relationship.target_table()) else {
+            return false;
+        };
+        let fk_columns = &relationship.fk_columns.fk_column;
+        !fk_columns.is_empty()
+            && fk_columns.iter().all(|fk| {
+                child.column_items().iter().any(|item| {
+                    matches!(item, Column::Normal(column)
+                        if column.physical_name == fk.fk_column_name
+                            && column.primary_key.unwrap_or(false))
+                })
+            })
+    }
+
+    /// The foreign key columns of the child, and the columns of the parent they refer
+    /// to, or else its primary key. None if either table is missing.
+    pub fn foreign_key_columns(
+        &self,
+        relationship: &Relationship,
+    ) -> Option<(Vec<String>, Vec<String>)> {
+        let parent = self.find_table(relationship.source_table())?;
+        let child = self.find_table(relationship.target_table())?;
+        let child_columns = self.resolve_columns(child);
+        let fk_columns: Vec<String> = relationship
+            .fk_columns
+            .fk_column
+            .iter()
+            .map(|column| column.fk_column_name.clone())
+            .collect();
+        let referred: Option<Vec<String>> = fk_columns
+            .iter()
+            .map(|fk_column| {
+                child_columns
+                    .iter()
+                    .find(|column| column.column.physical_name == *fk_column)
+                    .and_then(|column| column.column.referred_table_and_column())
+                    .map(|(_, referred)| referred.to_string())
+            })
+            .collect();
+        let referred = referred.unwrap_or_else(|| {
+            self.resolve_columns(parent)
+                .into_iter()
+                .filter(ResolvedColumn::is_primary_key)
+                .map(|column| column.column.physical_name)
+                .collect()
+        });
+        Some((fk_columns, referred))
+    }
+
+    pub fn categories(&self) -> &[Category] {
+        self.diagram_settings
+            .category_settings
+            .as_ref()
+            .map(|settings| settings.categories.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn find_category(&self, name: &str) -> Option<&Category> {
+        self.categories()
+            .iter()
+            .find(|category| category.name == name)
+    }
+
+    pub fn find_vdiagram(&self, name: &str) -> Option<&VDiagram> {
+        self.vdiagrams.iter().find(|vdiagram| vdiagram.name == name)
+    }
+
+    /// The part of the diagram shown by the category or the virtual diagram `name`:
+    /// its tables with the relationships among them, and on a virtual diagram its own
+    /// positions, notes and groups. Foreign key columns keep the type of the column they
+    /// refer to even when its table is left out. `None` if there is no such category or
+    /// virtual diagram.
+    pub fn extract(&self, name: &str) -> Option<Diagram> {
+        // The tables to keep, with their position on a virtual diagram.
+        let (members, notes, groups): (HashMap<&str, Option<(u16, u16)>>, _, _) =
+            if let Some(category) = self.find_category(name) {
+                let members: HashMap<_, _> = category
+                    .node_elements
+                    .iter()
+                    .map(|id| (table_name_of(id), None))
+                    .collect();
+                let groups: Vec<WalkerGroup> = self
+                    .walker_groups()
+                    .iter()
+                    .filter(|group| {
+                        !group.walkers.is_empty()
+                            && group
+                                .walkers
+                                .iter()
+                                .all(|id| members.contains_key(table_name_of(id)))
+                    })
+                    .cloned()
+                    .collect();
+                (members, Vec::new(), groups)
+            } else {
+                let vdiagram = self.find_vdiagram(name)?;
+                let members = vdiagram
+                    .tables
+                    .iter()
+                    .map(|vtable| (table_name_of(&vtable.table_id), Some((vtable.x, vtable.y))))
+                    .collect();
+                (members, vdiagram.notes.clone(), vdiagram.groups.clone())
+            };
+
+        let tables: Vec<Table> = self
+            .tables()
+            .iter()
+            .filter_map(|table| {
+                let position = members.get(table.physical_name.as_str())?;
+                let mut table = table.clone();
+                if let Some((x, y)) = position {
+                    (table.x, table.y) = (*x, *y);
+                }
+                for item in table.columns.items.iter_mut().flatten() {
+                    if let Column::Normal(column) = item {
+                        *column = self.resolve_column(column);
+                    }
+                }
+                if let Some(relationships) = &mut table.connections.relationships {
+                    relationships
+                        .retain(|relationship| members.contains_key(relationship.source_table()));
+                }
+                Some(table)
+            })
+            .collect();
+
+        Some(Diagram {
+            page_settings: self.page_settings.clone(),
+            diagram_settings: self.diagram_settings.clone(),
+            dictionary: self.dictionary.clone(),
+            diagram_walkers: DiagramWalkers {
+                tables: (!tables.is_empty()).then_some(tables),
+                notes: (!notes.is_empty()).then_some(notes),
+                groups: (!groups.is_empty()).then_some(groups),
+            },
+            vdiagrams: Vec::new(),
+            column_groups: self.column_groups.clone(),
+        })
+    }
+
+    /// The columns of `table` in order, with column groups expanded and the types of
+    /// foreign key columns resolved. Unknown column groups are skipped.
+    pub fn resolve_columns(&self, table: &Table) -> Vec<ResolvedColumn> {
+        table
+            .column_items()
+            .iter()
+            .flat_map(|item| match item {
+                Column::Normal(column) => vec![ResolvedColumn {
+                    column: self.resolve_column(column),
+                    column_group: None,
+                }],
+                Column::Group(name) => self
+                    .find_column_group(name)
+                    .into_iter()
+                    .flat_map(|group| group.columns.normal_columns.iter().flatten())
+                    .map(|column| ResolvedColumn {
+                        column: column.clone().into(),
+                        column_group: Some(name.clone()),
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Fills the type, and the logical name if missing, of a foreign key column from
+    /// the column it refers to.
+    pub fn resolve_column(&self, column: &NormalColumn) -> NormalColumn {
+        self.resolve_reference(column.clone(), 0)
+    }
+
+    /// Finds a column of a table by its physical name, looking into column groups.
+    pub(crate) fn find_normal_column(&self, table: &str, column: &str) -> Option<NormalColumn> {
+        let table = self.find_table(table)?;
+        table.column_items().iter().find_map(|item| match item {
+            Column::Normal(normal) if normal.physical_name == column => Some(normal.clone()),
+            Column::Normal(_) => None,
+            Column::Group(name) => self
+                .find_column_group(name)?
+                .columns
+                .normal_columns
+                .iter()
+                .flatten()
+                .find(|normal| normal.physical_name == column)
+                .map(|normal| normal.clone().into()),
+        })
+    }
+
+    fn resolve_reference(&self, mut column: NormalColumn, depth: usize) -> NormalColumn {
+        if column.column_type.is_some() || depth >= MAX_REFERENCE_DEPTH {
+            return column;
+        }
+        let Some(referred) = column
+            .referred_table_and_column()
+            .and_then(|(table, referred)| self.find_normal_column(table, referred))
+        else {
+            return column;
+        };
+        let referred = self.resolve_reference(referred, depth + 1);
+        column.column_type = referred.column_type;
+        column.length = referred.length;
+        column.decimal = referred.decimal;
+        column.unsigned = referred.unsigned;
+        column.args = referred.args;
+        if column.logical_name.is_none() {
+            column.logical_name = referred.logical_name;
+        }
+        column
+    }
+}
+
 impl From<crate::entities::diagram::Diagram> for Diagram {
     fn from(entity: crate::entities::diagram::Diagram) -> Self {
         Self {
+            page_settings: entity.page_settings.map(Into::into),
             diagram_settings: entity.diagram_settings.into(),
+            dictionary: entity.dictionary.map(Into::into).unwrap_or_default(),
             diagram_walkers: entity.diagram_walkers.into(),
+            vdiagrams: entity
+                .vdiagrams
+                .and_then(|vdiagrams| vdiagrams.vdiagrams)
+                .map(|v| v.into_iter().map(Into::into).collect())
+                .unwrap_or_default(),
             column_groups: entity.column_groups.into(),
         }
     }
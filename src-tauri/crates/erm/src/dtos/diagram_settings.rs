@@ -1,15 +1,121 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct DiagramSettings {
     pub database: String,
+
+    /// How relationships are drawn: `IE` (crow's feet, the default) or `IDEF1X`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notation: Option<String>,
+
+    /// Whether physical names are checked against the rules of the database, such as
+    /// reserved words.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validate_physical_name: Option<bool>,
+
+    /// Whether the validator is turned off in the editor.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suspend_validator: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub export_settings: Option<ExportSettings>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category_settings: Option<CategorySettings>,
+}
+
+/// Where and how the diagram is exported. Empty paths mean not configured.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category_name_to_export: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ddl_output: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excel_output: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_output: Option<String>,
+
+    /// Whether the Excel workbook gets a sheet with the image of the diagram.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub put_diagram_on_excel: Option<bool>,
+
+    /// Whether the sheets of tables in the Excel workbook are named by logical name
+    /// rather than physical name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub use_logical_name_as_sheet: Option<bool>,
+}
+
+/// A named subset of the diagram, shown on its own tab of the editor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Category {
+    pub name: String,
+
+    /// Ids of the members, e.g. `table.MEMBERS`.
+    #[serde(default)]
+    pub node_elements: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CategorySettings {
+    #[serde(default)]
+    pub categories: Vec<Category>,
 }
 
 impl From<crate::entities::diagram_settings::DiagramSettings> for DiagramSettings {
     fn from(entity: crate::entities::diagram_settings::DiagramSettings) -> Self {
         Self {
             database: entity.database,
+            notation: entity.notation,
+            validate_physical_name: entity.validate_physical_name,
+            suspend_validator: entity.suspend_validator,
+            export_settings: entity.export_settings.map(Into::into),
+            category_settings: entity.category_settings.map(Into::into),
+        }
+    }
+}
+
+impl From<crate::entities::diagram_settings::ExportSettings> for ExportSettings {
+    fn from(entity: crate::entities::diagram_settings::ExportSettings) -> Self {
+        Self {
+            category_name_to_export: entity.category_name_to_export,
+            ddl_output: entity.ddl_output,
+            excel_output: entity.excel_output,
+            image_output: entity.image_output,
+            put_diagram_on_excel: entity.put_diagram_on_excel,
+            use_logical_name_as_sheet: entity.use_logical_name_as_sheet,
+        }
+    }
+}
+
+impl From<crate::entities::diagram_settings::Category> for Category {
+    fn from(entity: crate::entities::diagram_settings::Category) -> Self {
+        Self {
+            name: entity.name,
+            node_elements: entity.node_elements.unwrap_or_default(),
+            selected: entity.selected,
+        }
+    }
+}
+
+impl From<crate::entities::diagram_settings::CategorySettings> for CategorySettings {
+    fn from(entity: crate::entities::diagram_settings::CategorySettings) -> Self {
+        Self {
+            categories: entity
+                .categories
+                .categories
+                .map(|v| v.into_iter().map(Into::into).collect())
+                .unwrap_or_default(),
         }
     }
 }
@@ -1,5 +1,12 @@
 use serde::{Deserialize, Serialize};
 
+pub(crate) const TABLE_ID_PREFIX: &str = "table.";
+
+/// Strips the `table.` prefix from ids such as `table.MEMBERS`.
+pub fn table_name_of(id: &str) -> &str {
+    id.strip_prefix(TABLE_ID_PREFIX).unwrap_or(id)
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Color {
@@ -61,6 +68,18 @@ pub struct Relationship {
     pub on_update_action: String,
 }
 
+impl Relationship {
+    /// Physical name of the referenced (parent) table.
+    pub fn source_table(&self) -> &str {
+        table_name_of(&self.source)
+    }
+
+    /// Physical name of the referencing (child) table.
+    pub fn target_table(&self) -> &str {
+        table_name_of(&self.target)
+    }
+}
+
 impl From<crate::entities::diagram_walkers::Relationship> for Relationship {
     fn from(entity: crate::entities::diagram_walkers::Relationship) -> Self {
         Self {
@@ -117,6 +136,10 @@ pub struct NormalColumn {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub unsigned: Option<bool>,
 
+    /// The values of an `enum`, e.g. `'draft', 'published'`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<String>,
+
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub not_null: Option<bool>,
 
@@ -139,6 +162,34 @@ pub struct NormalColumn {
     pub relationship: Option<String>,
 }
 
+impl NormalColumn {
+    /// Splits `referred_column` such as `table.MEMBERS.MEMBER_ID` into the referred
+    /// table and column names.
+    pub fn referred_table_and_column(&self) -> Option<(&str, &str)> {
+        let referred = self.referred_column.as_deref()?;
+        table_name_of(referred).rsplit_once('.')
+    }
+}
+
+impl From<crate::dtos::column_groups::NormalColumn> for NormalColumn {
+    fn from(column: crate::dtos::column_groups::NormalColumn) -> Self {
+        Self {
+            physical_name: column.physical_name,
+            logical_name: column.logical_name,
+            description: column.description,
+            column_type: Some(column.column_type),
+            length: column.length,
+            decimal: column.decimal,
+            unsigned: column.unsigned,
+            args: column.args,
+            not_null: column.not_null,
+            unique_key: column.unique_key,
+            default_value: column.default_value,
+            ..Default::default()
+        }
+    }
+}
+
 impl From<crate::entities::diagram_walkers::NormalColumn> for NormalColumn {
     fn from(entity: crate::entities::diagram_walkers::NormalColumn) -> Self {
         Self {
@@ -149,6 +200,7 @@ impl From<crate::entities::diagram_walkers::NormalColumn> for NormalColumn {
             length: entity.length,
             decimal: entity.decimal,
             unsigned: entity.unsigned,
+            args: entity.args,
             not_null: entity.not_null,
             unique_key: entity.unique_key,
             default_value: entity.default_value,
@@ -193,6 +245,171 @@ impl From<crate::entities::diagram_walkers::Columns> for Columns {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexColumn {
+    pub column_id: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desc: Option<bool>,
+}
+
+impl IndexColumn {
+    /// Physical name of the indexed column, dropping any table prefix of the id.
+    pub fn column_name(&self) -> &str {
+        self.column_id
+            .rsplit_once('.')
+            .map_or(self.column_id.as_str(), |(_, name)| name)
+    }
+}
+
+impl From<crate::entities::diagram_walkers::IndexColumn> for IndexColumn {
+    fn from(entity: crate::entities::diagram_walkers::IndexColumn) -> Self {
+        Self {
+            column_id: entity.column_id,
+            desc: entity.desc,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexColumns {
+    #[serde(default)]
+    pub column: Vec<IndexColumn>,
+}
+
+/// Formats the columns as `A, B DESC`.
+impl std::fmt::Display for IndexColumns {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, column) in self.column.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(column.column_name())?;
+            if column.desc.unwrap_or(false) {
+                f.write_str(" DESC")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<crate::entities::diagram_walkers::IndexColumns> for IndexColumns {
+    fn from(entity: crate::entities::diagram_walkers::IndexColumns) -> Self {
+        Self {
+            column: entity.column.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Index {
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_type: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_text: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub non_unique: Option<bool>,
+
+    pub columns: IndexColumns,
+}
+
+impl Index {
+    pub fn is_unique(&self) -> bool {
+        !self.non_unique.unwrap_or(true)
+    }
+
+    pub fn column_names(&self) -> Vec<&str> {
+        self.columns
+            .column
+            .iter()
+            .map(IndexColumn::column_name)
+            .collect()
+    }
+}
+
+impl From<crate::entities::diagram_walkers::Index> for Index {
+    fn from(entity: crate::entities::diagram_walkers::Index) -> Self {
+        Self {
+            name: entity.name,
+            index_type: entity.index_type,
+            description: entity.description,
+            full_text: entity.full_text,
+            non_unique: entity.non_unique,
+            columns: entity.columns.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Indexes {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indexes: Option<Vec<Index>>,
+}
+
+impl From<crate::entities::diagram_walkers::Indexes> for Indexes {
+    fn from(entity: crate::entities::diagram_walkers::Indexes) -> Self {
+        Self {
+            indexes: entity
+                .indexes
+                .map(|v| v.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompoundUniqueKey {
+    pub name: String,
+    pub columns: IndexColumns,
+}
+
+impl CompoundUniqueKey {
+    pub fn column_names(&self) -> Vec<&str> {
+        self.columns
+            .column
+            .iter()
+            .map(IndexColumn::column_name)
+            .collect()
+    }
+}
+
+impl From<crate::entities::diagram_walkers::CompoundUniqueKey> for CompoundUniqueKey {
+    fn from(entity: crate::entities::diagram_walkers::CompoundUniqueKey) -> Self {
+        Self {
+            name: entity.name,
+            columns: entity.columns.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CompoundUniqueKeyList {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compound_unique_keys: Option<Vec<CompoundUniqueKey>>,
+}
+
+impl From<crate::entities::diagram_walkers::CompoundUniqueKeyList> for CompoundUniqueKeyList {
+    fn from(entity: crate::entities::diagram_walkers::CompoundUniqueKeyList) -> Self {
+        Self {
+            compound_unique_keys: entity
+                .compound_unique_keys
+                .map(|v| v.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Table {
@@ -218,6 +435,41 @@ pub struct Table {
     pub option: Option<String>,
 
     pub columns: Columns,
+
+    #[serde(default)]
+    pub indexes: Indexes,
+
+    #[serde(default)]
+    pub compound_unique_key_list: CompoundUniqueKeyList,
+}
+
+impl Table {
+    /// The id other objects use to refer to this table, e.g. `table.MEMBERS`.
+    pub fn id(&self) -> String {
+        format!("{TABLE_ID_PREFIX}{}", self.physical_name)
+    }
+
+    pub fn relationships(&self) -> &[Relationship] {
+        self.connections
+            .relationships
+            .as_deref()
+            .unwrap_or_default()
+    }
+
+    pub fn column_items(&self) -> &[Column] {
+        self.columns.items.as_deref().unwrap_or_default()
+    }
+
+    pub fn index_list(&self) -> &[Index] {
+        self.indexes.indexes.as_deref().unwrap_or_default()
+    }
+
+    pub fn compound_unique_keys(&self) -> &[CompoundUniqueKey] {
+        self.compound_unique_key_list
+            .compound_unique_keys
+            .as_deref()
+            .unwrap_or_default()
+    }
 }
 
 impl From<crate::entities::diagram_walkers::Table> for Table {
@@ -238,6 +490,71 @@ impl From<crate::entities::diagram_walkers::Table> for Table {
             primary_key_name: entity.primary_key_name,
             option: entity.option,
             columns: entity.columns.into(),
+            indexes: entity.indexes.into(),
+            compound_unique_key_list: entity.compound_unique_key_list.into(),
+        }
+    }
+}
+
+/// A free-text note placed on the diagram.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Note {
+    pub note_text: String,
+    pub height: u16,
+    pub width: u16,
+    pub font_name: String,
+    pub font_size: u16,
+    pub x: u16,
+    pub y: u16,
+    pub color: Color,
+}
+
+impl From<crate::entities::diagram_walkers::Note> for Note {
+    fn from(entity: crate::entities::diagram_walkers::Note) -> Self {
+        Self {
+            note_text: entity.note_text,
+            height: entity.height,
+            width: entity.width,
+            font_name: entity.font_name,
+            font_size: entity.font_size,
+            x: entity.x,
+            y: entity.y,
+            color: entity.color.into(),
+        }
+    }
+}
+
+/// A named frame drawn around tables and notes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalkerGroup {
+    pub group_name: String,
+    pub height: u16,
+    pub width: u16,
+    pub font_name: String,
+    pub font_size: u16,
+    pub x: u16,
+    pub y: u16,
+    pub color: Color,
+
+    /// Ids of the members, e.g. `table.MEMBERS`.
+    #[serde(default)]
+    pub walkers: Vec<String>,
+}
+
+impl From<crate::entities::diagram_walkers::WalkerGroup> for WalkerGroup {
+    fn from(entity: crate::entities::diagram_walkers::WalkerGroup) -> Self {
+        Self {
+            group_name: entity.group_name,
+            height: entity.height,
+            width: entity.width,
+            font_name: entity.font_name,
+            font_size: entity.font_size,
+            x: entity.x,
+            y: entity.y,
+            color: entity.color.into(),
+            walkers: entity.walkers.walkers.unwrap_or_default(),
         }
     }
 }
@@ -247,6 +564,12 @@ impl From<crate::entities::diagram_walkers::Table> for Table {
 pub struct DiagramWalkers {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tables: Option<Vec<Table>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Vec<Note>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<WalkerGroup>>,
 }
 
 impl From<crate::entities::diagram_walkers::DiagramWalkers> for DiagramWalkers {
@@ -255,6 +578,12 @@ impl From<crate::entities::diagram_walkers::DiagramWalkers> for DiagramWalkers {
             tables: entity
                 .tables
                 .map(|v| v.into_iter().map(Into::into).collect()),
+            notes: entity
+                .notes
+                .map(|v| v.into_iter().map(Into::into).collect()),
+            groups: entity
+                .groups
+                .map(|v| v.into_iter().map(Into::into).collect()),
         }
     }
 }
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+/// A logical word and its physical counterpart, e.g. 会員 and `MEMBER`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Word {
+    pub physical_name: String,
+    pub logical_name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl From<crate::entities::dictionary::Word> for Word {
+    fn from(entity: crate::entities::dictionary::Word) -> Self {
+        Self {
+            physical_name: entity.physical_name,
+            logical_name: entity.logical_name,
+            description: entity.description,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Dictionary {
+    #[serde(default)]
+    pub words: Vec<Word>,
+}
+
+impl From<crate::entities::dictionary::Dictionary> for Dictionary {
+    fn from(entity: crate::entities::dictionary::Dictionary) -> Self {
+        Self {
+            words: entity
+                .words
+                .map(|v| v.into_iter().map(Into::into).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+fn is_separator(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '_' | '-' | '・')
+}
+
+impl Dictionary {
+    pub fn find_by_logical_name(&self, logical_name: &str) -> Option<&Word> {
+        self.words
+            .iter()
+            .find(|word| word.logical_name == logical_name)
+    }
+
+    /// Adds the word, replacing the word with the same logical name if any.
+    pub fn insert(&mut self, word: Word) {
+        match self
+            .words
+            .iter_mut()
+            .find(|found| found.logical_name == word.logical_name)
+        {
+            Some(found) => *found = word,
+            None => self.words.push(word),
+        }
+    }
+
+    /// Derives a physical name from a logical name by splitting it into the longest
+    /// words of the dictionary, e.g. 会員プロフィールID into `MEMBER_PROFILE_ID` given
+    /// 会員 and プロフィール. ASCII words not in the dictionary, such as `ID`, are kept
+    /// as they are. Returns `None` if some part of the name is not in the dictionary.
+    pub fn suggest_physical_name(&self, logical_name: &str) -> Option<String> {
+        if let Some(word) = self.find_by_logical_name(logical_name) {
+            return Some(word.physical_name.clone());
+        }
+        let mut parts = Vec::new();
+        let mut rest = logical_name;
+        while let Some(c) = rest.chars().next() {
+            if is_separator(c) {
+                rest = &rest[c.len_utf8()..];
+            } else if let Some(word) = self
+                .words
+                .iter()
+                .filter(|word| {
+                    !word.logical_name.is_empty() && rest.starts_with(&word.logical_name)
+                })
+                .max_by_key(|word| word.logical_name.len())
+            {
+                parts.push(word.physical_name.clone());
+                rest = &rest[word.logical_name.len()..];
+            } else if c.is_ascii_alphanumeric() {
+                let end = rest
+                    .find(|c: char| !c.is_ascii_alphanumeric())
+                    .unwrap_or(rest.len());
+                parts.push(rest[..end].to_string());
+                rest = &rest[end..];
+            } else {
+                return None;
+            }
+        }
+        (!parts.is_empty()).then(|| parts.join("_"))
+    }
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// How the diagram is printed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PageSettings {
+    /// Landscape rather than portrait.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direction_horizontal: Option<bool>,
+
+    /// In percent, also the default scale of images of the diagram.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale: Option<u16>,
+
+    /// e.g. `A4 210 x 297 mm`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paper_size: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_margin: Option<u16>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub left_margin: Option<u16>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bottom_margin: Option<u16>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub right_margin: Option<u16>,
+}
+
+impl From<crate::entities::page_settings::PageSettings> for PageSettings {
+    fn from(entity: crate::entities::page_settings::PageSettings) -> Self {
+        Self {
+            direction_horizontal: entity.direction_horizontal,
+            scale: entity.scale,
+            paper_size: entity.paper_size,
+            top_margin: entity.top_margin,
+            left_margin: entity.left_margin,
+            bottom_margin: entity.bottom_margin,
+            right_margin: entity.right_margin,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use super::diagram_walkers::{Note, WalkerGroup};
+use serde::{Deserialize, Serialize};
+
+/// A table shown on a virtual diagram, at its own position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VTable {
+    /// The id of the table, e.g. `table.MEMBERS`.
+    pub table_id: String,
+    pub x: u16,
+    pub y: u16,
+    pub font_name: String,
+    pub font_size: u16,
+}
+
+impl From<crate::entities::vdiagrams::VTable> for VTable {
+    fn from(entity: crate::entities::vdiagrams::VTable) -> Self {
+        Self {
+            table_id: entity.table_id,
+            x: entity.x,
+            y: entity.y,
+            font_name: entity.font_name,
+            font_size: entity.font_size,
+        }
+    }
+}
+
+/// A virtual diagram: a view of some of the tables, laid out separately from the
+/// main diagram.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VDiagram {
+    pub name: String,
+
+    #[serde(default)]
+    pub tables: Vec<VTable>,
+
+    #[serde(default)]
+    pub notes: Vec<Note>,
+
+    #[serde(default)]
+    pub groups: Vec<WalkerGroup>,
+}
+
+impl From<crate::entities::vdiagrams::VDiagram> for VDiagram {
+    fn from(entity: crate::entities::vdiagrams::VDiagram) -> Self {
+        Self {
+            name: entity.vdiagram_name,
+            tables: entity
+                .vtables
+                .vtables
+                .map(|v| v.into_iter().map(Into::into).collect())
+                .unwrap_or_default(),
+            notes: entity
+                .walker_notes
+                .notes
+                .map(|v| v.into_iter().map(Into::into).collect())
+                .unwrap_or_default(),
+            groups: entity
+                .walker_groups
+                .groups
+                .map(|v| v.into_iter().map(Into::into).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
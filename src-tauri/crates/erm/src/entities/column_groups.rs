@@ -7,9 +7,18 @@ pub struct NormalColumn {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub logical_name: Option<String>,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
     #[serde(rename = "type")]
     pub column_type: String,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length: Option<u16>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimal: Option<u16>,
+
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub not_null: Option<bool>,
 
@@ -18,6 +27,12 @@ pub struct NormalColumn {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub unsigned: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -39,3 +54,50 @@ pub struct ColumnGroups {
     #[serde(rename = "column_group")]
     pub column_groups: Option<Vec<ColumnGroup>>,
 }
+
+impl From<crate::dtos::column_groups::NormalColumn> for NormalColumn {
+    fn from(dto: crate::dtos::column_groups::NormalColumn) -> Self {
+        Self {
+            physical_name: dto.physical_name,
+            logical_name: dto.logical_name,
+            description: dto.description,
+            column_type: dto.column_type,
+            length: dto.length,
+            decimal: dto.decimal,
+            not_null: dto.not_null,
+            unique_key: dto.unique_key,
+            unsigned: dto.unsigned,
+            args: dto.args,
+            default_value: dto.default_value,
+        }
+    }
+}
+
+impl From<crate::dtos::column_groups::Columns> for Columns {
+    fn from(dto: crate::dtos::column_groups::Columns) -> Self {
+        Self {
+            normal_columns: dto
+                .normal_columns
+                .map(|v| v.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl From<crate::dtos::column_groups::ColumnGroup> for ColumnGroup {
+    fn from(dto: crate::dtos::column_groups::ColumnGroup) -> Self {
+        Self {
+            column_group_name: dto.column_group_name,
+            columns: dto.columns.into(),
+        }
+    }
+}
+
+impl From<crate::dtos::column_groups::ColumnGroups> for ColumnGroups {
+    fn from(dto: crate::dtos::column_groups::ColumnGroups) -> Self {
+        Self {
+            column_groups: dto
+                .column_groups
+                .map(|v| v.into_iter().map(Into::into).collect()),
+        }
+    }
+}
@@ -1,11 +1,40 @@
 use super::column_groups::ColumnGroups;
 use super::diagram_settings::DiagramSettings;
 use super::diagram_walkers::DiagramWalkers;
+use super::dictionary::Dictionary;
+use super::page_settings::PageSettings;
+use super::vdiagrams::VDiagrams;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Diagram {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_settings: Option<PageSettings>,
+
     pub diagram_settings: DiagramSettings,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dictionary: Option<Dictionary>,
+
     pub diagram_walkers: DiagramWalkers,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vdiagrams: Option<VDiagrams>,
+
     pub column_groups: ColumnGroups,
 }
+
+impl From<crate::dtos::diagram::Diagram> for Diagram {
+    fn from(dto: crate::dtos::diagram::Diagram) -> Self {
+        Self {
+            page_settings: dto.page_settings.map(Into::into),
+            diagram_settings: dto.diagram_settings.into(),
+            dictionary: (!dto.dictionary.words.is_empty()).then(|| dto.dictionary.into()),
+            diagram_walkers: dto.diagram_walkers.into(),
+            vdiagrams: (!dto.vdiagrams.is_empty()).then(|| VDiagrams {
+                vdiagrams: Some(dto.vdiagrams.into_iter().map(Into::into).collect()),
+            }),
+            column_groups: dto.column_groups.into(),
+        }
+    }
+}
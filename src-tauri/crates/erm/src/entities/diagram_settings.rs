@@ -6,9 +6,71 @@ pub struct DiagramSettings {
     // notation_expand_group: bool,
     // table_style: String,
     pub database: String,
-    // notation: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notation: Option<String>,
     // notation_level: u32,
     // view_mode: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validate_physical_name: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suspend_validator: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub export_settings: Option<ExportSettings>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category_settings: Option<CategorySettings>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category_name_to_export: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ddl_output: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excel_output: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_output: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub put_diagram_on_excel: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub use_logical_name_as_sheet: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Category {
+    #[serde(default)]
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "node_element")]
+    pub node_elements: Option<Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct Categories {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "category")]
+    pub categories: Option<Vec<Category>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CategorySettings {
+    // free_layout: bool,
+    // show_referred_tables: bool,
+    #[serde(default)]
+    pub categories: Categories,
 }
 
 // impl DiagramSetting {
@@ -16,3 +78,50 @@ pub struct DiagramSettings {
 //         &self.database
 //     }
 // }
+
+impl From<crate::dtos::diagram_settings::DiagramSettings> for DiagramSettings {
+    fn from(dto: crate::dtos::diagram_settings::DiagramSettings) -> Self {
+        Self {
+            database: dto.database,
+            notation: dto.notation,
+            validate_physical_name: dto.validate_physical_name,
+            suspend_validator: dto.suspend_validator,
+            export_settings: dto.export_settings.map(Into::into),
+            category_settings: dto.category_settings.map(Into::into),
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_settings::ExportSettings> for ExportSettings {
+    fn from(dto: crate::dtos::diagram_settings::ExportSettings) -> Self {
+        Self {
+            category_name_to_export: dto.category_name_to_export,
+            ddl_output: dto.ddl_output,
+            excel_output: dto.excel_output,
+            image_output: dto.image_output,
+            put_diagram_on_excel: dto.put_diagram_on_excel,
+            use_logical_name_as_sheet: dto.use_logical_name_as_sheet,
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_settings::Category> for Category {
+    fn from(dto: crate::dtos::diagram_settings::Category) -> Self {
+        Self {
+            name: dto.name,
+            node_elements: (!dto.node_elements.is_empty()).then_some(dto.node_elements),
+            selected: dto.selected,
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_settings::CategorySettings> for CategorySettings {
+    fn from(dto: crate::dtos::diagram_settings::CategorySettings) -> Self {
+        Self {
+            categories: Categories {
+                categories: (!dto.categories.is_empty())
+                    .then(|| dto.categories.into_iter().map(Into::into).collect()),
+            },
+        }
+    }
+}
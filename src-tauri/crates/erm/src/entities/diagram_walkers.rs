@@ -33,7 +33,7 @@ pub struct Relationship {
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct Connections {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     #[serde(rename = "relationship")]
     pub relationships: Option<Vec<Relationship>>,
 }
@@ -61,6 +61,9 @@ pub struct NormalColumn {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub unsigned: Option<bool>,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<String>,
+
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub not_null: Option<bool>,
 
@@ -100,10 +103,61 @@ pub struct Columns {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct Indexes {}
+pub struct IndexColumn {
+    pub column_id: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desc: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct IndexColumns {
+    #[serde(default)]
+    pub column: Vec<IndexColumn>,
+}
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct CompoundUniqueKeyList {}
+pub struct Index {
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "type")]
+    pub index_type: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_text: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub non_unique: Option<bool>,
+
+    #[serde(default)]
+    pub columns: IndexColumns,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct Indexes {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "index")]
+    pub indexes: Option<Vec<Index>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompoundUniqueKey {
+    pub name: String,
+
+    #[serde(default)]
+    pub columns: IndexColumns,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct CompoundUniqueKeyList {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "compound_unique_key")]
+    pub compound_unique_keys: Option<Vec<CompoundUniqueKey>>,
+}
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TableProperties {}
@@ -132,14 +186,280 @@ pub struct Table {
     pub option: Option<String>,
 
     pub columns: Columns,
+
+    #[serde(default)]
     pub indexes: Indexes,
+
+    #[serde(default)]
     pub compound_unique_key_list: CompoundUniqueKeyList,
     pub table_properties: TableProperties,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Note {
+    #[serde(default)]
+    pub note_text: String,
+    pub height: u16,
+    pub width: u16,
+    pub font_name: String,
+    pub font_size: u16,
+    pub x: u16,
+    pub y: u16,
+    pub color: Color,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct GroupWalkers {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "walker")]
+    pub walkers: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalkerGroup {
+    #[serde(default)]
+    pub group_name: String,
+    pub height: u16,
+    pub width: u16,
+    pub font_name: String,
+    pub font_size: u16,
+    pub x: u16,
+    pub y: u16,
+    pub color: Color,
+
+    #[serde(default)]
+    pub walkers: GroupWalkers,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DiagramWalkers {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[serde(rename = "table")]
     pub tables: Option<Vec<Table>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "walker_note")]
+    pub notes: Option<Vec<Note>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "walker_group")]
+    pub groups: Option<Vec<WalkerGroup>>,
+}
+
+impl From<crate::dtos::diagram_walkers::Color> for Color {
+    fn from(dto: crate::dtos::diagram_walkers::Color) -> Self {
+        Self {
+            r: dto.r,
+            g: dto.g,
+            b: dto.b,
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_walkers::FkColumn> for FkColumn {
+    fn from(dto: crate::dtos::diagram_walkers::FkColumn) -> Self {
+        Self {
+            fk_column_name: dto.fk_column_name,
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_walkers::FkColumns> for FkColumns {
+    fn from(dto: crate::dtos::diagram_walkers::FkColumns) -> Self {
+        Self {
+            fk_column: dto.fk_column.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_walkers::Relationship> for Relationship {
+    fn from(dto: crate::dtos::diagram_walkers::Relationship) -> Self {
+        Self {
+            name: dto.name,
+            source: dto.source,
+            target: dto.target,
+            fk_columns: dto.fk_columns.into(),
+            parent_cardinality: dto.parent_cardinality,
+            child_cardinality: dto.child_cardinality,
+            reference_for_pk: dto.reference_for_pk,
+            on_delete_action: dto.on_delete_action,
+            on_update_action: dto.on_update_action,
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_walkers::Connections> for Connections {
+    fn from(dto: crate::dtos::diagram_walkers::Connections) -> Self {
+        Self {
+            relationships: dto
+                .relationships
+                .map(|v| v.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_walkers::NormalColumn> for NormalColumn {
+    fn from(dto: crate::dtos::diagram_walkers::NormalColumn) -> Self {
+        Self {
+            physical_name: dto.physical_name,
+            logical_name: dto.logical_name,
+            description: dto.description,
+            column_type: dto.column_type,
+            length: dto.length,
+            decimal: dto.decimal,
+            unsigned: dto.unsigned,
+            args: dto.args,
+            not_null: dto.not_null,
+            unique_key: dto.unique_key,
+            default_value: dto.default_value,
+            primary_key: dto.primary_key,
+            auto_increment: dto.auto_increment,
+            referred_column: dto.referred_column,
+            relationship: dto.relationship,
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_walkers::Columns> for Columns {
+    fn from(dto: crate::dtos::diagram_walkers::Columns) -> Self {
+        Self {
+            items: dto.items.map(|v| {
+                v.into_iter()
+                    .map(|item| match item {
+                        crate::dtos::diagram_walkers::Column::Normal(column) => {
+                            Column::Normal(column.into())
+                        }
+                        crate::dtos::diagram_walkers::Column::Group(column) => {
+                            Column::Group(column)
+                        }
+                    })
+                    .collect()
+            }),
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_walkers::IndexColumn> for IndexColumn {
+    fn from(dto: crate::dtos::diagram_walkers::IndexColumn) -> Self {
+        Self {
+            column_id: dto.column_id,
+            desc: dto.desc,
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_walkers::IndexColumns> for IndexColumns {
+    fn from(dto: crate::dtos::diagram_walkers::IndexColumns) -> Self {
+        Self {
+            column: dto.column.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_walkers::Index> for Index {
+    fn from(dto: crate::dtos::diagram_walkers::Index) -> Self {
+        Self {
+            name: dto.name,
+            index_type: dto.index_type,
+            description: dto.description,
+            full_text: dto.full_text,
+            non_unique: dto.non_unique,
+            columns: dto.columns.into(),
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_walkers::Indexes> for Indexes {
+    fn from(dto: crate::dtos::diagram_walkers::Indexes) -> Self {
+        Self {
+            indexes: dto.indexes.map(|v| v.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_walkers::CompoundUniqueKey> for CompoundUniqueKey {
+    fn from(dto: crate::dtos::diagram_walkers::CompoundUniqueKey) -> Self {
+        Self {
+            name: dto.name,
+            columns: dto.columns.into(),
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_walkers::CompoundUniqueKeyList> for CompoundUniqueKeyList {
+    fn from(dto: crate::dtos::diagram_walkers::CompoundUniqueKeyList) -> Self {
+        Self {
+            compound_unique_keys: dto
+                .compound_unique_keys
+                .map(|v| v.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_walkers::Table> for Table {
+    fn from(dto: crate::dtos::diagram_walkers::Table) -> Self {
+        Self {
+            physical_name: dto.physical_name,
+            logical_name: dto.logical_name,
+            description: dto.description,
+            height: dto.height,
+            width: dto.width,
+            font_name: dto.font_name,
+            font_size: dto.font_size,
+            x: dto.x,
+            y: dto.y,
+            color: dto.color.into(),
+            connections: dto.connections.into(),
+            table_constraint: dto.table_constraint,
+            primary_key_name: dto.primary_key_name,
+            option: dto.option,
+            columns: dto.columns.into(),
+            indexes: dto.indexes.into(),
+            compound_unique_key_list: dto.compound_unique_key_list.into(),
+            table_properties: TableProperties {},
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_walkers::Note> for Note {
+    fn from(dto: crate::dtos::diagram_walkers::Note) -> Self {
+        Self {
+            note_text: dto.note_text,
+            height: dto.height,
+            width: dto.width,
+            font_name: dto.font_name,
+            font_size: dto.font_size,
+            x: dto.x,
+            y: dto.y,
+            color: dto.color.into(),
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_walkers::WalkerGroup> for WalkerGroup {
+    fn from(dto: crate::dtos::diagram_walkers::WalkerGroup) -> Self {
+        Self {
+            group_name: dto.group_name,
+            height: dto.height,
+            width: dto.width,
+            font_name: dto.font_name,
+            font_size: dto.font_size,
+            x: dto.x,
+            y: dto.y,
+            color: dto.color.into(),
+            walkers: GroupWalkers {
+                walkers: (!dto.walkers.is_empty()).then_some(dto.walkers),
+            },
+        }
+    }
+}
+
+impl From<crate::dtos::diagram_walkers::DiagramWalkers> for DiagramWalkers {
+    fn from(dto: crate::dtos::diagram_walkers::DiagramWalkers) -> Self {
+        Self {
+            tables: dto.tables.map(|v| v.into_iter().map(Into::into).collect()),
+            notes: dto.notes.map(|v| v.into_iter().map(Into::into).collect()),
+            groups: dto.groups.map(|v| v.into_iter().map(Into::into).collect()),
+        }
+    }
 }
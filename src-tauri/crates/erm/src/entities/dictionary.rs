@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Word {
+    pub physical_name: String,
+    pub logical_name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl From<crate::dtos::dictionary::Word> for Word {
+    fn from(dto: crate::dtos::dictionary::Word) -> Self {
+        Self {
+            physical_name: dto.physical_name,
+            logical_name: dto.logical_name,
+            description: dto.description,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct Dictionary {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "word")]
+    pub words: Option<Vec<Word>>,
+}
+
+impl From<crate::dtos::dictionary::Dictionary> for Dictionary {
+    fn from(dto: crate::dtos::dictionary::Dictionary) -> Self {
+        Self {
+            words: (!dto.words.is_empty()).then(|| dto.words.into_iter().map(Into::into).collect()),
+        }
+    }
+}
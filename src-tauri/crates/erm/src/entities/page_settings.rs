@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct PageSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direction_horizontal: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale: Option<u16>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paper_size: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_margin: Option<u16>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub left_margin: Option<u16>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bottom_margin: Option<u16>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub right_margin: Option<u16>,
+}
+
+impl From<crate::dtos::page_settings::PageSettings> for PageSettings {
+    fn from(dto: crate::dtos::page_settings::PageSettings) -> Self {
+        Self {
+            direction_horizontal: dto.direction_horizontal,
+            scale: dto.scale,
+            paper_size: dto.paper_size,
+            top_margin: dto.top_margin,
+            left_margin: dto.left_margin,
+            bottom_margin: dto.bottom_margin,
+            right_margin: dto.right_margin,
+        }
+    }
+}
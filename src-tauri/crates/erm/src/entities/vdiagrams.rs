@@ -0,0 +1,89 @@
+use super::diagram_walkers::{Note, WalkerGroup};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VTable {
+    pub table_id: String,
+    pub x: u16,
+    pub y: u16,
+
+    #[serde(default)]
+    pub font_name: String,
+
+    #[serde(default)]
+    pub font_size: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct VTables {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "vtable")]
+    pub vtables: Option<Vec<VTable>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct WalkerNotes {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "walker_note")]
+    pub notes: Option<Vec<Note>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct WalkerGroups {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "walker_group")]
+    pub groups: Option<Vec<WalkerGroup>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VDiagram {
+    pub vdiagram_name: String,
+
+    #[serde(default)]
+    pub vtables: VTables,
+
+    #[serde(default)]
+    pub walker_notes: WalkerNotes,
+
+    #[serde(default)]
+    pub walker_groups: WalkerGroups,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct VDiagrams {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "vdiagram")]
+    pub vdiagrams: Option<Vec<VDiagram>>,
+}
+
+impl From<crate::dtos::vdiagrams::VTable> for VTable {
+    fn from(dto: crate::dtos::vdiagrams::VTable) -> Self {
+        Self {
+            table_id: dto.table_id,
+            x: dto.x,
+            y: dto.y,
+            font_name: dto.font_name,
+            font_size: dto.font_size,
+        }
+    }
+}
+
+impl From<crate::dtos::vdiagrams::VDiagram> for VDiagram {
+    fn from(dto: crate::dtos::vdiagrams::VDiagram) -> Self {
+        Self {
+            vdiagram_name: dto.name,
+            vtables: VTables {
+                vtables: (!dto.tables.is_empty())
+                    .then(|| dto.tables.into_iter().map(Into::into).collect()),
+            },
+            walker_notes: WalkerNotes {
+                notes: (!dto.notes.is_empty())
+                    .then(|| dto.notes.into_iter().map(Into::into).collect()),
+            },
+            walker_groups: WalkerGroups {
+                groups: (!dto.groups.is_empty())
+                    .then(|| dto.groups.into_iter().map(Into::into).collect()),
+            },
+        }
+    }
+}
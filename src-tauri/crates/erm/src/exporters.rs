@@ -0,0 +1,155 @@
+pub mod dbml;
+pub mod ddl;
+pub mod dot;
+pub mod html;
+pub mod json_schema;
+pub mod markdown;
+pub mod mermaid;
+pub mod plantuml;
+pub mod png;
+pub mod prisma;
+pub mod rust;
+pub mod svg;
+pub mod text;
+pub mod typescript;
+pub mod xlsx;
+
+use crate::dtos::diagram::Diagram;
+use std::collections::{HashMap, HashSet};
+
+/// Escapes text for HTML and SVG, in content as well as in attribute values.
+pub(crate) fn escape_markup(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The crow's-foot ends of a relationship line, as written by Mermaid and PlantUML:
+/// the parent end on the left of the line and the child end on its right.
+pub(crate) fn crows_foot_ends(
+    parent_cardinality: &str,
+    child_cardinality: &str,
+) -> (&'static str, &'static str) {
+    let parent = match parent_cardinality {
+        "0..1" => "|o",
+        "1..n" => "}|",
+        "0..n" => "}o",
+        _ => "||",
+    };
+    let child = match child_cardinality {
+        "0..1" => "o|",
+        "1..n" => "|{",
+        "0..n" => "o{",
+        _ => "||",
+    };
+    (parent, child)
+}
+
+/// Replaces the characters other than ASCII letters, digits and underscores, which
+/// diagram languages do not accept in bare names.
+pub(crate) fn identifier(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// The [`identifier`]s of the tables for diagram languages. Names that lose characters
+/// and would clash with another table's get a counter, so `会員` and `投稿` become `__`
+/// and `___2`.
+pub(crate) struct EntityNames(HashMap<String, String>);
+
+impl EntityNames {
+    pub(crate) fn new(diagram: &Diagram) -> Self {
+        let mut tables: Vec<&str> = diagram
+            .tables()
+            .iter()
+            .map(|table| table.physical_name.as_str())
+            .collect();
+        tables.sort();
+        // Names that are identifiers already keep them.
+        let mut taken: HashSet<String> = tables
+            .iter()
+            .filter(|name| identifier(name) == **name)
+            .map(|name| name.to_string())
+            .collect();
+        let mut names = HashMap::new();
+        for name in tables {
+            let base = identifier(name);
+            let mut entity = base.clone();
+            if entity != name {
+                let mut count = 1;
+                while !taken.insert(entity.clone()) {
+                    count += 1;
+                    entity = format!("{base}_{count}");
+                }
+            }
+            names.insert(name.to_string(), entity);
+        }
+        Self(names)
+    }
+
+    /// The entity name of a table, by physical name.
+    pub(crate) fn get(&self, table: &str) -> String {
+        self.0
+            .get(table)
+            .cloned()
+            .unwrap_or_else(|| identifier(table))
+    }
+}
+
+/// A pascal case name for a type, e.g. `MemberProfiles` for `MEMBER_PROFILES`. Words
+/// in mixed case, e.g. `authorId`, keep their case.
+pub(crate) fn pascal_case(name: &str) -> String {
+    let mut pascal = String::new();
+    for word in identifier(name).split('_') {
+        let mixed_case = word.contains(|c: char| c.is_ascii_lowercase())
+            && word.contains(|c: char| c.is_ascii_uppercase());
+        let word = if mixed_case {
+            word.to_string()
+        } else {
+            word.to_lowercase()
+        };
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            pascal.push(first.to_ascii_uppercase());
+            pascal.extend(chars);
+        }
+    }
+    if pascal.is_empty() || pascal.starts_with(|c: char| c.is_ascii_digit()) {
+        pascal.insert(0, '_');
+    }
+    pascal
+}
+
+/// The lines documenting a table or a column in generated code: the logical name,
+/// unless it is the physical name, then the description.
+pub(crate) fn doc_lines(physical_name: &str, logical_name: &str, description: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let logical_name = logical_name.trim();
+    if !logical_name.is_empty() && logical_name != physical_name {
+        lines.push(logical_name.to_string());
+    }
+    let description = description.trim();
+    if !description.is_empty() {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.extend(description.lines().map(|line| line.trim_end().to_string()));
+    }
+    lines
+}
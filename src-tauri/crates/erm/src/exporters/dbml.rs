@@ -0,0 +1,266 @@
+//! DBML of the schema, for dbdiagram.io. Logical names and descriptions are written
+//! as notes, the logical name on the first line, so that they survive a round trip
+//! through [`crate::importers::dbml`]. Categories become table groups.
+//!
+//! Refs are one-to-many or one-to-one; DBML has no optional ends, so the exact
+//! cardinalities are derived from the columns again on import.
+
+use crate::column_type::format_column_type;
+use crate::dtos::diagram::{Diagram, ResolvedColumn};
+use crate::dtos::diagram_walkers::{Relationship, Table, table_name_of};
+use std::fmt::Write;
+
+fn is_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// A name, in double quotes unless it is a plain identifier.
+fn name(name: &str) -> String {
+    if is_identifier(name) {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+/// A string, in triple quotes when it spans several lines.
+fn string(value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('\'', "\\'");
+    if value.contains('\n') {
+        format!("'''{escaped}'''")
+    } else {
+        format!("'{escaped}'")
+    }
+}
+
+/// A column type, quoted when it has spaces such as `bigint unsigned`.
+fn column_type(column: &ResolvedColumn) -> String {
+    let column_type = format_column_type(&column.column);
+    if column_type.is_empty() {
+        return "unknown".to_string();
+    }
+    let (base, args) = column_type
+        .split_once('(')
+        .map_or((column_type.as_str(), ""), |(base, args)| (base, args));
+    let plain_args = args
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, ',' | ' ' | ')'));
+    if is_identifier(base) && plain_args {
+        column_type
+    } else {
+        name(&column_type)
+    }
+}
+
+/// A default value: numbers and keywords as they are, expressions such as
+/// `CURRENT_TIMESTAMP` or `now()` in backticks and anything else as a string.
+fn default_value(value: &str) -> String {
+    let lowercase = value.to_lowercase();
+    if value.parse::<f64>().is_ok() || matches!(lowercase.as_str(), "true" | "false" | "null") {
+        value.to_string()
+    } else if value.contains('(')
+        || (is_identifier(value) && value.chars().all(|c| !c.is_ascii_lowercase()))
+    {
+        format!("`{value}`")
+    } else {
+        string(value)
+    }
+}
+
+/// The note holding a logical name and a description, if there is anything to keep.
+fn note(physical_name: &str, logical_name: &str, description: &str) -> Option<String> {
+    let description = description.trim();
+    let logical_name = match logical_name.trim() {
+        "" => physical_name,
+        logical_name => logical_name,
+    };
+    if description.is_empty() {
+        (logical_name != physical_name).then(|| logical_name.to_string())
+    } else {
+        Some(format!("{logical_name}\n{description}"))
+    }
+}
+
+fn push_column(out: &mut String, column: &ResolvedColumn, single_primary_key: bool) {
+    let _ = write!(
+        out,
+        "  {} {}",
+        name(&column.column.physical_name),
+        column_type(column)
+    );
+    let mut settings = Vec::new();
+    if column.is_primary_key() && single_primary_key {
+        settings.push("pk".to_string());
+    }
+    if column.column.auto_increment.unwrap_or(false) {
+        settings.push("increment".to_string());
+    }
+    if column.is_not_null() && !(column.is_primary_key() && single_primary_key) {
+        settings.push("not null".to_string());
+    }
+    if column.is_unique() {
+        settings.push("unique".to_string());
+    }
+    if let Some(value) = &column.column.default_value {
+        settings.push(format!("default: {}", default_value(value)));
+    }
+    if let Some(note) = note(
+        &column.column.physical_name,
+        column.column.logical_name.as_deref().unwrap_or_default(),
+        column.column.description.as_deref().unwrap_or_default(),
+    ) {
+        settings.push(format!("note: {}", string(&note)));
+    }
+    if !settings.is_empty() {
+        let _ = write!(out, " [{}]", settings.join(", "));
+    }
+    out.push('\n');
+}
+
+fn column_list(columns: &[&str]) -> String {
+    let names: Vec<String> = columns.iter().map(|column| name(column)).collect();
+    if names.len() == 1 {
+        names[0].clone()
+    } else {
+        format!("({})", names.join(", "))
+    }
+}
+
+fn push_table(out: &mut String, diagram: &Diagram, table: &Table) {
+    let columns = diagram.resolve_columns(table);
+    let primary_key: Vec<&str> = columns
+        .iter()
+        .filter(|column| column.is_primary_key())
+        .map(|column| column.column.physical_name.as_str())
+        .collect();
+    let _ = writeln!(out, "Table {} {{", name(&table.physical_name));
+    for column in &columns {
+        push_column(out, column, primary_key.len() == 1);
+    }
+    if let Some(note) = note(
+        &table.physical_name,
+        &table.logical_name,
+        &table.description,
+    ) {
+        let _ = writeln!(out, "\n  Note: {}", string(&note));
+    }
+
+    let mut indexes = Vec::new();
+    if primary_key.len() > 1 {
+        indexes.push(format!("{} [pk]", column_list(&primary_key)));
+    }
+    for index in table.index_list() {
+        let columns: Vec<&str> = index
+            .columns
+            .column
+            .iter()
+            .map(|column| column.column_name())
+            .collect();
+        let unique = if index.is_unique() { "unique, " } else { "" };
+        indexes.push(format!(
+            "{} [{unique}name: {}]",
+            column_list(&columns),
+            string(&index.name)
+        ));
+    }
+    for key in table.compound_unique_keys() {
+        let columns: Vec<&str> = key
+            .columns
+            .column
+            .iter()
+            .map(|column| column.column_name())
+            .collect();
+        indexes.push(format!(
+            "{} [unique, name: {}]",
+            column_list(&columns),
+            string(&key.name)
+        ));
+    }
+    if !indexes.is_empty() {
+        out.push_str("\n  indexes {\n");
+        for index in indexes {
+            let _ = writeln!(out, "    {index}");
+        }
+        out.push_str("  }\n");
+    }
+    out.push_str("}\n\n");
+}
+
+fn action(action: &str) -> String {
+    action.trim().to_lowercase()
+}
+
+fn push_relationship(out: &mut String, diagram: &Diagram, relationship: &Relationship) {
+    let Some((fk_columns, referred)) = diagram.foreign_key_columns(relationship) else {
+        return;
+    };
+    let fk_columns: Vec<&str> = fk_columns.iter().map(String::as_str).collect();
+    let referred: Vec<&str> = referred.iter().map(String::as_str).collect();
+
+    let operator = match relationship.child_cardinality.as_str() {
+        "0..1" | "1" => "-",
+        _ => ">",
+    };
+    let _ = write!(out, "Ref");
+    if !relationship.name.is_empty() {
+        let _ = write!(out, " {}", name(&relationship.name));
+    }
+    let _ = write!(
+        out,
+        ": {}.{} {operator} {}.{}",
+        name(relationship.target_table()),
+        column_list(&fk_columns),
+        name(relationship.source_table()),
+        column_list(&referred)
+    );
+    let mut settings = Vec::new();
+    if !relationship.on_delete_action.trim().is_empty() {
+        settings.push(format!(
+            "delete: {}",
+            action(&relationship.on_delete_action)
+        ));
+    }
+    if !relationship.on_update_action.trim().is_empty() {
+        settings.push(format!(
+            "update: {}",
+            action(&relationship.on_update_action)
+        ));
+    }
+    if !settings.is_empty() {
+        let _ = write!(out, " [{}]", settings.join(", "));
+    }
+    out.push('\n');
+}
+
+/// Renders the diagram as DBML: the project with the database, the tables in the
+/// order of the diagram, the refs and the table groups.
+pub fn render(diagram: &Diagram) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Project diagram {{\n  database_type: {}\n}}\n",
+        string(&diagram.diagram_settings.database)
+    );
+    for table in diagram.tables() {
+        push_table(&mut out, diagram, table);
+    }
+    let relationships: Vec<&Relationship> = diagram.relationships().collect();
+    for relationship in &relationships {
+        push_relationship(&mut out, diagram, relationship);
+    }
+    if !relationships.is_empty() {
+        out.push('\n');
+    }
+    for category in diagram.categories() {
+        let _ = writeln!(out, "TableGroup {} {{", name(&category.name));
+        for id in &category.node_elements {
+            let _ = writeln!(out, "  {}", name(table_name_of(id)));
+        }
+        out.push_str("}\n\n");
+    }
+    let trimmed = out.trim_end().len();
+    out.truncate(trimmed);
+    out.push('\n');
+    out
+}
@@ -0,0 +1,338 @@
+//! Generation of a DDL script creating the schema of a diagram.
+//!
+//! Tables come first, followed by indexes, comments and finally foreign keys, so the
+//! script runs regardless of the order of the tables. Identifiers are quoted only when
+//! they would not be valid otherwise, e.g. reserved words.
+
+use crate::column_type::format_sql_type;
+use crate::dtos::diagram::{Diagram, ResolvedColumn};
+use crate::dtos::diagram_walkers::{Column, Index, Relationship, Table};
+use crate::importers::ddl::Dialect;
+use crate::validation::reserved_words;
+use std::fmt::Write;
+
+struct Generator<'a> {
+    diagram: &'a Diagram,
+    dialect: Dialect,
+    out: String,
+}
+
+fn is_plain_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+pub(crate) fn quote_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// The comment of a table or column: the logical name, followed by the description on
+/// the next lines as the importers expect.
+fn comment(logical_name: Option<&str>, description: Option<&str>) -> Option<String> {
+    let logical_name = logical_name.filter(|name| !name.is_empty());
+    let description = description.filter(|description| !description.is_empty());
+    match (logical_name, description) {
+        (Some(name), Some(description)) => Some(format!("{name}\n{description}")),
+        (Some(name), None) => Some(name.to_string()),
+        (None, Some(description)) => Some(description.to_string()),
+        (None, None) => None,
+    }
+}
+
+/// Default values are stored unquoted; numbers, keywords and expressions are kept as
+/// they are and everything else becomes a string literal.
+fn default_value(value: &str) -> String {
+    let upper = value.to_ascii_uppercase();
+    if value.parse::<f64>().is_ok()
+        || value.contains('(')
+        || matches!(
+            upper.as_str(),
+            "NULL"
+                | "TRUE"
+                | "FALSE"
+                | "CURRENT_TIMESTAMP"
+                | "CURRENT_DATE"
+                | "CURRENT_TIME"
+                | "LOCALTIMESTAMP"
+        )
+    {
+        value.to_string()
+    } else {
+        quote_string(value)
+    }
+}
+
+/// Quotes an identifier only when it would not be valid otherwise, e.g. a reserved word.
+pub(crate) fn quote_identifier(dialect: Dialect, name: &str) -> String {
+    if is_plain_identifier(name) && !reserved_words::is_reserved(dialect.database(), name) {
+        return name.to_string();
+    }
+    match dialect {
+        Dialect::MySql => format!("`{}`", name.replace('`', "``")),
+        Dialect::PostgreSql => format!("\"{}\"", name.replace('"', "\"\"")),
+    }
+}
+
+impl Generator<'_> {
+    fn quote(&self, name: &str) -> String {
+        quote_identifier(self.dialect, name)
+    }
+
+    fn column_list<'n>(&self, names: impl IntoIterator<Item = &'n str>) -> String {
+        names
+            .into_iter()
+            .map(|name| self.quote(name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn column_type(&self, column: &ResolvedColumn) -> String {
+        let formatted = format_sql_type(&column.column);
+        match self.dialect {
+            Dialect::MySql => formatted,
+            Dialect::PostgreSql => postgresql_type(&formatted),
+        }
+    }
+
+    fn push_column(&mut self, column: &ResolvedColumn, single_primary_key: bool) {
+        let mut line = format!(
+            "  {} {}",
+            self.quote(&column.column.physical_name),
+            self.column_type(column)
+        );
+        let auto_increment = column.column.auto_increment.unwrap_or(false);
+        if auto_increment && self.dialect == Dialect::PostgreSql {
+            line.push_str(" GENERATED BY DEFAULT AS IDENTITY");
+        }
+        if column.is_not_null() {
+            line.push_str(" NOT NULL");
+        }
+        if let Some(value) = &column.column.default_value {
+            let _ = write!(line, " DEFAULT {}", default_value(value));
+        }
+        if auto_increment && self.dialect == Dialect::MySql {
+            line.push_str(" AUTO_INCREMENT");
+        }
+        // A single-column primary key is unique already.
+        if column.is_unique() && !(column.is_primary_key() && single_primary_key) {
+            line.push_str(" UNIQUE");
+        }
+        if self.dialect == Dialect::MySql
+            && let Some(comment) = comment(
+                column.column.logical_name.as_deref(),
+                column.column.description.as_deref(),
+            )
+        {
+            let _ = write!(line, " COMMENT {}", quote_string(&comment));
+        }
+        self.out.push_str(&line);
+    }
+
+    fn push_table(&mut self, table: &Table) {
+        let columns = self.diagram.resolve_columns(table);
+        let primary_key: Vec<&str> = columns
+            .iter()
+            .filter(|column| column.is_primary_key())
+            .map(|column| column.column.physical_name.as_str())
+            .collect();
+
+        let _ = writeln!(
+            self.out,
+            "CREATE TABLE {} (",
+            self.quote(&table.physical_name)
+        );
+        let mut first = true;
+        let mut separate = |out: &mut String| {
+            if !first {
+                out.push_str(",\n");
+            }
+            first = false;
+        };
+        for column in &columns {
+            separate(&mut self.out);
+            self.push_column(column, primary_key.len() == 1);
+        }
+        if !primary_key.is_empty() {
+            separate(&mut self.out);
+            let name = table
+                .primary_key_name
+                .as_deref()
+                .filter(|name| !name.is_empty())
+                .map(|name| format!("CONSTRAINT {} ", self.quote(name)))
+                .unwrap_or_default();
+            let _ = write!(
+                self.out,
+                "  {name}PRIMARY KEY ({})",
+                self.column_list(primary_key)
+            );
+        }
+        for key in table.compound_unique_keys() {
+            separate(&mut self.out);
+            let _ = write!(
+                self.out,
+                "  CONSTRAINT {} UNIQUE ({})",
+                self.quote(&key.name),
+                self.column_list(key.column_names())
+            );
+        }
+        if let Some(constraint) = table
+            .table_constraint
+            .as_deref()
+            .filter(|constraint| !constraint.trim().is_empty())
+        {
+            separate(&mut self.out);
+            let _ = write!(self.out, "  {}", constraint.trim());
+        }
+        self.out.push_str("\n)");
+        if self.dialect == Dialect::MySql {
+            if let Some(option) = table.option.as_deref().filter(|option| !option.is_empty()) {
+                let _ = write!(self.out, " {option}");
+            }
+            if let Some(comment) = comment(Some(&table.logical_name), Some(&table.description)) {
+                let _ = write!(self.out, " COMMENT={}", quote_string(&comment));
+            }
+        }
+        self.out.push_str(";\n\n");
+    }
+
+    fn push_index(&mut self, table: &Table, index: &Index) {
+        let columns = index
+            .columns
+            .column
+            .iter()
+            .map(|column| {
+                let name = self.quote(column.column_name());
+                if column.desc.unwrap_or(false) {
+                    format!("{name} DESC")
+                } else {
+                    name
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writeln!(
+            self.out,
+            "CREATE {}INDEX {} ON {} ({columns});",
+            if index.is_unique() { "UNIQUE " } else { "" },
+            self.quote(&index.name),
+            self.quote(&table.physical_name)
+        );
+    }
+
+    fn push_comments(&mut self, table: &Table) {
+        let name = self.quote(&table.physical_name);
+        if let Some(comment) = comment(Some(&table.logical_name), Some(&table.description)) {
+            let _ = writeln!(
+                self.out,
+                "COMMENT ON TABLE {name} IS {};",
+                quote_string(&comment)
+            );
+        }
+        for column in self.diagram.resolve_columns(table) {
+            if let Some(comment) = comment(
+                column.column.logical_name.as_deref(),
+                column.column.description.as_deref(),
+            ) {
+                let _ = writeln!(
+                    self.out,
+                    "COMMENT ON COLUMN {name}.{} IS {};",
+                    self.quote(&column.column.physical_name),
+                    quote_string(&comment)
+                );
+            }
+        }
+    }
+
+    fn push_foreign_key(&mut self, table: &Table, relationship: &Relationship) {
+        let source = relationship.source_table();
+        // The referenced columns are those the foreign key columns refer to.
+        let mut columns = Vec::new();
+        let mut referenced = Vec::new();
+        for item in table.column_items() {
+            if let Column::Normal(column) = item
+                && let Some((referred_table, referred_column)) = column.referred_table_and_column()
+                && referred_table == source
+                && (relationship.fk_columns.fk_column.is_empty()
+                    || relationship
+                        .fk_columns
+                        .fk_column
+                        .iter()
+                        .any(|fk| fk.fk_column_name == column.physical_name))
+            {
+                columns.push(column.physical_name.as_str());
+                referenced.push(referred_column);
+            }
+        }
+        if columns.is_empty() {
+            return;
+        }
+        let _ = write!(
+            self.out,
+            "ALTER TABLE {} ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({})",
+            self.quote(&table.physical_name),
+            self.quote(&relationship.name),
+            self.column_list(columns),
+            self.quote(source),
+            self.column_list(referenced)
+        );
+        for (clause, action) in [
+            ("ON DELETE", &relationship.on_delete_action),
+            ("ON UPDATE", &relationship.on_update_action),
+        ] {
+            if !action.is_empty() {
+                let _ = write!(self.out, " {clause} {action}");
+            }
+        }
+        self.out.push_str(";\n");
+    }
+}
+
+/// Maps the MySQL-flavored types of ERFlute to PostgreSQL, e.g. `datetime` to
+/// `timestamp`. PostgreSQL has no unsigned types, so `unsigned` is dropped.
+fn postgresql_type(formatted: &str) -> String {
+    let formatted = formatted.strip_suffix(" unsigned").unwrap_or(formatted);
+    let (name, args) = match formatted.split_once('(') {
+        Some((name, args)) => (name, Some(format!("({args}"))),
+        None => (formatted, None),
+    };
+    let args = args.unwrap_or_default();
+    match name {
+        "datetime" => "timestamp".to_string(),
+        "tinyint" | "year" => "smallint".to_string(),
+        "mediumint" | "int" => "integer".to_string(),
+        "double" => "double precision".to_string(),
+        "float" if !args.is_empty() => "real".to_string(),
+        "tinytext" | "mediumtext" | "longtext" | "enum" | "set" => "text".to_string(),
+        "blob" | "tinyblob" | "mediumblob" | "longblob" | "binary" | "varbinary" => {
+            "bytea".to_string()
+        }
+        "smallint" | "bigint" | "integer" => name.to_string(),
+        _ => format!("{name}{args}"),
+    }
+}
+
+/// Generates the DDL creating every table, index and foreign key of the diagram.
+pub fn generate(diagram: &Diagram, dialect: Dialect) -> String {
+    let mut generator = Generator {
+        diagram,
+        dialect,
+        out: String::new(),
+    };
+    for table in diagram.tables() {
+        generator.push_table(table);
+    }
+    for table in diagram.tables() {
+        for index in table.index_list() {
+            generator.push_index(table, index);
+        }
+        if dialect == Dialect::PostgreSql {
+            generator.push_comments(table);
+        }
+    }
+    for table in diagram.tables() {
+        for relationship in table.relationships() {
+            generator.push_foreign_key(table, relationship);
+        }
+    }
+    generator.out.trim_end().to_string() + "\n"
+}
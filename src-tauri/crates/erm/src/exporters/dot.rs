@@ -0,0 +1,177 @@
+//! A Graphviz DOT graph of the schema for a dependency overview, laid out by Graphviz
+//! rather than from the stored positions. Tables are record nodes and relationships are
+//! edges from the child to the parent, labeled with the cardinality at each end.
+
+use crate::column_type::format_column_type;
+use crate::dtos::diagram::Diagram;
+use crate::dtos::diagram_walkers::{Relationship, Table};
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Write;
+
+/// Restricts the graph to the tables within `hops` relationships of `table`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Neighbourhood {
+    pub table: String,
+    pub hops: usize,
+}
+
+/// Options of [`render`].
+#[derive(Debug, Clone, Default)]
+pub struct DotOptions {
+    /// Shows tables by name only, without their columns.
+    pub collapse_columns: bool,
+
+    /// Fills the nodes with the color of the tables.
+    pub use_table_colors: bool,
+
+    pub neighbourhood: Option<Neighbourhood>,
+}
+
+/// Quotes an id or a plain label.
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Escapes the characters that structure record labels.
+fn record_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '{' | '}' | '|' | '<' | '>' | '"' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The physical names of the tables within the neighbourhood, following relationships
+/// in both directions.
+fn neighbourhood(
+    diagram: &Diagram,
+    neighbourhood: &Neighbourhood,
+) -> Result<HashSet<String>, Box<dyn std::error::Error>> {
+    let start = diagram
+        .find_table(&neighbourhood.table)
+        .ok_or_else(|| format!("unknown table {}", neighbourhood.table))?;
+    let mut found = HashSet::from([start.physical_name.clone()]);
+    let mut queue = VecDeque::from([(start.physical_name.as_str(), 0)]);
+    while let Some((table, distance)) = queue.pop_front() {
+        if distance == neighbourhood.hops {
+            continue;
+        }
+        for relationship in diagram.relationships() {
+            let next = if relationship.source_table() == table {
+                relationship.target_table()
+            } else if relationship.target_table() == table {
+                relationship.source_table()
+            } else {
+                continue;
+            };
+            if diagram.find_table(next).is_some() && found.insert(next.to_string()) {
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    Ok(found)
+}
+
+fn push_table(out: &mut String, diagram: &Diagram, table: &Table, options: &DotOptions) {
+    let mut title = record_text(&table.physical_name);
+    if !table.logical_name.is_empty() && table.logical_name != table.physical_name {
+        title.push_str("\\n");
+        title.push_str(&record_text(&table.logical_name));
+    }
+    let mut fields = vec![title];
+    if !options.collapse_columns {
+        let columns: String = diagram
+            .resolve_columns(table)
+            .iter()
+            .map(|column| {
+                let mut marks = Vec::new();
+                if column.is_primary_key() {
+                    marks.push("PK");
+                }
+                if column.is_foreign_key() {
+                    marks.push("FK");
+                }
+                let mut line = column.column.physical_name.clone();
+                let column_type = format_column_type(&column.column);
+                if !column_type.is_empty() {
+                    let _ = write!(line, ": {column_type}");
+                }
+                if !marks.is_empty() {
+                    let _ = write!(line, " ({})", marks.join(", "));
+                }
+                format!("{}\\l", record_text(&line))
+            })
+            .collect();
+        if !columns.is_empty() {
+            fields.push(columns);
+        }
+    }
+    let _ = write!(
+        out,
+        "  {} [label=\"{{{}}}\"",
+        quote(&table.physical_name),
+        fields.join("|")
+    );
+    if options.use_table_colors {
+        let _ = write!(
+            out,
+            ", style=filled, fillcolor=\"#{:02x}{:02x}{:02x}\"",
+            table.color.r, table.color.g, table.color.b
+        );
+    }
+    out.push_str("];\n");
+}
+
+fn push_relationship(out: &mut String, relationship: &Relationship) {
+    let _ = writeln!(
+        out,
+        "  {} -> {} [taillabel={}, headlabel={}, tooltip={}];",
+        quote(relationship.target_table()),
+        quote(relationship.source_table()),
+        quote(&relationship.child_cardinality),
+        quote(&relationship.parent_cardinality),
+        quote(&relationship.name)
+    );
+}
+
+/// Renders the graph. Fails if the table of the neighbourhood does not exist.
+pub fn render(
+    diagram: &Diagram,
+    options: &DotOptions,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let included = match &options.neighbourhood {
+        Some(around) => Some(neighbourhood(diagram, around)?),
+        None => None,
+    };
+    let is_included = |name: &str| {
+        included
+            .as_ref()
+            .is_none_or(|included| included.contains(name))
+    };
+
+    let mut out = String::from(
+        "digraph schema {\n  node [shape=record, fontsize=10];\n  edge [fontsize=9];\n",
+    );
+    for table in diagram.tables() {
+        if is_included(&table.physical_name) {
+            push_table(&mut out, diagram, table, options);
+        }
+    }
+    for relationship in diagram.relationships() {
+        if is_included(relationship.source_table())
+            && is_included(relationship.target_table())
+            && diagram.find_table(relationship.source_table()).is_some()
+        {
+            push_relationship(&mut out, relationship);
+        }
+    }
+    out.push_str("}\n");
+    Ok(out)
+}
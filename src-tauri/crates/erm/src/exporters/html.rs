@@ -0,0 +1,431 @@
+//! A static HTML documentation site of the schema: an index page with the diagram and
+//! a search box, and a page per table linking to the tables it references and is
+//! referenced by. Styles, scripts and the diagram are embedded in the pages, so the
+//! directory can be browsed offline or published as is.
+
+use super::escape_markup as escape;
+use super::svg::{self, SvgOptions};
+use crate::column_type::format_column_type;
+use crate::dtos::diagram::{Diagram, ResolvedColumn};
+use crate::dtos::diagram_walkers::{Relationship, Table};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::path::Path;
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 0 auto; max-width: 1200px; padding: 1em 2em; color: #222; }
+a { color: #0550ae; text-decoration: none; }
+a:hover { text-decoration: underline; }
+table { border-collapse: collapse; margin: 0.5em 0 1.5em; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; vertical-align: top; }
+th { background: #f3f3f3; }
+td.check { text-align: center; }
+.logical { color: #666; }
+.description { white-space: pre-wrap; }
+#search { font-size: 1em; padding: 0.4em; width: 24em; }
+#results { list-style: none; padding: 0; }
+#results li { padding: 0.15em 0; }
+.diagram { border: 1px solid #ccc; overflow: auto; margin-bottom: 1.5em; }
+";
+
+const SEARCH_SCRIPT: &str = "
+const input = document.getElementById('search');
+const results = document.getElementById('results');
+input.addEventListener('input', () => {
+  const query = input.value.trim().toLowerCase();
+  results.replaceChildren();
+  if (!query) return;
+  for (const entry of SEARCH_INDEX) {
+    if (!entry.physicalName.toLowerCase().includes(query)
+        && !entry.logicalName.toLowerCase().includes(query)) continue;
+    const item = document.createElement('li');
+    const link = document.createElement('a');
+    link.href = entry.url;
+    link.textContent = entry.table === entry.physicalName
+      ? entry.physicalName
+      : entry.table + '.' + entry.physicalName;
+    item.append(link);
+    if (entry.logicalName) {
+      const logical = document.createElement('span');
+      logical.className = 'logical';
+      logical.textContent = ' ' + entry.logicalName;
+      item.append(logical);
+    }
+    results.append(item);
+  }
+});
+";
+
+/// A table or column found by the search box of the index page.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchEntry {
+    pub physical_name: String,
+    pub logical_name: String,
+    /// The table of a column, or the table itself.
+    pub table: String,
+    /// Relative to the index page.
+    pub url: String,
+}
+
+/// A file of the site, with its path relative to the output directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page {
+    pub path: String,
+    pub content: String,
+}
+
+/// The file name of the page of a table, with the characters not safe in file names
+/// and URLs written as the hex of their UTF-8 bytes, e.g. `_E4_BC_9A` for `会`.
+fn file_stem(physical_name: &str) -> String {
+    let mut stem = String::new();
+    for c in physical_name.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+            stem.push(c);
+        } else {
+            let mut bytes = [0; 4];
+            for byte in c.encode_utf8(&mut bytes).bytes() {
+                let _ = write!(stem, "_{byte:02X}");
+            }
+        }
+    }
+    stem
+}
+
+/// The file names of the pages of the tables, keyed by physical name. Names that would
+/// clash with an earlier table's, also on file systems ignoring case, get a counter,
+/// e.g. `members-2.html`.
+pub fn table_file_names(diagram: &Diagram) -> HashMap<String, String> {
+    let mut taken = HashSet::new();
+    let mut names = HashMap::new();
+    for table in sorted_tables(diagram) {
+        let stem = file_stem(&table.physical_name);
+        let mut name = format!("{stem}.html");
+        let mut count = 1;
+        while !taken.insert(name.to_lowercase()) {
+            count += 1;
+            name = format!("{stem}-{count}.html");
+        }
+        names.insert(table.physical_name.clone(), name);
+    }
+    names
+}
+
+/// Percent-encodes a URL fragment such as a column anchor.
+fn encode_fragment(fragment: &str) -> String {
+    let mut encoded = String::new();
+    for byte in fragment.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+fn table_link(
+    diagram: &Diagram,
+    file_names: &HashMap<String, String>,
+    physical_name: &str,
+    prefix: &str,
+) -> String {
+    let file_name = diagram
+        .find_table(physical_name)
+        .and_then(|table| file_names.get(&table.physical_name));
+    match file_name {
+        Some(file_name) => format!(
+            "<a href=\"{prefix}{}\">{}</a>",
+            escape(file_name),
+            escape(physical_name)
+        ),
+        None => escape(physical_name),
+    }
+}
+
+fn table_title(table: &Table) -> String {
+    if table.logical_name.is_empty() {
+        escape(&table.physical_name)
+    } else {
+        format!(
+            "{} <span class=\"logical\">{}</span>",
+            escape(&table.physical_name),
+            escape(&table.logical_name)
+        )
+    }
+}
+
+fn push_document(out: &mut String, title: &str) {
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>{}</title>
+<style>{STYLE}</style>
+</head>
+<body>
+",
+        escape(title)
+    );
+}
+
+fn check(value: bool) -> &'static str {
+    if value { "✓" } else { "" }
+}
+
+fn push_column(
+    out: &mut String,
+    diagram: &Diagram,
+    file_names: &HashMap<String, String>,
+    column: &ResolvedColumn,
+) {
+    let referred = match column.column.referred_table_and_column() {
+        Some((table, referred)) => {
+            format!(
+                "{}.{}",
+                table_link(diagram, file_names, table, ""),
+                escape(referred)
+            )
+        }
+        None => String::new(),
+    };
+    let _ = writeln!(
+        out,
+        "<tr id=\"column-{}\"><td>{}</td><td>{}</td><td>{}</td><td class=\"check\">{}</td>\
+         <td>{referred}</td><td class=\"check\">{}</td><td class=\"check\">{}</td><td>{}</td>\
+         <td>{}</td><td class=\"description\">{}</td></tr>",
+        escape(&column.column.physical_name),
+        escape(&column.column.physical_name),
+        escape(column.column.logical_name.as_deref().unwrap_or_default()),
+        escape(&format_column_type(&column.column)),
+        check(column.is_primary_key()),
+        check(column.is_not_null()),
+        check(column.is_unique()),
+        escape(column.column.default_value.as_deref().unwrap_or_default()),
+        escape(column.column_group.as_deref().unwrap_or_default()),
+        escape(
+            column
+                .column
+                .description
+                .as_deref()
+                .unwrap_or_default()
+                .trim()
+        ),
+    );
+}
+
+fn fk_column_names(relationship: &Relationship) -> String {
+    relationship
+        .fk_columns
+        .fk_column
+        .iter()
+        .map(|column| escape(&column.fk_column_name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn table_page(diagram: &Diagram, file_names: &HashMap<String, String>, table: &Table) -> String {
+    let mut out = String::new();
+    push_document(&mut out, &table.physical_name);
+    out.push_str("<p><a href=\"../index.html\">Index</a></p>\n");
+    let _ = writeln!(out, "<h1>{}</h1>", table_title(table));
+    if !table.description.trim().is_empty() {
+        let _ = writeln!(
+            out,
+            "<p class=\"description\">{}</p>",
+            escape(table.description.trim())
+        );
+    }
+
+    out.push_str(
+        "<h2>Columns</h2>
+<table>
+<tr><th>Name</th><th>Logical name</th><th>Type</th><th>PK</th><th>FK</th><th>NOT NULL</th>\
+<th>UNIQUE</th><th>Default</th><th>Group</th><th>Description</th></tr>
+",
+    );
+    for column in diagram.resolve_columns(table) {
+        push_column(&mut out, diagram, file_names, &column);
+    }
+    out.push_str("</table>\n");
+
+    if !table.index_list().is_empty() {
+        out.push_str(
+            "<h2>Indexes</h2>\n<table>\n<tr><th>Name</th><th>Columns</th><th>Unique</th></tr>\n",
+        );
+        for index in table.index_list() {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td class=\"check\">{}</td></tr>",
+                escape(&index.name),
+                escape(&index.columns.to_string()),
+                check(index.is_unique())
+            );
+        }
+        out.push_str("</table>\n");
+    }
+
+    let mut outgoing: Vec<&Relationship> = table.relationships().iter().collect();
+    outgoing.sort_by(|a, b| a.name.cmp(&b.name));
+    if !outgoing.is_empty() {
+        out.push_str(
+            "<h2>References</h2>\n<table>\n<tr><th>Relationship</th><th>Columns</th>\
+             <th>Table</th><th>Cardinality</th><th>On delete</th><th>On update</th></tr>\n",
+        );
+        for relationship in outgoing {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{} : {}</td><td>{}</td><td>{}</td></tr>",
+                escape(&relationship.name),
+                fk_column_names(relationship),
+                table_link(diagram, file_names, relationship.source_table(), ""),
+                escape(&relationship.parent_cardinality),
+                escape(&relationship.child_cardinality),
+                escape(&relationship.on_delete_action),
+                escape(&relationship.on_update_action)
+            );
+        }
+        out.push_str("</table>\n");
+    }
+
+    let mut incoming: Vec<&Relationship> = diagram
+        .relationships()
+        .filter(|relationship| relationship.source_table() == table.physical_name)
+        .collect();
+    incoming.sort_by(|a, b| (a.target_table(), &a.name).cmp(&(b.target_table(), &b.name)));
+    if !incoming.is_empty() {
+        out.push_str(
+            "<h2>Referenced by</h2>\n<table>\n<tr><th>Relationship</th><th>Table</th>\
+             <th>Columns</th><th>Cardinality</th></tr>\n",
+        );
+        for relationship in incoming {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{} : {}</td></tr>",
+                escape(&relationship.name),
+                table_link(diagram, file_names, relationship.target_table(), ""),
+                fk_column_names(relationship),
+                escape(&relationship.parent_cardinality),
+                escape(&relationship.child_cardinality)
+            );
+        }
+        out.push_str("</table>\n");
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Lists every table and column, for the search box of the index page.
+pub fn search_index(diagram: &Diagram) -> Vec<SearchEntry> {
+    let file_names = table_file_names(diagram);
+    let mut entries = Vec::new();
+    for table in sorted_tables(diagram) {
+        let url = format!("tables/{}", file_names[&table.physical_name]);
+        entries.push(SearchEntry {
+            physical_name: table.physical_name.clone(),
+            logical_name: table.logical_name.clone(),
+            table: table.physical_name.clone(),
+            url: url.clone(),
+        });
+        for column in diagram.resolve_columns(table) {
+            entries.push(SearchEntry {
+                url: format!(
+                    "{url}#{}",
+                    encode_fragment(&format!("column-{}", column.column.physical_name))
+                ),
+                physical_name: column.column.physical_name,
+                logical_name: column.column.logical_name.unwrap_or_default(),
+                table: table.physical_name.clone(),
+            });
+        }
+    }
+    entries
+}
+
+fn sorted_tables(diagram: &Diagram) -> Vec<&Table> {
+    let mut tables: Vec<&Table> = diagram.tables().iter().collect();
+    tables.sort_by(|a, b| a.physical_name.cmp(&b.physical_name));
+    tables
+}
+
+fn index_page(
+    diagram: &Diagram,
+    file_names: &HashMap<String, String>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut out = String::new();
+    push_document(&mut out, "Tables");
+    out.push_str(
+        "<h1>Tables</h1>
+<p><input id=\"search\" type=\"search\" placeholder=\"Search tables and columns\"></p>
+<ul id=\"results\"></ul>
+<div class=\"diagram\">
+",
+    );
+    out.push_str(&svg::render(
+        diagram,
+        &SvgOptions {
+            table_link: Some(&|table| format!("tables/{}", file_names[&table.physical_name])),
+        },
+    ));
+    out.push_str("</div>\n<table>\n<tr><th>Table</th><th>Logical name</th><th>Columns</th></tr>\n");
+    for table in sorted_tables(diagram) {
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+            table_link(diagram, file_names, &table.physical_name, "tables/"),
+            escape(&table.logical_name),
+            diagram.resolve_columns(table).len()
+        );
+    }
+    out.push_str("</table>\n");
+
+    // `</` would end the script element early.
+    let index = serde_json::to_string(&search_index(diagram))?.replace("</", "<\\/");
+    let _ = write!(
+        out,
+        "<script>\nconst SEARCH_INDEX = {index};\n{}</script>\n</body>\n</html>\n",
+        SEARCH_SCRIPT.trim_start()
+    );
+    Ok(out)
+}
+
+/// Renders every file of the site: `index.html` and a page per table under `tables/`.
+pub fn render(diagram: &Diagram) -> Result<Vec<Page>, Box<dyn std::error::Error>> {
+    let file_names = table_file_names(diagram);
+    let mut pages = vec![Page {
+        path: "index.html".to_string(),
+        content: index_page(diagram, &file_names)?,
+    }];
+    for table in sorted_tables(diagram) {
+        pages.push(Page {
+            path: format!("tables/{}", file_names[&table.physical_name]),
+            content: table_page(diagram, &file_names, table),
+        });
+    }
+    Ok(pages)
+}
+
+/// Writes the site into `directory`, creating it if needed.
+pub fn export(diagram: &Diagram, directory: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let pages = render(diagram)?;
+    // Two pages of one path would silently overwrite each other.
+    let mut paths = HashSet::new();
+    if let Some(page) = pages
+        .iter()
+        .find(|page| !paths.insert(page.path.to_lowercase()))
+    {
+        return Err(format!("two pages would be written to {}", page.path).into());
+    }
+    for page in pages {
+        let path = Path::new(directory).join(&page.path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, page.content)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,233 @@
+//! A JSON Schema of each table for API contracts, as a 2020-12 document with the tables
+//! under `$defs` or as an OpenAPI 3.1 document with the tables under `components.schemas`.
+//! Column types become JSON types and formats, lengths `maxLength`, decimal scales
+//! `multipleOf`, and not null columns are required while the others accept `null`.
+//! Logical names and descriptions become descriptions.
+
+use super::doc_lines;
+use crate::column_type::{ColumnType, enum_values};
+use crate::dtos::diagram::{Diagram, ResolvedColumn};
+use crate::dtos::diagram_walkers::Table;
+use serde_json::{Map, Value, json};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SchemaFormat {
+    /// A JSON Schema 2020-12 document with a definition per table under `$defs`.
+    #[default]
+    JsonSchema,
+    /// An OpenAPI 3.1 document with a schema per table under `components.schemas`.
+    OpenApi,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PropertyNames {
+    #[default]
+    Physical,
+    /// Logical names, falling back on the physical name of columns without one or whose
+    /// logical name is taken by an earlier column.
+    Logical,
+}
+
+/// Options of [`render`].
+#[derive(Debug, Clone, Default)]
+pub struct JsonSchemaOptions {
+    pub format: SchemaFormat,
+    pub property_names: PropertyNames,
+}
+
+/// The schema of a column's values, without null.
+fn value_schema(column: &ResolvedColumn) -> Map<String, Value> {
+    let column = &column.column;
+    let mut schema = Map::new();
+    let Some(column_type) = column.column_type.as_deref() else {
+        return schema;
+    };
+    // Types unknown to ERFlute are taken as text, `uuid` with its format.
+    let Some(known) = ColumnType::parse(column_type) else {
+        schema.insert("type".into(), json!("string"));
+        if column_type.eq_ignore_ascii_case("uuid") {
+            schema.insert("format".into(), json!("uuid"));
+        }
+        return schema;
+    };
+    let config = known.config();
+    let length = column.length.filter(|_| config.supports_length);
+    let unsigned = config.supports_unsigned && column.unsigned.unwrap_or(false);
+    let (json_type, format) = match known {
+        ColumnType::Char
+        | ColumnType::CharN
+        | ColumnType::VarCharN
+        | ColumnType::Text
+        | ColumnType::TinyText
+        | ColumnType::MediumText
+        | ColumnType::LongText
+        | ColumnType::Set => ("string", None),
+        ColumnType::TinyInt
+        | ColumnType::TinyIntN
+        | ColumnType::SmallInt
+        | ColumnType::SmallIntN
+        | ColumnType::MediumInt
+        | ColumnType::MediumIntN
+        | ColumnType::Int
+        | ColumnType::IntN
+        | ColumnType::Year2
+        | ColumnType::Year4 => ("integer", Some("int32")),
+        ColumnType::BigInt | ColumnType::BigIntN => ("integer", Some("int64")),
+        ColumnType::Float | ColumnType::FloatMD | ColumnType::FloatP => ("number", Some("float")),
+        ColumnType::Double | ColumnType::DoubleMD | ColumnType::Real | ColumnType::RealMD => {
+            ("number", Some("double"))
+        }
+        ColumnType::Decimal
+        | ColumnType::DecimalP
+        | ColumnType::DecimalPS
+        | ColumnType::Numeric
+        | ColumnType::NumericP
+        | ColumnType::NumericPS => ("number", None),
+        ColumnType::Boolean => ("boolean", None),
+        ColumnType::Date => ("string", Some("date")),
+        ColumnType::Datetime | ColumnType::Timestamp => ("string", Some("date-time")),
+        ColumnType::Time => ("string", Some("time")),
+        ColumnType::Enum => ("string", None),
+        ColumnType::Binary
+        | ColumnType::BinaryN
+        | ColumnType::VarBinaryN
+        | ColumnType::BitN
+        | ColumnType::Blob
+        | ColumnType::TinyBlob
+        | ColumnType::MediumBlob
+        | ColumnType::LongBlob
+        | ColumnType::Geometry => {
+            schema.insert("type".into(), json!("string"));
+            schema.insert("contentEncoding".into(), json!("base64"));
+            return schema;
+        }
+        // Any JSON value.
+        ColumnType::Json => return schema,
+    };
+    schema.insert("type".into(), json!(json_type));
+    if let Some(format) = format {
+        schema.insert("format".into(), json!(format));
+    }
+    match json_type {
+        "string" => {
+            if known == ColumnType::Enum {
+                let values = column.args.as_deref().map(enum_values).unwrap_or_default();
+                if !values.is_empty() {
+                    schema.insert("enum".into(), json!(values));
+                }
+            } else if let Some(length) = length {
+                schema.insert("maxLength".into(), json!(length));
+            }
+        }
+        "integer" | "number" => {
+            if unsigned {
+                schema.insert("minimum".into(), json!(0));
+            }
+            let scale = column.decimal.filter(|_| config.supports_decimal);
+            if let Some(scale) = scale.filter(|_| format.is_none()) {
+                // `1e-2` rather than `10f64.powi(-2)`, which is not exactly `0.01`.
+                let multiple: f64 = format!("1e-{scale}").parse().unwrap_or(1.0);
+                schema.insert("multipleOf".into(), json!(multiple));
+            }
+        }
+        _ => {}
+    }
+    schema
+}
+
+/// The schema of a column, accepting `null` unless the column is not null.
+fn column_schema(column: &ResolvedColumn) -> Value {
+    let mut schema = Map::new();
+    let description = doc_lines(
+        &column.column.physical_name,
+        column.column.logical_name.as_deref().unwrap_or_default(),
+        column.column.description.as_deref().unwrap_or_default(),
+    );
+    if !description.is_empty() {
+        schema.insert("description".into(), json!(description.join("\n")));
+    }
+    let mut values = value_schema(column);
+    if !column.is_not_null() {
+        if let Some(json_type) = values.get_mut("type") {
+            *json_type = json!([json_type.clone(), "null"]);
+        }
+        if let Some(Value::Array(values)) = values.get_mut("enum") {
+            values.push(Value::Null);
+        }
+    }
+    schema.extend(values);
+    Value::Object(schema)
+}
+
+fn property_name<'a>(
+    column: &'a ResolvedColumn,
+    options: &JsonSchemaOptions,
+    taken: &HashSet<&str>,
+) -> &'a str {
+    let physical = column.column.physical_name.as_str();
+    match options.property_names {
+        PropertyNames::Physical => physical,
+        PropertyNames::Logical => match column.column.logical_name.as_deref() {
+            Some(logical) if !logical.is_empty() && !taken.contains(logical) => logical,
+            _ => physical,
+        },
+    }
+}
+
+fn table_schema(diagram: &Diagram, table: &Table, options: &JsonSchemaOptions) -> Value {
+    let mut schema = Map::new();
+    if !table.logical_name.is_empty() {
+        schema.insert("title".into(), json!(table.logical_name));
+    }
+    if !table.description.is_empty() {
+        schema.insert("description".into(), json!(table.description));
+    }
+    schema.insert("type".into(), json!("object"));
+
+    let columns = diagram.resolve_columns(table);
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    let mut taken = HashSet::new();
+    for column in &columns {
+        let name = property_name(column, options, &taken);
+        taken.insert(name);
+        properties.insert(name.to_string(), column_schema(column));
+        if column.is_not_null() {
+            required.push(name);
+        }
+    }
+    schema.insert("properties".into(), Value::Object(properties));
+    if !required.is_empty() {
+        schema.insert("required".into(), json!(required));
+    }
+    Value::Object(schema)
+}
+
+/// Renders the document of the table schemas, keyed by the physical table names.
+pub fn render(diagram: &Diagram, options: &JsonSchemaOptions) -> String {
+    let schemas: Map<String, Value> = diagram
+        .tables()
+        .iter()
+        .map(|table| {
+            (
+                table.physical_name.clone(),
+                table_schema(diagram, table, options),
+            )
+        })
+        .collect();
+    let document = match options.format {
+        SchemaFormat::JsonSchema => json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "$defs": schemas,
+        }),
+        SchemaFormat::OpenApi => json!({
+            "openapi": "3.1.0",
+            "info": { "title": "Tables", "version": "1.0.0" },
+            "components": { "schemas": schemas },
+        }),
+    };
+    let mut out = serde_json::to_string_pretty(&document).unwrap_or_default();
+    out.push('\n');
+    out
+}
@@ -0,0 +1,176 @@
+//! A Markdown data dictionary of the schema, e.g. for publishing table definitions to
+//! a wiki. Every table gets a section with its columns and relationships.
+
+use crate::column_type::format_column_type;
+use crate::dtos::diagram::{Diagram, ResolvedColumn};
+use crate::dtos::diagram_walkers::{Relationship, Table};
+use std::fmt::Write;
+
+const CHECK: &str = "✓";
+
+/// Escapes text for a table cell, which must stay on one line.
+fn cell(value: &str) -> String {
+    value
+        .trim()
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+fn check(value: bool) -> &'static str {
+    if value { CHECK } else { "" }
+}
+
+fn push_row(out: &mut String, cells: &[&str]) {
+    let _ = writeln!(out, "| {} |", cells.join(" | "));
+}
+
+fn push_header(out: &mut String, cells: &[&str]) {
+    push_row(out, cells);
+    push_row(out, &vec!["---"; cells.len()]);
+}
+
+fn fk_column_names(relationship: &Relationship) -> String {
+    relationship
+        .fk_columns
+        .fk_column
+        .iter()
+        .map(|column| column.fk_column_name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn push_column(out: &mut String, column: &ResolvedColumn) {
+    let referred = column
+        .column
+        .referred_table_and_column()
+        .map(|(table, referred)| format!("{table}.{referred}"))
+        .unwrap_or_default();
+    push_row(
+        out,
+        &[
+            &cell(&column.column.physical_name),
+            &cell(column.column.logical_name.as_deref().unwrap_or_default()),
+            &cell(&format_column_type(&column.column)),
+            check(column.is_primary_key()),
+            &cell(&referred),
+            check(column.is_not_null()),
+            check(column.is_unique()),
+            &cell(column.column.default_value.as_deref().unwrap_or_default()),
+            &cell(column.column_group.as_deref().unwrap_or_default()),
+            &cell(column.column.description.as_deref().unwrap_or_default()),
+        ],
+    );
+}
+
+fn push_table(out: &mut String, diagram: &Diagram, table: &Table) {
+    if table.logical_name.is_empty() {
+        let _ = writeln!(out, "## {}\n", table.physical_name);
+    } else {
+        let _ = writeln!(out, "## {} ({})\n", table.logical_name, table.physical_name);
+    }
+    let _ = writeln!(out, "- Physical name: `{}`", table.physical_name);
+    if !table.logical_name.is_empty() {
+        let _ = writeln!(out, "- Logical name: {}", table.logical_name);
+    }
+    out.push('\n');
+    if !table.description.trim().is_empty() {
+        let _ = writeln!(out, "{}\n", table.description.trim());
+    }
+
+    out.push_str("### Columns\n\n");
+    push_header(
+        out,
+        &[
+            "Name",
+            "Logical name",
+            "Type",
+            "PK",
+            "FK",
+            "NOT NULL",
+            "UNIQUE",
+            "Default",
+            "Group",
+            "Description",
+        ],
+    );
+    for column in diagram.resolve_columns(table) {
+        push_column(out, &column);
+    }
+    out.push('\n');
+
+    let mut outgoing: Vec<&Relationship> = table.relationships().iter().collect();
+    outgoing.sort_by(|a, b| a.name.cmp(&b.name));
+    if !outgoing.is_empty() {
+        out.push_str("### References\n\n");
+        push_header(
+            out,
+            &[
+                "Relationship",
+                "Columns",
+                "Table",
+                "Cardinality",
+                "On delete",
+                "On update",
+            ],
+        );
+        for relationship in outgoing {
+            push_row(
+                out,
+                &[
+                    &cell(&relationship.name),
+                    &cell(&fk_column_names(relationship)),
+                    &cell(relationship.source_table()),
+                    &cell(&format!(
+                        "{} : {}",
+                        relationship.parent_cardinality, relationship.child_cardinality
+                    )),
+                    &cell(&relationship.on_delete_action),
+                    &cell(&relationship.on_update_action),
+                ],
+            );
+        }
+        out.push('\n');
+    }
+
+    let mut incoming: Vec<&Relationship> = diagram
+        .relationships()
+        .filter(|relationship| relationship.source_table() == table.physical_name)
+        .collect();
+    incoming.sort_by(|a, b| (a.target_table(), &a.name).cmp(&(b.target_table(), &b.name)));
+    if !incoming.is_empty() {
+        out.push_str("### Referenced by\n\n");
+        push_header(out, &["Relationship", "Table", "Columns", "Cardinality"]);
+        for relationship in incoming {
+            push_row(
+                out,
+                &[
+                    &cell(&relationship.name),
+                    &cell(relationship.target_table()),
+                    &cell(&fk_column_names(relationship)),
+                    &cell(&format!(
+                        "{} : {}",
+                        relationship.parent_cardinality, relationship.child_cardinality
+                    )),
+                ],
+            );
+        }
+        out.push('\n');
+    }
+}
+
+/// Renders a section for every table, sorted by physical name. Columns keep their
+/// order in the table, with the columns of column groups expanded in place.
+pub fn render(diagram: &Diagram) -> String {
+    let mut out = String::from("# Tables\n\n");
+
+    let mut tables: Vec<&Table> = diagram.tables().iter().collect();
+    tables.sort_by(|a, b| a.physical_name.cmp(&b.physical_name));
+    for table in tables {
+        push_table(&mut out, diagram, table);
+    }
+
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
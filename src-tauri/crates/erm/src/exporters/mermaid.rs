@@ -0,0 +1,125 @@
+//! A Mermaid `erDiagram` of the schema, e.g. for design documents written in Markdown.
+//! Columns are listed with their type, key markers and logical name, and relationships
+//! get the crow's feet of their cardinality. Non-identifying relationships are dotted.
+//!
+//! To export only a category or a virtual diagram, render [`Diagram::extract`].
+
+use super::{EntityNames, crows_foot_ends, identifier};
+use crate::column_type::format_column_type;
+use crate::dtos::diagram::{Diagram, ResolvedColumn};
+use crate::dtos::diagram_walkers::{Relationship, Table};
+use std::fmt::Write;
+
+/// Mermaid takes a single word as the type, starting with a letter.
+fn attribute_type(column: &ResolvedColumn) -> String {
+    let column_type = format_column_type(&column.column);
+    let column_type: String = column_type
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '(' | ')' | '[' | ']') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if column_type.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        column_type
+    } else {
+        format!("t_{column_type}")
+    }
+}
+
+/// Mermaid strings cannot contain double quotes.
+fn string(value: &str) -> String {
+    format!("\"{}\"", value.trim().replace('"', "'").replace('\n', " "))
+}
+
+fn push_column(out: &mut String, column: &ResolvedColumn) {
+    let _ = write!(
+        out,
+        "        {} {}",
+        attribute_type(column),
+        identifier(&column.column.physical_name)
+    );
+    let keys: Vec<&str> = [
+        (column.is_primary_key(), "PK"),
+        (column.is_foreign_key(), "FK"),
+        (column.is_unique(), "UK"),
+    ]
+    .iter()
+    .filter(|(is_key, _)| *is_key)
+    .map(|(_, key)| *key)
+    .collect();
+    if !keys.is_empty() {
+        let _ = write!(out, " {}", keys.join(", "));
+    }
+    if let Some(logical_name) = column
+        .column
+        .logical_name
+        .as_deref()
+        .filter(|name| !name.trim().is_empty())
+    {
+        let _ = write!(out, " {}", string(logical_name));
+    }
+    out.push('\n');
+}
+
+fn push_table(out: &mut String, diagram: &Diagram, names: &EntityNames, table: &Table) {
+    let mut entity = names.get(&table.physical_name);
+    // Names that are not identifiers are shown as an alias.
+    if entity != table.physical_name {
+        entity = format!("{entity}[{}]", string(&table.physical_name));
+    }
+    let columns = diagram.resolve_columns(table);
+    if columns.is_empty() {
+        let _ = writeln!(out, "    {entity}");
+        return;
+    }
+    let _ = writeln!(out, "    {entity} {{");
+    for column in &columns {
+        push_column(out, column);
+    }
+    out.push_str("    }\n");
+}
+
+fn push_relationship(
+    out: &mut String,
+    diagram: &Diagram,
+    names: &EntityNames,
+    relationship: &Relationship,
+) {
+    let (parent, child) = crows_foot_ends(
+        &relationship.parent_cardinality,
+        &relationship.child_cardinality,
+    );
+    let line = if diagram.is_identifying(relationship) {
+        "--"
+    } else {
+        ".."
+    };
+    let _ = writeln!(
+        out,
+        "    {} {parent}{line}{child} {} : {}",
+        names.get(relationship.source_table()),
+        names.get(relationship.target_table()),
+        string(&relationship.name)
+    );
+}
+
+/// Renders the `erDiagram`, tables sorted by physical name and relationships by name.
+pub fn render(diagram: &Diagram) -> String {
+    let mut out = String::from("erDiagram\n");
+    let names = EntityNames::new(diagram);
+    let mut tables: Vec<&Table> = diagram.tables().iter().collect();
+    tables.sort_by(|a, b| a.physical_name.cmp(&b.physical_name));
+    for table in tables {
+        push_table(&mut out, diagram, &names, table);
+    }
+    let mut relationships: Vec<&Relationship> = diagram.relationships().collect();
+    relationships.sort_by(|a, b| a.name.cmp(&b.name));
+    for relationship in relationships {
+        push_relationship(&mut out, diagram, &names, relationship);
+    }
+    out
+}
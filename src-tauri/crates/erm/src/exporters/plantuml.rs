@@ -0,0 +1,107 @@
+//! A PlantUML entity-relationship diagram of the schema. Each table is an entity with
+//! its primary key above the separator, `*` marking mandatory columns, and relationships
+//! get the crow's feet of their cardinality. Non-identifying relationships are dotted.
+//!
+//! To export only a category or a virtual diagram, render [`Diagram::extract`].
+
+use super::{EntityNames, crows_foot_ends};
+use crate::column_type::format_column_type;
+use crate::dtos::diagram::{Diagram, ResolvedColumn};
+use crate::dtos::diagram_walkers::{Relationship, Table};
+use std::fmt::Write;
+
+/// PlantUML strings cannot contain double quotes.
+fn string(value: &str) -> String {
+    format!("\"{}\"", value.trim().replace('"', "'").replace('\n', " "))
+}
+
+fn push_column(out: &mut String, column: &ResolvedColumn) {
+    let mandatory = if column.is_not_null() { "* " } else { "" };
+    let _ = write!(out, "  {mandatory}{}", column.column.physical_name);
+    let column_type = format_column_type(&column.column);
+    if !column_type.is_empty() {
+        let _ = write!(out, " : {column_type}");
+    }
+    for (is_key, stereotype) in [
+        (column.is_primary_key(), "<<PK>>"),
+        (column.is_foreign_key(), "<<FK>>"),
+        (column.is_unique(), "<<UK>>"),
+    ] {
+        if is_key {
+            let _ = write!(out, " {stereotype}");
+        }
+    }
+    out.push('\n');
+}
+
+fn push_table(out: &mut String, diagram: &Diagram, names: &EntityNames, table: &Table) {
+    let title = if table.logical_name.trim().is_empty() {
+        table.physical_name.clone()
+    } else {
+        format!("{}\\n{}", table.physical_name, table.logical_name.trim())
+    };
+    let _ = writeln!(
+        out,
+        "entity {} as {} {{",
+        string(&title),
+        names.get(&table.physical_name)
+    );
+    let (keys, others): (Vec<_>, Vec<_>) = diagram
+        .resolve_columns(table)
+        .into_iter()
+        .partition(ResolvedColumn::is_primary_key);
+    for column in &keys {
+        push_column(out, column);
+    }
+    out.push_str("  --\n");
+    for column in &others {
+        push_column(out, column);
+    }
+    out.push_str("}\n\n");
+}
+
+fn push_relationship(
+    out: &mut String,
+    diagram: &Diagram,
+    names: &EntityNames,
+    relationship: &Relationship,
+) {
+    let (parent, child) = crows_foot_ends(
+        &relationship.parent_cardinality,
+        &relationship.child_cardinality,
+    );
+    let line = if diagram.is_identifying(relationship) {
+        "--"
+    } else {
+        ".."
+    };
+    let _ = write!(
+        out,
+        "{} {parent}{line}{child} {}",
+        names.get(relationship.source_table()),
+        names.get(relationship.target_table())
+    );
+    if !relationship.name.is_empty() {
+        let _ = write!(out, " : {}", relationship.name);
+    }
+    out.push('\n');
+}
+
+/// Renders the diagram between `@startuml` and `@enduml`, tables sorted by physical
+/// name and relationships by name.
+pub fn render(diagram: &Diagram) -> String {
+    let mut out = String::from("@startuml\nhide circle\nskinparam linetype ortho\n\n");
+    let names = EntityNames::new(diagram);
+    let mut tables: Vec<&Table> = diagram.tables().iter().collect();
+    tables.sort_by(|a, b| a.physical_name.cmp(&b.physical_name));
+    for table in tables {
+        push_table(&mut out, diagram, &names, table);
+    }
+    let mut relationships: Vec<&Relationship> = diagram.relationships().collect();
+    relationships.sort_by(|a, b| a.name.cmp(&b.name));
+    for relationship in relationships {
+        push_relationship(&mut out, diagram, &names, relationship);
+    }
+    out.push_str("@enduml\n");
+    out
+}
@@ -0,0 +1,96 @@
+//! A PNG image of the diagram, rasterized from the SVG drawing entirely in Rust, so that
+//! images can be generated headless, e.g. by the CLI in CI. Render
+//! [`Diagram::extract`] to draw a single category or virtual diagram.
+
+use super::svg::{self, SvgOptions};
+use crate::dtos::diagram::Diagram;
+use resvg::usvg::fontdb::{Database, Family, Query};
+use resvg::{tiny_skia, usvg};
+use std::sync::{Arc, OnceLock};
+
+const DEFAULT_SCALE: u16 = 100;
+
+/// Options of [`render`].
+#[derive(Debug, Clone, Default)]
+pub struct PngOptions {
+    /// In percent, 100 giving one pixel per unit of the diagram, or 96 DPI. Defaults to
+    /// `page_settings.scale`.
+    pub scale: Option<u16>,
+}
+
+impl PngOptions {
+    pub fn scale_of(&self, diagram: &Diagram) -> u16 {
+        self.scale
+            .or_else(|| diagram.page_settings.as_ref()?.scale)
+            .filter(|scale| *scale > 0)
+            .unwrap_or(DEFAULT_SCALE)
+    }
+}
+
+/// The system fonts, loaded once. Text falls back on the generic sans-serif font, which
+/// is Arial unless set; when it is not installed, an installed sans-serif font is used.
+fn fonts() -> Arc<Database> {
+    static FONTS: OnceLock<Arc<Database>> = OnceLock::new();
+    FONTS
+        .get_or_init(|| {
+            let mut fonts = Database::new();
+            fonts.load_system_fonts();
+            let sans_serif = Query {
+                families: &[Family::SansSerif],
+                ..Default::default()
+            };
+            if fonts.query(&sans_serif).is_none() {
+                let families: Vec<&str> = fonts
+                    .faces()
+                    .flat_map(|face| face.families.iter().map(|(family, _)| family.as_str()))
+                    .collect();
+                let fallback = families
+                    .iter()
+                    .find(|family| family.contains("Sans") && !family.contains("Mono"))
+                    .or(families.first())
+                    .map(|family| family.to_string());
+                if let Some(family) = fallback {
+                    fonts.set_sans_serif_family(family);
+                }
+            }
+            Arc::new(fonts)
+        })
+        .clone()
+}
+
+/// Renders the image, with a white background like the SVG drawing.
+pub fn render(
+    diagram: &Diagram,
+    options: &PngOptions,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let scale = f32::from(options.scale_of(diagram)) / 100.0;
+    let usvg_options = usvg::Options {
+        fontdb: fonts(),
+        ..Default::default()
+    };
+    let tree = usvg::Tree::from_str(&svg::render(diagram, &SvgOptions::default()), &usvg_options)?;
+    let size = tree
+        .size()
+        .to_int_size()
+        .scale_by(scale)
+        .ok_or("the image is too large")?;
+    let mut pixmap =
+        tiny_skia::Pixmap::new(size.width(), size.height()).ok_or("the image is too large")?;
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+    Ok(pixmap.encode_png()?)
+}
+
+/// Writes the image to `filename`, as configured by `export_settings.image_output` for
+/// instance.
+pub fn export(
+    diagram: &Diagram,
+    options: &PngOptions,
+    filename: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::write(filename, render(diagram, options)?)?;
+    Ok(())
+}
@@ -0,0 +1,749 @@
+//! A Prisma schema of the diagram, for Node services using Prisma. Tables become
+//! models and columns become fields, named the Prisma way and mapped onto the physical
+//! names with `@map` and `@@map`. Types become scalar types with `@db.*` native types
+//! for the database of the diagram, and relationships become relation fields on both
+//! sides. Logical names and descriptions are written as `///` comments, the logical name
+//! on the first line, so that they survive a round trip through
+//! [`crate::importers::prisma`].
+
+use super::{identifier, pascal_case};
+use crate::column_type::{ColumnType, enum_values, format_column_type};
+use crate::dtos::diagram::{Diagram, ResolvedColumn};
+use crate::dtos::diagram_walkers::{NormalColumn, Relationship, Table};
+use crate::importers::{postgresql, sqlite};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Provider {
+    MySql,
+    PostgreSql,
+    Sqlite,
+}
+
+impl Provider {
+    fn of(diagram: &Diagram) -> Self {
+        let database = &diagram.diagram_settings.database;
+        if database.eq_ignore_ascii_case(postgresql::DATABASE) {
+            Provider::PostgreSql
+        } else if database.eq_ignore_ascii_case(sqlite::DATABASE) {
+            Provider::Sqlite
+        } else {
+            Provider::MySql
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Provider::MySql => "mysql",
+            Provider::PostgreSql => "postgresql",
+            Provider::Sqlite => "sqlite",
+        }
+    }
+}
+
+/// The scalar type of a field and its native type, without `@db.`.
+struct FieldType {
+    scalar: String,
+    native: Option<String>,
+}
+
+impl FieldType {
+    fn new(scalar: &str, native: Option<String>) -> Self {
+        Self {
+            scalar: scalar.to_string(),
+            native,
+        }
+    }
+
+    fn of(column: &NormalColumn, provider: Provider) -> Self {
+        let Some(column_type) = column.column_type.as_deref() else {
+            return Self::new("String", None);
+        };
+        let Some(known) = ColumnType::parse(column_type) else {
+            return match column_type.to_lowercase().as_str() {
+                "uuid" if provider == Provider::PostgreSql => {
+                    Self::new("String", Some("Uuid".to_string()))
+                }
+                "jsonb" => Self::new("Json", None),
+                _ => Self::new(&unsupported(&format_column_type(column)), None),
+            };
+        };
+        let length = column.length.unwrap_or(0);
+        let decimal = column.decimal.unwrap_or(0);
+        let unsigned = provider == Provider::MySql
+            && known.config().supports_unsigned
+            && column.unsigned.unwrap_or(false);
+        let mysql = provider == Provider::MySql;
+        let postgresql = provider == Provider::PostgreSql;
+        let native = |name: &str| Some(name.to_string());
+        let integer = |name: &str| {
+            if unsigned {
+                Some(format!("Unsigned{name}"))
+            } else {
+                Some(name.to_string())
+            }
+        };
+        let (scalar, native) = match known {
+            ColumnType::Char if provider != Provider::Sqlite => ("String", native("Char(1)")),
+            ColumnType::CharN if provider != Provider::Sqlite => {
+                ("String", Some(format!("Char({length})")))
+            }
+            ColumnType::VarCharN if provider != Provider::Sqlite => {
+                ("String", Some(format!("VarChar({length})")))
+            }
+            ColumnType::Text if mysql => ("String", native("Text")),
+            ColumnType::TinyText if mysql => ("String", native("TinyText")),
+            ColumnType::MediumText if mysql => ("String", native("MediumText")),
+            ColumnType::LongText if mysql => ("String", native("LongText")),
+            ColumnType::Char
+            | ColumnType::CharN
+            | ColumnType::VarCharN
+            | ColumnType::Text
+            | ColumnType::TinyText
+            | ColumnType::MediumText
+            | ColumnType::LongText
+            | ColumnType::Set => ("String", None),
+            ColumnType::Int | ColumnType::IntN if unsigned => ("Int", integer("Int")),
+            ColumnType::TinyInt | ColumnType::TinyIntN if mysql => ("Int", integer("TinyInt")),
+            ColumnType::SmallInt | ColumnType::SmallIntN if mysql => ("Int", integer("SmallInt")),
+            ColumnType::MediumInt | ColumnType::MediumIntN if mysql => {
+                ("Int", integer("MediumInt"))
+            }
+            ColumnType::TinyInt
+            | ColumnType::TinyIntN
+            | ColumnType::SmallInt
+            | ColumnType::SmallIntN
+                if postgresql =>
+            {
+                ("Int", native("SmallInt"))
+            }
+            ColumnType::Int
+            | ColumnType::IntN
+            | ColumnType::TinyInt
+            | ColumnType::TinyIntN
+            | ColumnType::SmallInt
+            | ColumnType::SmallIntN
+            | ColumnType::MediumInt
+            | ColumnType::MediumIntN => ("Int", None),
+            ColumnType::BigInt | ColumnType::BigIntN if unsigned => {
+                ("BigInt", native("UnsignedBigInt"))
+            }
+            ColumnType::BigInt | ColumnType::BigIntN => ("BigInt", None),
+            ColumnType::Year2 | ColumnType::Year4 if mysql => ("Int", native("Year")),
+            ColumnType::Year2 | ColumnType::Year4 if postgresql => ("Int", native("SmallInt")),
+            ColumnType::Year2 | ColumnType::Year4 => ("Int", None),
+            ColumnType::Decimal | ColumnType::Numeric => ("Decimal", None),
+            ColumnType::DecimalP | ColumnType::NumericP if provider != Provider::Sqlite => {
+                ("Decimal", Some(format!("Decimal({length}, 0)")))
+            }
+            ColumnType::DecimalPS | ColumnType::NumericPS if provider != Provider::Sqlite => {
+                ("Decimal", Some(format!("Decimal({length}, {decimal})")))
+            }
+            ColumnType::DecimalP
+            | ColumnType::NumericP
+            | ColumnType::DecimalPS
+            | ColumnType::NumericPS => ("Decimal", None),
+            ColumnType::Float | ColumnType::FloatMD | ColumnType::FloatP if mysql => {
+                ("Float", native("Float"))
+            }
+            ColumnType::Float
+            | ColumnType::FloatMD
+            | ColumnType::FloatP
+            | ColumnType::Real
+            | ColumnType::RealMD
+                if postgresql =>
+            {
+                ("Float", native("Real"))
+            }
+            ColumnType::Double
+            | ColumnType::DoubleMD
+            | ColumnType::Float
+            | ColumnType::FloatMD
+            | ColumnType::FloatP
+            | ColumnType::Real
+            | ColumnType::RealMD => ("Float", None),
+            ColumnType::Date if provider != Provider::Sqlite => ("DateTime", native("Date")),
+            ColumnType::Datetime if mysql => ("DateTime", native("DateTime(0)")),
+            ColumnType::Timestamp if mysql => ("DateTime", native("Timestamp(0)")),
+            ColumnType::Time if mysql => ("DateTime", native("Time(0)")),
+            ColumnType::Datetime | ColumnType::Timestamp if postgresql => {
+                ("DateTime", native("Timestamp(6)"))
+            }
+            ColumnType::Time if postgresql => ("DateTime", native("Time(6)")),
+            ColumnType::Date | ColumnType::Datetime | ColumnType::Timestamp | ColumnType::Time => {
+                ("DateTime", None)
+            }
+            ColumnType::Boolean => ("Boolean", None),
+            // Prisma takes `bit(1)` as a boolean, and other bit strings of PostgreSQL
+            // as strings.
+            ColumnType::BitN if provider != Provider::Sqlite && length == 1 => {
+                ("Boolean", native("Bit(1)"))
+            }
+            ColumnType::BitN if mysql => ("Bytes", Some(format!("Bit({length})"))),
+            ColumnType::BitN if postgresql => ("String", Some(format!("Bit({length})"))),
+            ColumnType::Binary if mysql => ("Bytes", native("Binary(1)")),
+            ColumnType::BinaryN if mysql => ("Bytes", Some(format!("Binary({length})"))),
+            ColumnType::VarBinaryN if mysql => ("Bytes", Some(format!("VarBinary({length})"))),
+            ColumnType::Blob if mysql => ("Bytes", native("Blob")),
+            ColumnType::TinyBlob if mysql => ("Bytes", native("TinyBlob")),
+            ColumnType::MediumBlob if mysql => ("Bytes", native("MediumBlob")),
+            ColumnType::LongBlob if mysql => ("Bytes", native("LongBlob")),
+            ColumnType::Binary
+            | ColumnType::BinaryN
+            | ColumnType::VarBinaryN
+            | ColumnType::BitN
+            | ColumnType::Blob
+            | ColumnType::TinyBlob
+            | ColumnType::MediumBlob
+            | ColumnType::LongBlob => ("Bytes", None),
+            ColumnType::Json if postgresql => ("Json", native("Json")),
+            ColumnType::Json => ("Json", None),
+            ColumnType::Enum => ("String", None),
+            ColumnType::Geometry => return Self::new(&unsupported("geometry"), None),
+        };
+        Self::new(scalar, native)
+    }
+}
+
+fn string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn unsupported(sql_type: &str) -> String {
+    format!("Unsupported({})", string(sql_type))
+}
+
+fn lower_first(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// A camel case name for a field, e.g. `memberId` for `MEMBER_ID`.
+fn camel_case(name: &str) -> String {
+    lower_first(&pascal_case(name))
+}
+
+/// An enum value as an identifier, mapped when it is not one.
+fn enum_value(value: &str) -> (String, bool) {
+    let mut name = identifier(value);
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    let mapped = name != value;
+    (name, mapped)
+}
+
+/// The `///` lines holding a logical name and a description, the logical name first
+/// whenever there is a description.
+fn doc_comment(physical_name: &str, logical_name: &str, description: &str) -> Vec<String> {
+    let description = description.trim();
+    let logical_name = match logical_name.trim() {
+        "" => physical_name,
+        logical_name => logical_name,
+    };
+    let mut lines = Vec::new();
+    if !description.is_empty() || logical_name != physical_name {
+        lines.push(logical_name.to_string());
+    }
+    if !description.is_empty() {
+        lines.push(String::new());
+        lines.extend(description.lines().map(|line| line.trim_end().to_string()));
+    }
+    lines
+}
+
+fn push_doc(out: &mut String, indent: &str, lines: &[String]) {
+    for line in lines {
+        if line.is_empty() {
+            let _ = writeln!(out, "{indent}///");
+        } else {
+            let _ = writeln!(out, "{indent}/// {line}");
+        }
+    }
+}
+
+/// The `@default` value of a column, with expressions other than `now()` in
+/// `dbgenerated`.
+fn default_value(value: &str, field_type: &FieldType, enum_type: Option<&EnumType>) -> String {
+    let trimmed = value.trim();
+    let lowercase = trimmed.to_lowercase();
+    if let Some(enum_type) = enum_type
+        && let Some((name, _)) = enum_type.values.iter().find(|(_, value)| value == trimmed)
+    {
+        return name.clone();
+    }
+    if field_type.scalar == "DateTime"
+        && matches!(
+            lowercase.as_str(),
+            "current_timestamp" | "current_timestamp()" | "now()"
+        )
+    {
+        return "now()".to_string();
+    }
+    match field_type.scalar.as_str() {
+        "Boolean" if matches!(lowercase.as_str(), "true" | "1") => "true".to_string(),
+        "Boolean" if matches!(lowercase.as_str(), "false" | "0") => "false".to_string(),
+        "Int" | "BigInt" | "Float" | "Decimal" if trimmed.parse::<f64>().is_ok() => {
+            trimmed.to_string()
+        }
+        _ if trimmed.contains('(')
+            || (trimmed.starts_with(|c: char| c.is_ascii_alphabetic())
+                && trimmed
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')) =>
+        {
+            format!("dbgenerated({})", string(trimmed))
+        }
+        _ => string(value),
+    }
+}
+
+/// An enum generated for a column with enum values.
+struct EnumType {
+    name: String,
+    /// The names of the values, with the values.
+    values: Vec<(String, String)>,
+}
+
+/// A relation field, on either side of a relationship.
+struct RelationField {
+    name: String,
+    model: String,
+    /// `?` or `[]`, if any.
+    modifier: &'static str,
+    attribute: Option<String>,
+}
+
+/// A model: its fields by physical name and its relation fields.
+struct Model {
+    name: String,
+    fields: HashMap<String, String>,
+    relations: Vec<RelationField>,
+    used: HashSet<String>,
+}
+
+impl Model {
+    fn field(&self, column: &str) -> String {
+        self.fields
+            .get(column)
+            .cloned()
+            .unwrap_or_else(|| camel_case(column))
+    }
+
+    /// A relation field name unused by the model, trying `preferred` first.
+    fn unused_name(&mut self, preferred: &str, qualified: &str) -> String {
+        let mut name = if self.used.contains(preferred) {
+            qualified.to_string()
+        } else {
+            preferred.to_string()
+        };
+        let base = name.clone();
+        let mut suffix = 2;
+        while self.used.contains(&name) {
+            name = format!("{base}{suffix}");
+            suffix += 1;
+        }
+        self.used.insert(name.clone());
+        name
+    }
+}
+
+fn action(action: &str) -> Option<&'static str> {
+    match action.trim().to_uppercase().as_str() {
+        "CASCADE" => Some("Cascade"),
+        "SET NULL" => Some("SetNull"),
+        "SET DEFAULT" => Some("SetDefault"),
+        "RESTRICT" => Some("Restrict"),
+        "NO ACTION" => Some("NoAction"),
+        _ => None,
+    }
+}
+
+/// Whether the columns are unique in the table, which Prisma requires of one-to-one
+/// relations.
+fn is_unique(table: &Table, columns: &[ResolvedColumn], names: &[String]) -> bool {
+    let same = |keys: Vec<&str>| {
+        keys.len() == names.len() && keys.iter().all(|key| names.iter().any(|name| name == key))
+    };
+    let primary_key: Vec<&str> = columns
+        .iter()
+        .filter(|column| column.is_primary_key())
+        .map(|column| column.column.physical_name.as_str())
+        .collect();
+    same(primary_key)
+        || (names.len() == 1
+            && columns
+                .iter()
+                .any(|column| column.column.physical_name == names[0] && column.is_unique()))
+        || table
+            .index_list()
+            .iter()
+            .filter(|index| index.is_unique())
+            .any(|index| same(index.column_names()))
+        || table
+            .compound_unique_keys()
+            .iter()
+            .any(|key| same(key.column_names()))
+}
+
+fn models(diagram: &Diagram) -> HashMap<String, Model> {
+    let mut models = HashMap::new();
+    let mut names = HashSet::new();
+    for table in diagram.tables() {
+        let mut name = pascal_case(&table.physical_name);
+        let base = name.clone();
+        let mut suffix = 2;
+        while !names.insert(name.clone()) {
+            name = format!("{base}{suffix}");
+            suffix += 1;
+        }
+        let mut fields = HashMap::new();
+        let mut used = HashSet::new();
+        for column in diagram.resolve_columns(table) {
+            let mut field = camel_case(&column.column.physical_name);
+            let base = field.clone();
+            let mut suffix = 2;
+            while !used.insert(field.clone()) {
+                field = format!("{base}{suffix}");
+                suffix += 1;
+            }
+            fields.insert(column.column.physical_name, field);
+        }
+        models.insert(
+            table.physical_name.clone(),
+            Model {
+                name,
+                fields,
+                relations: Vec::new(),
+                used,
+            },
+        );
+    }
+
+    let relationships: Vec<&Relationship> = diagram.relationships().collect();
+    for relationship in &relationships {
+        let (child, parent) = (relationship.target_table(), relationship.source_table());
+        let (Some(child_table), true) = (diagram.find_table(child), models.contains_key(parent))
+        else {
+            continue;
+        };
+        let Some((fk_columns, referred)) = diagram.foreign_key_columns(relationship) else {
+            continue;
+        };
+        let ambiguous = child == parent
+            || relationships
+                .iter()
+                .filter(|other| {
+                    let (other_child, other_parent) = (other.target_table(), other.source_table());
+                    (other_child == child && other_parent == parent)
+                        || (other_child == parent && other_parent == child)
+                })
+                .count()
+                > 1;
+        let columns = diagram.resolve_columns(child_table);
+        let optional = fk_columns.iter().any(|name| {
+            columns
+                .iter()
+                .any(|column| &column.column.physical_name == name && !column.is_not_null())
+        });
+        let one_to_one = matches!(relationship.child_cardinality.as_str(), "0..1" | "1")
+            && is_unique(child_table, &columns, &fk_columns);
+
+        let parent_model = &models[parent];
+        let child_model = &models[child];
+        let parent_name = parent_model.name.clone();
+        let child_name = child_model.name.clone();
+        let fk_suffix: String = fk_columns.iter().map(|name| pascal_case(name)).collect();
+        let fields: Vec<String> = fk_columns
+            .iter()
+            .map(|name| child_model.field(name))
+            .collect();
+        let references: Vec<String> = referred
+            .iter()
+            .map(|name| parent_model.field(name))
+            .collect();
+
+        let mut arguments = Vec::new();
+        if ambiguous {
+            arguments.push(string(&relationship.name));
+        }
+        arguments.push(format!("fields: [{}]", fields.join(", ")));
+        arguments.push(format!("references: [{}]", references.join(", ")));
+        if let Some(on_delete) = action(&relationship.on_delete_action) {
+            arguments.push(format!("onDelete: {on_delete}"));
+        }
+        if let Some(on_update) = action(&relationship.on_update_action) {
+            arguments.push(format!("onUpdate: {on_update}"));
+        }
+        if !relationship.name.is_empty() {
+            arguments.push(format!("map: {}", string(&relationship.name)));
+        }
+
+        let preferred = lower_first(&parent_name);
+        let qualified = format!("{preferred}By{fk_suffix}");
+        let child_model = models.get_mut(child).expect("the child is a model");
+        let name =
+            child_model.unused_name(if ambiguous { &qualified } else { &preferred }, &qualified);
+        child_model.relations.push(RelationField {
+            name,
+            model: parent_name,
+            modifier: if optional { "?" } else { "" },
+            attribute: Some(format!("@relation({})", arguments.join(", "))),
+        });
+
+        let preferred = lower_first(&child_name);
+        let qualified = format!("{preferred}By{fk_suffix}");
+        let parent_model = models.get_mut(parent).expect("the parent is a model");
+        let name =
+            parent_model.unused_name(if ambiguous { &qualified } else { &preferred }, &qualified);
+        parent_model.relations.push(RelationField {
+            name,
+            model: child_name,
+            modifier: if one_to_one { "?" } else { "[]" },
+            attribute: ambiguous.then(|| format!("@relation({})", string(&relationship.name))),
+        });
+    }
+    models
+}
+
+/// Fields aligned in columns, as `prisma format` writes them.
+struct Field {
+    doc: Vec<String>,
+    name: String,
+    field_type: String,
+    attributes: Vec<String>,
+}
+
+fn push_fields(out: &mut String, fields: &[Field]) {
+    let name_width = fields
+        .iter()
+        .map(|field| field.name.len())
+        .max()
+        .unwrap_or(0);
+    let type_width = fields
+        .iter()
+        .map(|field| field.field_type.len())
+        .max()
+        .unwrap_or(0);
+    for field in fields {
+        push_doc(out, "  ", &field.doc);
+        let line = format!(
+            "  {:name_width$} {:type_width$} {}",
+            field.name,
+            field.field_type,
+            field.attributes.join(" ")
+        );
+        let _ = writeln!(out, "{}", line.trim_end());
+    }
+}
+
+fn index_fields(model: &Model, columns: &[(&str, bool)]) -> String {
+    let fields: Vec<String> = columns
+        .iter()
+        .map(|(name, desc)| {
+            let field = model.field(name);
+            if *desc {
+                format!("{field}(sort: Desc)")
+            } else {
+                field
+            }
+        })
+        .collect();
+    format!("[{}]", fields.join(", "))
+}
+
+fn push_model(
+    out: &mut String,
+    diagram: &Diagram,
+    table: &Table,
+    model: &Model,
+    provider: Provider,
+    enums: &mut Vec<EnumType>,
+) {
+    let columns = diagram.resolve_columns(table);
+    let primary_key: Vec<&ResolvedColumn> = columns
+        .iter()
+        .filter(|column| column.is_primary_key())
+        .collect();
+
+    push_doc(
+        out,
+        "",
+        &doc_comment(
+            &table.physical_name,
+            &table.logical_name,
+            &table.description,
+        ),
+    );
+    let _ = writeln!(out, "model {} {{", model.name);
+    let mut fields = Vec::new();
+    for column in &columns {
+        let name = model.field(&column.column.physical_name);
+        let mut field_type = FieldType::of(&column.column, provider);
+        let values = column
+            .column
+            .args
+            .as_deref()
+            .map(enum_values)
+            .unwrap_or_default();
+        let is_enum = column
+            .column
+            .column_type
+            .as_deref()
+            .and_then(ColumnType::parse)
+            == Some(ColumnType::Enum);
+        let enum_type = (is_enum && !values.is_empty()).then(|| EnumType {
+            name: format!(
+                "{}{}",
+                model.name,
+                pascal_case(&column.column.physical_name)
+            ),
+            values: values
+                .iter()
+                .map(|value| (enum_value(value).0, value.clone()))
+                .collect(),
+        });
+        if let Some(enum_type) = &enum_type {
+            field_type = FieldType::new(&enum_type.name, None);
+        }
+
+        let mut attributes = Vec::new();
+        if column.is_primary_key() && primary_key.len() == 1 {
+            attributes.push("@id".to_string());
+        }
+        if column.column.auto_increment.unwrap_or(false) {
+            attributes.push("@default(autoincrement())".to_string());
+        } else if let Some(value) = &column.column.default_value {
+            attributes.push(format!(
+                "@default({})",
+                default_value(value, &field_type, enum_type.as_ref())
+            ));
+        }
+        if column.is_unique() && !(column.is_primary_key() && primary_key.len() == 1) {
+            attributes.push("@unique".to_string());
+        }
+        if name != column.column.physical_name {
+            attributes.push(format!("@map({})", string(&column.column.physical_name)));
+        }
+        if let Some(native) = &field_type.native {
+            attributes.push(format!("@db.{native}"));
+        }
+        let optional = !column.is_not_null();
+        fields.push(Field {
+            doc: doc_comment(
+                &column.column.physical_name,
+                column.column.logical_name.as_deref().unwrap_or_default(),
+                column.column.description.as_deref().unwrap_or_default(),
+            ),
+            name,
+            field_type: format!("{}{}", field_type.scalar, if optional { "?" } else { "" }),
+            attributes,
+        });
+        enums.extend(enum_type);
+    }
+    push_fields(out, &fields);
+
+    if !model.relations.is_empty() {
+        out.push('\n');
+        let relations: Vec<Field> = model
+            .relations
+            .iter()
+            .map(|relation| Field {
+                doc: Vec::new(),
+                name: relation.name.clone(),
+                field_type: format!("{}{}", relation.model, relation.modifier),
+                attributes: relation.attribute.iter().cloned().collect(),
+            })
+            .collect();
+        push_fields(out, &relations);
+    }
+
+    let mut block_attributes = Vec::new();
+    if primary_key.len() > 1 {
+        let names: Vec<(&str, bool)> = primary_key
+            .iter()
+            .map(|column| (column.column.physical_name.as_str(), false))
+            .collect();
+        block_attributes.push(format!("@@id({})", index_fields(model, &names)));
+    }
+    for index in table.index_list() {
+        let names: Vec<(&str, bool)> = index
+            .columns
+            .column
+            .iter()
+            .map(|column| (column.column_name(), column.desc.unwrap_or(false)))
+            .collect();
+        let kind = if index.is_unique() { "unique" } else { "index" };
+        block_attributes.push(format!(
+            "@@{kind}({}, map: {})",
+            index_fields(model, &names),
+            string(&index.name)
+        ));
+    }
+    for key in table.compound_unique_keys() {
+        let names: Vec<(&str, bool)> = key
+            .column_names()
+            .into_iter()
+            .map(|name| (name, false))
+            .collect();
+        block_attributes.push(format!(
+            "@@unique({}, map: {})",
+            index_fields(model, &names),
+            string(&key.name)
+        ));
+    }
+    if model.name != table.physical_name {
+        block_attributes.push(format!("@@map({})", string(&table.physical_name)));
+    }
+    if !block_attributes.is_empty() {
+        out.push('\n');
+        for attribute in block_attributes {
+            let _ = writeln!(out, "  {attribute}");
+        }
+    }
+    out.push_str("}\n\n");
+}
+
+/// Renders the schema: the client generator, the datasource of the database of the
+/// diagram, a model per table in the order of the diagram and the enums of the columns.
+pub fn render(diagram: &Diagram) -> String {
+    let provider = Provider::of(diagram);
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "generator client {{\n  provider = \"prisma-client-js\"\n}}\n\n\
+         datasource db {{\n  provider = \"{}\"\n  url      = env(\"DATABASE_URL\")\n}}\n",
+        provider.name()
+    );
+    let models = models(diagram);
+    let mut enums = Vec::new();
+    for table in diagram.tables() {
+        push_model(
+            &mut out,
+            diagram,
+            table,
+            &models[&table.physical_name],
+            provider,
+            &mut enums,
+        );
+    }
+    for enum_type in enums {
+        let _ = writeln!(out, "enum {} {{", enum_type.name);
+        for (name, value) in &enum_type.values {
+            if enum_value(value).1 {
+                let _ = writeln!(out, "  {name} @map({})", string(value));
+            } else {
+                let _ = writeln!(out, "  {name}");
+            }
+        }
+        out.push_str("}\n\n");
+    }
+    let trimmed = out.trim_end().len();
+    out.truncate(trimmed);
+    out.push('\n');
+    out
+}
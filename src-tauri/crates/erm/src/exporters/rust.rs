@@ -0,0 +1,693 @@
+//! Rust model code for the tables of the diagram: SeaORM entity modules, or Diesel
+//! `table!` macros with `Queryable` structs. Column types map to Rust types for the
+//! database of the diagram, nullable columns become `Option`, relationships become
+//! relations, and logical names and descriptions become doc comments.
+
+use super::{doc_lines, identifier};
+use crate::column_type::ColumnType;
+use crate::dtos::diagram::{Diagram, ResolvedColumn};
+use crate::dtos::diagram_walkers::{Relationship, Table};
+use crate::importers::{postgresql, sqlite};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orm {
+    /// An entity module per table, with `mod.rs` and `prelude.rs`.
+    SeaOrm,
+    /// `schema.rs` with the `table!` macros, and `models.rs` with the structs.
+    Diesel,
+}
+
+impl Orm {
+    /// Parses an ORM name such as `sea-orm` or `diesel`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "sea-orm" | "seaorm" | "sea_orm" => Some(Orm::SeaOrm),
+            "diesel" => Some(Orm::Diesel),
+            _ => None,
+        }
+    }
+}
+
+/// A generated file, with its path relative to the output directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceFile {
+    pub path: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+    MySql,
+    PostgreSql,
+    Sqlite,
+}
+
+impl Backend {
+    fn of(diagram: &Diagram) -> Self {
+        let database = &diagram.diagram_settings.database;
+        if database.eq_ignore_ascii_case(postgresql::DATABASE) {
+            Backend::PostgreSql
+        } else if database.eq_ignore_ascii_case(sqlite::DATABASE) {
+            Backend::Sqlite
+        } else {
+            Backend::MySql
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Text,
+    Varchar,
+    TinyInt,
+    SmallInt,
+    Int,
+    BigInt,
+    Bool,
+    Decimal,
+    Float,
+    Double,
+    Date,
+    Time,
+    DateTime,
+    Timestamp,
+    Binary,
+    Json,
+}
+
+impl Kind {
+    fn of(column: &ResolvedColumn, backend: Backend) -> Self {
+        let column_type = column.column.column_type.as_deref().unwrap_or_default();
+        let name = match ColumnType::parse(column_type) {
+            Some(known) => known.config().label_without_args.to_string(),
+            // Types unknown to ERFlute are kept as declared, e.g. `uuid`.
+            None => column_type
+                .split(['(', ' '])
+                .next()
+                .unwrap_or_default()
+                .to_lowercase(),
+        };
+        match name.as_str() {
+            "char" | "varchar" => Kind::Varchar,
+            "tinyint" if backend == Backend::MySql => Kind::TinyInt,
+            "tinyint" | "smallint" | "year(2)" | "year(4)" => Kind::SmallInt,
+            "mediumint" | "int" | "integer" => Kind::Int,
+            "bigint" => Kind::BigInt,
+            "boolean" | "bool" => Kind::Bool,
+            "decimal" | "numeric" => Kind::Decimal,
+            "float" | "real" => Kind::Float,
+            "double" => Kind::Double,
+            "date" => Kind::Date,
+            "time" => Kind::Time,
+            "datetime" => Kind::DateTime,
+            "timestamp" => Kind::Timestamp,
+            "binary" | "varbinary" | "bit" | "blob" | "tinyblob" | "mediumblob" | "longblob"
+            | "geometry" | "bytea" => Kind::Binary,
+            "json" | "jsonb" => Kind::Json,
+            _ => Kind::Text,
+        }
+    }
+
+    fn is_integer(self) -> bool {
+        matches!(
+            self,
+            Kind::TinyInt | Kind::SmallInt | Kind::Int | Kind::BigInt
+        )
+    }
+}
+
+/// The type of a column, unsigned only where the database has unsigned types.
+struct FieldType {
+    kind: Kind,
+    unsigned: bool,
+}
+
+impl FieldType {
+    fn of(column: &ResolvedColumn, backend: Backend) -> Self {
+        let kind = Kind::of(column, backend);
+        Self {
+            kind,
+            unsigned: backend == Backend::MySql
+                && kind.is_integer()
+                && column.column.unsigned.unwrap_or(false),
+        }
+    }
+
+    fn rust(&self, orm: Orm) -> &'static str {
+        match (self.kind, self.unsigned) {
+            (Kind::Text | Kind::Varchar, _) => "String",
+            (Kind::TinyInt, false) => "i8",
+            (Kind::TinyInt, true) => "u8",
+            (Kind::SmallInt, false) => "i16",
+            (Kind::SmallInt, true) => "u16",
+            (Kind::Int, false) => "i32",
+            (Kind::Int, true) => "u32",
+            (Kind::BigInt, false) => "i64",
+            (Kind::BigInt, true) => "u64",
+            (Kind::Bool, _) => "bool",
+            (Kind::Float, _) => "f32",
+            (Kind::Double, _) => "f64",
+            (Kind::Binary, _) => "Vec<u8>",
+            // The prelude of SeaORM has aliases of these.
+            (Kind::Decimal, _) if orm == Orm::SeaOrm => "Decimal",
+            (Kind::Date, _) if orm == Orm::SeaOrm => "Date",
+            (Kind::Time, _) if orm == Orm::SeaOrm => "Time",
+            (Kind::DateTime | Kind::Timestamp, _) if orm == Orm::SeaOrm => "DateTime",
+            (Kind::Json, _) if orm == Orm::SeaOrm => "Json",
+            (Kind::Decimal, _) => "bigdecimal::BigDecimal",
+            (Kind::Date, _) => "chrono::NaiveDate",
+            (Kind::Time, _) => "chrono::NaiveTime",
+            (Kind::DateTime | Kind::Timestamp, _) => "chrono::NaiveDateTime",
+            (Kind::Json, _) => "serde_json::Value",
+        }
+    }
+
+    /// The type of `diesel::sql_types`.
+    fn diesel(&self, backend: Backend) -> String {
+        let sql_type = match self.kind {
+            Kind::Text => "Text",
+            Kind::Varchar => "Varchar",
+            Kind::TinyInt => "TinyInt",
+            Kind::SmallInt => "SmallInt",
+            Kind::Int => "Integer",
+            Kind::BigInt => "BigInt",
+            Kind::Bool => "Bool",
+            Kind::Decimal => "Numeric",
+            Kind::Float => "Float",
+            Kind::Double => "Double",
+            Kind::Date => "Date",
+            Kind::Time => "Time",
+            Kind::DateTime if backend == Backend::MySql => "Datetime",
+            Kind::DateTime | Kind::Timestamp => "Timestamp",
+            Kind::Binary => "Binary",
+            Kind::Json => "Json",
+        };
+        if self.unsigned {
+            format!("Unsigned<{sql_type}>")
+        } else {
+            sql_type.to_string()
+        }
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// A snake case name for a table or column, e.g. `member_id` for `MEMBER_ID`, raw if it
+/// is a keyword.
+fn snake_case(name: &str) -> String {
+    let mut snake = identifier(name).to_lowercase();
+    if snake.is_empty() || snake.starts_with(|c: char| c.is_ascii_digit()) {
+        snake.insert(0, '_');
+    }
+    match snake.as_str() {
+        // Keywords that cannot be raw identifiers.
+        "crate" | "self" | "super" => snake + "_",
+        _ if KEYWORDS.contains(&snake.as_str()) => format!("r#{snake}"),
+        _ => snake,
+    }
+}
+
+/// A pascal case name matching [`snake_case`], as SeaORM derives the variants of
+/// `Column` from the field names, e.g. `MemberProfiles` for `MEMBER_PROFILES`.
+fn pascal_case(name: &str) -> String {
+    super::pascal_case(snake_case(name).trim_start_matches("r#"))
+}
+
+fn push_doc(out: &mut String, indent: &str, marker: &str, lines: &[String]) {
+    for line in lines {
+        if line.is_empty() {
+            let _ = writeln!(out, "{indent}{marker}");
+        } else {
+            let _ = writeln!(out, "{indent}{marker} {line}");
+        }
+    }
+}
+
+fn column_doc(column: &ResolvedColumn) -> Vec<String> {
+    doc_lines(
+        &column.column.physical_name,
+        column.column.logical_name.as_deref().unwrap_or_default(),
+        column.column.description.as_deref().unwrap_or_default(),
+    )
+}
+
+fn field_type(column: &ResolvedColumn, backend: Backend, orm: Orm) -> String {
+    let rust = FieldType::of(column, backend).rust(orm);
+    if column.is_not_null() {
+        rust.to_string()
+    } else {
+        format!("Option<{rust}>")
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// The `ForeignKeyAction` of SeaORM for a referential action such as `SET NULL`.
+fn sea_orm_action(action: &str) -> Option<&'static str> {
+    match action.trim().to_uppercase().as_str() {
+        "CASCADE" => Some("Cascade"),
+        "SET NULL" => Some("SetNull"),
+        "SET DEFAULT" => Some("SetDefault"),
+        "RESTRICT" => Some("Restrict"),
+        "NO ACTION" => Some("NoAction"),
+        _ => None,
+    }
+}
+
+/// A relation of a SeaORM entity.
+struct Relation<'a> {
+    variant: String,
+    relationship: &'a Relationship,
+    /// The other table, or None for a relationship of the table with itself.
+    other: Option<&'a Table>,
+    belongs_to: bool,
+    /// The foreign key columns of the child and the columns of the parent they refer to.
+    columns: (Vec<String>, Vec<String>),
+}
+
+fn sea_orm_columns(columns: &[String], module: Option<&str>) -> String {
+    let variants: Vec<String> = columns
+        .iter()
+        .map(|column| match module {
+            Some(module) => format!("super::{module}::Column::{}", pascal_case(column)),
+            None => format!("Column::{}", pascal_case(column)),
+        })
+        .collect();
+    if variants.len() == 1 {
+        variants[0].clone()
+    } else {
+        format!("({})", variants.join(", "))
+    }
+}
+
+/// The relations of an entity. Relationships whose keys cannot be resolved are left
+/// out on both sides, as SeaORM derives `has_many` from the other side's `belongs_to`.
+fn sea_orm_relations<'a>(diagram: &'a Diagram, table: &'a Table) -> Vec<Relation<'a>> {
+    let mut relations = Vec::new();
+    for relationship in table.relationships() {
+        let Some(parent) = diagram.find_table(relationship.source_table()) else {
+            continue;
+        };
+        let Some(columns) = diagram.foreign_key_columns(relationship) else {
+            continue;
+        };
+        let other = (parent.physical_name != table.physical_name).then_some(parent);
+        relations.push(Relation {
+            variant: pascal_case(&parent.physical_name),
+            relationship,
+            other,
+            belongs_to: true,
+            columns,
+        });
+    }
+    for relationship in diagram.relationships() {
+        if relationship.source_table() != table.physical_name
+            || relationship.target_table() == table.physical_name
+        {
+            continue;
+        }
+        let Some(child) = diagram.find_table(relationship.target_table()) else {
+            continue;
+        };
+        let Some(columns) = diagram.foreign_key_columns(relationship) else {
+            continue;
+        };
+        relations.push(Relation {
+            variant: pascal_case(&child.physical_name),
+            relationship,
+            other: Some(child),
+            belongs_to: false,
+            columns,
+        });
+    }
+
+    // Tables related more than once are told apart by the names of the relationships.
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for relation in &relations {
+        *counts.entry(relation.variant.clone()).or_default() += 1;
+    }
+    for relation in &mut relations {
+        if counts[&relation.variant] > 1 || relation.other.is_none() {
+            relation.variant = pascal_case(&relation.relationship.name);
+        }
+    }
+    relations
+}
+
+fn sea_orm_entity(diagram: &Diagram, table: &Table, backend: Backend) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "//! `{}`", table.physical_name);
+    let doc = doc_lines(
+        &table.physical_name,
+        &table.logical_name,
+        &table.description,
+    );
+    if !doc.is_empty() {
+        out.push_str("//!\n");
+        push_doc(&mut out, "", "//!", &doc);
+    }
+    out.push_str(
+        "\nuse sea_orm::entity::prelude::*;\n\n\
+         #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]\n",
+    );
+    let _ = writeln!(
+        out,
+        "#[sea_orm(table_name = {})]\npub struct Model {{",
+        quote(&table.physical_name)
+    );
+    for column in diagram.resolve_columns(table) {
+        let field = snake_case(&column.column.physical_name);
+        push_doc(&mut out, "    ", "///", &column_doc(&column));
+        let mut attributes = Vec::new();
+        if field.trim_start_matches("r#") != column.column.physical_name {
+            attributes.push(format!(
+                "column_name = {}",
+                quote(&column.column.physical_name)
+            ));
+        }
+        if column.is_primary_key() {
+            attributes.push("primary_key".to_string());
+            let integer = FieldType::of(&column, backend).kind.is_integer();
+            if !integer || !column.column.auto_increment.unwrap_or(false) {
+                attributes.push("auto_increment = false".to_string());
+            }
+        }
+        if column.is_unique() {
+            attributes.push("unique".to_string());
+        }
+        if !attributes.is_empty() {
+            let _ = writeln!(out, "    #[sea_orm({})]", attributes.join(", "));
+        }
+        let _ = writeln!(
+            out,
+            "    pub {field}: {},",
+            field_type(&column, backend, Orm::SeaOrm)
+        );
+    }
+    out.push_str("}\n\n#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]\n");
+
+    let relations = sea_orm_relations(diagram, table);
+    if relations.is_empty() {
+        out.push_str("pub enum Relation {}\n");
+    } else {
+        out.push_str("pub enum Relation {\n");
+        for relation in &relations {
+            let module = relation.other.map(|other| snake_case(&other.physical_name));
+            let entity = match &module {
+                Some(module) => format!("super::{module}::Entity"),
+                None => "Entity".to_string(),
+            };
+            if !relation.belongs_to {
+                let kind = match relation.relationship.child_cardinality.as_str() {
+                    "0..1" | "1" => "has_one",
+                    _ => "has_many",
+                };
+                let _ = writeln!(
+                    out,
+                    "    #[sea_orm({kind} = {})]\n    {},",
+                    quote(&entity),
+                    relation.variant
+                );
+                continue;
+            }
+            let (fk_columns, referred) = &relation.columns;
+            let mut attributes = vec![
+                format!("belongs_to = {}", quote(&entity)),
+                format!("from = {}", quote(&sea_orm_columns(fk_columns, None))),
+                format!(
+                    "to = {}",
+                    quote(&sea_orm_columns(referred, module.as_deref()))
+                ),
+            ];
+            if let Some(action) = sea_orm_action(&relation.relationship.on_update_action) {
+                attributes.push(format!("on_update = {}", quote(action)));
+            }
+            if let Some(action) = sea_orm_action(&relation.relationship.on_delete_action) {
+                attributes.push(format!("on_delete = {}", quote(action)));
+            }
+            let _ = writeln!(
+                out,
+                "    #[sea_orm(\n        {}\n    )]\n    {},",
+                attributes.join(",\n        "),
+                relation.variant
+            );
+        }
+        out.push_str("}\n");
+    }
+
+    // `Related` can only be implemented once per entity.
+    let mut related: Vec<&Relation> = Vec::new();
+    for relation in &relations {
+        let Some(other) = relation.other else {
+            continue;
+        };
+        let same = |r: &&Relation| r.other.map(|o| &o.physical_name) == Some(&other.physical_name);
+        if relations.iter().filter(same).count() == 1 {
+            related.push(relation);
+        }
+    }
+    for relation in related {
+        let module = snake_case(&relation.other.unwrap().physical_name);
+        let _ = writeln!(
+            out,
+            "\nimpl Related<super::{module}::Entity> for Entity {{\n    \
+             fn to() -> RelationDef {{\n        Relation::{}.def()\n    }}\n}}",
+            relation.variant
+        );
+    }
+    out.push_str("\nimpl ActiveModelBehavior for ActiveModel {}\n");
+    out
+}
+
+fn sea_orm(diagram: &Diagram, backend: Backend) -> Vec<SourceFile> {
+    let mut tables: Vec<&Table> = diagram.tables().iter().collect();
+    tables.sort_by(|a, b| a.physical_name.cmp(&b.physical_name));
+
+    let mut modules = String::from("pub mod prelude;\n\n");
+    let mut prelude = String::new();
+    let mut files = Vec::new();
+    for table in tables {
+        let module = snake_case(&table.physical_name);
+        let _ = writeln!(modules, "pub mod {module};");
+        let _ = writeln!(
+            prelude,
+            "pub use super::{module}::Entity as {};",
+            pascal_case(&table.physical_name)
+        );
+        files.push(SourceFile {
+            path: format!("{}.rs", module.trim_start_matches("r#")),
+            content: sea_orm_entity(diagram, table, backend),
+        });
+    }
+    files.insert(
+        0,
+        SourceFile {
+            path: "mod.rs".to_string(),
+            content: modules,
+        },
+    );
+    files.insert(
+        1,
+        SourceFile {
+            path: "prelude.rs".to_string(),
+            content: prelude,
+        },
+    );
+    files
+}
+
+/// A relationship Diesel can join on: a single column referring to the primary key of
+/// another table.
+fn diesel_joinable(diagram: &Diagram, relationship: &Relationship) -> Option<String> {
+    if relationship.source_table() == relationship.target_table() {
+        return None;
+    }
+    let parent = diagram.find_table(relationship.source_table())?;
+    let (fk_columns, referred) = diagram.foreign_key_columns(relationship)?;
+    let primary_key: Vec<String> = diagram
+        .resolve_columns(parent)
+        .into_iter()
+        .filter(ResolvedColumn::is_primary_key)
+        .map(|column| column.column.physical_name)
+        .collect();
+    match fk_columns.as_slice() {
+        [fk_column] if !primary_key.is_empty() && referred == primary_key => {
+            Some(fk_column.clone())
+        }
+        _ => None,
+    }
+}
+
+fn diesel(diagram: &Diagram, backend: Backend) -> Vec<SourceFile> {
+    let mut schema = String::new();
+    let mut models = String::from("use diesel::prelude::*;\n");
+    let mut names = Vec::new();
+    let mut joins = Vec::new();
+
+    for table in diagram.tables() {
+        let name = snake_case(&table.physical_name);
+        let columns = diagram.resolve_columns(table);
+        let primary_key: Vec<String> = columns
+            .iter()
+            .filter(|column| column.is_primary_key())
+            .map(|column| snake_case(&column.column.physical_name))
+            .collect();
+        if primary_key.is_empty() {
+            let _ = writeln!(
+                schema,
+                "// `{}` is left out: Diesel needs a primary key.\n",
+                table.physical_name
+            );
+            continue;
+        }
+        let doc = doc_lines(
+            &table.physical_name,
+            &table.logical_name,
+            &table.description,
+        );
+
+        schema.push_str("diesel::table! {\n");
+        push_doc(&mut schema, "    ", "///", &doc);
+        if name.trim_start_matches("r#") != table.physical_name {
+            let _ = writeln!(schema, "    #[sql_name = {}]", quote(&table.physical_name));
+        }
+        let _ = writeln!(schema, "    {name} ({}) {{", primary_key.join(", "));
+        for column in &columns {
+            let field = snake_case(&column.column.physical_name);
+            push_doc(&mut schema, "        ", "///", &column_doc(column));
+            if field.trim_start_matches("r#") != column.column.physical_name {
+                let _ = writeln!(
+                    schema,
+                    "        #[sql_name = {}]",
+                    quote(&column.column.physical_name)
+                );
+            }
+            let sql_type = FieldType::of(column, backend).diesel(backend);
+            if column.is_not_null() {
+                let _ = writeln!(schema, "        {field} -> {sql_type},");
+            } else {
+                let _ = writeln!(schema, "        {field} -> Nullable<{sql_type}>,");
+            }
+        }
+        schema.push_str("    }\n}\n\n");
+
+        // Diesel takes one association per pair of tables.
+        let mut belongs_to = Vec::new();
+        for relationship in table.relationships() {
+            let Some(fk_column) = diesel_joinable(diagram, relationship) else {
+                continue;
+            };
+            let parent = snake_case(relationship.source_table());
+            if !joins
+                .iter()
+                .any(|(child, other, _)| *child == name && *other == parent)
+            {
+                belongs_to.push((
+                    pascal_case(relationship.source_table()),
+                    snake_case(&fk_column),
+                ));
+                joins.push((name.clone(), parent, snake_case(&fk_column)));
+            }
+        }
+
+        let _ = writeln!(models);
+        push_doc(&mut models, "", "///", &doc);
+        let derives = if belongs_to.is_empty() {
+            "Queryable, Selectable, Identifiable"
+        } else {
+            "Queryable, Selectable, Identifiable, Associations"
+        };
+        let _ = writeln!(models, "#[derive(Debug, Clone, PartialEq, {derives})]");
+        let _ = writeln!(
+            models,
+            "#[diesel(table_name = crate::schema::{name}, primary_key({}))]",
+            primary_key.join(", ")
+        );
+        for (parent, fk_column) in &belongs_to {
+            let _ = writeln!(
+                models,
+                "#[diesel(belongs_to({parent}, foreign_key = {fk_column}))]"
+            );
+        }
+        let _ = writeln!(
+            models,
+            "pub struct {} {{",
+            pascal_case(&table.physical_name)
+        );
+        for column in &columns {
+            push_doc(&mut models, "    ", "///", &column_doc(column));
+            let _ = writeln!(
+                models,
+                "    pub {}: {},",
+                snake_case(&column.column.physical_name),
+                field_type(column, backend, Orm::Diesel)
+            );
+        }
+        models.push_str("}\n");
+        names.push(name);
+    }
+
+    for (child, parent, fk_column) in &joins {
+        let _ = writeln!(
+            schema,
+            "diesel::joinable!({child} -> {parent} ({fk_column}));"
+        );
+    }
+    if !joins.is_empty() {
+        schema.push('\n');
+    }
+    if names.len() > 1 {
+        let _ = writeln!(
+            schema,
+            "diesel::allow_tables_to_appear_in_same_query!(\n    {},\n);",
+            names.join(",\n    ")
+        );
+    }
+    if schema.ends_with("\n\n") {
+        schema.pop();
+    }
+
+    vec![
+        SourceFile {
+            path: "schema.rs".to_string(),
+            content: schema,
+        },
+        SourceFile {
+            path: "models.rs".to_string(),
+            content: models,
+        },
+    ]
+}
+
+/// Renders the files of the models.
+pub fn render(diagram: &Diagram, orm: Orm) -> Vec<SourceFile> {
+    let backend = Backend::of(diagram);
+    match orm {
+        Orm::SeaOrm => sea_orm(diagram, backend),
+        Orm::Diesel => diesel(diagram, backend),
+    }
+}
+
+/// Writes the files into `directory`, creating it if needed.
+pub fn export(
+    diagram: &Diagram,
+    orm: Orm,
+    directory: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::create_dir_all(directory)?;
+    for file in render(diagram, orm) {
+        std::fs::write(Path::new(directory).join(&file.path), file.content)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,651 @@
+//! SVG rendering of the diagram, laid out like the editor draws it: tables at their
+//! position with a header and a row per column, relationships as straight lines between
+//! the borders of the tables, plus notes and groups. Relationships use crow's feet, or
+//! IDEF1X when `diagram_settings.notation` says so.
+
+use super::escape_markup as escape;
+use crate::column_type::format_column_type;
+use crate::dtos::diagram::{Diagram, ResolvedColumn};
+use crate::dtos::diagram_walkers::{Color, Note, Relationship, Table, WalkerGroup};
+use std::fmt::Write;
+
+// The sizes of the table card of the editor.
+const HEADER_HEIGHT: f64 = 20.0;
+const ROW_HEIGHT: f64 = 20.0;
+const PADDING: f64 = 4.0;
+const ICON_WIDTH: f64 = 16.0;
+const HEADER_FONT_RATIO: f64 = 1.2;
+
+// The sizes of the cardinality symbols of the editor.
+const DEFAULT_OFFSET: f64 = 16.0;
+const SYMBOL_SPACING: f64 = 6.0;
+const LINE_LENGTH: f64 = 10.0;
+const CROWFOOT_DEPTH: f64 = 10.0;
+const CROWFOOT_SPREAD: f64 = 12.0;
+const CIRCLE_RADIUS: f64 = 3.0;
+const ADJACENT_SPACING: f64 = 2.0;
+
+const DEFAULT_FONT_SIZE: u16 = 9;
+const DEFAULT_FONT_FAMILY: &str = "sans-serif";
+const MARGIN: f64 = 20.0;
+const SELF_LOOP: f64 = 24.0;
+const NOTE_FOLD: f64 = 10.0;
+const STROKE: &str = "#555";
+
+/// How the cardinality of relationships is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Notation {
+    /// Information engineering: crow's feet, bars and circles.
+    CrowsFoot,
+    /// Dots at the child end, and dashed lines for non-identifying relationships.
+    Idef1x,
+}
+
+impl Notation {
+    /// Reads `diagram_settings.notation`, where ERFlute writes `IE` or `IDEF1X`.
+    pub fn of(diagram: &Diagram) -> Self {
+        match diagram.diagram_settings.notation.as_deref() {
+            Some(notation) if notation.eq_ignore_ascii_case("IDEF1X") => Notation::Idef1x,
+            _ => Notation::CrowsFoot,
+        }
+    }
+}
+
+/// Options of [`render`].
+#[derive(Default)]
+pub struct SvgOptions<'a> {
+    /// Links every table to the returned URL, e.g. its page in a documentation site.
+    pub table_link: Option<&'a dyn Fn(&Table) -> String>,
+}
+
+/// Position and size of an object of the diagram.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    fn center(&self) -> (f64, f64) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+
+    fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+}
+
+/// Formats a coordinate with at most two decimals.
+fn number(value: f64) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+    if rounded == rounded.trunc() {
+        format!("{}", rounded as i64)
+    } else {
+        format!("{rounded}")
+    }
+}
+
+fn rgb(color: &Color) -> String {
+    format!("rgb({},{},{})", color.r, color.g, color.b)
+}
+
+fn font_family(font_name: &str) -> String {
+    if font_name.trim().is_empty() {
+        DEFAULT_FONT_FAMILY.to_string()
+    } else {
+        format!("{}, {DEFAULT_FONT_FAMILY}", escape(font_name.trim()))
+    }
+}
+
+/// Font sizes are in points; this gives pixels.
+fn font_pixels(font_size: u16) -> f64 {
+    let points = if font_size == 0 {
+        DEFAULT_FONT_SIZE
+    } else {
+        font_size
+    };
+    f64::from(points) * 4.0 / 3.0
+}
+
+/// Estimates the width of a text, counting wide (e.g. Japanese) characters as a full
+/// em and others as about half of it.
+fn text_width(text: &str, font_pixels: f64) -> f64 {
+    text.chars()
+        .map(|c| if c.is_ascii() { 0.6 } else { 1.0 })
+        .sum::<f64>()
+        * font_pixels
+}
+
+fn column_label(column: &ResolvedColumn) -> String {
+    let column_type = format_column_type(&column.column);
+    if column_type.is_empty() {
+        column.column.physical_name.clone()
+    } else {
+        format!("{}: {column_type}", column.column.physical_name)
+    }
+}
+
+/// The bounds of a table as drawn: its stored size, grown to fit its columns like the
+/// table card of the editor does.
+pub fn table_bounds(diagram: &Diagram, table: &Table) -> Rect {
+    let columns = diagram.resolve_columns(table);
+    let font = font_pixels(table.font_size);
+    let content_width = columns
+        .iter()
+        .map(|column| 2.0 * ICON_WIDTH + text_width(&column_label(column), font))
+        .fold(0.0, f64::max);
+    let header_width = text_width(&table.physical_name, font * HEADER_FONT_RATIO) + 2.0 * PADDING;
+    let min_width = (2.0 * PADDING + content_width).max(header_width);
+    let min_height = HEADER_HEIGHT + PADDING + columns.len() as f64 * ROW_HEIGHT;
+    Rect {
+        x: f64::from(table.x),
+        y: f64::from(table.y),
+        width: f64::from(table.width).max(min_width.ceil()),
+        height: f64::from(table.height).max(min_height),
+    }
+}
+
+fn note_bounds(note: &Note) -> Rect {
+    Rect {
+        x: f64::from(note.x),
+        y: f64::from(note.y),
+        width: f64::from(note.width),
+        height: f64::from(note.height),
+    }
+}
+
+fn group_bounds(group: &WalkerGroup) -> Rect {
+    Rect {
+        x: f64::from(group.x),
+        y: f64::from(group.y),
+        width: f64::from(group.width),
+        height: f64::from(group.height),
+    }
+}
+
+/// Where the line from the center of `rect` towards `from` leaves `rect`, as in the
+/// editor.
+fn intersection(from: (f64, f64), rect: &Rect) -> (f64, f64) {
+    let (cx, cy) = rect.center();
+    let (vx, vy) = (from.0 - cx, from.1 - cy);
+    let (half_width, half_height) = (rect.width / 2.0, rect.height / 2.0);
+    if vx == 0.0 && vy == 0.0 {
+        return (rect.right(), cy);
+    }
+    if half_width == 0.0 || half_height == 0.0 {
+        return (cx, cy);
+    }
+    if vx.abs() / half_width > vy.abs() / half_height {
+        let y = cy + vy * half_width / vx.abs();
+        (
+            cx + vx.signum() * half_width,
+            y.clamp(cy - half_height, cy + half_height),
+        )
+    } else {
+        let x = cx + vx * half_height / vy.abs();
+        (
+            x.clamp(cx - half_width, cx + half_width),
+            cy + vy.signum() * half_height,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SymbolPart {
+    Line,
+    Circle,
+    Crowfoot,
+}
+
+fn symbol_parts(cardinality: &str) -> &'static [SymbolPart] {
+    match cardinality {
+        "0..1" => &[SymbolPart::Circle, SymbolPart::Line],
+        "1..n" => &[SymbolPart::Crowfoot, SymbolPart::Line],
+        "0..n" => &[SymbolPart::Crowfoot, SymbolPart::Circle],
+        _ => &[SymbolPart::Line],
+    }
+}
+
+fn spacing(current: SymbolPart, next: SymbolPart) -> f64 {
+    if (current == SymbolPart::Crowfoot) != (next == SymbolPart::Crowfoot) {
+        ADJACENT_SPACING
+    } else {
+        SYMBOL_SPACING
+    }
+}
+
+/// One end of a relationship line: the point on the table and the direction of the
+/// line away from it.
+#[derive(Debug, Clone, Copy)]
+struct End {
+    x: f64,
+    y: f64,
+    dx: f64,
+    dy: f64,
+}
+
+impl End {
+    fn at(&self, offset: f64) -> (f64, f64) {
+        (self.x + self.dx * offset, self.y + self.dy * offset)
+    }
+}
+
+struct Renderer<'a> {
+    diagram: &'a Diagram,
+    notation: Notation,
+    options: &'a SvgOptions<'a>,
+    out: String,
+}
+
+impl Renderer<'_> {
+    fn push_crows_foot(&mut self, end: End, cardinality: &str, length: f64) {
+        let parts = symbol_parts(cardinality);
+        let max_reach = (length / 2.0 - 2.0).max(0.0);
+        let span: f64 = parts.windows(2).map(|pair| spacing(pair[0], pair[1])).sum();
+        let start = DEFAULT_OFFSET.min(max_reach - span);
+        if start <= 0.0 {
+            return;
+        }
+        let (px, py) = (-end.dy, end.dx);
+        let mut offset = start;
+        for (index, part) in parts.iter().enumerate() {
+            let (cx, cy) = end.at(offset.min(max_reach));
+            match part {
+                SymbolPart::Line => {
+                    let half = LINE_LENGTH / 2.0;
+                    let _ = writeln!(
+                        self.out,
+                        "<path d=\"M {} {} L {} {}\" stroke=\"{STROKE}\" fill=\"none\"/>",
+                        number(cx + px * half),
+                        number(cy + py * half),
+                        number(cx - px * half),
+                        number(cy - py * half)
+                    );
+                }
+                SymbolPart::Circle => {
+                    let _ = writeln!(
+                        self.out,
+                        "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{STROKE}\"/>",
+                        number(cx),
+                        number(cy),
+                        number(CIRCLE_RADIUS)
+                    );
+                }
+                SymbolPart::Crowfoot => {
+                    let (bx, by) = (cx - end.dx * CROWFOOT_DEPTH, cy - end.dy * CROWFOOT_DEPTH);
+                    let half = CROWFOOT_SPREAD / 2.0;
+                    let _ = writeln!(
+                        self.out,
+                        "<path d=\"M {} {} L {} {} L {} {}\" stroke=\"{STROKE}\" fill=\"none\" \
+                         stroke-linejoin=\"round\"/>",
+                        number(bx + px * half),
+                        number(by + py * half),
+                        number(cx),
+                        number(cy),
+                        number(bx - px * half),
+                        number(by - py * half)
+                    );
+                }
+            }
+            if let Some(next) = parts.get(index + 1) {
+                offset += spacing(*part, *next);
+            }
+        }
+    }
+
+    /// IDEF1X marks the child end with a dot, labeled `P` for one or more, `Z` for
+    /// zero or one and `1` for exactly one.
+    fn push_idef1x_child(&mut self, end: End, cardinality: &str) {
+        let (cx, cy) = end.at(CIRCLE_RADIUS);
+        let _ = writeln!(
+            self.out,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{STROKE}\"/>",
+            number(cx),
+            number(cy),
+            number(CIRCLE_RADIUS)
+        );
+        let label = match cardinality {
+            "1..n" => "P",
+            "0..1" => "Z",
+            "1" => "1",
+            _ => return,
+        };
+        let (lx, ly) = end.at(CIRCLE_RADIUS * 2.0 + 6.0);
+        let _ = writeln!(
+            self.out,
+            "<text x=\"{}\" y=\"{}\" font-size=\"10\" fill=\"{STROKE}\">{label}</text>",
+            number(lx - end.dy * 8.0),
+            number(ly + end.dx * 8.0 + 3.0)
+        );
+    }
+
+    /// IDEF1X marks an optional parent with a hollow diamond.
+    fn push_idef1x_parent(&mut self, end: End, cardinality: &str) {
+        if cardinality != "0..1" {
+            return;
+        }
+        let (px, py) = (-end.dy, end.dx);
+        let (tx, ty) = end.at(0.0);
+        let (mx, my) = end.at(6.0);
+        let (bx, by) = end.at(12.0);
+        let _ = writeln!(
+            self.out,
+            "<path d=\"M {} {} L {} {} L {} {} L {} {} Z\" stroke=\"{STROKE}\" fill=\"white\"/>",
+            number(tx),
+            number(ty),
+            number(mx + px * 4.0),
+            number(my + py * 4.0),
+            number(bx),
+            number(by),
+            number(mx - px * 4.0),
+            number(my - py * 4.0)
+        );
+    }
+
+    fn push_relationship(&mut self, relationship: &Relationship) {
+        let (Some(parent), Some(child)) = (
+            self.diagram.find_table(relationship.source_table()),
+            self.diagram.find_table(relationship.target_table()),
+        ) else {
+            return;
+        };
+        let parent_bounds = table_bounds(self.diagram, parent);
+        let child_bounds = table_bounds(self.diagram, child);
+
+        let (path, source, target, length) = if parent.physical_name == child.physical_name {
+            // A loop over the top right corner.
+            let (sx, sy) = (
+                parent_bounds.right(),
+                parent_bounds.y + parent_bounds.height / 4.0,
+            );
+            let (tx, ty) = (
+                parent_bounds.x + parent_bounds.width * 3.0 / 4.0,
+                parent_bounds.y,
+            );
+            let path = format!(
+                "M {} {} H {} V {} H {} V {}",
+                number(sx),
+                number(sy),
+                number(sx + SELF_LOOP),
+                number(ty - SELF_LOOP),
+                number(tx),
+                number(ty)
+            );
+            let length = 2.0 * SELF_LOOP + (sy - ty) + (sx - tx);
+            let source = End {
+                x: sx,
+                y: sy,
+                dx: 1.0,
+                dy: 0.0,
+            };
+            let target = End {
+                x: tx,
+                y: ty,
+                dx: 0.0,
+                dy: -1.0,
+            };
+            (path, source, target, length.max(4.0 * DEFAULT_OFFSET))
+        } else {
+            let (sx, sy) = intersection(child_bounds.center(), &parent_bounds);
+            let (tx, ty) = intersection(parent_bounds.center(), &child_bounds);
+            let length = (tx - sx).hypot(ty - sy);
+            if length <= 0.0001 {
+                return;
+            }
+            let (dx, dy) = ((tx - sx) / length, (ty - sy) / length);
+            let path = format!(
+                "M {} {} L {} {}",
+                number(sx),
+                number(sy),
+                number(tx),
+                number(ty)
+            );
+            let source = End {
+                x: sx,
+                y: sy,
+                dx,
+                dy,
+            };
+            let target = End {
+                x: tx,
+                y: ty,
+                dx: -dx,
+                dy: -dy,
+            };
+            (path, source, target, length)
+        };
+
+        // IDEF1X draws identifying relationships solid, and others dashed.
+        let dash =
+            if self.notation == Notation::Idef1x && !self.diagram.is_identifying(relationship) {
+                " stroke-dasharray=\"6 4\""
+            } else {
+                ""
+            };
+        let _ = writeln!(
+            self.out,
+            "<g class=\"relationship\" data-name=\"{}\">",
+            escape(&relationship.name)
+        );
+        let _ = writeln!(
+            self.out,
+            "<path d=\"{path}\" stroke=\"{STROKE}\" fill=\"none\"{dash}/>"
+        );
+        match self.notation {
+            Notation::CrowsFoot => {
+                self.push_crows_foot(source, &relationship.parent_cardinality, length);
+                self.push_crows_foot(target, &relationship.child_cardinality, length);
+            }
+            Notation::Idef1x => {
+                self.push_idef1x_parent(source, &relationship.parent_cardinality);
+                self.push_idef1x_child(target, &relationship.child_cardinality);
+            }
+        }
+        self.out.push_str("</g>\n");
+    }
+
+    fn push_table(&mut self, index: usize, table: &Table) {
+        let bounds = table_bounds(self.diagram, table);
+        let font = font_pixels(table.font_size);
+        let clip = format!("clip-table-{index}");
+        let link = self.options.table_link.map(|link| link(table));
+        if let Some(link) = &link {
+            let _ = writeln!(self.out, "<a href=\"{}\">", escape(link));
+        }
+        let _ = writeln!(
+            self.out,
+            "<g class=\"table\" data-name=\"{}\" font-family=\"{}\">",
+            escape(&table.physical_name),
+            font_family(&table.font_name)
+        );
+        let _ = writeln!(
+            self.out,
+            "<clipPath id=\"{clip}\"><rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/></clipPath>",
+            number(bounds.x),
+            number(bounds.y),
+            number(bounds.width),
+            number(bounds.height)
+        );
+        let _ = writeln!(
+            self.out,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"2\" fill=\"{}\" stroke=\"{STROKE}\"/>",
+            number(bounds.x),
+            number(bounds.y),
+            number(bounds.width),
+            number(bounds.height),
+            rgb(&table.color)
+        );
+        let _ = writeln!(
+            self.out,
+            "<text x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\">{}</text>",
+            number(bounds.x + bounds.width / 2.0),
+            number(bounds.y + HEADER_HEIGHT / 2.0 + font * HEADER_FONT_RATIO * 0.35),
+            number(font * HEADER_FONT_RATIO),
+            escape(&table.physical_name)
+        );
+        let _ = writeln!(
+            self.out,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"white\"/>",
+            number(bounds.x + PADDING),
+            number(bounds.y + HEADER_HEIGHT),
+            number((bounds.width - 2.0 * PADDING).max(0.0)),
+            number((bounds.height - HEADER_HEIGHT - PADDING).max(0.0))
+        );
+
+        let _ = writeln!(
+            self.out,
+            "<g clip-path=\"url(#{clip})\" font-size=\"{}\">",
+            number(font)
+        );
+        for (row, column) in self.diagram.resolve_columns(table).iter().enumerate() {
+            let top = bounds.y + HEADER_HEIGHT + row as f64 * ROW_HEIGHT;
+            let middle = top + ROW_HEIGHT / 2.0;
+            let icon_x = bounds.x + PADDING + ICON_WIDTH / 2.0;
+            if column.is_primary_key() || column.is_foreign_key() {
+                let fill = if column.is_primary_key() {
+                    "#eab308"
+                } else {
+                    "#9ca3af"
+                };
+                let _ = writeln!(
+                    self.out,
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"3.5\" fill=\"{fill}\"/>",
+                    number(icon_x),
+                    number(middle)
+                );
+            }
+            if column.is_not_null() {
+                let _ = writeln!(
+                    self.out,
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"3.5\" fill=\"#4ade80\"/>",
+                    number(icon_x + ICON_WIDTH),
+                    number(middle)
+                );
+            }
+            let _ = writeln!(
+                self.out,
+                "<text x=\"{}\" y=\"{}\">{}</text>",
+                number(bounds.x + PADDING + 2.0 * ICON_WIDTH),
+                number(middle + font * 0.35),
+                escape(&column_label(column))
+            );
+        }
+        self.out.push_str("</g>\n</g>\n");
+        if link.is_some() {
+            self.out.push_str("</a>\n");
+        }
+    }
+
+    fn push_note(&mut self, note: &Note) {
+        let bounds = note_bounds(note);
+        let font = font_pixels(note.font_size);
+        let fold = NOTE_FOLD.min(bounds.width / 2.0).min(bounds.height / 2.0);
+        let _ = writeln!(
+            self.out,
+            "<g class=\"note\" font-family=\"{}\" font-size=\"{}\">",
+            font_family(&note.font_name),
+            number(font)
+        );
+        let _ = writeln!(
+            self.out,
+            "<path d=\"M {x} {y} H {} L {r} {} V {b} H {x} Z\" fill=\"{}\" stroke=\"{STROKE}\"/>",
+            number(bounds.right() - fold),
+            number(bounds.y + fold),
+            rgb(&note.color),
+            x = number(bounds.x),
+            y = number(bounds.y),
+            r = number(bounds.right()),
+            b = number(bounds.bottom()),
+        );
+        for (line, text) in note.note_text.lines().enumerate() {
+            let _ = writeln!(
+                self.out,
+                "<text x=\"{}\" y=\"{}\">{}</text>",
+                number(bounds.x + PADDING),
+                number(bounds.y + PADDING + font * (line as f64 + 1.0) * 1.2),
+                escape(text)
+            );
+        }
+        self.out.push_str("</g>\n");
+    }
+
+    fn push_group(&mut self, group: &WalkerGroup) {
+        let bounds = group_bounds(group);
+        let font = font_pixels(group.font_size);
+        let _ = writeln!(
+            self.out,
+            "<g class=\"group\" font-family=\"{}\" font-size=\"{}\">",
+            font_family(&group.font_name),
+            number(font)
+        );
+        let _ = writeln!(
+            self.out,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" fill-opacity=\"0.3\" \
+             stroke=\"{STROKE}\" stroke-dasharray=\"4 2\"/>",
+            number(bounds.x),
+            number(bounds.y),
+            number(bounds.width),
+            number(bounds.height),
+            rgb(&group.color)
+        );
+        let _ = writeln!(
+            self.out,
+            "<text x=\"{}\" y=\"{}\">{}</text>",
+            number(bounds.x + PADDING),
+            number(bounds.y + PADDING + font),
+            escape(&group.group_name)
+        );
+        self.out.push_str("</g>\n");
+    }
+}
+
+/// Renders the whole diagram. Groups are drawn first so they stay behind the tables,
+/// and notes last.
+pub fn render(diagram: &Diagram, options: &SvgOptions) -> String {
+    let mut bounds: Vec<Rect> = diagram
+        .tables()
+        .iter()
+        .map(|table| table_bounds(diagram, table))
+        .collect();
+    bounds.extend(diagram.notes().iter().map(note_bounds));
+    bounds.extend(diagram.walker_groups().iter().map(group_bounds));
+    let width = bounds.iter().map(Rect::right).fold(0.0, f64::max) + MARGIN;
+    let height = bounds.iter().map(Rect::bottom).fold(0.0, f64::max) + MARGIN;
+
+    let mut renderer = Renderer {
+        diagram,
+        notation: Notation::of(diagram),
+        options,
+        out: String::new(),
+    };
+    let _ = writeln!(
+        renderer.out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+         viewBox=\"0 0 {w} {h}\">",
+        w = number(width),
+        h = number(height)
+    );
+    renderer
+        .out
+        .push_str("<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+    for group in diagram.walker_groups() {
+        renderer.push_group(group);
+    }
+    for relationship in diagram.relationships() {
+        renderer.push_relationship(relationship);
+    }
+    for (index, table) in diagram.tables().iter().enumerate() {
+        renderer.push_table(index, table);
+    }
+    for note in diagram.notes() {
+        renderer.push_note(note);
+    }
+    renderer.out.push_str("</svg>\n");
+    renderer.out
+}
@@ -0,0 +1,164 @@
+//! A stable, line-oriented rendering of the schema meant for reading diffs, e.g. as a
+//! git `textconv` driver. Visual properties such as positions and colors are omitted.
+
+use crate::column_type::{format_column_type, format_group_column_type};
+use crate::dtos::column_groups::ColumnGroup;
+use crate::dtos::diagram::Diagram;
+use crate::dtos::diagram_walkers::{Column, NormalColumn, Relationship, Table};
+use std::fmt::Write;
+
+fn push_description(out: &mut String, indent: &str, description: Option<&str>) {
+    for line in description.unwrap_or_default().lines() {
+        let _ = writeln!(out, "{indent}# {line}");
+    }
+}
+
+fn push_name(out: &mut String, physical_name: &str, logical_name: Option<&str>) {
+    out.push_str(physical_name);
+    if let Some(logical_name) = logical_name.filter(|name| !name.is_empty()) {
+        let _ = write!(out, " \"{logical_name}\"");
+    }
+}
+
+fn push_flag(out: &mut String, flag: Option<bool>, label: &str) {
+    if flag.unwrap_or(false) {
+        out.push(' ');
+        out.push_str(label);
+    }
+}
+
+fn push_default(out: &mut String, default_value: Option<&str>) {
+    if let Some(default_value) = default_value {
+        let _ = write!(out, " DEFAULT {default_value}");
+    }
+}
+
+fn push_column(out: &mut String, diagram: &Diagram, column: &NormalColumn) {
+    out.push_str("  ");
+    push_name(out, &column.physical_name, column.logical_name.as_deref());
+
+    // Foreign key columns usually have no type of their own.
+    let column_type = format_column_type(&diagram.resolve_column(column));
+    if !column_type.is_empty() {
+        let _ = write!(out, " {column_type}");
+    }
+
+    push_flag(out, column.primary_key, "PK");
+    if let Some((table, referred)) = column.referred_table_and_column() {
+        let _ = write!(out, " FK({table}.{referred})");
+    }
+    push_flag(out, column.not_null, "NOT NULL");
+    push_flag(out, column.unique_key, "UNIQUE");
+    push_flag(out, column.auto_increment, "AUTO_INCREMENT");
+    push_default(out, column.default_value.as_deref());
+    out.push('\n');
+    push_description(out, "    ", column.description.as_deref());
+}
+
+fn push_table(out: &mut String, diagram: &Diagram, table: &Table) {
+    out.push_str("table ");
+    push_name(out, &table.physical_name, Some(&table.logical_name));
+    out.push('\n');
+    push_description(out, "  ", Some(&table.description));
+    for item in table.column_items() {
+        match item {
+            Column::Normal(column) => push_column(out, diagram, column),
+            Column::Group(name) => {
+                let _ = writeln!(out, "  column_group {name}");
+            }
+        }
+    }
+    for index in table.index_list() {
+        let kind = if index.is_unique() {
+            "unique_index"
+        } else {
+            "index"
+        };
+        let _ = writeln!(out, "  {kind} {} ({})", index.name, index.columns);
+    }
+    for key in table.compound_unique_keys() {
+        let _ = writeln!(out, "  unique {} ({})", key.name, key.columns);
+    }
+    if let Some(primary_key_name) = &table.primary_key_name {
+        let _ = writeln!(out, "  primary_key_name {primary_key_name}");
+    }
+    if let Some(table_constraint) = &table.table_constraint {
+        let _ = writeln!(out, "  constraint {table_constraint}");
+    }
+    if let Some(option) = &table.option {
+        let _ = writeln!(out, "  option {option}");
+    }
+    out.push('\n');
+}
+
+fn push_relationship(out: &mut String, relationship: &Relationship) {
+    let fk_columns: Vec<&str> = relationship
+        .fk_columns
+        .fk_column
+        .iter()
+        .map(|column| column.fk_column_name.as_str())
+        .collect();
+    let _ = writeln!(out, "relationship {}", relationship.name);
+    let _ = writeln!(
+        out,
+        "  {} ({}) -> {} ({})",
+        relationship.source_table(),
+        relationship.parent_cardinality,
+        relationship.target_table(),
+        relationship.child_cardinality
+    );
+    let _ = writeln!(out, "  columns {}", fk_columns.join(", "));
+    if relationship.reference_for_pk {
+        out.push_str("  references primary key\n");
+    }
+    let _ = writeln!(
+        out,
+        "  on delete {}, on update {}",
+        relationship.on_delete_action, relationship.on_update_action
+    );
+    out.push('\n');
+}
+
+fn push_column_group(out: &mut String, group: &ColumnGroup) {
+    let _ = writeln!(out, "column_group {}", group.column_group_name);
+    for column in group.columns.normal_columns.iter().flatten() {
+        out.push_str("  ");
+        push_name(out, &column.physical_name, column.logical_name.as_deref());
+        let _ = write!(out, " {}", format_group_column_type(column));
+        push_flag(out, column.not_null, "NOT NULL");
+        push_flag(out, column.unique_key, "UNIQUE");
+        push_default(out, column.default_value.as_deref());
+        out.push('\n');
+        push_description(out, "    ", column.description.as_deref());
+    }
+    out.push('\n');
+}
+
+/// Renders tables, relationships and column groups, each sorted by name. Columns keep
+/// their order in the table since it is part of the schema.
+pub fn render(diagram: &Diagram) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "database {}\n", diagram.diagram_settings.database);
+
+    let mut tables: Vec<&Table> = diagram.tables().iter().collect();
+    tables.sort_by(|a, b| a.physical_name.cmp(&b.physical_name));
+    for table in tables {
+        push_table(&mut out, diagram, table);
+    }
+
+    let mut relationships: Vec<&Relationship> = diagram.relationships().collect();
+    relationships.sort_by(|a, b| a.name.cmp(&b.name));
+    for relationship in relationships {
+        push_relationship(&mut out, relationship);
+    }
+
+    let mut groups: Vec<&ColumnGroup> = diagram.groups().iter().collect();
+    groups.sort_by(|a, b| a.column_group_name.cmp(&b.column_group_name));
+    for group in groups {
+        push_column_group(&mut out, group);
+    }
+
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
@@ -0,0 +1,247 @@
+//! TypeScript interfaces of the tables, each with a matching Zod schema validating rows.
+//! Types come from the same catalogue as the DDL: lengths become `.max()`, enums become
+//! unions of their values, 64-bit integers become `bigint`, decimals become strings
+//! checked against their precision and nullable columns accept `null`. Logical names
+//! and descriptions become JSDoc.
+
+use super::{doc_lines, pascal_case};
+use crate::column_type::{ColumnType, enum_values};
+use crate::dtos::diagram::{Diagram, ResolvedColumn};
+use crate::dtos::diagram_walkers::Table;
+use std::fmt::Write;
+
+/// The TypeScript type of a column and the Zod schema checking it, both without null.
+struct FieldType {
+    typescript: String,
+    zod: String,
+}
+
+impl FieldType {
+    fn new(typescript: &str, zod: impl Into<String>) -> Self {
+        Self {
+            typescript: typescript.to_string(),
+            zod: zod.into(),
+        }
+    }
+
+    fn of(column: &ResolvedColumn) -> Self {
+        let column = &column.column;
+        let Some(column_type) = column.column_type.as_deref() else {
+            return Self::new("unknown", "z.unknown()");
+        };
+        // Types unknown to ERFlute, e.g. `uuid`, are taken as text.
+        let Some(known) = ColumnType::parse(column_type) else {
+            return Self::new("string", "z.string()");
+        };
+        let config = known.config();
+        let length = column.length.filter(|_| config.supports_length);
+        let unsigned = config.supports_unsigned && column.unsigned.unwrap_or(false);
+        match known {
+            ColumnType::Char
+            | ColumnType::CharN
+            | ColumnType::VarCharN
+            | ColumnType::Text
+            | ColumnType::TinyText
+            | ColumnType::MediumText
+            | ColumnType::LongText => match length {
+                Some(length) => Self::new("string", format!("z.string().max({length})")),
+                None => Self::new("string", "z.string()"),
+            },
+            ColumnType::Int
+            | ColumnType::IntN
+            | ColumnType::TinyInt
+            | ColumnType::TinyIntN
+            | ColumnType::SmallInt
+            | ColumnType::SmallIntN
+            | ColumnType::MediumInt
+            | ColumnType::MediumIntN
+            | ColumnType::Year2
+            | ColumnType::Year4 => {
+                let mut zod = String::from("z.number().int()");
+                if unsigned {
+                    zod.push_str(".nonnegative()");
+                }
+                Self::new("number", zod)
+            }
+            // A number would lose the digits beyond 2^53, and drivers return 64-bit
+            // integers as strings, which the schema converts.
+            ColumnType::BigInt | ColumnType::BigIntN => {
+                let mut zod = String::from("z.coerce.bigint()");
+                if unsigned {
+                    zod.push_str(".nonnegative()");
+                }
+                Self::new("bigint", zod)
+            }
+            ColumnType::Double
+            | ColumnType::DoubleMD
+            | ColumnType::Float
+            | ColumnType::FloatMD
+            | ColumnType::FloatP
+            | ColumnType::Real
+            | ColumnType::RealMD => {
+                let mut zod = String::from("z.number()");
+                if unsigned {
+                    zod.push_str(".nonnegative()");
+                }
+                Self::new("number", zod)
+            }
+            // Strings, as a number would lose the digits beyond its precision.
+            ColumnType::Decimal
+            | ColumnType::DecimalP
+            | ColumnType::DecimalPS
+            | ColumnType::Numeric
+            | ColumnType::NumericP
+            | ColumnType::NumericPS => {
+                let decimal = column.decimal.filter(|_| config.supports_decimal);
+                Self::new(
+                    "string",
+                    format!(
+                        "z.string().regex({})",
+                        decimal_pattern(length, decimal, unsigned)
+                    ),
+                )
+            }
+            ColumnType::Enum => {
+                let values = column.args.as_deref().map(enum_values).unwrap_or_default();
+                if values.is_empty() {
+                    return Self::new("string", "z.string()");
+                }
+                let literals: Vec<String> = values.iter().map(|value| string(value)).collect();
+                Self {
+                    typescript: literals.join(" | "),
+                    zod: format!("z.enum([{}])", literals.join(", ")),
+                }
+            }
+            ColumnType::Boolean => Self::new("boolean", "z.boolean()"),
+            ColumnType::Binary
+            | ColumnType::BinaryN
+            | ColumnType::VarBinaryN
+            | ColumnType::BitN
+            | ColumnType::Blob
+            | ColumnType::TinyBlob
+            | ColumnType::MediumBlob
+            | ColumnType::LongBlob
+            | ColumnType::Geometry => Self::new("Uint8Array", "z.instanceof(Uint8Array)"),
+            ColumnType::Json => Self::new("unknown", "z.unknown()"),
+            // Dates and times as the database formats them.
+            ColumnType::Date
+            | ColumnType::Datetime
+            | ColumnType::Time
+            | ColumnType::Timestamp
+            | ColumnType::Set => Self::new("string", "z.string()"),
+        }
+    }
+}
+
+/// A regular expression literal accepting the decimals of the given precision and scale.
+fn decimal_pattern(precision: Option<u16>, scale: Option<u16>, unsigned: bool) -> String {
+    let sign = if unsigned { "" } else { "-?" };
+    match (precision, scale.unwrap_or(0)) {
+        (Some(precision), 0) => format!("/^{sign}\\d{{1,{precision}}}$/"),
+        (Some(precision), scale) => {
+            let integer_digits = precision.saturating_sub(scale).max(1);
+            format!("/^{sign}\\d{{1,{integer_digits}}}(\\.\\d{{1,{scale}}})?$/")
+        }
+        (None, _) => format!("/^{sign}\\d+(\\.\\d+)?$/"),
+    }
+}
+
+fn string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+/// A property name, quoted unless it is an identifier.
+fn property(name: &str) -> String {
+    let is_identifier = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        name.to_string()
+    } else {
+        string(name)
+    }
+}
+
+fn push_jsdoc(out: &mut String, indent: &str, lines: &[String]) {
+    match lines {
+        [] => {}
+        [line] => {
+            let _ = writeln!(out, "{indent}/** {} */", line.replace("*/", "*\\/"));
+        }
+        _ => {
+            let _ = writeln!(out, "{indent}/**");
+            for line in lines {
+                if line.is_empty() {
+                    let _ = writeln!(out, "{indent} *");
+                } else {
+                    let _ = writeln!(out, "{indent} * {}", line.replace("*/", "*\\/"));
+                }
+            }
+            let _ = writeln!(out, "{indent} */");
+        }
+    }
+}
+
+fn push_table(out: &mut String, diagram: &Diagram, table: &Table) {
+    let name = pascal_case(&table.physical_name);
+    let columns = diagram.resolve_columns(table);
+    let fields: Vec<(&ResolvedColumn, FieldType)> = columns
+        .iter()
+        .map(|column| (column, FieldType::of(column)))
+        .collect();
+    let table_doc = doc_lines(
+        &table.physical_name,
+        &table.logical_name,
+        &table.description,
+    );
+
+    push_jsdoc(out, "", &table_doc);
+    let _ = writeln!(out, "export interface {name} {{");
+    for (column, field_type) in &fields {
+        push_jsdoc(
+            out,
+            "  ",
+            &doc_lines(
+                &column.column.physical_name,
+                column.column.logical_name.as_deref().unwrap_or_default(),
+                column.column.description.as_deref().unwrap_or_default(),
+            ),
+        );
+        let null = if column.is_not_null() { "" } else { " | null" };
+        let _ = writeln!(
+            out,
+            "  {}: {}{null};",
+            property(&column.column.physical_name),
+            field_type.typescript
+        );
+    }
+    out.push_str("}\n\n");
+
+    push_jsdoc(out, "", &table_doc);
+    let _ = writeln!(out, "export const {name}Schema = z.object({{");
+    for (column, field_type) in &fields {
+        let null = if column.is_not_null() {
+            ""
+        } else {
+            ".nullable()"
+        };
+        let _ = writeln!(
+            out,
+            "  {}: {}{null},",
+            property(&column.column.physical_name),
+            field_type.zod
+        );
+    }
+    let _ = writeln!(out, "}}) satisfies z.ZodType<{name}>;");
+}
+
+/// Renders a module of an interface and a schema per table.
+pub fn render(diagram: &Diagram) -> String {
+    let mut out = String::from("import { z } from \"zod\";\n");
+    for table in diagram.tables() {
+        out.push('\n');
+        push_table(&mut out, diagram, table);
+    }
+    out
+}
@@ -0,0 +1,381 @@
+//! An Excel workbook of table definitions: an index sheet, a sheet per table and a
+//! sheet of relationships. The sheets of tables are named as configured by
+//! `export_settings.use_logical_name_as_sheet`, and `put_diagram_on_excel` adds a sheet
+//! with the image of the diagram.
+
+use super::png::{self, PngOptions};
+use crate::column_type::format_column_type;
+use crate::dtos::diagram::Diagram;
+use crate::dtos::diagram_walkers::{Relationship, Table};
+use rust_xlsxwriter::{Format, FormatBorder, Image, Url, Workbook, Worksheet};
+use std::collections::HashSet;
+
+const INDEX_SHEET: &str = "Index";
+const RELATIONSHIPS_SHEET: &str = "Relationships";
+const DIAGRAM_SHEET: &str = "Diagram";
+
+// Excel limits sheet names to 31 characters and rejects some punctuation.
+const MAX_SHEET_NAME_LENGTH: usize = 31;
+const INVALID_SHEET_NAME_CHARS: &[char] = &['[', ']', ':', '*', '?', '/', '\\'];
+
+const CHECK: &str = "✓";
+
+struct Formats {
+    title: Format,
+    label: Format,
+    header: Format,
+    cell: Format,
+}
+
+impl Formats {
+    fn new() -> Self {
+        let cell = Format::new().set_border(FormatBorder::Thin);
+        Self {
+            title: Format::new().set_bold().set_font_size(14),
+            label: Format::new().set_bold(),
+            header: cell.clone().set_bold().set_background_color(0xD9E1F2),
+            cell: cell.set_text_wrap(),
+        }
+    }
+}
+
+/// Picks unique, valid sheet names, since table names may be too long, contain
+/// characters Excel rejects, or collide once truncated.
+struct SheetNames {
+    used: HashSet<String>,
+}
+
+impl SheetNames {
+    fn new() -> Self {
+        Self {
+            used: [INDEX_SHEET, RELATIONSHIPS_SHEET, DIAGRAM_SHEET]
+                .iter()
+                .map(|name| name.to_lowercase())
+                .collect(),
+        }
+    }
+
+    fn pick(&mut self, name: &str) -> String {
+        let cleaned: String = name
+            .chars()
+            .map(|c| {
+                if INVALID_SHEET_NAME_CHARS.contains(&c) {
+                    '_'
+                } else {
+                    c
+                }
+            })
+            .collect();
+        let cleaned = cleaned.trim_matches('\'');
+        let cleaned = if cleaned.is_empty() { "Table" } else { cleaned };
+        let mut number = 1;
+        loop {
+            let suffix = if number == 1 {
+                String::new()
+            } else {
+                format!(" ({number})")
+            };
+            let base: String = cleaned
+                .chars()
+                .take(MAX_SHEET_NAME_LENGTH - suffix.chars().count())
+                .collect();
+            let candidate = format!("{}{suffix}", base.trim_end());
+            if self.used.insert(candidate.to_lowercase()) {
+                return candidate;
+            }
+            number += 1;
+        }
+    }
+}
+
+fn internal_link(sheet: &str) -> Url {
+    Url::new(format!("internal:'{}'!A1", sheet.replace('\'', "''")))
+}
+
+fn check(value: bool) -> &'static str {
+    if value { CHECK } else { "" }
+}
+
+/// Writes `values` into consecutive cells of `row`, starting at `first_column`.
+fn write_row(
+    worksheet: &mut Worksheet,
+    row: u32,
+    first_column: u16,
+    values: &[&str],
+    format: &Format,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for (column, value) in values.iter().enumerate() {
+        worksheet.write_string_with_format(row, first_column + column as u16, *value, format)?;
+    }
+    Ok(())
+}
+
+fn set_column_widths(
+    worksheet: &mut Worksheet,
+    widths: &[f64],
+) -> Result<(), Box<dyn std::error::Error>> {
+    for (column, width) in widths.iter().enumerate() {
+        worksheet.set_column_width(column as u16, *width)?;
+    }
+    Ok(())
+}
+
+fn sheet_name_of_table(table: &Table, use_logical_name: bool) -> &str {
+    if use_logical_name && !table.logical_name.trim().is_empty() {
+        &table.logical_name
+    } else {
+        &table.physical_name
+    }
+}
+
+fn write_table_sheet(
+    worksheet: &mut Worksheet,
+    diagram: &Diagram,
+    table: &Table,
+    formats: &Formats,
+) -> Result<(), Box<dyn std::error::Error>> {
+    worksheet.write_url(0, 0, internal_link(INDEX_SHEET).set_text(INDEX_SHEET))?;
+    worksheet.write_string_with_format(1, 0, &table.physical_name, &formats.title)?;
+    for (row, label, value) in [
+        (2, "Physical name", table.physical_name.as_str()),
+        (3, "Logical name", table.logical_name.as_str()),
+        (4, "Description", table.description.trim()),
+    ] {
+        worksheet.write_string_with_format(row, 0, label, &formats.label)?;
+        worksheet.write_string(row, 1, value)?;
+    }
+
+    let mut row = 6;
+    write_row(
+        worksheet,
+        row,
+        0,
+        &[
+            "No",
+            "Physical name",
+            "Logical name",
+            "Type",
+            "PK",
+            "FK",
+            "NOT NULL",
+            "UNIQUE",
+            "Default",
+            "Group",
+            "Description",
+        ],
+        &formats.header,
+    )?;
+    for (number, column) in diagram.resolve_columns(table).iter().enumerate() {
+        row += 1;
+        let referred = column
+            .column
+            .referred_table_and_column()
+            .map(|(table, referred)| format!("{table}.{referred}"))
+            .unwrap_or_default();
+        worksheet.write_number_with_format(row, 0, (number + 1) as f64, &formats.cell)?;
+        write_row(
+            worksheet,
+            row,
+            1,
+            &[
+                column.column.physical_name.as_str(),
+                column.column.logical_name.as_deref().unwrap_or_default(),
+                &format_column_type(&column.column),
+                check(column.is_primary_key()),
+                &referred,
+                check(column.is_not_null()),
+                check(column.is_unique()),
+                column.column.default_value.as_deref().unwrap_or_default(),
+                column.column_group.as_deref().unwrap_or_default(),
+                column
+                    .column
+                    .description
+                    .as_deref()
+                    .unwrap_or_default()
+                    .trim(),
+            ],
+            &formats.cell,
+        )?;
+    }
+
+    if !table.index_list().is_empty() {
+        row += 2;
+        write_row(
+            worksheet,
+            row,
+            0,
+            &["No", "Index", "Columns", "Unique"],
+            &formats.header,
+        )?;
+        for (number, index) in table.index_list().iter().enumerate() {
+            row += 1;
+            worksheet.write_number_with_format(row, 0, (number + 1) as f64, &formats.cell)?;
+            write_row(
+                worksheet,
+                row,
+                1,
+                &[
+                    &index.name,
+                    &index.columns.to_string(),
+                    check(index.is_unique()),
+                ],
+                &formats.cell,
+            )?;
+        }
+    }
+
+    set_column_widths(
+        worksheet,
+        &[
+            6.0, 24.0, 24.0, 18.0, 5.0, 28.0, 10.0, 9.0, 14.0, 12.0, 40.0,
+        ],
+    )?;
+    worksheet.set_freeze_panes(7, 0)?;
+    Ok(())
+}
+
+fn write_relationships_sheet(
+    worksheet: &mut Worksheet,
+    diagram: &Diagram,
+    formats: &Formats,
+) -> Result<(), Box<dyn std::error::Error>> {
+    write_row(
+        worksheet,
+        0,
+        0,
+        &[
+            "Relationship",
+            "Parent table",
+            "Child table",
+            "Foreign key columns",
+            "Parent cardinality",
+            "Child cardinality",
+            "On delete",
+            "On update",
+        ],
+        &formats.header,
+    )?;
+    let mut relationships: Vec<&Relationship> = diagram.relationships().collect();
+    relationships.sort_by(|a, b| a.name.cmp(&b.name));
+    for (row, relationship) in relationships.iter().enumerate() {
+        let fk_columns = relationship
+            .fk_columns
+            .fk_column
+            .iter()
+            .map(|column| column.fk_column_name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        write_row(
+            worksheet,
+            row as u32 + 1,
+            0,
+            &[
+                &relationship.name,
+                relationship.source_table(),
+                relationship.target_table(),
+                &fk_columns,
+                &relationship.parent_cardinality,
+                &relationship.child_cardinality,
+                &relationship.on_delete_action,
+                &relationship.on_update_action,
+            ],
+            &formats.cell,
+        )?;
+    }
+    set_column_widths(worksheet, &[36.0, 24.0, 24.0, 28.0, 18.0, 18.0, 12.0, 12.0])?;
+    worksheet.set_freeze_panes(1, 0)?;
+    Ok(())
+}
+
+/// Renders the workbook. When `export_settings.put_diagram_on_excel` is set, the diagram
+/// is put on its own sheet: `diagram_image` if given, e.g. as drawn by the editor, or
+/// else the image rendered by [`png::render`].
+pub fn render(
+    diagram: &Diagram,
+    diagram_image: Option<&[u8]>,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let settings = diagram
+        .diagram_settings
+        .export_settings
+        .clone()
+        .unwrap_or_default();
+    let use_logical_name = settings.use_logical_name_as_sheet.unwrap_or(false);
+    let formats = Formats::new();
+
+    let mut tables: Vec<&Table> = diagram.tables().iter().collect();
+    tables.sort_by(|a, b| a.physical_name.cmp(&b.physical_name));
+    let mut names = SheetNames::new();
+    let sheet_names: Vec<String> = tables
+        .iter()
+        .map(|table| names.pick(sheet_name_of_table(table, use_logical_name)))
+        .collect();
+
+    let mut workbook = Workbook::new();
+
+    let index = workbook.add_worksheet();
+    index.set_name(INDEX_SHEET)?;
+    write_row(
+        index,
+        0,
+        0,
+        &[
+            "No",
+            "Sheet",
+            "Physical name",
+            "Logical name",
+            "Description",
+        ],
+        &formats.header,
+    )?;
+    for (number, (table, sheet)) in tables.iter().zip(&sheet_names).enumerate() {
+        let row = number as u32 + 1;
+        index.write_number_with_format(row, 0, (number + 1) as f64, &formats.cell)?;
+        index.write_url_with_text(row, 1, internal_link(sheet), sheet)?;
+        write_row(
+            index,
+            row,
+            2,
+            &[
+                &table.physical_name,
+                &table.logical_name,
+                table.description.trim(),
+            ],
+            &formats.cell,
+        )?;
+    }
+    set_column_widths(index, &[6.0, 24.0, 24.0, 24.0, 48.0])?;
+    index.set_freeze_panes(1, 0)?;
+
+    if settings.put_diagram_on_excel.unwrap_or(false) {
+        let image = match diagram_image {
+            Some(image) => Image::new_from_buffer(image)?,
+            None => Image::new_from_buffer(&png::render(diagram, &PngOptions::default())?)?,
+        };
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(DIAGRAM_SHEET)?;
+        worksheet.insert_image(0, 0, &image)?;
+    }
+
+    for (table, sheet) in tables.iter().zip(&sheet_names) {
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(sheet)?;
+        write_table_sheet(worksheet, diagram, table, &formats)?;
+    }
+
+    let relationships = workbook.add_worksheet();
+    relationships.set_name(RELATIONSHIPS_SHEET)?;
+    write_relationships_sheet(relationships, diagram, &formats)?;
+
+    Ok(workbook.save_to_buffer()?)
+}
+
+/// Writes the workbook to `filename`, as configured by `export_settings.excel_output`
+/// for instance.
+pub fn export(
+    diagram: &Diagram,
+    diagram_image: Option<&[u8]>,
+    filename: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::write(filename, render(diagram, diagram_image)?)?;
+    Ok(())
+}
@@ -0,0 +1,414 @@
+pub mod dbml;
+pub mod ddl;
+pub mod information_schema;
+pub mod mysql;
+pub mod postgresql;
+pub mod prisma;
+pub mod sqlite;
+
+use crate::column_type::parse_sql_type;
+use crate::dtos::column_groups::ColumnGroups;
+use crate::dtos::diagram::Diagram;
+use crate::dtos::diagram_settings::DiagramSettings;
+use crate::dtos::diagram_walkers::{
+    Color, Column, Columns, CompoundUniqueKey, CompoundUniqueKeyList, Connections, DiagramWalkers,
+    FkColumn, FkColumns, Index, IndexColumn, IndexColumns, Indexes, NormalColumn, Relationship,
+    Table,
+};
+use crate::dtos::dictionary::Dictionary;
+use crate::exporters::svg::table_bounds;
+use crate::layout::{LayoutOptions, Strategy, layout_all};
+
+/// A table as read from a database catalog or a script, before it is turned into a
+/// diagram table.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ImportedTable {
+    pub name: String,
+    pub comment: Option<String>,
+    pub columns: Vec<ImportedColumn>,
+    pub primary_key: Vec<String>,
+    pub foreign_keys: Vec<ImportedForeignKey>,
+    pub indexes: Vec<ImportedIndex>,
+
+    /// Unique constraints, as opposed to unique indexes.
+    pub unique_keys: Vec<ImportedIndex>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ImportedColumn {
+    pub name: String,
+    pub sql_type: String,
+    pub not_null: bool,
+    pub unique: bool,
+    pub default_value: Option<String>,
+    pub auto_increment: bool,
+    pub comment: Option<String>,
+
+    /// The values of an enum, as in [`NormalColumn::args`].
+    pub args: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ImportedForeignKey {
+    pub name: Option<String>,
+    pub columns: Vec<String>,
+    pub referenced_table: String,
+    pub referenced_columns: Vec<String>,
+    pub on_delete: Option<String>,
+    pub on_update: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ImportedIndex {
+    pub name: String,
+
+    /// Column names with their descending flag.
+    pub columns: Vec<(String, bool)>,
+    pub unique: bool,
+}
+
+const DEFAULT_COLOR: Color = Color {
+    r: 128,
+    g: 128,
+    b: 192,
+};
+const DEFAULT_FONT_SIZE: u16 = 9;
+
+/// Splits a database comment into a logical name and a description. The first line,
+/// or the text before the first `;`, is the logical name.
+pub(crate) fn split_comment(comment: Option<&str>) -> (Option<String>, Option<String>) {
+    let Some(comment) = comment.map(str::trim).filter(|comment| !comment.is_empty()) else {
+        return (None, None);
+    };
+    let split = comment
+        .find(['\n', ';'])
+        .map(|position| (&comment[..position], &comment[position + 1..]));
+    match split {
+        Some((logical_name, description)) => {
+            let description = description.trim();
+            (
+                Some(logical_name.trim().to_string()),
+                (!description.is_empty()).then(|| description.to_string()),
+            )
+        }
+        None => (Some(comment.to_string()), None),
+    }
+}
+
+/// Strips the quotes of string literals in a column default, e.g. `'abc'` becomes `abc`.
+pub(crate) fn unquote_default(default_value: &str) -> String {
+    let trimmed = default_value.trim();
+    match trimmed
+        .strip_prefix('\'')
+        .and_then(|rest| rest.strip_suffix('\''))
+    {
+        Some(inner) => inner.replace("''", "'"),
+        None => trimmed.to_string(),
+    }
+}
+
+/// Points foreign keys declared without target columns, e.g. `REFERENCES members`, at
+/// the primary key of the parent table.
+pub(crate) fn fill_referenced_primary_keys(tables: &mut [ImportedTable]) {
+    let primary_keys: Vec<(String, Vec<String>)> = tables
+        .iter()
+        .map(|table| (table.name.clone(), table.primary_key.clone()))
+        .collect();
+    for foreign_key in tables.iter_mut().flat_map(|table| &mut table.foreign_keys) {
+        if foreign_key.referenced_columns.is_empty()
+            && let Some((_, primary_key)) = primary_keys
+                .iter()
+                .find(|(name, _)| *name == foreign_key.referenced_table)
+        {
+            foreign_key.referenced_columns = primary_key.clone();
+        }
+    }
+}
+
+fn flag(value: bool) -> Option<bool> {
+    value.then_some(true)
+}
+
+fn index_columns(columns: &[(String, bool)]) -> IndexColumns {
+    IndexColumns {
+        column: columns
+            .iter()
+            .map(|(name, desc)| IndexColumn {
+                column_id: name.clone(),
+                desc: flag(*desc),
+            })
+            .collect(),
+    }
+}
+
+fn build_column(table: &ImportedTable, column: &ImportedColumn) -> NormalColumn {
+    let sql_type = parse_sql_type(&column.sql_type);
+    let primary_key = table.primary_key.contains(&column.name);
+    let single_unique_key = table
+        .unique_keys
+        .iter()
+        .any(|key| key.columns.len() == 1 && key.columns[0].0 == column.name);
+    let (logical_name, description) = split_comment(column.comment.as_deref());
+    NormalColumn {
+        physical_name: column.name.clone(),
+        logical_name,
+        description,
+        column_type: sql_type.column_type,
+        length: sql_type.length,
+        decimal: sql_type.decimal,
+        unsigned: flag(sql_type.unsigned),
+        args: column.args.clone().or(sql_type.args),
+        not_null: flag(column.not_null || primary_key),
+        unique_key: flag(column.unique || single_unique_key),
+        default_value: column.default_value.clone(),
+        primary_key: flag(primary_key),
+        auto_increment: flag(column.auto_increment || sql_type.auto_increment),
+        referred_column: None,
+        relationship: None,
+    }
+}
+
+fn relationship_name(child: &str, parent: &str, used: &mut Vec<String>) -> String {
+    let base = format!("FK_{child}_{parent}");
+    let mut name = base.clone();
+    let mut suffix = 2;
+    while used.contains(&name) {
+        name = format!("{base}_{suffix}");
+        suffix += 1;
+    }
+    used.push(name.clone());
+    name
+}
+
+fn build_relationship(
+    imported: &[ImportedTable],
+    child: &ImportedTable,
+    foreign_key: &ImportedForeignKey,
+    name: String,
+) -> Relationship {
+    let parent = imported
+        .iter()
+        .find(|table| table.name == foreign_key.referenced_table);
+    let not_null = foreign_key.columns.iter().all(|name| {
+        child
+            .columns
+            .iter()
+            .any(|column| &column.name == name && column.not_null)
+            || child.primary_key.contains(name)
+    });
+    let same_columns = |columns: &[String]| {
+        columns.len() == foreign_key.columns.len()
+            && columns
+                .iter()
+                .all(|name| foreign_key.columns.contains(name))
+    };
+    let unique = same_columns(&child.primary_key)
+        || child
+            .unique_keys
+            .iter()
+            .chain(child.indexes.iter().filter(|index| index.unique))
+            .any(|key| {
+                same_columns(
+                    &key.columns
+                        .iter()
+                        .map(|(name, _)| name.clone())
+                        .collect::<Vec<_>>(),
+                )
+            })
+        || (foreign_key.columns.len() == 1
+            && child
+                .columns
+                .iter()
+                .any(|column| column.name == foreign_key.columns[0] && column.unique));
+    let reference_for_pk = parent.is_some_and(|parent| {
+        parent.primary_key.len() == foreign_key.referenced_columns.len()
+            && parent
+                .primary_key
+                .iter()
+                .all(|name| foreign_key.referenced_columns.contains(name))
+    });
+    let action = |action: &Option<String>| {
+        action
+            .as_deref()
+            .map_or("RESTRICT".to_string(), str::to_uppercase)
+    };
+
+    Relationship {
+        name,
+        source: format!("table.{}", foreign_key.referenced_table),
+        target: format!("table.{}", child.name),
+        fk_columns: FkColumns {
+            fk_column: foreign_key
+                .columns
+                .iter()
+                .map(|name| FkColumn {
+                    fk_column_name: name.clone(),
+                })
+                .collect(),
+        },
+        parent_cardinality: if not_null { "1" } else { "0..1" }.to_string(),
+        child_cardinality: if unique { "0..1" } else { "0..n" }.to_string(),
+        reference_for_pk,
+        on_delete_action: action(&foreign_key.on_delete),
+        on_update_action: action(&foreign_key.on_update),
+    }
+}
+
+/// Builds a diagram from imported tables, linking foreign keys into relationships and
+/// arranging the tables on a grid with [`layout_all`].
+pub(crate) fn build_diagram(database: &str, imported: &[ImportedTable]) -> Diagram {
+    let mut used_names = Vec::new();
+    let tables: Vec<Table> = imported
+        .iter()
+        .map(|table| {
+            let mut columns: Vec<NormalColumn> = table
+                .columns
+                .iter()
+                .map(|column| build_column(table, column))
+                .collect();
+
+            let relationships: Vec<Relationship> = table
+                .foreign_keys
+                .iter()
+                .map(|foreign_key| {
+                    let name = match &foreign_key.name {
+                        Some(name) if !used_names.contains(name) => {
+                            used_names.push(name.clone());
+                            name.clone()
+                        }
+                        _ => relationship_name(
+                            &table.name,
+                            &foreign_key.referenced_table,
+                            &mut used_names,
+                        ),
+                    };
+                    for (column_name, referenced) in foreign_key
+                        .columns
+                        .iter()
+                        .zip(&foreign_key.referenced_columns)
+                    {
+                        if let Some(column) = columns
+                            .iter_mut()
+                            .find(|column| &column.physical_name == column_name)
+                        {
+                            column.referred_column = Some(format!(
+                                "table.{}.{}",
+                                foreign_key.referenced_table, referenced
+                            ));
+                            column.relationship = Some(name.clone());
+                        }
+                    }
+                    build_relationship(imported, table, foreign_key, name)
+                })
+                .collect();
+
+            let indexes: Vec<Index> = table
+                .indexes
+                .iter()
+                .map(|index| Index {
+                    name: index.name.clone(),
+                    index_type: None,
+                    description: None,
+                    full_text: None,
+                    non_unique: Some(!index.unique),
+                    columns: index_columns(&index.columns),
+                })
+                .collect();
+            let compound_unique_keys: Vec<CompoundUniqueKey> = table
+                .unique_keys
+                .iter()
+                .filter(|key| key.columns.len() > 1)
+                .map(|key| CompoundUniqueKey {
+                    name: key.name.clone(),
+                    columns: index_columns(&key.columns),
+                })
+                .collect();
+
+            let (logical_name, description) = split_comment(table.comment.as_deref());
+            Table {
+                physical_name: table.name.clone(),
+                logical_name: logical_name.unwrap_or_else(|| table.name.clone()),
+                description: description.unwrap_or_default(),
+                height: 0,
+                width: 0,
+                font_name: String::new(),
+                font_size: DEFAULT_FONT_SIZE,
+                x: 0,
+                y: 0,
+                color: DEFAULT_COLOR,
+                connections: Connections {
+                    relationships: (!relationships.is_empty()).then_some(relationships),
+                },
+                table_constraint: None,
+                primary_key_name: None,
+                option: None,
+                columns: Columns {
+                    items: (!columns.is_empty())
+                        .then(|| columns.into_iter().map(Column::Normal).collect()),
+                },
+                indexes: Indexes {
+                    indexes: (!indexes.is_empty()).then_some(indexes),
+                },
+                compound_unique_key_list: CompoundUniqueKeyList {
+                    compound_unique_keys: (!compound_unique_keys.is_empty())
+                        .then_some(compound_unique_keys),
+                },
+            }
+        })
+        .collect();
+
+    let mut diagram = Diagram {
+        page_settings: None,
+        diagram_settings: DiagramSettings {
+            database: database.to_string(),
+            ..Default::default()
+        },
+        dictionary: Dictionary::default(),
+        diagram_walkers: DiagramWalkers {
+            tables: (!tables.is_empty()).then_some(tables),
+            notes: None,
+            groups: None,
+        },
+        vdiagrams: Vec::new(),
+        column_groups: ColumnGroups {
+            column_groups: None,
+        },
+    };
+
+    // Tables are as large as drawn with their columns.
+    let sizes: Vec<(u16, u16)> = diagram
+        .tables()
+        .iter()
+        .map(|table| {
+            let bounds = table_bounds(&diagram, table);
+            (bounds.width.ceil() as u16, bounds.height.ceil() as u16)
+        })
+        .collect();
+    for (table, (width, height)) in diagram
+        .diagram_walkers
+        .tables
+        .iter_mut()
+        .flatten()
+        .zip(sizes)
+    {
+        (table.width, table.height) = (width, height);
+    }
+    let options = LayoutOptions {
+        strategy: Strategy::Grid,
+        ..Default::default()
+    };
+    layout_all(&mut diagram, &options);
+    diagram
+}
+
+/// Imports the schema of a running database given its connection URL. The scheme
+/// selects the database: `postgres://` or `postgresql://` for PostgreSQL, `mysql://`
+/// for MySQL.
+pub fn import_database(url: &str) -> Result<Diagram, Box<dyn std::error::Error>> {
+    let scheme = url.split_once("://").map(|(scheme, _)| scheme);
+    match scheme {
+        Some("postgres" | "postgresql") => postgresql::import(url),
+        Some("mysql") => mysql::import(url),
+        _ => Err(format!("unsupported database URL: {url}").into()),
+    }
+}
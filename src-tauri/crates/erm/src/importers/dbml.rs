@@ -0,0 +1,905 @@
+//! Import of DBML, the schema language of dbdiagram.io.
+//!
+//! Tables with their columns, settings and indexes, refs and table groups are read.
+//! Notes become logical names and descriptions as database comments do: the first line
+//! of a note is the logical name. Table groups become categories. The database comes
+//! from `database_type` of the project, and is PostgreSQL, dbdiagram's default,
+//! otherwise. Columns of an enum type become enum columns with its values. Sticky notes
+//! and many-to-many refs have no place in a diagram and are reported as warnings.
+
+use super::{
+    ImportedColumn, ImportedForeignKey, ImportedIndex, ImportedTable, build_diagram,
+    fill_referenced_primary_keys,
+};
+use crate::column_type::quote_values;
+use crate::dtos::diagram::Diagram;
+use crate::dtos::diagram_settings::{Category, CategorySettings};
+use crate::dtos::diagram_walkers::TABLE_ID_PREFIX;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbmlWarning {
+    /// Line of the element, counting from 1.
+    pub line: u64,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbmlImport {
+    pub diagram: Diagram,
+    pub warnings: Vec<DbmlWarning>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    /// A bare word, such as a keyword, a name or a number.
+    Word(String),
+    /// A name in double quotes.
+    Quoted(String),
+    /// A string in single or triple single quotes.
+    Text(String),
+    /// An expression in backticks.
+    Expression(String),
+    Symbol(char),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    line: u64,
+}
+
+type Error = Box<dyn std::error::Error>;
+
+fn syntax_error(line: u64, message: impl std::fmt::Display) -> Error {
+    format!("line {line}: {message}").into()
+}
+
+/// Removes the indentation common to the lines of a triple-quoted string, as DBML does.
+fn dedent(text: &str) -> String {
+    let text = text.strip_prefix('\n').unwrap_or(text);
+    let indent = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    text.lines()
+        .map(|line| line.get(indent..).unwrap_or(line.trim_start()))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, Error> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start_line = line;
+        match c {
+            '\n' => {
+                line += 1;
+                i += 1;
+            }
+            c if c.is_whitespace() => i += 1,
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    if chars[i] == '\n' {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err(syntax_error(start_line, "unterminated comment"));
+                }
+                i += 2;
+            }
+            '\'' if chars[i..].starts_with(&['\'', '\'', '\'']) => {
+                i += 3;
+                let mut text = String::new();
+                loop {
+                    match chars.get(i) {
+                        None => return Err(syntax_error(start_line, "unterminated string")),
+                        Some('\'') if chars[i..].starts_with(&['\'', '\'', '\'']) => break,
+                        Some('\\') if chars.get(i + 1).is_some() => {
+                            text.push(chars[i + 1]);
+                            i += 2;
+                        }
+                        Some(&c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            text.push(c);
+                            i += 1;
+                        }
+                    }
+                }
+                i += 3;
+                tokens.push(Token {
+                    kind: TokenKind::Text(dedent(&text)),
+                    line: start_line,
+                });
+            }
+            '\'' | '"' | '`' => {
+                i += 1;
+                let mut text = String::new();
+                loop {
+                    match chars.get(i) {
+                        None | Some('\n') if c != '`' => {
+                            return Err(syntax_error(start_line, "unterminated string"));
+                        }
+                        None => return Err(syntax_error(start_line, "unterminated expression")),
+                        Some('\\') if c != '`' && chars.get(i + 1).is_some() => {
+                            text.push(match chars[i + 1] {
+                                'n' => '\n',
+                                't' => '\t',
+                                other => other,
+                            });
+                            i += 2;
+                        }
+                        Some(&end) if end == c => break,
+                        Some(&other) => {
+                            if other == '\n' {
+                                line += 1;
+                            }
+                            text.push(other);
+                            i += 1;
+                        }
+                    }
+                }
+                i += 1;
+                let kind = match c {
+                    '\'' => TokenKind::Text(text),
+                    '"' => TokenKind::Quoted(text),
+                    _ => TokenKind::Expression(text),
+                };
+                tokens.push(Token {
+                    kind,
+                    line: start_line,
+                });
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '#' => {
+                let mut word = String::new();
+                word.push(c);
+                i += 1;
+                // Decimal numbers, e.g. a default of 1.5.
+                while let Some(&next) = chars.get(i) {
+                    let decimal_point = next == '.'
+                        && word.chars().all(|c| c.is_ascii_digit())
+                        && chars.get(i + 1).is_some_and(char::is_ascii_digit);
+                    if next.is_alphanumeric() || next == '_' || decimal_point {
+                        word.push(next);
+                        i += 1;
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Word(word),
+                    line: start_line,
+                });
+            }
+            _ => {
+                tokens.push(Token {
+                    kind: TokenKind::Symbol(c),
+                    line: start_line,
+                });
+                i += 1;
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// One side of a ref: a table and its columns.
+#[derive(Debug, Clone)]
+struct Endpoint {
+    table: String,
+    columns: Vec<String>,
+}
+
+/// A setting value, e.g. the `'abc'` of `default: 'abc'`.
+#[derive(Debug, Clone)]
+enum Value {
+    Text(String),
+    Expression(String),
+    Plain(String),
+    /// The operator and the other side of an inline `ref`.
+    Ref(String, Endpoint),
+}
+
+impl Value {
+    fn into_string(self) -> String {
+        match self {
+            Value::Text(value) | Value::Expression(value) | Value::Plain(value) => value,
+            Value::Ref(operator, endpoint) => {
+                format!(
+                    "{operator} {}.{}",
+                    endpoint.table,
+                    endpoint.columns.join(",")
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Ref {
+    name: Option<String>,
+    left: Endpoint,
+    operator: String,
+    right: Endpoint,
+    on_delete: Option<String>,
+    on_update: Option<String>,
+    line: u64,
+}
+
+#[derive(Debug, Clone)]
+struct TableGroup {
+    name: String,
+    tables: Vec<(String, u64)>,
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    database: Option<String>,
+    tables: Vec<ImportedTable>,
+    aliases: HashMap<String, String>,
+    enums: HashMap<String, Vec<String>>,
+    refs: Vec<Ref>,
+    table_groups: Vec<TableGroup>,
+    warnings: Vec<DbmlWarning>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.position).map(|token| &token.kind)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&TokenKind> {
+        self.tokens
+            .get(self.position + offset)
+            .map(|token| &token.kind)
+    }
+
+    fn line(&self) -> u64 {
+        self.tokens
+            .get(self.position)
+            .or(self.tokens.last())
+            .map_or(1, |token| token.line)
+    }
+
+    fn next(&mut self) -> Result<TokenKind, Error> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .ok_or_else(|| syntax_error(self.line(), "unexpected end of input"))?;
+        self.position += 1;
+        Ok(token.kind.clone())
+    }
+
+    fn is_symbol(&self, symbol: char) -> bool {
+        self.peek() == Some(&TokenKind::Symbol(symbol))
+    }
+
+    fn eat_symbol(&mut self, symbol: char) -> bool {
+        let found = self.is_symbol(symbol);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> Result<(), Error> {
+        if self.eat_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(syntax_error(self.line(), format!("expected `{symbol}`")))
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(TokenKind::Word(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn is_name(&self) -> bool {
+        matches!(self.peek(), Some(TokenKind::Word(_) | TokenKind::Quoted(_)))
+    }
+
+    fn name(&mut self) -> Result<String, Error> {
+        match self.next()? {
+            TokenKind::Word(name) | TokenKind::Quoted(name) => Ok(name),
+            _ => Err(syntax_error(
+                self.tokens[self.position - 1].line,
+                "expected a name",
+            )),
+        }
+    }
+
+    /// A possibly schema-qualified name; the schema is dropped.
+    fn qualified_name(&mut self) -> Result<String, Error> {
+        let mut name = self.name()?;
+        while self.is_symbol('.')
+            && matches!(
+                self.peek_at(1),
+                Some(TokenKind::Word(_) | TokenKind::Quoted(_))
+            )
+        {
+            self.position += 1;
+            name = self.name()?;
+        }
+        Ok(name)
+    }
+
+    fn warn(&mut self, line: u64, message: String) {
+        self.warnings.push(DbmlWarning { line, message });
+    }
+
+    /// Skips a `{ ... }` block, including nested blocks.
+    fn skip_block(&mut self) -> Result<(), Error> {
+        self.expect_symbol('{')?;
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next()? {
+                TokenKind::Symbol('{') => depth += 1,
+                TokenKind::Symbol('}') => depth -= 1,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<Value, Error> {
+        let line = self.line();
+        match self.next()? {
+            TokenKind::Text(text) => Ok(Value::Text(text)),
+            TokenKind::Expression(expression) => Ok(Value::Expression(expression)),
+            TokenKind::Word(word) | TokenKind::Quoted(word) => Ok(Value::Plain(word)),
+            TokenKind::Symbol('-') => match self.next()? {
+                TokenKind::Word(number) => Ok(Value::Plain(format!("-{number}"))),
+                _ => Err(syntax_error(line, "expected a number")),
+            },
+            _ => Err(syntax_error(line, "expected a value")),
+        }
+    }
+
+    /// A note, either `Note: '...'` or `Note { '...' }`, after the `Note` keyword.
+    fn note(&mut self) -> Result<String, Error> {
+        if self.eat_symbol(':') {
+            return Ok(self.value()?.into_string());
+        }
+        self.expect_symbol('{')?;
+        let note = self.value()?.into_string();
+        self.expect_symbol('}')?;
+        Ok(note)
+    }
+
+    /// Settings in brackets, e.g. `[pk, not null, default: 0]`, as lowercase keys with
+    /// their values. The brackets are optional.
+    fn settings(&mut self) -> Result<Vec<(String, Option<Value>, u64)>, Error> {
+        let mut settings = Vec::new();
+        if !self.eat_symbol('[') {
+            return Ok(settings);
+        }
+        loop {
+            if self.eat_symbol(']') {
+                return Ok(settings);
+            }
+            let line = self.line();
+            let mut words = Vec::new();
+            while let Some(TokenKind::Word(word)) = self.peek() {
+                words.push(word.to_lowercase());
+                self.position += 1;
+            }
+            if words.is_empty() {
+                return Err(syntax_error(line, "expected a setting"));
+            }
+            let key = words.join(" ");
+            let value = if self.eat_symbol(':') {
+                if key == "ref" {
+                    Some(Value::Ref(self.operator()?, self.endpoint()?))
+                } else {
+                    match self.value()? {
+                        // Values of several words, e.g. `delete: set null`.
+                        Value::Plain(mut words) => {
+                            while let Some(TokenKind::Word(word)) = self.peek() {
+                                words.push(' ');
+                                words.push_str(word);
+                                self.position += 1;
+                            }
+                            Some(Value::Plain(words))
+                        }
+                        value => Some(value),
+                    }
+                }
+            } else {
+                None
+            };
+            settings.push((key, value, line));
+            if !self.eat_symbol(',') && !self.is_symbol(']') {
+                return Err(syntax_error(self.line(), "expected `,` or `]`"));
+            }
+        }
+    }
+
+    /// The operator of a ref: `>`, `<`, `-` or `<>`.
+    fn operator(&mut self) -> Result<String, Error> {
+        let line = self.line();
+        match self.next()? {
+            TokenKind::Symbol('<') if self.eat_symbol('>') => Ok("<>".to_string()),
+            TokenKind::Symbol(symbol @ ('<' | '>' | '-')) => Ok(symbol.to_string()),
+            _ => Err(syntax_error(line, "expected `>`, `<`, `-` or `<>`")),
+        }
+    }
+
+    /// A column reference such as `users.id`, `public.users.id` or `users.(a, b)`.
+    fn endpoint(&mut self) -> Result<Endpoint, Error> {
+        let line = self.line();
+        let mut parts = vec![self.name()?];
+        let mut columns = Vec::new();
+        while self.eat_symbol('.') {
+            if self.eat_symbol('(') {
+                loop {
+                    columns.push(self.name()?);
+                    if !self.eat_symbol(',') {
+                        break;
+                    }
+                }
+                self.expect_symbol(')')?;
+                break;
+            }
+            parts.push(self.name()?);
+        }
+        if columns.is_empty() {
+            columns.push(parts.pop().unwrap_or_default());
+        }
+        let table = parts
+            .pop()
+            .ok_or_else(|| syntax_error(line, "expected a table and a column"))?;
+        Ok(Endpoint {
+            table: self.aliases.get(&table).cloned().unwrap_or(table),
+            columns,
+        })
+    }
+
+    fn project(&mut self) -> Result<(), Error> {
+        if self.is_name() {
+            self.name()?;
+        }
+        self.expect_symbol('{')?;
+        while !self.eat_symbol('}') {
+            let key = self.name()?;
+            if key.eq_ignore_ascii_case("note") {
+                self.note()?;
+            } else if self.eat_symbol(':') {
+                let value = self.value()?.into_string();
+                if key.eq_ignore_ascii_case("database_type") {
+                    self.database = Some(value);
+                }
+            } else {
+                self.skip_block()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn column(&mut self, table: &mut ImportedTable) -> Result<(), Error> {
+        let name = self.name()?;
+        let line = self.line();
+        let mut sql_type = match self.next()? {
+            TokenKind::Word(word) | TokenKind::Quoted(word) => word,
+            _ => return Err(syntax_error(line, format!("expected the type of {name}"))),
+        };
+        // A schema-qualified type, e.g. of an enum.
+        while self.is_symbol('.') {
+            self.position += 1;
+            sql_type = self.name()?;
+        }
+        if self.eat_symbol('(') {
+            let mut args = Vec::new();
+            while !self.eat_symbol(')') {
+                match self.next()? {
+                    TokenKind::Symbol(',') => {}
+                    TokenKind::Word(arg) | TokenKind::Text(arg) | TokenKind::Quoted(arg) => {
+                        args.push(arg)
+                    }
+                    _ => return Err(syntax_error(line, "expected type arguments")),
+                }
+            }
+            sql_type = format!("{sql_type}({})", args.join(","));
+        }
+        if self.is_symbol('[') && self.peek_at(1) == Some(&TokenKind::Symbol(']')) {
+            self.position += 2;
+            sql_type.push_str("[]");
+        }
+        let args = self.enums.get(&sql_type).map(|values| quote_values(values));
+        if args.is_some() {
+            sql_type = "enum".to_string();
+        }
+
+        let mut column = ImportedColumn {
+            name: name.clone(),
+            sql_type,
+            args,
+            ..Default::default()
+        };
+        for (key, value, line) in self.settings()? {
+            match (key.as_str(), value) {
+                ("pk" | "primary key", None) => table.primary_key.push(name.clone()),
+                ("not null", None) => column.not_null = true,
+                ("null", None) => column.not_null = false,
+                ("unique", None) => column.unique = true,
+                ("increment", None) => column.auto_increment = true,
+                ("default", Some(value)) => column.default_value = Some(value.into_string()),
+                ("note", Some(value)) => column.comment = Some(value.into_string()),
+                ("ref", Some(Value::Ref(operator, right))) => self.refs.push(Ref {
+                    name: None,
+                    left: Endpoint {
+                        table: table.name.clone(),
+                        columns: vec![name.clone()],
+                    },
+                    operator,
+                    right,
+                    on_delete: None,
+                    on_update: None,
+                    line,
+                }),
+                (key, _) => self.warn(line, format!("setting {key} of column {name} is ignored")),
+            }
+        }
+        table.columns.push(column);
+        Ok(())
+    }
+
+    fn indexes(&mut self, table: &mut ImportedTable) -> Result<(), Error> {
+        self.expect_symbol('{')?;
+        while !self.eat_symbol('}') {
+            let line = self.line();
+            let mut columns = Vec::new();
+            let mut expression = false;
+            let mut part = |kind: TokenKind| match kind {
+                TokenKind::Word(name) | TokenKind::Quoted(name) => columns.push(name),
+                TokenKind::Expression(_) => expression = true,
+                _ => {}
+            };
+            if self.eat_symbol('(') {
+                while !self.eat_symbol(')') {
+                    part(self.next()?);
+                }
+            } else {
+                part(self.next()?);
+            }
+            let mut index = ImportedIndex {
+                name: String::new(),
+                columns: columns.into_iter().map(|name| (name, false)).collect(),
+                unique: false,
+            };
+            let mut primary_key = false;
+            for (key, value, line) in self.settings()? {
+                match (key.as_str(), value) {
+                    ("pk", None) => primary_key = true,
+                    ("unique", None) => index.unique = true,
+                    ("name", Some(value)) => index.name = value.into_string(),
+                    ("type" | "note", _) => {}
+                    (key, _) => self.warn(line, format!("setting {key} of index is ignored")),
+                }
+            }
+            if expression {
+                self.warn(
+                    line,
+                    format!("index on an expression of {} is not imported", table.name),
+                );
+            } else if primary_key {
+                table.primary_key = index.columns.into_iter().map(|(name, _)| name).collect();
+            } else {
+                if index.name.is_empty() {
+                    let names: Vec<&str> = index
+                        .columns
+                        .iter()
+                        .map(|(name, _)| name.as_str())
+                        .collect();
+                    index.name = format!("IDX_{}_{}", table.name, names.join("_"));
+                }
+                table.indexes.push(index);
+            }
+        }
+        Ok(())
+    }
+
+    fn table(&mut self) -> Result<(), Error> {
+        let name = self.qualified_name()?;
+        if self.is_keyword("as") {
+            self.position += 1;
+            let alias = self.name()?;
+            self.aliases.insert(alias, name.clone());
+        }
+        let mut table = ImportedTable {
+            name: name.clone(),
+            ..Default::default()
+        };
+        for (key, value, _) in self.settings()? {
+            if key == "note" {
+                table.comment = value.map(Value::into_string);
+            }
+        }
+        self.expect_symbol('{')?;
+        while !self.eat_symbol('}') {
+            if self.is_keyword("note")
+                && matches!(self.peek_at(1), Some(TokenKind::Symbol(':' | '{')))
+            {
+                self.position += 1;
+                table.comment = Some(self.note()?);
+            } else if self.is_keyword("indexes") && self.peek_at(1) == Some(&TokenKind::Symbol('{'))
+            {
+                self.position += 1;
+                self.indexes(&mut table)?;
+            } else {
+                self.column(&mut table)?;
+            }
+        }
+        self.tables.push(table);
+        Ok(())
+    }
+
+    fn reference(&mut self) -> Result<(), Error> {
+        let line = self.line();
+        let name = if self.is_name() {
+            Some(self.name()?)
+        } else {
+            None
+        };
+        let block = if self.eat_symbol(':') {
+            false
+        } else {
+            self.expect_symbol('{')?;
+            true
+        };
+        let left = self.endpoint()?;
+        let operator = self.operator()?;
+        let right = self.endpoint()?;
+        let mut reference = Ref {
+            name,
+            left,
+            operator,
+            right,
+            on_delete: None,
+            on_update: None,
+            line,
+        };
+        for (key, value, _) in self.settings()? {
+            match key.as_str() {
+                "delete" => reference.on_delete = value.map(Value::into_string),
+                "update" => reference.on_update = value.map(Value::into_string),
+                _ => {}
+            }
+        }
+        if block {
+            self.expect_symbol('}')?;
+        }
+        self.refs.push(reference);
+        Ok(())
+    }
+
+    fn table_group(&mut self) -> Result<(), Error> {
+        let name = self.name()?;
+        self.settings()?;
+        self.expect_symbol('{')?;
+        let mut tables = Vec::new();
+        while !self.eat_symbol('}') {
+            if self.is_keyword("note")
+                && matches!(self.peek_at(1), Some(TokenKind::Symbol(':' | '{')))
+            {
+                self.position += 1;
+                self.note()?;
+                continue;
+            }
+            let line = self.line();
+            tables.push((self.qualified_name()?, line));
+        }
+        self.table_groups.push(TableGroup { name, tables });
+        Ok(())
+    }
+
+    fn enumeration(&mut self) -> Result<(), Error> {
+        let name = self.qualified_name()?;
+        self.expect_symbol('{')?;
+        let mut values = Vec::new();
+        while !self.eat_symbol('}') {
+            values.push(self.name()?);
+            // Notes of values have no place in a diagram.
+            self.settings()?;
+        }
+        self.enums.insert(name, values);
+        Ok(())
+    }
+
+    fn parse(&mut self) -> Result<(), Error> {
+        // Enums may be declared after the tables using them.
+        let mut depth = 0;
+        while let Some(kind) = self.peek().cloned() {
+            self.position += 1;
+            match kind {
+                TokenKind::Symbol('{') => depth += 1,
+                TokenKind::Symbol('}') => depth -= 1,
+                TokenKind::Word(word) if depth == 0 && word.eq_ignore_ascii_case("enum") => {
+                    self.enumeration()?;
+                }
+                _ => {}
+            }
+        }
+        self.position = 0;
+
+        while let Some(kind) = self.peek().cloned() {
+            let line = self.line();
+            let TokenKind::Word(keyword) = kind else {
+                return Err(syntax_error(
+                    line,
+                    "expected Table, Ref, TableGroup or Project",
+                ));
+            };
+            self.position += 1;
+            match keyword.to_lowercase().as_str() {
+                "project" => self.project()?,
+                "table" => self.table()?,
+                "ref" => self.reference()?,
+                "tablegroup" => self.table_group()?,
+                "enum" => {
+                    self.qualified_name()?;
+                    self.skip_block()?;
+                }
+                "note" | "tablepartial" => {
+                    self.name()?;
+                    self.skip_block()?;
+                    self.warn(line, format!("{keyword} is not imported"));
+                }
+                _ => return Err(syntax_error(line, format!("unexpected {keyword}"))),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn database_name(database_type: &str) -> String {
+    match database_type.to_lowercase().as_str() {
+        "postgresql" | "postgres" => super::postgresql::DATABASE.to_string(),
+        "mysql" => super::mysql::DATABASE.to_string(),
+        "sqlite" => super::sqlite::DATABASE.to_string(),
+        _ => database_type.to_string(),
+    }
+}
+
+fn action(action: Option<String>) -> Option<String> {
+    action.map(|action| action.to_uppercase())
+}
+
+/// Builds a diagram from DBML. Syntax errors fail the import; elements that have no
+/// place in a diagram are reported as warnings.
+pub fn import_script(dbml: &str) -> Result<DbmlImport, Box<dyn std::error::Error>> {
+    let mut parser = Parser {
+        tokens: tokenize(dbml)?,
+        position: 0,
+        database: None,
+        tables: Vec::new(),
+        aliases: HashMap::new(),
+        enums: HashMap::new(),
+        refs: Vec::new(),
+        table_groups: Vec::new(),
+        warnings: Vec::new(),
+    };
+    parser.parse()?;
+    let mut warnings = parser.warnings;
+    let mut tables = parser.tables;
+
+    // Refs put the foreign key on the many side, or on the left of one-to-one refs.
+    let mut one_to_one = Vec::new();
+    for reference in parser.refs {
+        let (child, parent) = match reference.operator.as_str() {
+            ">" | "-" => (reference.left, reference.right),
+            "<" => (reference.right, reference.left),
+            _ => {
+                warnings.push(DbmlWarning {
+                    line: reference.line,
+                    message: format!(
+                        "many-to-many ref between {} and {} is not imported",
+                        reference.left.table, reference.right.table
+                    ),
+                });
+                continue;
+            }
+        };
+        let resolve = |name: &str| {
+            parser
+                .aliases
+                .get(name)
+                .cloned()
+                .unwrap_or(name.to_string())
+        };
+        let (child_table, parent_table) = (resolve(&child.table), resolve(&parent.table));
+        let Some(table) = tables.iter_mut().find(|table| table.name == child_table) else {
+            warnings.push(DbmlWarning {
+                line: reference.line,
+                message: format!("ref from unknown table {child_table} is not imported"),
+            });
+            continue;
+        };
+        if reference.operator == "-" {
+            one_to_one.push((child_table.clone(), child.columns.clone()));
+        }
+        table.foreign_keys.push(ImportedForeignKey {
+            name: reference.name,
+            columns: child.columns,
+            referenced_table: parent_table,
+            referenced_columns: parent.columns,
+            on_delete: action(reference.on_delete),
+            on_update: action(reference.on_update),
+        });
+    }
+    fill_referenced_primary_keys(&mut tables);
+
+    let database = parser
+        .database
+        .as_deref()
+        .map_or(super::postgresql::DATABASE.to_string(), database_name);
+    let mut diagram = build_diagram(&database, &tables);
+
+    for table in diagram.diagram_walkers.tables.iter_mut().flatten() {
+        let name = table.physical_name.clone();
+        for relationship in table.connections.relationships.iter_mut().flatten() {
+            let columns: Vec<String> = relationship
+                .fk_columns
+                .fk_column
+                .iter()
+                .map(|column| column.fk_column_name.clone())
+                .collect();
+            if one_to_one.contains(&(name.clone(), columns)) {
+                relationship.child_cardinality = "0..1".to_string();
+            }
+        }
+    }
+
+    let mut categories = Vec::new();
+    for group in parser.table_groups {
+        let mut node_elements = Vec::new();
+        for (table, line) in group.tables {
+            let table = parser.aliases.get(&table).cloned().unwrap_or(table);
+            if diagram.find_table(&table).is_some() {
+                node_elements.push(format!("{TABLE_ID_PREFIX}{table}"));
+            } else {
+                warnings.push(DbmlWarning {
+                    line,
+                    message: format!("table group {} has unknown table {table}", group.name),
+                });
+            }
+        }
+        categories.push(Category {
+            name: group.name,
+            node_elements,
+            selected: None,
+        });
+    }
+    if !categories.is_empty() {
+        diagram.diagram_settings.category_settings = Some(CategorySettings { categories });
+    }
+
+    Ok(DbmlImport { diagram, warnings })
+}
+
+/// Reads DBML from a file and builds a diagram from it.
+pub fn import(filename: &str) -> Result<DbmlImport, Box<dyn std::error::Error>> {
+    let dbml = std::fs::read_to_string(filename)?;
+    import_script(&dbml)
+}
@@ -0,0 +1,476 @@
+//! Import of `CREATE TABLE` scripts such as a `schema.sql` or a schema dump.
+//!
+//! Besides `CREATE TABLE`, the script may contain `ALTER TABLE ... ADD CONSTRAINT`,
+//! `CREATE INDEX` and `COMMENT ON` statements. Other statements that parse, such as
+//! `SET` or `INSERT`, do not describe tables and are skipped.
+
+use super::{
+    ImportedColumn, ImportedForeignKey, ImportedIndex, ImportedTable, build_diagram,
+    fill_referenced_primary_keys, unquote_default,
+};
+use crate::dtos::diagram::Diagram;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::{
+    AlterColumnOperation, AlterTableOperation, ColumnDef, ColumnOption, CommentDef, CommentObject,
+    CreateIndex, CreateTable, Expr, GeneratedAs, Ident, ObjectName, ReferentialAction, Statement,
+    TableConstraint, Value,
+};
+use sqlparser::dialect::{MySqlDialect, PostgreSqlDialect};
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, TokenWithSpan, Tokenizer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Dialect {
+    MySql,
+    PostgreSql,
+}
+
+impl Dialect {
+    /// The database name used in the diagram settings.
+    pub fn database(self) -> &'static str {
+        match self {
+            Dialect::MySql => super::mysql::DATABASE,
+            Dialect::PostgreSql => super::postgresql::DATABASE,
+        }
+    }
+
+    /// Parses a dialect name such as `mysql` or `postgresql`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "mysql" => Some(Dialect::MySql),
+            "postgresql" | "postgres" => Some(Dialect::PostgreSql),
+            _ => None,
+        }
+    }
+
+    /// The dialect of a diagram's database, e.g. `PostgreSQL`.
+    pub fn from_database(database: &str) -> Option<Self> {
+        [Dialect::MySql, Dialect::PostgreSql]
+            .into_iter()
+            .find(|dialect| dialect.database().eq_ignore_ascii_case(database))
+    }
+
+    fn parser_dialect(self) -> Box<dyn sqlparser::dialect::Dialect> {
+        match self {
+            Dialect::MySql => Box::new(MySqlDialect {}),
+            Dialect::PostgreSql => Box::new(PostgreSqlDialect {}),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DdlWarning {
+    /// Line of the script the statement starts on, counting from 1.
+    pub line: u64,
+
+    /// The first line of the statement.
+    pub statement: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DdlImport {
+    pub diagram: Diagram,
+    pub warnings: Vec<DdlWarning>,
+}
+
+struct Importer {
+    tables: Vec<ImportedTable>,
+    warnings: Vec<DdlWarning>,
+    line: u64,
+    statement: String,
+}
+
+fn last_name(name: &ObjectName) -> String {
+    name.0
+        .last()
+        .map(|ident| ident.value.clone())
+        .unwrap_or_default()
+}
+
+fn names(idents: &[Ident]) -> Vec<String> {
+    idents.iter().map(|ident| ident.value.clone()).collect()
+}
+
+fn action(action: &Option<ReferentialAction>) -> Option<String> {
+    action.as_ref().map(ToString::to_string)
+}
+
+fn is_sequence_default(expr: &Expr) -> bool {
+    expr.to_string().to_lowercase().starts_with("nextval(")
+}
+
+/// Turns a column default into the text shown in the diagram, e.g. `'active'` and
+/// PostgreSQL's `'active'::character varying` both become `active`.
+fn default_value(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Value(Value::Null) => None,
+        Expr::Value(Value::SingleQuotedString(value)) => Some(value.clone()),
+        Expr::Cast { expr, .. } => default_value(expr),
+        expr => Some(unquote_default(&expr.to_string())),
+    }
+}
+
+impl Importer {
+    fn warn(&mut self, message: impl Into<String>) {
+        self.warnings.push(DdlWarning {
+            line: self.line,
+            statement: self.statement.clone(),
+            message: message.into(),
+        });
+    }
+
+    fn table_mut(&mut self, name: &ObjectName) -> Option<&mut ImportedTable> {
+        let name = last_name(name);
+        let position = self
+            .tables
+            .iter()
+            .position(|table| table.name.eq_ignore_ascii_case(&name));
+        if position.is_none() {
+            self.warn(format!("unknown table {name}"));
+        }
+        position.map(|position| &mut self.tables[position])
+    }
+
+    fn statement(&mut self, statement: Statement) {
+        match statement {
+            Statement::CreateTable(create_table) => self.create_table(create_table),
+            Statement::AlterTable {
+                name, operations, ..
+            } => {
+                for operation in operations {
+                    self.alter_table(&name, operation);
+                }
+            }
+            Statement::CreateIndex(create_index) => self.create_index(create_index),
+            Statement::Comment {
+                object_type,
+                object_name,
+                comment,
+                ..
+            } => self.comment(object_type, &object_name, comment),
+            _ => {}
+        }
+    }
+
+    fn create_table(&mut self, create_table: CreateTable) {
+        let mut table = ImportedTable {
+            name: last_name(&create_table.name),
+            comment: create_table.comment.map(|comment| match comment {
+                CommentDef::WithEq(comment)
+                | CommentDef::WithoutEq(comment)
+                | CommentDef::AfterColumnDefsWithoutEq(comment) => comment,
+            }),
+            ..Default::default()
+        };
+        for column in create_table.columns {
+            add_column(&mut table, column);
+        }
+        for constraint in create_table.constraints {
+            add_constraint(&mut table, constraint);
+        }
+        if self
+            .tables
+            .iter()
+            .any(|existing| existing.name.eq_ignore_ascii_case(&table.name))
+        {
+            self.warn(format!("table {} is defined twice", table.name));
+            return;
+        }
+        self.tables.push(table);
+    }
+
+    fn alter_table(&mut self, name: &ObjectName, operation: AlterTableOperation) {
+        match operation {
+            AlterTableOperation::AddConstraint(constraint) => {
+                if let Some(table) = self.table_mut(name) {
+                    add_constraint(table, constraint);
+                }
+            }
+            AlterTableOperation::AddColumn { column_def, .. } => {
+                if let Some(table) = self.table_mut(name) {
+                    add_column(table, column_def);
+                }
+            }
+            AlterTableOperation::AlterColumn { column_name, op } => {
+                let Some(table) = self.table_mut(name) else {
+                    return;
+                };
+                let Some(column) = table
+                    .columns
+                    .iter_mut()
+                    .find(|column| column.name == column_name.value)
+                else {
+                    let message = format!("unknown column {}", column_name.value);
+                    self.warn(message);
+                    return;
+                };
+                match op {
+                    // pg_dump declares `serial` columns as integers with a sequence default.
+                    AlterColumnOperation::SetDefault { value } if is_sequence_default(&value) => {
+                        column.auto_increment = true;
+                    }
+                    AlterColumnOperation::SetDefault { value } => {
+                        column.default_value = default_value(&value);
+                    }
+                    AlterColumnOperation::DropDefault => column.default_value = None,
+                    AlterColumnOperation::SetNotNull => column.not_null = true,
+                    AlterColumnOperation::DropNotNull => column.not_null = false,
+                    AlterColumnOperation::AddGenerated { .. } => column.auto_increment = true,
+                    op => self.warn(format!("unsupported ALTER COLUMN operation: {op}")),
+                }
+            }
+            // Ownership does not change the schema.
+            AlterTableOperation::OwnerTo { .. } => {}
+            operation => self.warn(format!("unsupported ALTER TABLE operation: {operation}")),
+        }
+    }
+
+    fn create_index(&mut self, create_index: CreateIndex) {
+        let mut columns = Vec::new();
+        for column in &create_index.columns {
+            match &column.expr {
+                Expr::Identifier(ident) => {
+                    columns.push((ident.value.clone(), column.asc == Some(false)));
+                }
+                expr => {
+                    self.warn(format!("index on expression {expr} is not supported"));
+                    return;
+                }
+            }
+        }
+        let Some(table) = self.table_mut(&create_index.table_name) else {
+            return;
+        };
+        let name = match &create_index.name {
+            Some(name) => last_name(name),
+            None => format!("IX_{}_{}", table.name, table.indexes.len() + 1),
+        };
+        table.indexes.push(ImportedIndex {
+            name,
+            columns,
+            unique: create_index.unique,
+        });
+    }
+
+    fn comment(&mut self, object_type: CommentObject, name: &ObjectName, comment: Option<String>) {
+        match object_type {
+            CommentObject::Table => {
+                if let Some(table) = self.table_mut(name) {
+                    table.comment = comment;
+                }
+            }
+            CommentObject::Column => {
+                let (column_name, table_name) = match name.0.split_last() {
+                    Some((column_name, table_name)) if !table_name.is_empty() => {
+                        (column_name.value.clone(), ObjectName(table_name.to_vec()))
+                    }
+                    _ => {
+                        self.warn(format!("invalid column name {name}"));
+                        return;
+                    }
+                };
+                let Some(table) = self.table_mut(&table_name) else {
+                    return;
+                };
+                match table
+                    .columns
+                    .iter_mut()
+                    .find(|column| column.name == column_name)
+                {
+                    Some(column) => column.comment = comment,
+                    None => self.warn(format!("unknown column {column_name}")),
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn add_constraint(table: &mut ImportedTable, constraint: TableConstraint) {
+    match constraint {
+        TableConstraint::PrimaryKey { columns, .. } => table.primary_key = names(&columns),
+        TableConstraint::Unique {
+            name,
+            index_name,
+            columns,
+            ..
+        } => {
+            let name = name
+                .or(index_name)
+                .map(|name| name.value)
+                .unwrap_or_else(|| format!("UK_{}_{}", table.name, table.unique_keys.len() + 1));
+            table.unique_keys.push(ImportedIndex {
+                name,
+                columns: columns
+                    .into_iter()
+                    .map(|column| (column.value, false))
+                    .collect(),
+                unique: true,
+            });
+        }
+        TableConstraint::ForeignKey {
+            name,
+            columns,
+            foreign_table,
+            referred_columns,
+            on_delete,
+            on_update,
+            ..
+        } => table.foreign_keys.push(ImportedForeignKey {
+            name: name.map(|name| name.value),
+            columns: names(&columns),
+            referenced_table: last_name(&foreign_table),
+            referenced_columns: names(&referred_columns),
+            on_delete: action(&on_delete),
+            on_update: action(&on_update),
+        }),
+        TableConstraint::Index { name, columns, .. }
+        | TableConstraint::FulltextOrSpatial {
+            opt_index_name: name,
+            columns,
+            ..
+        } => {
+            let name = name
+                .map(|name| name.value)
+                .unwrap_or_else(|| format!("IX_{}_{}", table.name, table.indexes.len() + 1));
+            table.indexes.push(ImportedIndex {
+                name,
+                columns: columns
+                    .into_iter()
+                    .map(|column| (column.value, false))
+                    .collect(),
+                unique: false,
+            });
+        }
+        TableConstraint::Check { .. } => {}
+    }
+}
+
+fn add_column(table: &mut ImportedTable, column: ColumnDef) {
+    let mut imported = ImportedColumn {
+        name: column.name.value,
+        sql_type: column.data_type.to_string(),
+        ..Default::default()
+    };
+    for option in column.options.into_iter().map(|option| option.option) {
+        match option {
+            ColumnOption::NotNull => imported.not_null = true,
+            ColumnOption::Null => imported.not_null = false,
+            ColumnOption::Default(expr) if is_sequence_default(&expr) => {
+                imported.auto_increment = true;
+            }
+            ColumnOption::Default(expr) => imported.default_value = default_value(&expr),
+            ColumnOption::Unique {
+                is_primary: true, ..
+            } => table.primary_key = vec![imported.name.clone()],
+            ColumnOption::Unique {
+                is_primary: false, ..
+            } => imported.unique = true,
+            ColumnOption::ForeignKey {
+                foreign_table,
+                referred_columns,
+                on_delete,
+                on_update,
+                ..
+            } => table.foreign_keys.push(ImportedForeignKey {
+                name: None,
+                columns: vec![imported.name.clone()],
+                referenced_table: last_name(&foreign_table),
+                referenced_columns: names(&referred_columns),
+                on_delete: action(&on_delete),
+                on_update: action(&on_update),
+            }),
+            ColumnOption::Comment(comment) => imported.comment = Some(comment),
+            ColumnOption::DialectSpecific(tokens) => {
+                imported.auto_increment |= tokens.iter().any(|token| {
+                    matches!(token, Token::Word(word)
+                        if word.value.eq_ignore_ascii_case("AUTO_INCREMENT")
+                            || word.value.eq_ignore_ascii_case("AUTOINCREMENT"))
+                });
+            }
+            ColumnOption::Generated {
+                generated_as: GeneratedAs::Always | GeneratedAs::ByDefault,
+                generation_expr: None,
+                ..
+            }
+            | ColumnOption::Identity(_) => imported.auto_increment = true,
+            _ => {}
+        }
+    }
+    table.columns.push(imported);
+}
+
+/// Splits the script into statements at the top-level semicolons. Statements made of
+/// whitespace and comments only are dropped.
+fn split_statements(tokens: Vec<TokenWithSpan>) -> Vec<Vec<TokenWithSpan>> {
+    let mut statements = vec![Vec::new()];
+    for token in tokens {
+        if token.token == Token::SemiColon {
+            statements.push(Vec::new());
+        } else if let Some(statement) = statements.last_mut() {
+            statement.push(token);
+        }
+    }
+    statements.retain(|statement| {
+        statement
+            .iter()
+            .any(|token| !matches!(token.token, Token::Whitespace(_)))
+    });
+    statements
+}
+
+fn first_line(tokens: &[TokenWithSpan]) -> String {
+    let text: String = tokens.iter().map(|token| token.token.to_string()).collect();
+    text.trim().lines().next().unwrap_or_default().to_string()
+}
+
+/// Builds a diagram from a DDL script. Statements that cannot be parsed, and parts of
+/// statements that have no place in a diagram, are reported as warnings.
+pub fn import_script(sql: &str, dialect: Dialect) -> DdlImport {
+    let parser_dialect = dialect.parser_dialect();
+    let mut importer = Importer {
+        tables: Vec::new(),
+        warnings: Vec::new(),
+        line: 0,
+        statement: String::new(),
+    };
+
+    match Tokenizer::new(parser_dialect.as_ref(), sql).tokenize_with_location() {
+        Ok(tokens) => {
+            for tokens in split_statements(tokens) {
+                importer.line = tokens
+                    .iter()
+                    .find(|token| !matches!(token.token, Token::Whitespace(_)))
+                    .map_or(0, |token| token.span.start.line);
+                importer.statement = first_line(&tokens);
+                match Parser::new(parser_dialect.as_ref())
+                    .with_tokens_with_locations(tokens)
+                    .parse_statement()
+                {
+                    Ok(statement) => importer.statement(statement),
+                    Err(error) => importer.warn(error.to_string()),
+                }
+            }
+        }
+        Err(error) => {
+            importer.line = error.location.line;
+            importer.warn(error.message);
+        }
+    }
+
+    fill_referenced_primary_keys(&mut importer.tables);
+    DdlImport {
+        diagram: build_diagram(dialect.database(), &importer.tables),
+        warnings: importer.warnings,
+    }
+}
+
+/// Reads a DDL script from a file and builds a diagram from it.
+pub fn import(filename: &str, dialect: Dialect) -> Result<DdlImport, Box<dyn std::error::Error>> {
+    let sql = std::fs::read_to_string(filename)?;
+    Ok(import_script(&sql, dialect))
+}
@@ -0,0 +1,207 @@
+//! Builds diagrams from the rows of `information_schema` style catalog queries. The
+//! PostgreSQL and MySQL importers read their catalogs into a [`Catalog`], which can
+//! also be filled by hand to import a schema without a running database.
+
+use super::{
+    ImportedColumn, ImportedForeignKey, ImportedIndex, ImportedTable, build_diagram,
+    unquote_default,
+};
+use crate::dtos::diagram::Diagram;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TableRow {
+    pub table_name: String,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ColumnRow {
+    pub table_name: String,
+    pub column_name: String,
+
+    /// The full declared type, e.g. `character varying(32)` or `int unsigned`.
+    pub data_type: String,
+    pub nullable: bool,
+    pub default_value: Option<String>,
+    pub auto_increment: bool,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintType {
+    PrimaryKey,
+    Unique,
+    ForeignKey,
+}
+
+impl ConstraintType {
+    /// Parses `information_schema.table_constraints.constraint_type`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "PRIMARY KEY" => Some(Self::PrimaryKey),
+            "UNIQUE" => Some(Self::Unique),
+            "FOREIGN KEY" => Some(Self::ForeignKey),
+            _ => None,
+        }
+    }
+}
+
+/// One column of a key constraint, in the order of the key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyColumnRow {
+    pub table_name: String,
+    pub constraint_name: String,
+    pub constraint_type: ConstraintType,
+    pub column_name: String,
+    pub referenced_table: Option<String>,
+    pub referenced_column: Option<String>,
+    pub delete_rule: Option<String>,
+    pub update_rule: Option<String>,
+}
+
+/// One column of an index, in the order of the index.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IndexColumnRow {
+    pub table_name: String,
+    pub index_name: String,
+
+    /// `None` for expressions, which makes the whole index unrepresentable.
+    pub column_name: Option<String>,
+    pub unique: bool,
+    pub descending: bool,
+}
+
+/// The catalog rows of one schema. Rows of tables missing from `tables`, such as
+/// views, are ignored.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Catalog {
+    pub tables: Vec<TableRow>,
+    pub columns: Vec<ColumnRow>,
+    pub key_columns: Vec<KeyColumnRow>,
+    pub index_columns: Vec<IndexColumnRow>,
+}
+
+/// Groups rows by name, keeping the order in which the names first appear.
+fn group_by<'a, T>(
+    rows: impl Iterator<Item = &'a T>,
+    name: impl Fn(&T) -> &str,
+) -> Vec<(&'a str, Vec<&'a T>)> {
+    let mut groups: Vec<(&'a str, Vec<&'a T>)> = Vec::new();
+    for row in rows {
+        let key = name(row);
+        match groups.iter_mut().find(|(existing, _)| *existing == key) {
+            Some((_, group)) => group.push(row),
+            None => groups.push((key, vec![row])),
+        }
+    }
+    groups
+}
+
+fn import_table(catalog: &Catalog, table: &TableRow) -> ImportedTable {
+    let mut imported = ImportedTable {
+        name: table.table_name.clone(),
+        comment: table.comment.clone(),
+        columns: catalog
+            .columns
+            .iter()
+            .filter(|column| column.table_name == table.table_name)
+            .map(|column| ImportedColumn {
+                name: column.column_name.clone(),
+                sql_type: column.data_type.clone(),
+                not_null: !column.nullable,
+                unique: false,
+                default_value: column.default_value.as_deref().map(unquote_default),
+                auto_increment: column.auto_increment,
+                comment: column.comment.clone(),
+                // The values of enums come with the type, e.g. `enum('a','b')` of MySQL.
+                args: None,
+            })
+            .collect(),
+        ..Default::default()
+    };
+
+    let constraints = group_by(
+        catalog
+            .key_columns
+            .iter()
+            .filter(|row| row.table_name == table.table_name),
+        |row| &row.constraint_name,
+    );
+    for (name, rows) in &constraints {
+        let columns: Vec<String> = rows.iter().map(|row| row.column_name.clone()).collect();
+        match rows[0].constraint_type {
+            ConstraintType::PrimaryKey => imported.primary_key = columns,
+            ConstraintType::Unique => imported.unique_keys.push(ImportedIndex {
+                name: name.to_string(),
+                columns: columns.into_iter().map(|column| (column, false)).collect(),
+                unique: true,
+            }),
+            ConstraintType::ForeignKey => {
+                // Keys into other schemas cannot be drawn.
+                let Some(referenced_table) = rows[0]
+                    .referenced_table
+                    .clone()
+                    .filter(|name| catalog.tables.iter().any(|table| table.table_name == *name))
+                else {
+                    continue;
+                };
+                imported.foreign_keys.push(ImportedForeignKey {
+                    name: Some(name.to_string()),
+                    columns,
+                    referenced_table,
+                    referenced_columns: rows
+                        .iter()
+                        .filter_map(|row| row.referenced_column.clone())
+                        .collect(),
+                    on_delete: rows[0].delete_rule.clone(),
+                    on_update: rows[0].update_rule.clone(),
+                });
+            }
+        }
+    }
+
+    let indexes = group_by(
+        catalog
+            .index_columns
+            .iter()
+            .filter(|row| row.table_name == table.table_name),
+        |row| &row.index_name,
+    );
+    for (name, rows) in indexes {
+        // Indexes backing a constraint are part of the constraint.
+        if constraints
+            .iter()
+            .any(|(constraint, _)| *constraint == name)
+        {
+            continue;
+        }
+        let Some(columns) = rows
+            .iter()
+            .map(|row| {
+                row.column_name
+                    .clone()
+                    .map(|column| (column, row.descending))
+            })
+            .collect::<Option<Vec<_>>>()
+        else {
+            continue;
+        };
+        imported.indexes.push(ImportedIndex {
+            name: name.to_string(),
+            columns,
+            unique: rows[0].unique,
+        });
+    }
+    imported
+}
+
+/// Builds a diagram from catalog rows, mapping table and column comments to logical
+/// names and descriptions.
+pub fn import_catalog(database: &str, catalog: &Catalog) -> Diagram {
+    let imported: Vec<ImportedTable> = catalog
+        .tables
+        .iter()
+        .map(|table| import_table(catalog, table))
+        .collect();
+    build_diagram(database, &imported)
+}
@@ -0,0 +1,912 @@
+//! Import of Prisma schemas, `schema.prisma`.
+//!
+//! Models become tables and their scalar fields columns, named after `@@map` and `@map`
+//! when given. Native types `@db.*` give the column types; fields without one get the
+//! type Prisma would create for the provider. Relation fields with `fields` and
+//! `references` become relationships, `@@index` indexes and `@@unique` unique keys. Enum
+//! fields become enum columns with the values of the enum. `///` comments become
+//! logical names and descriptions as database comments do: the first line is the
+//! logical name. The database comes from the provider of the datasource, and is
+//! PostgreSQL otherwise. Views, composite types, implicit many-to-many relations and
+//! defaults generated by Prisma Client have no place in a diagram and are reported as
+//! warnings.
+
+use super::{
+    ImportedColumn, ImportedForeignKey, ImportedIndex, ImportedTable, build_diagram,
+    fill_referenced_primary_keys,
+};
+use crate::dtos::diagram::Diagram;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrismaWarning {
+    /// Line of the element, counting from 1.
+    pub line: u64,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrismaImport {
+    pub diagram: Diagram,
+    pub warnings: Vec<PrismaWarning>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    /// A name, a keyword or a number.
+    Word(String),
+    /// A string in double quotes.
+    Text(String),
+    /// The text of a `///` comment.
+    Doc(String),
+    Symbol(char),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    line: u64,
+}
+
+type Error = Box<dyn std::error::Error>;
+
+fn syntax_error(line: u64, message: impl std::fmt::Display) -> Error {
+    format!("line {line}: {message}").into()
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, Error> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start_line = line;
+        match c {
+            '\n' => {
+                line += 1;
+                i += 1;
+            }
+            c if c.is_whitespace() => i += 1,
+            '/' if chars[i..].starts_with(&['/', '/', '/']) => {
+                let start = i + 3;
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let text = text.strip_prefix(' ').unwrap_or(&text);
+                tokens.push(Token {
+                    kind: TokenKind::Doc(text.trim_end().to_string()),
+                    line: start_line,
+                });
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '"' => {
+                i += 1;
+                let mut text = String::new();
+                loop {
+                    match chars.get(i) {
+                        None | Some('\n') => {
+                            return Err(syntax_error(start_line, "unterminated string"));
+                        }
+                        Some('\\') if chars.get(i + 1).is_some() => {
+                            text.push(match chars[i + 1] {
+                                'n' => '\n',
+                                't' => '\t',
+                                other => other,
+                            });
+                            i += 2;
+                        }
+                        Some('"') => break,
+                        Some(&other) => {
+                            text.push(other);
+                            i += 1;
+                        }
+                    }
+                }
+                i += 1;
+                tokens.push(Token {
+                    kind: TokenKind::Text(text),
+                    line: start_line,
+                });
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut word = String::new();
+                word.push(c);
+                i += 1;
+                // Decimal numbers, e.g. a default of 1.5.
+                while let Some(&next) = chars.get(i) {
+                    let decimal_point = next == '.'
+                        && word.chars().all(|c| c.is_ascii_digit())
+                        && chars.get(i + 1).is_some_and(char::is_ascii_digit);
+                    if next.is_alphanumeric() || next == '_' || decimal_point {
+                        word.push(next);
+                        i += 1;
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Word(word),
+                    line: start_line,
+                });
+            }
+            _ => {
+                tokens.push(Token {
+                    kind: TokenKind::Symbol(c),
+                    line: start_line,
+                });
+                i += 1;
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// An argument value, e.g. the `[id]` of `fields: [id]` or the `now()` of
+/// `@default(now())`.
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Text(String),
+    /// A name or a number, possibly dotted or negative.
+    Word(String),
+    Call(String, Vec<Arg>),
+    List(Vec<Expr>),
+}
+
+impl Expr {
+    fn text(&self) -> Option<&str> {
+        match self {
+            Expr::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    fn word(&self) -> Option<&str> {
+        match self {
+            Expr::Word(word) => Some(word),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Arg {
+    name: Option<String>,
+    value: Expr,
+}
+
+/// An attribute, e.g. `@db.VarChar(32)` as `db.VarChar` with `32`.
+#[derive(Debug, Clone)]
+struct Attribute {
+    name: String,
+    args: Vec<Arg>,
+    line: u64,
+}
+
+impl Attribute {
+    /// The argument of that name, or else the positional argument at `position`.
+    fn arg(&self, name: &str, position: usize) -> Option<&Expr> {
+        self.args
+            .iter()
+            .find(|arg| arg.name.as_deref() == Some(name))
+            .or_else(|| {
+                self.args
+                    .iter()
+                    .filter(|arg| arg.name.is_none())
+                    .nth(position)
+            })
+            .map(|arg| &arg.value)
+    }
+
+    fn named(&self, name: &str) -> Option<&Expr> {
+        self.args
+            .iter()
+            .find(|arg| arg.name.as_deref() == Some(name))
+            .map(|arg| &arg.value)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Field {
+    name: String,
+    field_type: String,
+    /// The argument of `Unsupported("...")`.
+    unsupported: Option<String>,
+    list: bool,
+    optional: bool,
+    attributes: Vec<Attribute>,
+    doc: Vec<String>,
+    line: u64,
+}
+
+impl Field {
+    fn attribute(&self, name: &str) -> Option<&Attribute> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.name == name)
+    }
+
+    /// The name of the column, after `@map`.
+    fn column_name(&self) -> String {
+        self.attribute("map")
+            .and_then(|map| map.arg("name", 0))
+            .and_then(Expr::text)
+            .unwrap_or(&self.name)
+            .to_string()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Model {
+    name: String,
+    fields: Vec<Field>,
+    attributes: Vec<Attribute>,
+    doc: Vec<String>,
+}
+
+impl Model {
+    /// The name of the table, after `@@map`.
+    fn table_name(&self) -> String {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.name == "map")
+            .and_then(|map| map.arg("name", 0))
+            .and_then(Expr::text)
+            .unwrap_or(&self.name)
+            .to_string()
+    }
+
+    fn column_name(&self, field: &str) -> String {
+        self.fields
+            .iter()
+            .find(|candidate| candidate.name == field)
+            .map_or(field.to_string(), Field::column_name)
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    provider: Option<String>,
+    models: Vec<Model>,
+    /// The values of the enums by name, as stored in the database.
+    enums: HashMap<String, Vec<(String, String)>>,
+    warnings: Vec<PrismaWarning>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.position).map(|token| &token.kind)
+    }
+
+    fn line(&self) -> u64 {
+        self.tokens
+            .get(self.position)
+            .or(self.tokens.last())
+            .map_or(1, |token| token.line)
+    }
+
+    fn next(&mut self) -> Result<TokenKind, Error> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .ok_or_else(|| syntax_error(self.line(), "unexpected end of input"))?;
+        self.position += 1;
+        Ok(token.kind.clone())
+    }
+
+    fn is_symbol(&self, symbol: char) -> bool {
+        self.peek() == Some(&TokenKind::Symbol(symbol))
+    }
+
+    fn eat_symbol(&mut self, symbol: char) -> bool {
+        let found = self.is_symbol(symbol);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> Result<(), Error> {
+        if self.eat_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(syntax_error(self.line(), format!("expected `{symbol}`")))
+        }
+    }
+
+    fn name(&mut self) -> Result<String, Error> {
+        let line = self.line();
+        match self.next()? {
+            TokenKind::Word(name) => Ok(name),
+            _ => Err(syntax_error(line, "expected a name")),
+        }
+    }
+
+    /// The `///` comments before an element.
+    fn docs(&mut self) -> Vec<String> {
+        let mut docs = Vec::new();
+        while let Some(TokenKind::Doc(doc)) = self.peek() {
+            docs.push(doc.clone());
+            self.position += 1;
+        }
+        docs
+    }
+
+    fn warn(&mut self, line: u64, message: String) {
+        self.warnings.push(PrismaWarning { line, message });
+    }
+
+    /// Skips a `{ ... }` block, including nested blocks.
+    fn skip_block(&mut self) -> Result<(), Error> {
+        self.expect_symbol('{')?;
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next()? {
+                TokenKind::Symbol('{') => depth += 1,
+                TokenKind::Symbol('}') => depth -= 1,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn expr(&mut self) -> Result<Expr, Error> {
+        let line = self.line();
+        match self.next()? {
+            TokenKind::Text(text) => Ok(Expr::Text(text)),
+            TokenKind::Symbol('[') => {
+                let mut items = Vec::new();
+                while !self.eat_symbol(']') {
+                    items.push(self.expr()?);
+                    if !self.eat_symbol(',') && !self.is_symbol(']') {
+                        return Err(syntax_error(self.line(), "expected `,` or `]`"));
+                    }
+                }
+                Ok(Expr::List(items))
+            }
+            TokenKind::Symbol('-') => match self.next()? {
+                TokenKind::Word(number) => Ok(Expr::Word(format!("-{number}"))),
+                _ => Err(syntax_error(line, "expected a number")),
+            },
+            TokenKind::Word(mut word) => {
+                while self.is_symbol('.') {
+                    self.position += 1;
+                    word.push('.');
+                    word.push_str(&self.name()?);
+                }
+                if self.is_symbol('(') {
+                    Ok(Expr::Call(word, self.args()?))
+                } else {
+                    Ok(Expr::Word(word))
+                }
+            }
+            _ => Err(syntax_error(line, "expected a value")),
+        }
+    }
+
+    /// Arguments in parentheses, e.g. `(fields: [a], references: [b])`.
+    fn args(&mut self) -> Result<Vec<Arg>, Error> {
+        self.expect_symbol('(')?;
+        let mut args = Vec::new();
+        while !self.eat_symbol(')') {
+            let named = matches!(self.peek(), Some(TokenKind::Word(_)))
+                && self.tokens.get(self.position + 1).map(|token| &token.kind)
+                    == Some(&TokenKind::Symbol(':'));
+            let name = if named {
+                let name = self.name()?;
+                self.position += 1;
+                Some(name)
+            } else {
+                None
+            };
+            args.push(Arg {
+                name,
+                value: self.expr()?,
+            });
+            if !self.eat_symbol(',') && !self.is_symbol(')') {
+                return Err(syntax_error(self.line(), "expected `,` or `)`"));
+            }
+        }
+        Ok(args)
+    }
+
+    /// An attribute after its `@` or `@@`.
+    fn attribute(&mut self) -> Result<Attribute, Error> {
+        let line = self.line();
+        let mut name = self.name()?;
+        while self.eat_symbol('.') {
+            name.push('.');
+            name.push_str(&self.name()?);
+        }
+        let args = if self.is_symbol('(') {
+            self.args()?
+        } else {
+            Vec::new()
+        };
+        Ok(Attribute { name, args, line })
+    }
+
+    fn field(&mut self, doc: Vec<String>) -> Result<Field, Error> {
+        let line = self.line();
+        let name = self.name()?;
+        let field_type = self.name()?;
+        let mut unsupported = None;
+        if self.is_symbol('(') {
+            let args = self.args()?;
+            unsupported = args
+                .first()
+                .and_then(|arg| arg.value.text())
+                .map(str::to_string);
+        }
+        let list = self.eat_symbol('[');
+        if list {
+            self.expect_symbol(']')?;
+        }
+        let optional = self.eat_symbol('?');
+        let mut attributes = Vec::new();
+        while self.is_symbol('@')
+            && self.tokens.get(self.position + 1).map(|token| &token.kind)
+                != Some(&TokenKind::Symbol('@'))
+        {
+            self.position += 1;
+            attributes.push(self.attribute()?);
+        }
+        Ok(Field {
+            name,
+            field_type,
+            unsupported,
+            list,
+            optional,
+            attributes,
+            doc,
+            line,
+        })
+    }
+
+    fn model(&mut self, doc: Vec<String>) -> Result<(), Error> {
+        let name = self.name()?;
+        let mut model = Model {
+            name,
+            fields: Vec::new(),
+            attributes: Vec::new(),
+            doc,
+        };
+        self.expect_symbol('{')?;
+        loop {
+            let doc = self.docs();
+            if self.eat_symbol('}') {
+                break;
+            }
+            if self.eat_symbol('@') {
+                self.expect_symbol('@')?;
+                model.attributes.push(self.attribute()?);
+            } else {
+                model.fields.push(self.field(doc)?);
+            }
+        }
+        self.models.push(model);
+        Ok(())
+    }
+
+    fn enumeration(&mut self) -> Result<(), Error> {
+        let name = self.name()?;
+        let mut values = Vec::new();
+        self.expect_symbol('{')?;
+        loop {
+            self.docs();
+            if self.eat_symbol('}') {
+                break;
+            }
+            if self.eat_symbol('@') {
+                self.expect_symbol('@')?;
+                self.attribute()?;
+                continue;
+            }
+            let value = self.name()?;
+            let mut mapped = value.clone();
+            while self.eat_symbol('@') {
+                let attribute = self.attribute()?;
+                if attribute.name == "map"
+                    && let Some(name) = attribute.arg("name", 0).and_then(Expr::text)
+                {
+                    mapped = name.to_string();
+                }
+            }
+            values.push((value, mapped));
+        }
+        self.enums.insert(name, values);
+        Ok(())
+    }
+
+    fn datasource(&mut self) -> Result<(), Error> {
+        self.name()?;
+        self.expect_symbol('{')?;
+        loop {
+            self.docs();
+            if self.eat_symbol('}') {
+                break;
+            }
+            let key = self.name()?;
+            self.expect_symbol('=')?;
+            let value = self.expr()?;
+            if key == "provider"
+                && let Some(provider) = value.text()
+            {
+                self.provider = Some(provider.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    fn parse(&mut self) -> Result<(), Error> {
+        loop {
+            let doc = self.docs();
+            let Some(kind) = self.peek().cloned() else {
+                return Ok(());
+            };
+            let line = self.line();
+            let TokenKind::Word(keyword) = kind else {
+                return Err(syntax_error(
+                    line,
+                    "expected model, enum, datasource or generator",
+                ));
+            };
+            self.position += 1;
+            match keyword.as_str() {
+                "model" => self.model(doc)?,
+                "enum" => self.enumeration()?,
+                "datasource" => self.datasource()?,
+                "generator" => {
+                    self.name()?;
+                    self.skip_block()?;
+                }
+                "view" | "type" => {
+                    let name = self.name()?;
+                    self.skip_block()?;
+                    self.warn(line, format!("{keyword} {name} is not imported"));
+                }
+                _ => return Err(syntax_error(line, format!("unexpected {keyword}"))),
+            }
+        }
+    }
+}
+
+fn database_name(provider: &str) -> String {
+    match provider.to_lowercase().as_str() {
+        "postgresql" | "postgres" | "cockroachdb" => super::postgresql::DATABASE.to_string(),
+        "mysql" => super::mysql::DATABASE.to_string(),
+        "sqlite" => super::sqlite::DATABASE.to_string(),
+        _ => provider.to_string(),
+    }
+}
+
+/// The SQL type of a native type, e.g. `varchar(32)` for `VarChar(32)` or
+/// `int unsigned` for `UnsignedInt`.
+fn native_sql_type(native: &str, args: &[Arg]) -> String {
+    let (name, unsigned) = match native.strip_prefix("Unsigned") {
+        Some(name) => (name, " unsigned"),
+        None => (native, ""),
+    };
+    let name = match name {
+        "DoublePrecision" => "double precision".to_string(),
+        name => name.to_lowercase(),
+    };
+    let args: Vec<&str> = args.iter().filter_map(|arg| arg.value.word()).collect();
+    if args.is_empty() {
+        format!("{name}{unsigned}")
+    } else {
+        format!("{name}({}){unsigned}", args.join(","))
+    }
+}
+
+/// The SQL type Prisma creates for a scalar type without a native type.
+fn default_sql_type(scalar: &str, provider: &str) -> Option<&'static str> {
+    let mysql = provider == super::mysql::DATABASE;
+    let postgresql = provider == super::postgresql::DATABASE;
+    Some(match scalar {
+        "String" if mysql => "varchar(191)",
+        "String" => "text",
+        "Int" => "int",
+        "BigInt" => "bigint",
+        "Float" => "double",
+        "Decimal" => "decimal",
+        "DateTime" if postgresql => "timestamp",
+        "DateTime" => "datetime",
+        "Boolean" => "boolean",
+        "Json" => "json",
+        "Bytes" if mysql => "longblob",
+        "Bytes" => "blob",
+        _ => return None,
+    })
+}
+
+/// A referential action of Prisma, e.g. `SetNull`, in SQL.
+fn action(action: &str) -> String {
+    match action {
+        "Cascade" => "CASCADE",
+        "Restrict" => "RESTRICT",
+        "NoAction" => "NO ACTION",
+        "SetNull" => "SET NULL",
+        "SetDefault" => "SET DEFAULT",
+        other => other,
+    }
+    .to_string()
+}
+
+/// The field names of a list such as `[a, b(sort: Desc)]`, with their descending flag.
+fn field_list(value: Option<&Expr>) -> Vec<(String, bool)> {
+    let Some(Expr::List(items)) = value else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| match item {
+            Expr::Word(name) => Some((name.clone(), false)),
+            Expr::Call(name, args) => Some((
+                name.clone(),
+                args.iter().any(|arg| {
+                    arg.name.as_deref() == Some("sort") && arg.value.word() == Some("Desc")
+                }),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Reads the columns of a model, its keys and indexes, and its foreign keys.
+fn import_model(
+    model: &Model,
+    models: &[Model],
+    enums: &HashMap<String, Vec<(String, String)>>,
+    database: &str,
+    warnings: &mut Vec<PrismaWarning>,
+) -> ImportedTable {
+    let warn = |warnings: &mut Vec<PrismaWarning>, line, message| {
+        warnings.push(PrismaWarning { line, message });
+    };
+    let mut table = ImportedTable {
+        name: model.table_name(),
+        comment: (!model.doc.is_empty()).then(|| model.doc.join("\n")),
+        ..Default::default()
+    };
+
+    for field in &model.fields {
+        if let Some(target) = models
+            .iter()
+            .find(|candidate| candidate.name == field.field_type)
+        {
+            let Some(relation) = field.attribute("relation") else {
+                let implicit = field.list
+                    && target.fields.iter().any(|other| {
+                        other.field_type == model.name
+                            && other.list
+                            && other
+                                .attribute("relation")
+                                .is_none_or(|relation| relation.named("fields").is_none())
+                    });
+                // Reported once, from the model that comes first.
+                if implicit && model.name <= target.name {
+                    warn(
+                        warnings,
+                        field.line,
+                        format!(
+                            "many-to-many relation between {} and {} is not imported",
+                            model.name, target.name
+                        ),
+                    );
+                }
+                continue;
+            };
+            let columns = field_list(relation.named("fields"));
+            if columns.is_empty() {
+                continue;
+            }
+            let referenced = field_list(relation.named("references"));
+            let on_delete = relation
+                .named("onDelete")
+                .and_then(Expr::word)
+                .map(action)
+                .unwrap_or_else(|| {
+                    if field.optional {
+                        "SET NULL"
+                    } else {
+                        "RESTRICT"
+                    }
+                    .to_string()
+                });
+            let on_update = relation
+                .named("onUpdate")
+                .and_then(Expr::word)
+                .map_or("CASCADE".to_string(), action);
+            table.foreign_keys.push(ImportedForeignKey {
+                name: relation
+                    .named("map")
+                    .and_then(Expr::text)
+                    .map(str::to_string),
+                columns: columns
+                    .iter()
+                    .map(|(name, _)| model.column_name(name))
+                    .collect(),
+                referenced_table: target.table_name(),
+                referenced_columns: referenced
+                    .iter()
+                    .map(|(name, _)| target.column_name(name))
+                    .collect(),
+                on_delete: Some(on_delete),
+                on_update: Some(on_update),
+            });
+            continue;
+        }
+
+        let name = field.column_name();
+        let native = field
+            .attributes
+            .iter()
+            .find_map(|attribute| Some((attribute.name.strip_prefix("db.")?, &attribute.args)));
+        let enum_values = enums.get(&field.field_type);
+        let mut sql_type = match (&field.unsupported, native, enum_values) {
+            (Some(unsupported), _, _) => unsupported.clone(),
+            (None, _, Some(_)) => "enum".to_string(),
+            (None, Some((native, args)), None) => native_sql_type(native, args),
+            (None, None, None) => match default_sql_type(&field.field_type, database) {
+                Some(sql_type) => sql_type.to_string(),
+                None => {
+                    warn(
+                        warnings,
+                        field.line,
+                        format!(
+                            "type {} of field {} is unknown and kept as is",
+                            field.field_type, field.name
+                        ),
+                    );
+                    field.field_type.to_lowercase()
+                }
+            },
+        };
+        if field.list {
+            sql_type.push_str("[]");
+        }
+        let mut column = ImportedColumn {
+            name: name.clone(),
+            sql_type,
+            not_null: !field.optional,
+            comment: (!field.doc.is_empty()).then(|| field.doc.join("\n")),
+            args: enum_values.map(|values| {
+                values
+                    .iter()
+                    .map(|(_, value)| format!("'{}'", value.replace('\'', "''")))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }),
+            ..Default::default()
+        };
+        for attribute in &field.attributes {
+            match attribute.name.as_str() {
+                "id" => table.primary_key.push(name.clone()),
+                "unique" => column.unique = true,
+                "default" => match attribute.arg("value", 0) {
+                    Some(Expr::Call(function, args)) => match function.as_str() {
+                        "autoincrement" => column.auto_increment = true,
+                        "now" => column.default_value = Some("CURRENT_TIMESTAMP".to_string()),
+                        "dbgenerated" => {
+                            column.default_value = args
+                                .first()
+                                .and_then(|arg| arg.value.text())
+                                .filter(|value| !value.is_empty())
+                                .map(str::to_string);
+                        }
+                        _ => warn(
+                            warnings,
+                            attribute.line,
+                            format!(
+                                "default {function}() of field {} is generated by Prisma Client and is not kept",
+                                field.name
+                            ),
+                        ),
+                    },
+                    Some(Expr::Text(value)) => column.default_value = Some(value.clone()),
+                    Some(Expr::Word(value)) => {
+                        let enum_value = enum_values
+                            .and_then(|values| values.iter().find(|(name, _)| name == value));
+                        column.default_value =
+                            Some(enum_value.map_or(value.clone(), |(_, mapped)| mapped.clone()));
+                    }
+                    _ => warn(
+                        warnings,
+                        attribute.line,
+                        format!("default of field {} is not kept", field.name),
+                    ),
+                },
+                _ => {}
+            }
+        }
+        table.columns.push(column);
+    }
+
+    for attribute in &model.attributes {
+        let columns: Vec<(String, bool)> = field_list(attribute.arg("fields", 0))
+            .into_iter()
+            .map(|(name, desc)| (model.column_name(&name), desc))
+            .collect();
+        let name = attribute
+            .named("map")
+            .or_else(|| attribute.named("name"))
+            .and_then(Expr::text)
+            .map(str::to_string);
+        let names: Vec<&str> = columns.iter().map(|(name, _)| name.as_str()).collect();
+        match attribute.name.as_str() {
+            "id" => table.primary_key = names.iter().map(|name| name.to_string()).collect(),
+            "unique" => table.unique_keys.push(ImportedIndex {
+                name: name.unwrap_or_else(|| format!("UK_{}_{}", table.name, names.join("_"))),
+                columns,
+                unique: true,
+            }),
+            "index" => table.indexes.push(ImportedIndex {
+                name: name.unwrap_or_else(|| format!("IDX_{}_{}", table.name, names.join("_"))),
+                columns,
+                unique: false,
+            }),
+            "map" | "schema" | "ignore" => {}
+            other => warn(
+                warnings,
+                attribute.line,
+                format!("attribute @@{other} of model {} is ignored", model.name),
+            ),
+        }
+    }
+    table
+}
+
+/// Builds a diagram from a Prisma schema. Syntax errors fail the import; elements that
+/// have no place in a diagram are reported as warnings.
+pub fn import_script(schema: &str) -> Result<PrismaImport, Box<dyn std::error::Error>> {
+    let mut parser = Parser {
+        tokens: tokenize(schema)?,
+        position: 0,
+        provider: None,
+        models: Vec::new(),
+        enums: HashMap::new(),
+        warnings: Vec::new(),
+    };
+    parser.parse()?;
+    let mut warnings = parser.warnings;
+    let database = parser
+        .provider
+        .as_deref()
+        .map_or(super::postgresql::DATABASE.to_string(), database_name);
+
+    let mut tables: Vec<ImportedTable> = parser
+        .models
+        .iter()
+        .map(|model| {
+            import_model(
+                model,
+                &parser.models,
+                &parser.enums,
+                &database,
+                &mut warnings,
+            )
+        })
+        .collect();
+    fill_referenced_primary_keys(&mut tables);
+
+    Ok(PrismaImport {
+        diagram: build_diagram(&database, &tables),
+        warnings,
+    })
+}
+
+/// Reads a Prisma schema from a file and builds a diagram from it.
+pub fn import(filename: &str) -> Result<PrismaImport, Box<dyn std::error::Error>> {
+    let schema = std::fs::read_to_string(filename)?;
+    import_script(&schema)
+}
@@ -0,0 +1,196 @@
+//! Reverse engineering of SQLite database files.
+
+use super::{
+    ImportedColumn, ImportedForeignKey, ImportedIndex, ImportedTable, build_diagram,
+    fill_referenced_primary_keys, unquote_default,
+};
+use crate::dtos::diagram::Diagram;
+use rusqlite::{Connection, OpenFlags};
+
+pub const DATABASE: &str = "SQLite";
+
+fn read_columns(
+    connection: &Connection,
+    table: &str,
+) -> rusqlite::Result<(Vec<ImportedColumn>, Vec<String>)> {
+    let mut statement = connection.prepare(
+        "SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?1) ORDER BY cid",
+    )?;
+    let rows = statement.query_map([table], |row| {
+        Ok((
+            ImportedColumn {
+                name: row.get(0)?,
+                sql_type: row.get(1)?,
+                not_null: row.get(2)?,
+                default_value: row
+                    .get::<_, Option<String>>(3)?
+                    .map(|value| unquote_default(&value)),
+                ..Default::default()
+            },
+            row.get::<_, i64>(4)?,
+        ))
+    })?;
+
+    let mut columns = Vec::new();
+    let mut primary_key = Vec::new();
+    for row in rows {
+        let (column, pk_position) = row?;
+        if pk_position > 0 {
+            primary_key.push((pk_position, column.name.clone()));
+        }
+        columns.push(column);
+    }
+    primary_key.sort();
+    Ok((
+        columns,
+        primary_key.into_iter().map(|(_, name)| name).collect(),
+    ))
+}
+
+fn read_foreign_keys(
+    connection: &Connection,
+    table: &str,
+) -> rusqlite::Result<Vec<ImportedForeignKey>> {
+    let mut statement = connection.prepare(
+        "SELECT id, \"table\", \"from\", \"to\", on_update, on_delete FROM pragma_foreign_key_list(?1) ORDER BY id, seq",
+    )?;
+    let rows = statement.query_map([table], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, Option<String>>(3)?,
+            row.get::<_, String>(4)?,
+            row.get::<_, String>(5)?,
+        ))
+    })?;
+
+    let mut foreign_keys: Vec<(i64, ImportedForeignKey)> = Vec::new();
+    for row in rows {
+        let (id, referenced_table, from, to, on_update, on_delete) = row?;
+        if foreign_keys
+            .last()
+            .is_none_or(|(last_id, _)| *last_id != id)
+        {
+            foreign_keys.push((
+                id,
+                ImportedForeignKey {
+                    referenced_table,
+                    on_delete: Some(on_delete),
+                    on_update: Some(on_update),
+                    ..Default::default()
+                },
+            ));
+        }
+        let (_, foreign_key) = foreign_keys.last_mut().expect("pushed above");
+        foreign_key.columns.push(from);
+        // Without target columns the key refers to the parent's primary key, which is
+        // filled in once all tables are read.
+        foreign_key.referenced_columns.extend(to);
+    }
+    Ok(foreign_keys
+        .into_iter()
+        .map(|(_, foreign_key)| foreign_key)
+        .collect())
+}
+
+fn read_index_columns(
+    connection: &Connection,
+    index: &str,
+) -> rusqlite::Result<Option<Vec<(String, bool)>>> {
+    let mut statement = connection.prepare(
+        "SELECT name, \"desc\" FROM pragma_index_xinfo(?1) WHERE key = 1 ORDER BY seqno",
+    )?;
+    let rows = statement.query_map([index], |row| {
+        Ok((row.get::<_, Option<String>>(0)?, row.get::<_, bool>(1)?))
+    })?;
+    let mut columns = Vec::new();
+    for row in rows {
+        match row? {
+            (Some(name), desc) => columns.push((name, desc)),
+            // Indexes on expressions cannot be represented in a diagram.
+            (None, _) => return Ok(None),
+        }
+    }
+    Ok(Some(columns))
+}
+
+fn read_indexes(connection: &Connection, table: &mut ImportedTable) -> rusqlite::Result<()> {
+    let mut statement = connection
+        .prepare("SELECT name, \"unique\", origin FROM pragma_index_list(?1) ORDER BY seq DESC")?;
+    let rows = statement.query_map([&table.name], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, bool>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })?;
+    for row in rows {
+        let (name, unique, origin) = row?;
+        let Some(columns) = read_index_columns(connection, &name)? else {
+            continue;
+        };
+        let index = ImportedIndex {
+            name,
+            columns,
+            unique,
+        };
+        match origin.as_str() {
+            "pk" => {}
+            "u" => table.unique_keys.push(index),
+            _ => table.indexes.push(index),
+        }
+    }
+    Ok(())
+}
+
+fn has_autoincrement(sql: &str) -> bool {
+    sql.to_uppercase().contains("AUTOINCREMENT")
+}
+
+/// Reads every table of an open SQLite database, except SQLite's internal tables.
+pub fn import_connection(connection: &Connection) -> Result<Diagram, Box<dyn std::error::Error>> {
+    let mut statement = connection.prepare(
+        "SELECT name, sql FROM sqlite_schema WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY rowid",
+    )?;
+    let tables = statement
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut imported = Vec::new();
+    for (name, sql) in tables {
+        let (mut columns, primary_key) = read_columns(connection, &name)?;
+        // Only an INTEGER PRIMARY KEY can be AUTOINCREMENT in SQLite.
+        if sql.as_deref().is_some_and(has_autoincrement)
+            && let [primary_key_column] = primary_key.as_slice()
+            && let Some(column) = columns
+                .iter_mut()
+                .find(|column| column.name == *primary_key_column)
+        {
+            column.auto_increment = true;
+        }
+        let mut table = ImportedTable {
+            foreign_keys: read_foreign_keys(connection, &name)?,
+            name,
+            columns,
+            primary_key,
+            ..Default::default()
+        };
+        read_indexes(connection, &mut table)?;
+        imported.push(table);
+    }
+
+    fill_referenced_primary_keys(&mut imported);
+    Ok(build_diagram(DATABASE, &imported))
+}
+
+/// Opens an SQLite database file read-only and builds a diagram from its schema.
+pub fn import(filename: &str) -> Result<Diagram, Box<dyn std::error::Error>> {
+    let connection = Connection::open_with_flags(
+        filename,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    import_connection(&connection)
+}
@@ -0,0 +1,651 @@
+//! Automatic placement of tables, and optionally notes, from the relationship graph, for
+//! diagrams without a sensible arrangement such as imported ones. Objects keep their
+//! size, as drawn with their columns, and never overlap each other.
+
+use crate::dtos::diagram::Diagram;
+use crate::dtos::diagram_walkers::TABLE_ID_PREFIX;
+use crate::exporters::svg::{Rect, table_bounds};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// Where the first object goes when the whole diagram is arranged, as for imports.
+const MARGIN: f64 = 50.0;
+const GROUP_PADDING: f64 = 20.0;
+const FORCE_ITERATIONS: usize = 300;
+const GRAVITY: f64 = 0.05;
+const OVERLAP_ROUNDS: usize = 1000;
+const BARYCENTER_SWEEPS: usize = 4;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Strategy {
+    /// Parents above their children, in rows.
+    #[default]
+    Layered,
+    /// Related tables pulled together and unrelated ones pushed apart.
+    ForceDirected,
+    /// Rows and columns, related tables next to each other.
+    Grid,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutOptions {
+    #[serde(default)]
+    pub strategy: Strategy,
+
+    /// Physical names or ids of the tables to arrange. Empty arranges every table.
+    #[serde(default)]
+    pub selection: Vec<String>,
+
+    /// Arranges the notes too. Only applies when arranging every table.
+    #[serde(default)]
+    pub include_notes: bool,
+
+    /// The space between objects.
+    #[serde(default = "default_spacing")]
+    pub spacing: u16,
+}
+
+fn default_spacing() -> u16 {
+    60
+}
+
+impl Default for LayoutOptions {
+    fn default() -> Self {
+        Self {
+            strategy: Strategy::default(),
+            selection: Vec::new(),
+            include_notes: false,
+            spacing: default_spacing(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Item {
+    Table(usize),
+    Note(usize),
+}
+
+/// An object being placed, with `x` and `y` of its top left corner.
+#[derive(Debug, Clone)]
+struct Node {
+    item: Item,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl Node {
+    fn center(&self) -> (f64, f64) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+
+    fn overlaps(&self, rect: &Rect, spacing: f64) -> bool {
+        self.x < rect.x + rect.width + spacing
+            && rect.x < self.x + self.width + spacing
+            && self.y < rect.y + rect.height + spacing
+            && rect.y < self.y + self.height + spacing
+    }
+
+    fn rect(&self) -> Rect {
+        Rect {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+/// Neighbours of each node, in both directions.
+fn neighbours(count: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut neighbours = vec![Vec::new(); count];
+    for &(parent, child) in edges {
+        neighbours[parent].push(child);
+        neighbours[child].push(parent);
+    }
+    neighbours
+}
+
+/// Nodes ordered by connected component, each component breadth first, so that related
+/// nodes are next to each other.
+fn related_order(count: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+    let neighbours = neighbours(count, edges);
+    let mut visited = vec![false; count];
+    let mut order = Vec::with_capacity(count);
+    for start in 0..count {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for &next in &neighbours[node] {
+                if !visited[next] {
+                    visited[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+    order
+}
+
+/// Places `order` row by row, `columns` per row, aligning the columns.
+fn place_in_grid(nodes: &mut [Node], order: &[usize], columns: usize, spacing: f64) {
+    let columns = columns.max(1);
+    let mut widths = vec![0.0f64; columns];
+    for (position, &node) in order.iter().enumerate() {
+        let column = position % columns;
+        widths[column] = widths[column].max(nodes[node].width);
+    }
+    let mut y = 0.0;
+    for row in order.chunks(columns) {
+        let mut x = 0.0;
+        for (column, &node) in row.iter().enumerate() {
+            nodes[node].x = x;
+            nodes[node].y = y;
+            x += widths[column] + spacing;
+        }
+        let height = row
+            .iter()
+            .map(|&node| nodes[node].height)
+            .fold(0.0, f64::max);
+        y += height + spacing;
+    }
+}
+
+fn grid_columns(count: usize) -> usize {
+    (count as f64).sqrt().ceil() as usize
+}
+
+fn grid(nodes: &mut [Node], edges: &[(usize, usize)], spacing: f64) {
+    let order = related_order(nodes.len(), edges);
+    place_in_grid(nodes, &order, grid_columns(nodes.len()), spacing);
+}
+
+/// The layer of each node with an edge: parents above children, each node one layer
+/// below its deepest parent. Edges closing a cycle are ignored.
+fn layers(count: usize, edges: &[(usize, usize)]) -> Vec<Option<usize>> {
+    let mut children = vec![Vec::new(); count];
+    for &(parent, child) in edges {
+        children[parent].push(child);
+    }
+
+    // Depth first, dropping the edges back to a node still being visited.
+    let mut state = vec![0u8; count];
+    let mut acyclic = vec![Vec::new(); count];
+    for start in 0..count {
+        if state[start] != 0 {
+            continue;
+        }
+        state[start] = 1;
+        let mut stack = vec![(start, 0)];
+        while let Some((node, next)) = stack.pop() {
+            if let Some(&child) = children[node].get(next) {
+                stack.push((node, next + 1));
+                match state[child] {
+                    0 => {
+                        acyclic[node].push(child);
+                        state[child] = 1;
+                        stack.push((child, 0));
+                    }
+                    2 => acyclic[node].push(child),
+                    _ => {}
+                }
+            } else {
+                state[node] = 2;
+            }
+        }
+    }
+
+    let mut incoming = vec![0; count];
+    for child in acyclic.iter().flatten() {
+        incoming[*child] += 1;
+    }
+    let mut layer = vec![0; count];
+    let mut queue: VecDeque<usize> = (0..count).filter(|&node| incoming[node] == 0).collect();
+    while let Some(node) = queue.pop_front() {
+        for &child in &acyclic[node] {
+            layer[child] = layer[child].max(layer[node] + 1);
+            incoming[child] -= 1;
+            if incoming[child] == 0 {
+                queue.push_back(child);
+            }
+        }
+    }
+
+    let connected = neighbours(count, edges);
+    (0..count)
+        .map(|node| (!connected[node].is_empty()).then_some(layer[node]))
+        .collect()
+}
+
+/// Orders the nodes within each layer by the mean position of their neighbours in the
+/// layers above, then below, to reduce crossings.
+fn order_layers(rows: &mut [Vec<usize>], count: usize, edges: &[(usize, usize)]) {
+    let neighbours = neighbours(count, edges);
+    let mut layer_of = vec![0; count];
+    for (layer, row) in rows.iter().enumerate() {
+        for &node in row {
+            layer_of[node] = layer;
+        }
+    }
+    let positions = |rows: &[Vec<usize>]| {
+        let mut positions = vec![0.0; count];
+        for row in rows {
+            for (index, &node) in row.iter().enumerate() {
+                positions[node] = (index as f64 + 0.5) / row.len() as f64;
+            }
+        }
+        positions
+    };
+
+    for sweep in 0..BARYCENTER_SWEEPS * 2 {
+        let downwards = sweep % 2 == 0;
+        let layers: Vec<usize> = if downwards {
+            (1..rows.len()).collect()
+        } else {
+            (0..rows.len().saturating_sub(1)).rev().collect()
+        };
+        for layer in layers {
+            let current = positions(rows);
+            let barycenter = |node: usize| {
+                let adjacent: Vec<f64> = neighbours[node]
+                    .iter()
+                    .filter(|&&next| {
+                        if downwards {
+                            layer_of[next] < layer
+                        } else {
+                            layer_of[next] > layer
+                        }
+                    })
+                    .map(|&next| current[next])
+                    .collect();
+                if adjacent.is_empty() {
+                    current[node]
+                } else {
+                    adjacent.iter().sum::<f64>() / adjacent.len() as f64
+                }
+            };
+            rows[layer].sort_by(|&a, &b| barycenter(a).total_cmp(&barycenter(b)));
+        }
+    }
+}
+
+fn layered(nodes: &mut [Node], edges: &[(usize, usize)], spacing: f64) {
+    let layer_of = layers(nodes.len(), edges);
+    let depth = layer_of
+        .iter()
+        .flatten()
+        .max()
+        .map_or(0, |deepest| deepest + 1);
+    let mut rows = vec![Vec::new(); depth];
+    let mut unrelated = Vec::new();
+    for (node, layer) in layer_of.iter().enumerate() {
+        match layer {
+            Some(layer) => rows[*layer].push(node),
+            None => unrelated.push(node),
+        }
+    }
+    order_layers(&mut rows, nodes.len(), edges);
+
+    // Rows centered on the widest one, with room for the lines between them.
+    let row_widths: Vec<f64> = rows
+        .iter()
+        .map(|row| {
+            row.iter().map(|&node| nodes[node].width).sum::<f64>()
+                + spacing * row.len().saturating_sub(1) as f64
+        })
+        .collect();
+    let width = row_widths.iter().copied().fold(0.0, f64::max);
+    let mut y = 0.0;
+    for (row, row_width) in rows.iter().zip(row_widths) {
+        let mut x = (width - row_width) / 2.0;
+        for &node in row {
+            nodes[node].x = x;
+            nodes[node].y = y;
+            x += nodes[node].width + spacing;
+        }
+        let height = row
+            .iter()
+            .map(|&node| nodes[node].height)
+            .fold(0.0, f64::max);
+        y += height + spacing * 2.0;
+    }
+
+    // Tables without relationships and notes go below, in a grid.
+    if !unrelated.is_empty() {
+        let mut rest: Vec<Node> = unrelated.iter().map(|&node| nodes[node].clone()).collect();
+        let order: Vec<usize> = (0..rest.len()).collect();
+        let columns = grid_columns(rest.len());
+        place_in_grid(&mut rest, &order, columns, spacing);
+        for (node, placed) in unrelated.into_iter().zip(rest) {
+            nodes[node].x = placed.x;
+            nodes[node].y = y + placed.y;
+        }
+    }
+}
+
+/// Fruchterman-Reingold, starting from the grid so that the result only depends on the
+/// diagram.
+fn force_directed(nodes: &mut [Node], edges: &[(usize, usize)], spacing: f64) {
+    grid(nodes, edges, spacing);
+    let count = nodes.len();
+    if count < 2 {
+        return;
+    }
+    // The distance between related objects: about their size, as they cannot overlap.
+    let ideal = nodes
+        .iter()
+        .map(|node| node.width.max(node.height))
+        .sum::<f64>()
+        / count as f64
+        + spacing;
+    let mut centers: Vec<(f64, f64)> = nodes.iter().map(Node::center).collect();
+    let mut temperature = ideal * 2.0;
+    let cooling = temperature / FORCE_ITERATIONS as f64;
+
+    for _ in 0..FORCE_ITERATIONS {
+        let mut moves = vec![(0.0, 0.0); count];
+        for a in 0..count {
+            for b in a + 1..count {
+                let (mut dx, mut dy) = (centers[a].0 - centers[b].0, centers[a].1 - centers[b].1);
+                let mut distance = dx.hypot(dy);
+                if distance < 0.01 {
+                    // Same place: separate in a direction of their own.
+                    let angle = (a * count + b) as f64;
+                    (dx, dy) = (angle.cos(), angle.sin());
+                    distance = 1.0;
+                }
+                let force = ideal * ideal / distance;
+                let (fx, fy) = (dx / distance * force, dy / distance * force);
+                moves[a].0 += fx;
+                moves[a].1 += fy;
+                moves[b].0 -= fx;
+                moves[b].1 -= fy;
+            }
+        }
+        for &(a, b) in edges {
+            let (dx, dy) = (centers[a].0 - centers[b].0, centers[a].1 - centers[b].1);
+            let distance = dx.hypot(dy).max(0.01);
+            let force = distance * distance / ideal;
+            let (fx, fy) = (dx / distance * force, dy / distance * force);
+            moves[a].0 -= fx;
+            moves[a].1 -= fy;
+            moves[b].0 += fx;
+            moves[b].1 += fy;
+        }
+        // A little gravity keeps unrelated groups of tables from drifting apart.
+        let (mx, my) = centers.iter().fold((0.0, 0.0), |sum, center| {
+            (sum.0 + center.0, sum.1 + center.1)
+        });
+        let (mx, my) = (mx / count as f64, my / count as f64);
+        for (center, step) in centers.iter_mut().zip(&mut moves) {
+            step.0 += (mx - center.0) * GRAVITY;
+            step.1 += (my - center.1) * GRAVITY;
+            let length = step.0.hypot(step.1);
+            if length > 0.0 {
+                let limited = length.min(temperature);
+                center.0 += step.0 / length * limited;
+                center.1 += step.1 / length * limited;
+            }
+        }
+        temperature = (temperature - cooling).max(1.0);
+    }
+
+    for (node, center) in nodes.iter_mut().zip(&centers) {
+        node.x = center.0 - node.width / 2.0;
+        node.y = center.1 - node.height / 2.0;
+    }
+    if !remove_overlaps(nodes, spacing) {
+        grid(nodes, edges, spacing);
+    }
+}
+
+/// Pushes overlapping nodes apart along the axis they overlap the least on. Returns
+/// whether no overlaps are left.
+fn remove_overlaps(nodes: &mut [Node], spacing: f64) -> bool {
+    for _ in 0..OVERLAP_ROUNDS {
+        let mut moved = false;
+        for a in 0..nodes.len() {
+            for b in a + 1..nodes.len() {
+                if !nodes[a].overlaps(&nodes[b].rect(), spacing) {
+                    continue;
+                }
+                moved = true;
+                let ((ax, ay), (bx, by)) = (nodes[a].center(), nodes[b].center());
+                let overlap_x = (nodes[a].width + nodes[b].width) / 2.0 + spacing - (ax - bx).abs();
+                let overlap_y =
+                    (nodes[a].height + nodes[b].height) / 2.0 + spacing - (ay - by).abs();
+                // Half each, and a pixel more so that rounding does not bring them back.
+                if overlap_x < overlap_y {
+                    let shift = (overlap_x / 2.0 + 1.0) * if ax < bx { 1.0 } else { -1.0 };
+                    nodes[a].x -= shift;
+                    nodes[b].x += shift;
+                } else {
+                    let shift = (overlap_y / 2.0 + 1.0) * if ay < by { 1.0 } else { -1.0 };
+                    nodes[a].y -= shift;
+                    nodes[b].y += shift;
+                }
+            }
+        }
+        if !moved {
+            return true;
+        }
+    }
+    false
+}
+
+fn coordinate(value: f64) -> u16 {
+    value.round().clamp(0.0, f64::from(u16::MAX)) as u16
+}
+
+/// Arranges the tables of `options.selection`, or every table. A selection keeps its
+/// place in the diagram, moved down past the objects it would overlap, and groups are
+/// resized around their tables. Fails if a selected table does not exist.
+pub fn layout(
+    diagram: &mut Diagram,
+    options: &LayoutOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if options.selection.is_empty() {
+        layout_all(diagram, options);
+        return Ok(());
+    }
+    let tables = diagram.tables();
+    let mut selected = vec![false; tables.len()];
+    for name in &options.selection {
+        let name = name.strip_prefix(TABLE_ID_PREFIX).unwrap_or(name);
+        let index = tables
+            .iter()
+            .position(|table| table.physical_name == name)
+            .ok_or_else(|| format!("unknown table {name}"))?;
+        selected[index] = true;
+    }
+    arrange(diagram, Some(&selected), options);
+    Ok(())
+}
+
+/// Arranges every table, and the notes with `options.include_notes`, from the top left
+/// corner of the diagram. `options.selection` is ignored, so unlike [`layout`] this
+/// cannot fail.
+pub fn layout_all(diagram: &mut Diagram, options: &LayoutOptions) {
+    arrange(diagram, None, options);
+}
+
+/// Arranges the tables flagged in `selected`, or every table when None.
+fn arrange(diagram: &mut Diagram, selected: Option<&[bool]>, options: &LayoutOptions) {
+    let tables = diagram.tables();
+    let whole = selected.is_none();
+    let selected = |index: usize| selected.is_none_or(|selected| selected[index]);
+    let include_notes = options.include_notes && whole;
+
+    let mut nodes = Vec::new();
+    let mut fixed = Vec::new();
+    for (index, table) in tables.iter().enumerate() {
+        let bounds = table_bounds(diagram, table);
+        if selected(index) {
+            nodes.push(Node {
+                item: Item::Table(index),
+                x: bounds.x,
+                y: bounds.y,
+                width: bounds.width,
+                height: bounds.height,
+            });
+        } else {
+            fixed.push(bounds);
+        }
+    }
+    for (index, note) in diagram.notes().iter().enumerate() {
+        let bounds = Rect {
+            x: f64::from(note.x),
+            y: f64::from(note.y),
+            width: f64::from(note.width),
+            height: f64::from(note.height),
+        };
+        if include_notes {
+            nodes.push(Node {
+                item: Item::Note(index),
+                x: bounds.x,
+                y: bounds.y,
+                width: bounds.width,
+                height: bounds.height,
+            });
+        } else {
+            fixed.push(bounds);
+        }
+    }
+    if nodes.is_empty() {
+        return;
+    }
+
+    let node_of: HashMap<&str, usize> = nodes
+        .iter()
+        .enumerate()
+        .filter_map(|(node, placed)| match placed.item {
+            Item::Table(index) => Some((tables[index].physical_name.as_str(), node)),
+            Item::Note(_) => None,
+        })
+        .collect();
+    let mut edges = Vec::new();
+    for relationship in diagram.relationships() {
+        let parent = node_of.get(relationship.source_table());
+        let child = node_of.get(relationship.target_table());
+        if let (Some(&parent), Some(&child)) = (parent, child)
+            && parent != child
+            && !edges.contains(&(parent, child))
+        {
+            edges.push((parent, child));
+        }
+    }
+
+    let origin = if whole {
+        (MARGIN, MARGIN)
+    } else {
+        nodes.iter().fold((f64::MAX, f64::MAX), |origin, node| {
+            (origin.0.min(node.x), origin.1.min(node.y))
+        })
+    };
+    let spacing = f64::from(options.spacing);
+    match options.strategy {
+        Strategy::Layered => layered(&mut nodes, &edges, spacing),
+        Strategy::ForceDirected => force_directed(&mut nodes, &edges, spacing),
+        Strategy::Grid => grid(&mut nodes, &edges, spacing),
+    }
+
+    let (min_x, min_y) = nodes.iter().fold((f64::MAX, f64::MAX), |min, node| {
+        (min.0.min(node.x), min.1.min(node.y))
+    });
+    for node in &mut nodes {
+        node.x += origin.0 - min_x;
+        node.y += origin.1 - min_y;
+    }
+    // Past every object left in place that the arrangement would overlap.
+    while let Some(bottom) = nodes.iter().find_map(|node| {
+        fixed
+            .iter()
+            .find(|rect| node.overlaps(rect, 0.0))
+            .map(|rect| rect.y + rect.height + spacing - node.y)
+    }) {
+        for node in &mut nodes {
+            node.y += bottom;
+        }
+    }
+
+    let walkers = &mut diagram.diagram_walkers;
+    for node in &nodes {
+        match node.item {
+            Item::Table(index) => {
+                let table = &mut walkers.tables.as_mut().unwrap()[index];
+                (table.x, table.y) = (coordinate(node.x), coordinate(node.y));
+            }
+            Item::Note(index) => {
+                let note = &mut walkers.notes.as_mut().unwrap()[index];
+                (note.x, note.y) = (coordinate(node.x), coordinate(node.y));
+            }
+        }
+    }
+    fit_groups(diagram, &nodes);
+}
+
+/// Resizes the groups with a moved table to enclose their tables again.
+fn fit_groups(diagram: &mut Diagram, moved: &[Node]) {
+    let tables = diagram.tables();
+    let moved: Vec<String> = moved
+        .iter()
+        .filter_map(|node| match node.item {
+            Item::Table(index) => Some(tables[index].id()),
+            Item::Note(_) => None,
+        })
+        .collect();
+    let mut fitted = Vec::new();
+    let groups = diagram
+        .diagram_walkers
+        .groups
+        .as_deref()
+        .unwrap_or_default();
+    for (index, group) in groups.iter().enumerate() {
+        if !group.walkers.iter().any(|walker| moved.contains(walker)) {
+            continue;
+        }
+        let members: Vec<Rect> = group
+            .walkers
+            .iter()
+            .filter_map(|walker| diagram.find_table(walker))
+            .map(|table| table_bounds(diagram, table))
+            .collect();
+        if members.is_empty() {
+            continue;
+        }
+        let left = members.iter().map(|rect| rect.x).fold(f64::MAX, f64::min);
+        let top = members.iter().map(|rect| rect.y).fold(f64::MAX, f64::min);
+        let right = members
+            .iter()
+            .map(|rect| rect.x + rect.width)
+            .fold(0.0, f64::max);
+        let bottom = members
+            .iter()
+            .map(|rect| rect.y + rect.height)
+            .fold(0.0, f64::max);
+        fitted.push((
+            index,
+            Rect {
+                x: left - GROUP_PADDING,
+                y: top - GROUP_PADDING * 2.0,
+                width: right - left + GROUP_PADDING * 2.0,
+                height: bottom - top + GROUP_PADDING * 3.0,
+            },
+        ));
+    }
+    if let Some(groups) = diagram.diagram_walkers.groups.as_mut() {
+        for (index, rect) in fitted {
+            let group = &mut groups[index];
+            (group.x, group.y) = (coordinate(rect.x), coordinate(rect.y));
+            (group.width, group.height) = (coordinate(rect.width), coordinate(rect.height));
+        }
+    }
+}
@@ -1,11 +1,87 @@
+pub mod column_type;
+pub mod dictionary;
+pub mod diff;
+pub mod drift;
 pub mod dtos;
 pub mod entities;
+pub mod exporters;
+pub mod importers;
+pub mod layout;
+pub mod merge;
+pub mod naming;
 mod reader;
+pub mod seed;
+pub mod stats;
+pub mod validation;
+mod writer;
 
+use diff::{DiagramDiff, DiffOptions};
+use drift::{DriftReport, DriftTarget};
 use dtos::diagram::Diagram;
+use merge::MergeResult;
+use naming::NamingConfig;
 use reader::read_file;
+use validation::Diagnostic;
+use writer::{update_file, write_file};
 
 pub fn open(filename: &str) -> Result<Diagram, Box<dyn std::error::Error>> {
-    let diagram = read_file(&filename)?;
+    let diagram = read_file(filename)?;
     Ok(diagram.into())
 }
+
+/// Writes the diagram as an .erm file. Only the sections modeled by `entities` are
+/// written, so settings that ersflute does not read yet are not preserved.
+pub fn save(filename: &str, diagram: &Diagram) -> Result<(), Box<dyn std::error::Error>> {
+    write_file(filename, &diagram.clone().into())
+}
+
+/// Writes the diagram over the .erm file it was read from, keeping the sections and
+/// settings that ersflute does not read, unlike [`save`].
+pub fn update(filename: &str, diagram: &Diagram) -> Result<(), Box<dyn std::error::Error>> {
+    update_file(filename, &diagram.clone().into())
+}
+
+pub fn diff_files(
+    old_filename: &str,
+    new_filename: &str,
+    options: &DiffOptions,
+) -> Result<DiagramDiff, Box<dyn std::error::Error>> {
+    let old = open(old_filename)?;
+    let new = open(new_filename)?;
+    Ok(diff::diff(&old, &new, options))
+}
+
+pub fn merge_files(
+    base_filename: &str,
+    ours_filename: &str,
+    theirs_filename: &str,
+) -> Result<MergeResult, Box<dyn std::error::Error>> {
+    let base = open(base_filename)?;
+    let ours = open(ours_filename)?;
+    let theirs = open(theirs_filename)?;
+    Ok(merge::merge(&base, &ours, &theirs))
+}
+
+/// Compares a diagram file with the schema of `target`.
+pub fn check_drift(
+    filename: &str,
+    target: DriftTarget,
+) -> Result<DriftReport, Box<dyn std::error::Error>> {
+    let diagram = open(filename)?;
+    drift::check_target(&diagram, target)
+}
+
+/// Validates a diagram file, including the naming conventions of the config next to it
+/// if there is one. See [`validation::validate`] and [`naming::check`]. Nothing is
+/// reported while the validator is suspended in the diagram settings, as in the editor.
+pub fn validate_file(filename: &str) -> Result<Vec<Diagnostic>, Box<dyn std::error::Error>> {
+    let diagram = open(filename)?;
+    if diagram.diagram_settings.suspend_validator.unwrap_or(false) {
+        return Ok(Vec::new());
+    }
+    let mut diagnostics = validation::validate(&diagram);
+    if let Some(config) = NamingConfig::find(filename)? {
+        diagnostics.extend(naming::check(&diagram, &config));
+    }
+    Ok(diagnostics)
+}
@@ -0,0 +1,561 @@
+use crate::dtos::column_groups::{self, ColumnGroup};
+use crate::dtos::diagram::Diagram;
+use crate::dtos::diagram_settings::{Category, CategorySettings, DiagramSettings, ExportSettings};
+use crate::dtos::diagram_walkers::{
+    Column, CompoundUniqueKey, Index, Note, Relationship, Table, WalkerGroup,
+};
+use crate::dtos::dictionary::Word;
+use crate::dtos::page_settings::PageSettings;
+use crate::dtos::vdiagrams::{VDiagram, VTable};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeConflict {
+    /// Location of the conflict, e.g. `table.MEMBERS.column.EMAIL`.
+    pub path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeResult {
+    /// The merged diagram. Conflicting parts keep the version from `ours`.
+    pub diagram: Diagram,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl MergeResult {
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+}
+
+fn conflict(conflicts: &mut Vec<MergeConflict>, path: &str, message: &str) {
+    conflicts.push(MergeConflict {
+        path: path.to_string(),
+        message: message.to_string(),
+    });
+}
+
+/// Merges a single value. When both sides changed it differently, `ours` wins and a
+/// conflict is recorded.
+fn merge_value<T: Clone + PartialEq + Debug>(
+    path: &str,
+    base: &T,
+    ours: &T,
+    theirs: &T,
+    conflicts: &mut Vec<MergeConflict>,
+) -> T {
+    if ours == theirs || theirs == base {
+        ours.clone()
+    } else if ours == base {
+        theirs.clone()
+    } else {
+        conflict(
+            conflicts,
+            path,
+            &format!("changed on both sides: ours {ours:?}, theirs {theirs:?}"),
+        );
+        ours.clone()
+    }
+}
+
+/// Merges the listed fields of `$base`, `$ours` and `$theirs` into `$merged` with
+/// [`merge_value`], at `$path.<field>`.
+macro_rules! merge_fields {
+    ($path:expr, $base:expr, $ours:expr, $theirs:expr, $conflicts:expr, $merged:expr; $($field:ident),+ $(,)?) => {
+        $(
+            $merged.$field = merge_value(
+                &format!("{}.{}", $path, stringify!($field)),
+                &$base.$field,
+                &$ours.$field,
+                &$theirs.$field,
+                $conflicts,
+            );
+        )+
+    };
+}
+
+/// Merges a value that may be missing: field by field with `merge_present` when all
+/// sides have it, as a whole with [`merge_value`] otherwise.
+fn merge_option<T: Clone + PartialEq + Debug>(
+    path: &str,
+    base: &Option<T>,
+    ours: &Option<T>,
+    theirs: &Option<T>,
+    merge_present: impl Fn(&str, &T, &T, &T, &mut Vec<MergeConflict>) -> T,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Option<T> {
+    match (base, ours, theirs) {
+        (Some(base), Some(ours), Some(theirs)) => {
+            Some(merge_present(path, base, ours, theirs, conflicts))
+        }
+        _ => merge_value(path, base, ours, theirs, conflicts),
+    }
+}
+
+/// Merges two lists of named items against their common base. Items keep the order of
+/// `ours`, and items only `theirs` has are inserted after their preceding neighbour in
+/// `theirs`. `merge_modified` is called when an item exists on all three sides and was
+/// changed on both.
+fn merge_keyed<T: Clone + PartialEq>(
+    path: &str,
+    base: &[T],
+    ours: &[T],
+    theirs: &[T],
+    key: impl Fn(&T) -> String,
+    merge_modified: impl Fn(&str, &T, &T, &T, &mut Vec<MergeConflict>) -> T,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Vec<T> {
+    let find = |items: &'_ [T], name: &str| -> Option<T> {
+        items.iter().find(|item| key(item) == name).cloned()
+    };
+
+    let mut names: Vec<String> = ours.iter().map(&key).collect();
+    let mut anchor = None;
+    for name in theirs.iter().map(&key) {
+        match names.iter().position(|existing| *existing == name) {
+            Some(position) => anchor = Some(position),
+            None => {
+                let position = anchor.map_or(0, |anchor| anchor + 1);
+                names.insert(position, name);
+                anchor = Some(position);
+            }
+        }
+    }
+
+    let mut merged = Vec::new();
+    for name in names {
+        let item_path = format!("{path}.{name}");
+        let base_item = find(base, &name);
+        let ours_item = find(ours, &name);
+        let theirs_item = find(theirs, &name);
+        let result = match (&base_item, &ours_item, &theirs_item) {
+            (_, ours_item, theirs_item) if ours_item == theirs_item => ours_item.clone(),
+            (base_item, ours_item, theirs_item) if ours_item == base_item => theirs_item.clone(),
+            (base_item, ours_item, theirs_item) if theirs_item == base_item => ours_item.clone(),
+            (Some(base_item), Some(ours_item), Some(theirs_item)) => Some(merge_modified(
+                &item_path,
+                base_item,
+                ours_item,
+                theirs_item,
+                conflicts,
+            )),
+            (Some(_), Some(ours_item), None) => {
+                conflict(conflicts, &item_path, "modified in ours, deleted in theirs");
+                Some(ours_item.clone())
+            }
+            (Some(_), None, Some(_)) => {
+                conflict(conflicts, &item_path, "deleted in ours, modified in theirs");
+                None
+            }
+            (None, ours_item, _) => {
+                conflict(conflicts, &item_path, "added differently on both sides");
+                ours_item.clone()
+            }
+            (Some(_), None, None) => None,
+        };
+        merged.extend(result);
+    }
+    merged
+}
+
+fn atomic<T: Clone>(
+    path: &str,
+    _base: &T,
+    ours: &T,
+    _theirs: &T,
+    conflicts: &mut Vec<MergeConflict>,
+) -> T {
+    conflict(conflicts, path, "modified differently on both sides");
+    ours.clone()
+}
+
+fn column_key(column: &Column) -> String {
+    match column {
+        Column::Normal(column) => column.physical_name.clone(),
+        Column::Group(name) => format!("column_group.{name}"),
+    }
+}
+
+fn merge_table(
+    path: &str,
+    base: &Table,
+    ours: &Table,
+    theirs: &Table,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Table {
+    let relationships = |table: &Table| table.connections.relationships.clone().unwrap_or_default();
+    let columns = |table: &Table| table.columns.items.clone().unwrap_or_default();
+
+    let merged_relationships = merge_keyed(
+        &format!("{path}.relationship"),
+        &relationships(base),
+        &relationships(ours),
+        &relationships(theirs),
+        |relationship: &Relationship| relationship.name.clone(),
+        atomic,
+        conflicts,
+    );
+    let merged_columns = merge_keyed(
+        &format!("{path}.column"),
+        &columns(base),
+        &columns(ours),
+        &columns(theirs),
+        column_key,
+        atomic,
+        conflicts,
+    );
+
+    let merged_indexes = merge_keyed(
+        &format!("{path}.index"),
+        base.index_list(),
+        ours.index_list(),
+        theirs.index_list(),
+        |index: &Index| index.name.clone(),
+        atomic,
+        conflicts,
+    );
+    let merged_unique_keys = merge_keyed(
+        &format!("{path}.compound_unique_key"),
+        base.compound_unique_keys(),
+        ours.compound_unique_keys(),
+        theirs.compound_unique_keys(),
+        |key: &CompoundUniqueKey| key.name.clone(),
+        atomic,
+        conflicts,
+    );
+
+    let mut table = ours.clone();
+    merge_fields!(path, base, ours, theirs, conflicts, table;
+        logical_name, description, height, width, font_name, font_size, x, y, color,
+        table_constraint, primary_key_name, option);
+    table.connections.relationships =
+        (!merged_relationships.is_empty()).then_some(merged_relationships);
+    table.columns.items = (!merged_columns.is_empty()).then_some(merged_columns);
+    table.indexes.indexes = (!merged_indexes.is_empty()).then_some(merged_indexes);
+    table.compound_unique_key_list.compound_unique_keys =
+        (!merged_unique_keys.is_empty()).then_some(merged_unique_keys);
+    table
+}
+
+fn merge_column_group(
+    path: &str,
+    base: &ColumnGroup,
+    ours: &ColumnGroup,
+    theirs: &ColumnGroup,
+    conflicts: &mut Vec<MergeConflict>,
+) -> ColumnGroup {
+    let columns = |group: &ColumnGroup| group.columns.normal_columns.clone().unwrap_or_default();
+    let merged_columns = merge_keyed(
+        &format!("{path}.column"),
+        &columns(base),
+        &columns(ours),
+        &columns(theirs),
+        |column: &column_groups::NormalColumn| column.physical_name.clone(),
+        atomic,
+        conflicts,
+    );
+
+    let mut group = ours.clone();
+    group.columns.normal_columns = (!merged_columns.is_empty()).then_some(merged_columns);
+    group
+}
+
+/// Notes have no name, so they are keyed by their text, numbered from the second
+/// note of the same text on.
+fn keyed_notes(notes: &[Note]) -> Vec<(String, Note)> {
+    let mut keyed: Vec<(String, Note)> = Vec::new();
+    for note in notes {
+        let count = keyed
+            .iter()
+            .filter(|(_, other)| other.note_text == note.note_text)
+            .count();
+        let key = match count {
+            0 => note.note_text.clone(),
+            _ => format!("{}#{}", note.note_text, count + 1),
+        };
+        keyed.push((key, note.clone()));
+    }
+    keyed
+}
+
+fn merge_notes(
+    path: &str,
+    base: &[Note],
+    ours: &[Note],
+    theirs: &[Note],
+    conflicts: &mut Vec<MergeConflict>,
+) -> Vec<Note> {
+    merge_keyed(
+        path,
+        &keyed_notes(base),
+        &keyed_notes(ours),
+        &keyed_notes(theirs),
+        |(key, _)| key.clone(),
+        |path, (_, base), (key, ours), (_, theirs), conflicts| {
+            let mut note = ours.clone();
+            merge_fields!(path, base, ours, theirs, conflicts, note;
+                height, width, font_name, font_size, x, y, color);
+            (key.clone(), note)
+        },
+        conflicts,
+    )
+    .into_iter()
+    .map(|(_, note)| note)
+    .collect()
+}
+
+/// Merges lists of ids or names, where an item is either there or not.
+fn merge_names(
+    path: &str,
+    base: &[String],
+    ours: &[String],
+    theirs: &[String],
+    conflicts: &mut Vec<MergeConflict>,
+) -> Vec<String> {
+    merge_keyed(path, base, ours, theirs, String::clone, atomic, conflicts)
+}
+
+fn merge_walker_groups(
+    path: &str,
+    base: &[WalkerGroup],
+    ours: &[WalkerGroup],
+    theirs: &[WalkerGroup],
+    conflicts: &mut Vec<MergeConflict>,
+) -> Vec<WalkerGroup> {
+    merge_keyed(
+        path,
+        base,
+        ours,
+        theirs,
+        |group: &WalkerGroup| group.group_name.clone(),
+        |path, base, ours, theirs, conflicts| {
+            let mut group = ours.clone();
+            merge_fields!(path, base, ours, theirs, conflicts, group;
+                height, width, font_name, font_size, x, y, color);
+            group.walkers = merge_names(
+                &format!("{path}.walker"),
+                &base.walkers,
+                &ours.walkers,
+                &theirs.walkers,
+                conflicts,
+            );
+            group
+        },
+        conflicts,
+    )
+}
+
+fn merge_vdiagram(
+    path: &str,
+    base: &VDiagram,
+    ours: &VDiagram,
+    theirs: &VDiagram,
+    conflicts: &mut Vec<MergeConflict>,
+) -> VDiagram {
+    let mut vdiagram = ours.clone();
+    vdiagram.tables = merge_keyed(
+        &format!("{path}.table"),
+        &base.tables,
+        &ours.tables,
+        &theirs.tables,
+        |table: &VTable| table.table_id.clone(),
+        |path, base, ours, theirs, conflicts| {
+            let mut table = ours.clone();
+            merge_fields!(path, base, ours, theirs, conflicts, table;
+                x, y, font_name, font_size);
+            table
+        },
+        conflicts,
+    );
+    vdiagram.notes = merge_notes(
+        &format!("{path}.note"),
+        &base.notes,
+        &ours.notes,
+        &theirs.notes,
+        conflicts,
+    );
+    vdiagram.groups = merge_walker_groups(
+        &format!("{path}.group"),
+        &base.groups,
+        &ours.groups,
+        &theirs.groups,
+        conflicts,
+    );
+    vdiagram
+}
+
+fn merge_category_settings(
+    path: &str,
+    base: &CategorySettings,
+    ours: &CategorySettings,
+    theirs: &CategorySettings,
+    conflicts: &mut Vec<MergeConflict>,
+) -> CategorySettings {
+    let categories = merge_keyed(
+        &format!("{path}.category"),
+        &base.categories,
+        &ours.categories,
+        &theirs.categories,
+        |category: &Category| category.name.clone(),
+        |path, base, ours, theirs, conflicts| {
+            let mut category = ours.clone();
+            merge_fields!(path, base, ours, theirs, conflicts, category; selected);
+            category.node_elements = merge_names(
+                &format!("{path}.node_element"),
+                &base.node_elements,
+                &ours.node_elements,
+                &theirs.node_elements,
+                conflicts,
+            );
+            category
+        },
+        conflicts,
+    );
+    CategorySettings { categories }
+}
+
+fn merge_diagram_settings(
+    path: &str,
+    base: &DiagramSettings,
+    ours: &DiagramSettings,
+    theirs: &DiagramSettings,
+    conflicts: &mut Vec<MergeConflict>,
+) -> DiagramSettings {
+    let mut settings = ours.clone();
+    merge_fields!(path, base, ours, theirs, conflicts, settings;
+        database, notation, validate_physical_name, suspend_validator);
+    settings.export_settings = merge_option(
+        &format!("{path}.export_settings"),
+        &base.export_settings,
+        &ours.export_settings,
+        &theirs.export_settings,
+        |path, base, ours, theirs, conflicts| {
+            let mut export: ExportSettings = ours.clone();
+            merge_fields!(path, base, ours, theirs, conflicts, export;
+                category_name_to_export, ddl_output, excel_output, image_output,
+                put_diagram_on_excel, use_logical_name_as_sheet);
+            export
+        },
+        conflicts,
+    );
+    settings.category_settings = merge_option(
+        &format!("{path}.category_settings"),
+        &base.category_settings,
+        &ours.category_settings,
+        &theirs.category_settings,
+        merge_category_settings,
+        conflicts,
+    );
+    settings
+}
+
+/// Merges `ours` and `theirs` against their common ancestor `base`.
+///
+/// Changes made on only one side are taken as is, so independent edits such as moving
+/// a table in one branch and adding a column to it in the other merge cleanly. Settings,
+/// tables, notes, groups, dictionary words and the items of virtual diagrams are merged
+/// field by field, while columns, relationships and indexes are merged as a whole.
+pub fn merge(base: &Diagram, ours: &Diagram, theirs: &Diagram) -> MergeResult {
+    let mut conflicts = Vec::new();
+
+    let mut diagram = ours.clone();
+    diagram.page_settings = merge_option(
+        "page_settings",
+        &base.page_settings,
+        &ours.page_settings,
+        &theirs.page_settings,
+        |path, base, ours, theirs, conflicts| {
+            let mut page: PageSettings = ours.clone();
+            merge_fields!(path, base, ours, theirs, conflicts, page;
+                direction_horizontal, scale, paper_size, top_margin, left_margin,
+                bottom_margin, right_margin);
+            page
+        },
+        &mut conflicts,
+    );
+    diagram.diagram_settings = merge_diagram_settings(
+        "diagram_settings",
+        &base.diagram_settings,
+        &ours.diagram_settings,
+        &theirs.diagram_settings,
+        &mut conflicts,
+    );
+    diagram.dictionary.words = merge_keyed(
+        "word",
+        &base.dictionary.words,
+        &ours.dictionary.words,
+        &theirs.dictionary.words,
+        |word: &Word| word.physical_name.clone(),
+        |path, base, ours, theirs, conflicts| {
+            let mut word = ours.clone();
+            merge_fields!(path, base, ours, theirs, conflicts, word;
+                logical_name, description);
+            word
+        },
+        &mut conflicts,
+    );
+
+    let tables = |diagram: &Diagram| diagram.diagram_walkers.tables.clone().unwrap_or_default();
+    let merged_tables = merge_keyed(
+        "table",
+        &tables(base),
+        &tables(ours),
+        &tables(theirs),
+        |table: &Table| table.physical_name.clone(),
+        merge_table,
+        &mut conflicts,
+    );
+    diagram.diagram_walkers.tables = (!merged_tables.is_empty()).then_some(merged_tables);
+
+    let merged_notes = merge_notes(
+        "note",
+        base.notes(),
+        ours.notes(),
+        theirs.notes(),
+        &mut conflicts,
+    );
+    diagram.diagram_walkers.notes = (!merged_notes.is_empty()).then_some(merged_notes);
+    let merged_walker_groups = merge_walker_groups(
+        "group",
+        base.walker_groups(),
+        ours.walker_groups(),
+        theirs.walker_groups(),
+        &mut conflicts,
+    );
+    diagram.diagram_walkers.groups =
+        (!merged_walker_groups.is_empty()).then_some(merged_walker_groups);
+
+    diagram.vdiagrams = merge_keyed(
+        "vdiagram",
+        &base.vdiagrams,
+        &ours.vdiagrams,
+        &theirs.vdiagrams,
+        |vdiagram: &VDiagram| vdiagram.name.clone(),
+        merge_vdiagram,
+        &mut conflicts,
+    );
+
+    let groups = |diagram: &Diagram| {
+        diagram
+            .column_groups
+            .column_groups
+            .clone()
+            .unwrap_or_default()
+    };
+    let merged_groups = merge_keyed(
+        "column_group",
+        &groups(base),
+        &groups(ours),
+        &groups(theirs),
+        |group: &ColumnGroup| group.column_group_name.clone(),
+        merge_column_group,
+        &mut conflicts,
+    );
+    diagram.column_groups.column_groups = (!merged_groups.is_empty()).then_some(merged_groups);
+
+    MergeResult { diagram, conflicts }
+}
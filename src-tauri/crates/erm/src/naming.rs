@@ -0,0 +1,509 @@
+//! Naming conventions of a project, declared in a TOML file next to the diagram.
+//!
+//! ```toml
+//! severity = "error"
+//!
+//! [tables]
+//! case = "upper_snake"
+//! plural = true
+//!
+//! [columns]
+//! case = "upper_snake"
+//!
+//! [primary_keys]
+//! pattern = "{singular}_ID"
+//!
+//! [foreign_keys]
+//! match_referenced = true
+//!
+//! [relationships]
+//! prefix = "FK_"
+//!
+//! [indexes]
+//! prefix = "IX_"
+//! unique_prefix = "UX_"
+//! ```
+//!
+//! Every rule is optional. Violations are reported as [`Diagnostic`]s whose suggestion
+//! is the name that satisfies all the rules of the named object at once.
+
+use crate::dtos::diagram::Diagram;
+use crate::dtos::diagram_walkers::{Column, Table};
+use crate::validation::{Diagnostic, Location, Rule, Severity};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+/// The name of the config file, looked up in the directory of the diagram.
+pub const CONFIG_FILE_NAME: &str = "erm-naming.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Case {
+    /// `MEMBER_PROFILES`
+    UpperSnake,
+    /// `member_profiles`
+    LowerSnake,
+    /// `MemberProfiles`
+    Pascal,
+    /// `memberProfiles`
+    Camel,
+}
+
+impl fmt::Display for Case {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Case::UpperSnake => "UPPER_SNAKE",
+            Case::LowerSnake => "lower_snake",
+            Case::Pascal => "PascalCase",
+            Case::Camel => "camelCase",
+        })
+    }
+}
+
+/// Rules on the names of tables, columns or relationships.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NameRules {
+    pub case: Option<Case>,
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+
+    /// Whether the last word of the name must be plural, e.g. `MEMBER_PROFILES`.
+    pub plural: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PrimaryKeyRules {
+    /// The name of single-column primary keys. `{table}` stands for the table name
+    /// and `{singular}` for the table name with its last word made singular.
+    pub pattern: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ForeignKeyRules {
+    /// Whether foreign key columns must be named after the column they refer to. A
+    /// role may precede the name, as in `PARENT_MEMBER_ID` referring to `MEMBER_ID`.
+    pub match_referenced: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IndexRules {
+    pub case: Option<Case>,
+    pub prefix: Option<String>,
+
+    /// The prefix of unique indexes, if it differs from `prefix`.
+    pub unique_prefix: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NamingConfig {
+    /// The severity of every violation. Defaults to warning.
+    pub severity: Option<Severity>,
+    pub tables: NameRules,
+    pub columns: NameRules,
+    pub primary_keys: PrimaryKeyRules,
+    pub foreign_keys: ForeignKeyRules,
+    pub relationships: NameRules,
+    pub indexes: IndexRules,
+}
+
+impl NamingConfig {
+    pub fn parse(text: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(toml::from_str(text)?)
+    }
+
+    pub fn load(filename: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::parse(&std::fs::read_to_string(filename)?)
+    }
+
+    /// Loads the config in the directory of the diagram file, if there is one.
+    pub fn find(diagram_filename: &str) -> Result<Option<Self>, Box<dyn std::error::Error>> {
+        let path = Path::new(diagram_filename).with_file_name(CONFIG_FILE_NAME);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let text = std::fs::read_to_string(&path)?;
+        let config = Self::parse(&text).map_err(|e| format!("{}: {e}", path.to_string_lossy()))?;
+        Ok(Some(config))
+    }
+}
+
+/// Splits a name into words at separators and at lowercase-to-uppercase changes, so
+/// `MEMBER_ID`, `member-id` and `memberId` all give `member` and `id`. Each word comes
+/// with its byte offset in `name`.
+fn word_spans(name: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut previous: Option<char> = None;
+    for (i, c) in name.char_indices() {
+        if c == '_' || c == '-' || c.is_whitespace() {
+            if start < i {
+                words.push((start, &name[start..i]));
+            }
+            start = i + c.len_utf8();
+            previous = None;
+            continue;
+        }
+        if c.is_uppercase() && previous.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit()) {
+            words.push((start, &name[start..i]));
+            start = i;
+        }
+        previous = Some(c);
+    }
+    if start < name.len() {
+        words.push((start, &name[start..]));
+    }
+    words
+}
+
+fn words(name: &str) -> Vec<&str> {
+    word_spans(name).into_iter().map(|(_, word)| word).collect()
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+fn apply_case(name: &str, case: Case) -> String {
+    let words = words(name);
+    match case {
+        Case::UpperSnake => words
+            .iter()
+            .map(|word| word.to_uppercase())
+            .collect::<Vec<_>>()
+            .join("_"),
+        Case::LowerSnake => words
+            .iter()
+            .map(|word| word.to_lowercase())
+            .collect::<Vec<_>>()
+            .join("_"),
+        Case::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+        Case::Camel => words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| {
+                if i == 0 {
+                    word.to_lowercase()
+                } else {
+                    capitalize(word)
+                }
+            })
+            .collect(),
+    }
+}
+
+const IRREGULAR: &[(&str, &str)] = &[
+    ("person", "people"),
+    ("child", "children"),
+    ("man", "men"),
+    ("woman", "women"),
+    ("datum", "data"),
+    ("medium", "media"),
+];
+
+/// Words whose plural is the same as the singular.
+const UNCOUNTABLE: &[&str] = &[
+    "data",
+    "equipment",
+    "information",
+    "news",
+    "series",
+    "species",
+    "staff",
+];
+
+/// Converts `word` to the case of `original`.
+fn match_case(original: &str, word: &str) -> String {
+    if !original.chars().any(char::is_lowercase) {
+        word.to_uppercase()
+    } else if original.starts_with(char::is_uppercase) {
+        capitalize(word)
+    } else {
+        word.to_string()
+    }
+}
+
+fn pluralize(word: &str) -> String {
+    let lower = word.to_lowercase();
+    if UNCOUNTABLE.contains(&lower.as_str()) || IRREGULAR.iter().any(|(_, plural)| *plural == lower)
+    {
+        return word.to_string();
+    }
+    let plural =
+        if let Some((_, plural)) = IRREGULAR.iter().find(|(singular, _)| *singular == lower) {
+            plural.to_string()
+        } else if let Some(stem) = lower.strip_suffix('y')
+            && !stem.ends_with(['a', 'e', 'i', 'o', 'u'])
+        {
+            format!("{stem}ies")
+        } else if ["s", "x", "z", "ch", "sh"]
+            .iter()
+            .any(|ending| lower.ends_with(ending))
+        {
+            format!("{lower}es")
+        } else {
+            format!("{lower}s")
+        };
+    match_case(word, &plural)
+}
+
+fn singularize(word: &str) -> String {
+    let lower = word.to_lowercase();
+    if UNCOUNTABLE.contains(&lower.as_str()) {
+        return word.to_string();
+    }
+    let singular =
+        if let Some((singular, _)) = IRREGULAR.iter().find(|(_, plural)| *plural == lower) {
+            singular.to_string()
+        } else if let Some(stem) = lower.strip_suffix("ies") {
+            format!("{stem}y")
+        } else if ["sses", "uses", "xes", "zes", "ches", "shes"]
+            .iter()
+            .any(|ending| lower.ends_with(ending))
+        {
+            lower[..lower.len() - 2].to_string()
+        } else if let Some(stem) = lower.strip_suffix('s')
+            && !stem.ends_with('s')
+        {
+            stem.to_string()
+        } else {
+            lower
+        };
+    match_case(word, &singular)
+}
+
+fn is_plural(word: &str) -> bool {
+    pluralize(&singularize(word)) == word
+}
+
+/// Replaces the last word of `name` with `f` of it, keeping the separators.
+fn map_last_word(name: &str, f: impl Fn(&str) -> String) -> String {
+    match word_spans(name).last() {
+        Some(&(start, last)) => format!(
+            "{}{}{}",
+            &name[..start],
+            f(last),
+            &name[start + last.len()..]
+        ),
+        None => name.to_string(),
+    }
+}
+
+struct Checker<'a> {
+    config: &'a NamingConfig,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Checker<'_> {
+    fn report(&mut self, rule: Rule, location: &Location, message: String, suggestion: &str) {
+        self.diagnostics.push(Diagnostic {
+            rule,
+            severity: self.config.severity.unwrap_or(rule.severity()),
+            location: location.clone(),
+            message,
+            suggestion: Some(suggestion.to_string()),
+        });
+    }
+
+    /// Checks `name` against `rules`. `other_prefixes` are prefixes of related rules
+    /// that the suggested name should not keep, such as the prefix of unique indexes.
+    fn check_name(
+        &mut self,
+        rules: &NameRules,
+        name: &str,
+        location: Location,
+        other_prefixes: &[&str],
+    ) {
+        let mut body = name;
+        for prefix in rules
+            .prefix
+            .iter()
+            .map(String::as_str)
+            .chain(other_prefixes.iter().copied())
+        {
+            if let Some(stripped) = body.strip_prefix(prefix) {
+                body = stripped;
+                break;
+            }
+        }
+        if let Some(suffix) = &rules.suffix {
+            body = body.strip_suffix(suffix.as_str()).unwrap_or(body);
+        }
+
+        let mut fixed = body.to_string();
+        if rules.plural {
+            fixed = map_last_word(&fixed, pluralize);
+        }
+        if let Some(case) = rules.case {
+            fixed = apply_case(&fixed, case);
+        }
+        let suggestion = format!(
+            "{}{fixed}{}",
+            rules.prefix.as_deref().unwrap_or(""),
+            rules.suffix.as_deref().unwrap_or("")
+        );
+
+        if let Some(case) = rules.case
+            && apply_case(body, case) != body
+        {
+            self.report(
+                Rule::NameCase,
+                &location,
+                format!("{name} is not {case}"),
+                &suggestion,
+            );
+        }
+        if let Some(prefix) = &rules.prefix
+            && !name.starts_with(prefix.as_str())
+        {
+            self.report(
+                Rule::NamePrefix,
+                &location,
+                format!("{name} does not start with {prefix}"),
+                &suggestion,
+            );
+        }
+        if let Some(suffix) = &rules.suffix
+            && !name.ends_with(suffix.as_str())
+        {
+            self.report(
+                Rule::NameSuffix,
+                &location,
+                format!("{name} does not end with {suffix}"),
+                &suggestion,
+            );
+        }
+        if rules.plural && words(body).last().is_some_and(|last| !is_plural(last)) {
+            self.report(
+                Rule::PluralName,
+                &location,
+                format!("{name} is not plural"),
+                &suggestion,
+            );
+        }
+    }
+
+    fn check_primary_key(&mut self, diagram: &Diagram, table: &Table) {
+        let Some(pattern) = &self.config.primary_keys.pattern else {
+            return;
+        };
+        let columns = diagram.resolve_columns(table);
+        let primary_keys: Vec<_> = columns
+            .iter()
+            .filter(|column| column.is_primary_key())
+            .collect();
+        // Composite keys and keys that are also foreign keys are named otherwise.
+        let [primary_key] = primary_keys[..] else {
+            return;
+        };
+        if primary_key.is_foreign_key() {
+            return;
+        }
+        let name = &primary_key.column.physical_name;
+        let expected = pattern.replace("{table}", &table.physical_name).replace(
+            "{singular}",
+            &map_last_word(&table.physical_name, singularize),
+        );
+        if *name != expected {
+            self.report(
+                Rule::PrimaryKeyName,
+                &Location::column(&table.physical_name, name),
+                format!("primary key {name} is not named {expected}"),
+                &expected,
+            );
+        }
+    }
+
+    fn check_table(&mut self, diagram: &Diagram, table: &Table) {
+        let config = self.config;
+        let name = &table.physical_name;
+        self.check_name(&config.tables, name, Location::table(name), &[]);
+
+        for item in table.column_items() {
+            let Column::Normal(column) = item else {
+                continue;
+            };
+            let column_name = &column.physical_name;
+            let location = Location::column(name, column_name);
+            self.check_name(&config.columns, column_name, location.clone(), &[]);
+            if config.foreign_keys.match_referenced
+                && let Some((_, referred)) = column.referred_table_and_column()
+                && column_name != referred
+                && !column_name.ends_with(&format!("_{referred}"))
+            {
+                self.report(
+                    Rule::ForeignKeyName,
+                    &location,
+                    format!("{column_name} is not named after the referenced column {referred}"),
+                    referred,
+                );
+            }
+        }
+        self.check_primary_key(diagram, table);
+
+        for relationship in table.relationships() {
+            self.check_name(
+                &config.relationships,
+                &relationship.name,
+                Location::relationship(name, &relationship.name),
+                &[],
+            );
+        }
+
+        for index in table.index_list() {
+            let rules = &config.indexes;
+            let unique_prefix = rules.unique_prefix.as_ref().or(rules.prefix.as_ref());
+            let (prefix, other) = if index.is_unique() {
+                (unique_prefix, rules.prefix.as_ref())
+            } else {
+                (rules.prefix.as_ref(), unique_prefix)
+            };
+            let name_rules = NameRules {
+                case: rules.case,
+                prefix: prefix.cloned(),
+                ..Default::default()
+            };
+            let other: Vec<&str> = other.map(String::as_str).into_iter().collect();
+            self.check_name(
+                &name_rules,
+                &index.name,
+                Location::index(name, &index.name),
+                &other,
+            );
+        }
+    }
+}
+
+/// Checks the names in the diagram against the conventions of `config`.
+pub fn check(diagram: &Diagram, config: &NamingConfig) -> Vec<Diagnostic> {
+    let mut checker = Checker {
+        config,
+        diagnostics: Vec::new(),
+    };
+    for table in diagram.tables() {
+        checker.check_table(diagram, table);
+    }
+    for group in diagram.groups() {
+        for column in group.columns.normal_columns.iter().flatten() {
+            checker.check_name(
+                &config.columns,
+                &column.physical_name,
+                Location::group_column(&group.column_group_name, &column.physical_name),
+                &[],
+            );
+        }
+    }
+    checker.diagnostics
+}
@@ -0,0 +1,1047 @@
+//! Synthetic rows for seeding a database with the schema of a diagram, as INSERT
+//! statements or as a CSV file per table.
+//!
+//! Tables are generated parents first, following the relationships, so that foreign
+//! keys refer to generated rows. Values follow the types, lengths, scales and `unsigned`
+//! of the columns, primary keys and unique keys stay unique, and nullable columns are
+//! sometimes null. Columns named like a kind of value, e.g. 名前 or `EMAIL`, get values
+//! of that kind, in Japanese when their logical name is. The same seed always gives the
+//! same rows, and each table has its own generator so that adding a table does not
+//! change the rows of the others.
+
+use crate::column_type::{ColumnType, enum_values};
+use crate::dtos::diagram::{Diagram, ResolvedColumn};
+use crate::dtos::diagram_walkers::{Relationship, Table};
+use crate::exporters::ddl::{quote_identifier, quote_string};
+use crate::importers::ddl::Dialect;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::Path;
+
+pub const DEFAULT_ROWS: usize = 10;
+
+// Attempts at a row before giving up on the table, when its unique keys keep clashing.
+const MAX_ATTEMPTS: usize = 100;
+
+/// Options of [`generate`].
+#[derive(Debug, Clone)]
+pub struct SeedOptions {
+    /// Rows per table. Tables whose unique keys run out of values get fewer, e.g. the
+    /// child of a one-to-one relationship with fewer parents.
+    pub rows: usize,
+
+    pub seed: u64,
+}
+
+impl Default for SeedOptions {
+    fn default() -> Self {
+        Self {
+            rows: DEFAULT_ROWS,
+            seed: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SeedValue {
+    Null,
+    /// A number as it is written in SQL, e.g. `12.50`.
+    Number(String),
+    /// Text, including dates and times, e.g. `2024-01-31 09:00:00`.
+    Text(String),
+    Boolean(bool),
+    Bytes(Vec<u8>),
+}
+
+/// The generated rows of a table, with a value per column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeedTable {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<SeedValue>>,
+}
+
+impl SeedTable {
+    pub fn value(&self, row: usize, column: &str) -> Option<&SeedValue> {
+        let index = self.columns.iter().position(|name| name == column)?;
+        self.rows.get(row)?.get(index)
+    }
+}
+
+/// SplitMix64: small, and unlike the hashers of `std`, stable across platforms and
+/// releases.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64, table: &str) -> Self {
+        // FNV-1a of the table name.
+        let hash = table.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+        Self(seed ^ hash)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `min` to `max`, both included.
+    fn range(&mut self, min: i128, max: i128) -> i128 {
+        if max <= min {
+            return min;
+        }
+        let width = (max - min) as u128 + 1;
+        min + (u128::from(self.next()) % width) as i128
+    }
+
+    fn below(&mut self, count: usize) -> usize {
+        self.range(0, count.saturating_sub(1) as i128) as usize
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+
+    fn one_in(&mut self, count: u64) -> bool {
+        self.next().is_multiple_of(count)
+    }
+}
+
+/// The kind of value a column holds, guessed from its names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Hint {
+    LastName,
+    FirstName,
+    PersonName,
+    Email,
+    Phone,
+    PostalCode,
+    Address,
+    Url,
+    Company,
+    Title,
+    Gender,
+    Password,
+    Code,
+    Money,
+    Age,
+    Count,
+    BirthDate,
+    Sentence,
+}
+
+// In order of precedence, e.g. `FIRST_NAME` "名前" is a first name. ASCII words match
+// whole words of the names, other words any part of them.
+const HINTS: &[(Hint, &[&str])] = &[
+    (
+        Hint::LastName,
+        &[
+            "苗字",
+            "名字",
+            "姓",
+            "last_name",
+            "lastname",
+            "surname",
+            "family_name",
+        ],
+    ),
+    (Hint::FirstName, &["first_name", "firstname", "given_name"]),
+    (
+        Hint::PersonName,
+        &["氏名", "名前", "人名", "full_name", "person_name"],
+    ),
+    (Hint::Email, &["メール", "email", "mail", "e_mail"]),
+    (Hint::Phone, &["電話", "phone", "tel", "telephone"]),
+    (
+        Hint::PostalCode,
+        &["郵便", "zip", "zip_code", "postal_code"],
+    ),
+    (Hint::Address, &["住所", "address"]),
+    (Hint::Url, &["url", "uri", "link"]),
+    (Hint::Company, &["会社", "企業", "company"]),
+    (
+        Hint::Title,
+        &["タイトル", "題名", "件名", "title", "subject"],
+    ),
+    (Hint::Gender, &["性別", "gender", "sex"]),
+    (Hint::Password, &["パスワード", "password"]),
+    (Hint::Code, &["コード", "code"]),
+    (
+        Hint::Money,
+        &["価格", "金額", "料金", "price", "amount", "cost"],
+    ),
+    (Hint::Age, &["年齢", "age"]),
+    (Hint::Count, &["数", "count", "quantity", "qty"]),
+    (
+        Hint::BirthDate,
+        &["生年月日", "誕生", "birthday", "birth_date"],
+    ),
+    (
+        Hint::Sentence,
+        &[
+            "本文",
+            "内容",
+            "説明",
+            "紹介",
+            "備考",
+            "コメント",
+            "text",
+            "body",
+            "description",
+            "comment",
+            "note",
+        ],
+    ),
+];
+
+impl Hint {
+    fn of(column: &ResolvedColumn) -> Option<Self> {
+        let names: Vec<String> = [
+            Some(column.column.physical_name.as_str()),
+            column.column.logical_name.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|name| format!("_{}_", name.to_lowercase().replace([' ', '-'], "_")))
+        .collect();
+        HINTS.iter().find_map(|(hint, words)| {
+            words
+                .iter()
+                .any(|word| {
+                    names.iter().any(|name| {
+                        if word.is_ascii() {
+                            name.contains(&format!("_{word}_"))
+                        } else {
+                            name.contains(word)
+                        }
+                    })
+                })
+                .then_some(*hint)
+        })
+    }
+}
+
+const LAST_NAMES_JA: &[&str] = &[
+    "佐藤", "鈴木", "高橋", "田中", "伊藤", "渡辺", "山本", "中村", "小林", "加藤",
+];
+const FIRST_NAMES_JA: &[&str] = &[
+    "太郎",
+    "花子",
+    "翔太",
+    "美咲",
+    "健一",
+    "陽菜",
+    "大輔",
+    "さくら",
+    "拓海",
+    "結衣",
+];
+const LAST_NAMES_EN: &[&str] = &[
+    "Smith", "Johnson", "Williams", "Brown", "Jones", "Garcia", "Miller", "Davis", "Wilson",
+    "Taylor",
+];
+const FIRST_NAMES_EN: &[&str] = &[
+    "James", "Mary", "John", "Patricia", "Robert", "Jennifer", "Michael", "Linda", "David", "Emma",
+];
+const CITIES_JA: &[&str] = &[
+    "東京都千代田区千代田",
+    "大阪府大阪市北区梅田",
+    "愛知県名古屋市中区栄",
+    "福岡県福岡市博多区博多駅前",
+    "北海道札幌市中央区北一条西",
+];
+const STREETS_EN: &[&str] = &[
+    "Main Street",
+    "Oak Avenue",
+    "Maple Road",
+    "Park Lane",
+    "Elm Street",
+];
+const COMPANIES_JA: &[&str] = &["サンプル", "テスト商事", "山田工業", "みらい", "さくら物産"];
+const COMPANIES_EN: &[&str] = &["Acme", "Globex", "Initech", "Umbrella", "Stark"];
+const TITLES_JA: &[&str] = &[
+    "はじめての投稿",
+    "今日の出来事",
+    "おすすめの本",
+    "週末の予定",
+    "質問があります",
+];
+const TITLES_EN: &[&str] = &[
+    "Hello world",
+    "Weekly update",
+    "A question",
+    "Release notes",
+    "Plans for the weekend",
+];
+const SENTENCES_JA: &[&str] = &[
+    "これはテスト用の文章です。",
+    "今日はとても良い天気でした。",
+    "よろしくお願いします。",
+    "詳しくは後ほどご連絡します。",
+    "新しい機能を試してみました。",
+];
+const SENTENCES_EN: &[&str] = &[
+    "Lorem ipsum dolor sit amet.",
+    "The quick brown fox jumps over the lazy dog.",
+    "Thanks for reading.",
+    "More details will follow.",
+    "This is sample text for testing.",
+];
+
+/// The smallest and largest values of an integer type.
+fn integer_range(column_type: ColumnType, unsigned: bool) -> (i128, i128) {
+    let bits = match column_type {
+        ColumnType::TinyInt | ColumnType::TinyIntN => 8,
+        ColumnType::SmallInt | ColumnType::SmallIntN => 16,
+        ColumnType::MediumInt | ColumnType::MediumIntN => 24,
+        ColumnType::BigInt | ColumnType::BigIntN => 64,
+        ColumnType::Year2 => return (0, 99),
+        ColumnType::Year4 => return (1901, 2155),
+        _ => 32,
+    };
+    if unsigned {
+        (0, (1 << bits) - 1)
+    } else {
+        (-(1 << (bits - 1)), (1 << (bits - 1)) - 1)
+    }
+}
+
+/// The date `days` after 1970-01-01, after Howard Hinnant's `civil_from_days`.
+fn date(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+// 2020-01-01 and 1950-01-01, in days since 1970-01-01.
+const RECENT_DAYS: i64 = 18_262;
+const BIRTH_DAYS: i64 = -7_305;
+
+fn time(rng: &mut Rng) -> String {
+    format!(
+        "{:02}:{:02}:{:02}",
+        rng.range(0, 23),
+        rng.range(0, 59),
+        rng.range(0, 59)
+    )
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut out, byte| {
+        let _ = write!(out, "{byte:02x}");
+        out
+    })
+}
+
+/// A column to generate, with what is known of its values.
+struct Plan {
+    column: ResolvedColumn,
+    hint: Option<Hint>,
+    japanese: bool,
+    /// The only column of a unique key, so it gets a different value on every row.
+    unique: bool,
+}
+
+impl Plan {
+    /// A value for the row, or None if the column has no value left for it.
+    fn value(&self, rng: &mut Rng, row: usize) -> Option<SeedValue> {
+        let column = &self.column.column;
+        if !self.column.is_not_null() && !self.unique && rng.one_in(10) {
+            return Some(SeedValue::Null);
+        }
+        let serial = row as i128 + 1;
+        let Some(known) = column.column_type.as_deref().and_then(ColumnType::parse) else {
+            if column
+                .column_type
+                .as_deref()
+                .is_some_and(|column_type| column_type.eq_ignore_ascii_case("uuid"))
+            {
+                let (high, low) = (rng.next(), rng.next());
+                return Some(SeedValue::Text(format!(
+                    "{:08x}-{:04x}-4{:03x}-a{:03x}-{:012x}",
+                    high >> 32,
+                    (high >> 16) & 0xffff,
+                    high & 0xfff,
+                    low >> 52,
+                    low & 0xffff_ffff_ffff
+                )));
+            }
+            return self.text(rng, row, None);
+        };
+        let config = known.config();
+        let length = column.length.filter(|_| config.supports_length);
+        let unsigned = config.supports_unsigned && column.unsigned.unwrap_or(false);
+        let value = match known {
+            ColumnType::Char
+            | ColumnType::CharN
+            | ColumnType::VarCharN
+            | ColumnType::Text
+            | ColumnType::TinyText
+            | ColumnType::MediumText
+            | ColumnType::LongText => {
+                // `char` without a length holds a single character.
+                let length = length.or((known == ColumnType::Char).then_some(1));
+                return self.text(rng, row, length.map(usize::from));
+            }
+            ColumnType::TinyInt
+            | ColumnType::TinyIntN
+            | ColumnType::SmallInt
+            | ColumnType::SmallIntN
+            | ColumnType::MediumInt
+            | ColumnType::MediumIntN
+            | ColumnType::Int
+            | ColumnType::IntN
+            | ColumnType::BigInt
+            | ColumnType::BigIntN
+            | ColumnType::Year2
+            | ColumnType::Year4 => {
+                let (min, max) = integer_range(known, unsigned);
+                let value = if self.unique {
+                    min.max(0) + serial
+                } else {
+                    let (low, high) = match self.hint {
+                        Some(Hint::Age) => (18, 80),
+                        Some(Hint::Money) => (100, 100_000),
+                        Some(Hint::Count) => (0, 1000),
+                        _ => (1, 1000),
+                    };
+                    rng.range(low.max(min), high.min(max))
+                };
+                if value > max {
+                    return None;
+                }
+                SeedValue::Number(value.to_string())
+            }
+            ColumnType::Decimal
+            | ColumnType::DecimalP
+            | ColumnType::DecimalPS
+            | ColumnType::Numeric
+            | ColumnType::NumericP
+            | ColumnType::NumericPS => {
+                let precision = i128::from(length.unwrap_or(10));
+                let scale = column.decimal.filter(|_| config.supports_decimal);
+                let scale = i128::from(scale.unwrap_or(0)).min(precision);
+                // At most 6 integer digits, which is plenty of seed data.
+                let max = 10_i128.pow((precision - scale).min(6) as u32) - 1;
+                let integer = if self.unique {
+                    serial
+                } else {
+                    match self.hint {
+                        Some(Hint::Money) => rng.range(100.min(max), 100_000.min(max)),
+                        _ => rng.range(0, max.min(1000)),
+                    }
+                };
+                if integer > max {
+                    return None;
+                }
+                let mut number = integer.to_string();
+                if scale > 0 {
+                    number.push('.');
+                    for _ in 0..scale {
+                        number.push(char::from(b'0' + rng.range(0, 9) as u8));
+                    }
+                }
+                SeedValue::Number(number)
+            }
+            ColumnType::Float
+            | ColumnType::FloatMD
+            | ColumnType::FloatP
+            | ColumnType::Double
+            | ColumnType::DoubleMD
+            | ColumnType::Real
+            | ColumnType::RealMD => {
+                let integer = if self.unique {
+                    serial
+                } else {
+                    rng.range(0, 1000)
+                };
+                SeedValue::Number(format!("{integer}.{:02}", rng.range(0, 99)))
+            }
+            ColumnType::Boolean => match (self.unique, row) {
+                (true, 0) => SeedValue::Boolean(false),
+                (true, 1) => SeedValue::Boolean(true),
+                (true, _) => return None,
+                (false, _) => SeedValue::Boolean(rng.one_in(2)),
+            },
+            ColumnType::Date => SeedValue::Text(date(self.day(rng, row))),
+            ColumnType::Datetime | ColumnType::Timestamp => {
+                SeedValue::Text(format!("{} {}", date(self.day(rng, row)), time(rng)))
+            }
+            ColumnType::Time => {
+                if self.unique {
+                    let seconds = row % 86_400;
+                    SeedValue::Text(format!(
+                        "{:02}:{:02}:{:02}",
+                        seconds / 3600,
+                        seconds / 60 % 60,
+                        seconds % 60
+                    ))
+                } else {
+                    SeedValue::Text(time(rng))
+                }
+            }
+            ColumnType::Enum | ColumnType::Set => {
+                let values = column.args.as_deref().map(enum_values).unwrap_or_default();
+                if values.is_empty() {
+                    return self.text(rng, row, None);
+                }
+                let value = if self.unique {
+                    values.get(row)?
+                } else {
+                    &values[rng.below(values.len())]
+                };
+                SeedValue::Text(value.clone())
+            }
+            ColumnType::BitN => {
+                let bits = u32::from(length.unwrap_or(1).clamp(1, 63));
+                let max = (1_i128 << bits) - 1;
+                let value = if self.unique {
+                    serial
+                } else {
+                    rng.range(0, max)
+                };
+                if value > max {
+                    return None;
+                }
+                SeedValue::Number(value.to_string())
+            }
+            ColumnType::Binary
+            | ColumnType::BinaryN
+            | ColumnType::VarBinaryN
+            | ColumnType::Blob
+            | ColumnType::TinyBlob
+            | ColumnType::MediumBlob
+            | ColumnType::LongBlob => {
+                let size = usize::from(length.unwrap_or(16).min(64));
+                SeedValue::Bytes((0..size).map(|_| rng.next() as u8).collect())
+            }
+            ColumnType::Geometry => SeedValue::Text(format!(
+                "POINT({} {})",
+                rng.range(-180, 180),
+                rng.range(-90, 90)
+            )),
+            ColumnType::Json => {
+                let id = if self.unique {
+                    serial
+                } else {
+                    rng.range(1, 1000)
+                };
+                SeedValue::Text(format!("{{\"id\": {id}}}"))
+            }
+        };
+        Some(value)
+    }
+
+    /// Days since 1970-01-01 of a date, recent or a birth date.
+    fn day(&self, rng: &mut Rng, row: usize) -> i64 {
+        let start = match self.hint {
+            Some(Hint::BirthDate) => BIRTH_DAYS,
+            _ => RECENT_DAYS,
+        };
+        if self.unique {
+            start + row as i64
+        } else {
+            start + rng.range(0, 5 * 365) as i64
+        }
+    }
+
+    fn text(&self, rng: &mut Rng, row: usize, length: Option<usize>) -> Option<SeedValue> {
+        let number = if self.unique {
+            row as i128 + 1
+        } else {
+            rng.range(1, 9999)
+        };
+        let ja = self.japanese;
+        let (text, numbered) = match self.hint {
+            Some(Hint::LastName) => (
+                rng.pick(if ja { LAST_NAMES_JA } else { LAST_NAMES_EN })
+                    .to_string(),
+                false,
+            ),
+            Some(Hint::FirstName) => (
+                rng.pick(if ja { FIRST_NAMES_JA } else { FIRST_NAMES_EN })
+                    .to_string(),
+                false,
+            ),
+            Some(Hint::PersonName) if ja => (
+                format!("{} {}", rng.pick(LAST_NAMES_JA), rng.pick(FIRST_NAMES_JA)),
+                false,
+            ),
+            Some(Hint::PersonName) => (
+                format!("{} {}", rng.pick(FIRST_NAMES_EN), rng.pick(LAST_NAMES_EN)),
+                false,
+            ),
+            Some(Hint::Email) => (format!("user{number}@example.com"), true),
+            Some(Hint::Phone) if ja => (format!("090-{:04}-{number:04}", rng.range(0, 9999)), true),
+            Some(Hint::Phone) => (format!("555-{:03}-{number:04}", rng.range(100, 999)), true),
+            Some(Hint::PostalCode) if ja => {
+                (format!("{:03}-{number:04}", rng.range(100, 999)), true)
+            }
+            Some(Hint::PostalCode) => (format!("{number:05}"), true),
+            Some(Hint::Address) if ja => (
+                format!("{}{}-{}", rng.pick(CITIES_JA), rng.range(1, 9), number),
+                true,
+            ),
+            Some(Hint::Address) => (format!("{number} {}", rng.pick(STREETS_EN)), true),
+            Some(Hint::Url) => (
+                format!(
+                    "https://example.com/{}/{number}",
+                    self.column
+                        .column
+                        .physical_name
+                        .to_lowercase()
+                        .replace('_', "-")
+                ),
+                true,
+            ),
+            Some(Hint::Company) if ja => (format!("株式会社{}", rng.pick(COMPANIES_JA)), false),
+            Some(Hint::Company) => (format!("{} Inc.", rng.pick(COMPANIES_EN)), false),
+            Some(Hint::Title) => (
+                rng.pick(if ja { TITLES_JA } else { TITLES_EN }).to_string(),
+                false,
+            ),
+            Some(Hint::Gender) => (
+                rng.pick(if ja {
+                    &["男性", "女性"]
+                } else {
+                    &["male", "female"]
+                })
+                .to_string(),
+                false,
+            ),
+            Some(Hint::Password) => {
+                let digest: Vec<u8> = (0..32).map(|_| rng.next() as u8).collect();
+                (hex(&digest), !self.unique)
+            }
+            Some(Hint::Code) => (format!("C{number:05}"), true),
+            Some(Hint::Sentence) => (
+                rng.pick(if ja { SENTENCES_JA } else { SENTENCES_EN })
+                    .to_string(),
+                false,
+            ),
+            _ => {
+                let column = &self.column.column;
+                let name = column
+                    .logical_name
+                    .as_deref()
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or(&column.physical_name);
+                (format!("{name} {number}"), true)
+            }
+        };
+        let suffix = if self.unique && !numbered {
+            format!(" {number}")
+        } else {
+            String::new()
+        };
+        let text = text + &suffix;
+        let text = match length {
+            Some(length) if text.chars().count() > length => {
+                if self.unique {
+                    // Cut short, it would no longer be unique.
+                    let number = number.to_string();
+                    (number.len() <= length).then_some(number)?
+                } else {
+                    text.chars().take(length).collect()
+                }
+            }
+            _ => text,
+        };
+        Some(SeedValue::Text(text))
+    }
+}
+
+/// The rows a foreign key refers to: of a generated parent, or of the table itself for
+/// a self-reference.
+enum Parent<'a> {
+    Table(&'a SeedTable),
+    Itself,
+    /// Not generated yet, as the relationships form a cycle.
+    Missing,
+}
+
+struct ForeignKey<'a> {
+    parent: Parent<'a>,
+    /// The indexes of the columns in the child, and the names in the parent.
+    columns: Vec<(usize, String)>,
+    nullable: bool,
+}
+
+struct Generator<'a> {
+    diagram: &'a Diagram,
+    options: &'a SeedOptions,
+    tables: Vec<SeedTable>,
+}
+
+impl Generator<'_> {
+    fn foreign_keys(&self, table: &Table, plans: &[Plan]) -> Vec<ForeignKey<'_>> {
+        self.diagram
+            .relationships()
+            .filter(|relationship| relationship.target_table() == table.physical_name)
+            .filter_map(|relationship| {
+                let (fk_columns, referred) = self.diagram.foreign_key_columns(relationship)?;
+                let columns: Option<Vec<(usize, String)>> = fk_columns
+                    .iter()
+                    .zip(referred)
+                    .map(|(fk_column, referred)| {
+                        let index = plans
+                            .iter()
+                            .position(|plan| plan.column.column.physical_name == *fk_column)?;
+                        Some((index, referred))
+                    })
+                    .collect();
+                let columns = columns?;
+                let parent_name = relationship.source_table();
+                let parent = if parent_name == table.physical_name {
+                    Parent::Itself
+                } else {
+                    self.tables
+                        .iter()
+                        .find(|seeded| seeded.name == parent_name)
+                        .map_or(Parent::Missing, Parent::Table)
+                };
+                let nullable = columns
+                    .iter()
+                    .all(|(index, _)| !plans[*index].column.is_not_null());
+                Some(ForeignKey {
+                    parent,
+                    columns,
+                    nullable,
+                })
+            })
+            .collect()
+    }
+
+    fn generate_table(&self, table: &Table) -> SeedTable {
+        let columns = self.diagram.resolve_columns(table);
+        let names: Vec<String> = columns
+            .iter()
+            .map(|column| column.column.physical_name.clone())
+            .collect();
+        let unique_keys = unique_keys(table, &columns);
+        let mut plans: Vec<Plan> = columns
+            .into_iter()
+            .enumerate()
+            .map(|(index, column)| Plan {
+                hint: Hint::of(&column),
+                japanese: column
+                    .column
+                    .logical_name
+                    .as_deref()
+                    .is_some_and(|name| !name.is_ascii()),
+                unique: unique_keys.iter().any(|key| key == &[index]),
+                column,
+            })
+            .collect();
+        let foreign_keys = self.foreign_keys(table, &plans);
+        for foreign_key in &foreign_keys {
+            for (index, _) in &foreign_key.columns {
+                // Foreign keys take their values from the parent instead.
+                plans[*index].unique = false;
+            }
+        }
+
+        let mut rng = Rng::new(self.options.seed, &table.physical_name);
+        let mut seeded = SeedTable {
+            name: table.physical_name.clone(),
+            columns: names,
+            rows: Vec::new(),
+        };
+        let mut taken: Vec<HashSet<Vec<SeedValue>>> = vec![HashSet::new(); unique_keys.len()];
+        'rows: for row in 0..self.options.rows {
+            for _ in 0..MAX_ATTEMPTS {
+                let Some(values) = generate_row(&mut rng, &plans, &foreign_keys, &seeded, row)
+                else {
+                    break 'rows;
+                };
+                let keys: Vec<Option<Vec<SeedValue>>> = unique_keys
+                    .iter()
+                    .map(|key| {
+                        let values: Vec<SeedValue> =
+                            key.iter().map(|index| values[*index].clone()).collect();
+                        // Keys with a null do not clash, as in SQL.
+                        (!values.contains(&SeedValue::Null)).then_some(values)
+                    })
+                    .collect();
+                let clashes = keys
+                    .iter()
+                    .zip(&taken)
+                    .any(|(key, taken)| key.as_ref().is_some_and(|key| taken.contains(key)));
+                if clashes {
+                    continue;
+                }
+                for (key, taken) in keys.into_iter().zip(&mut taken) {
+                    if let Some(key) = key {
+                        taken.insert(key);
+                    }
+                }
+                seeded.rows.push(values);
+                continue 'rows;
+            }
+            break;
+        }
+        seeded
+    }
+}
+
+/// The primary key, unique columns, compound unique keys and unique indexes of a table,
+/// as indexes of its columns.
+fn unique_keys(table: &Table, columns: &[ResolvedColumn]) -> Vec<Vec<usize>> {
+    let index_of = |name: &str| {
+        columns
+            .iter()
+            .position(|column| column.column.physical_name == name)
+    };
+    let mut keys: Vec<Vec<usize>> = Vec::new();
+    let primary_key: Vec<usize> = (0..columns.len())
+        .filter(|index| columns[*index].is_primary_key())
+        .collect();
+    if !primary_key.is_empty() {
+        keys.push(primary_key);
+    }
+    keys.extend(
+        (0..columns.len())
+            .filter(|index| columns[*index].is_unique())
+            .map(|index| vec![index]),
+    );
+    let named = table
+        .compound_unique_keys()
+        .iter()
+        .map(|key| key.column_names())
+        .chain(
+            table
+                .index_list()
+                .iter()
+                .filter(|index| index.is_unique())
+                .map(|index| index.column_names()),
+        );
+    for names in named {
+        let key: Option<Vec<usize>> = names.into_iter().map(index_of).collect();
+        if let Some(key) = key.filter(|key| !key.is_empty()) {
+            keys.push(key);
+        }
+    }
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+/// A row of values, or None if a column or a foreign key has no value left for it.
+fn generate_row(
+    rng: &mut Rng,
+    plans: &[Plan],
+    foreign_keys: &[ForeignKey],
+    seeded: &SeedTable,
+    row: usize,
+) -> Option<Vec<SeedValue>> {
+    let is_foreign_key = |index: usize| {
+        foreign_keys
+            .iter()
+            .any(|fk| fk.columns.iter().any(|(i, _)| *i == index))
+    };
+    let mut values: Vec<SeedValue> = plans
+        .iter()
+        .enumerate()
+        .map(|(index, plan)| {
+            if is_foreign_key(index) {
+                Some(SeedValue::Null)
+            } else {
+                plan.value(rng, row)
+            }
+        })
+        .collect::<Option<_>>()?;
+
+    // After the other columns, so that a self-reference can refer to its own row.
+    for foreign_key in foreign_keys {
+        let parent_rows = match foreign_key.parent {
+            Parent::Table(parent) => parent.rows.len(),
+            Parent::Itself => seeded.rows.len() + 1,
+            Parent::Missing => 0,
+        };
+        if foreign_key.nullable && (parent_rows == 0 || rng.one_in(10)) {
+            continue;
+        }
+        if parent_rows == 0 {
+            return None;
+        }
+        // A nullable self-reference starts the chain with null rather than itself.
+        let parent_row = rng.below(parent_rows);
+        if matches!(foreign_key.parent, Parent::Itself)
+            && parent_row == seeded.rows.len()
+            && foreign_key.nullable
+        {
+            continue;
+        }
+        for (index, referred) in &foreign_key.columns {
+            let value = match foreign_key.parent {
+                Parent::Table(parent) => parent.value(parent_row, referred).cloned(),
+                Parent::Itself => match seeded.rows.get(parent_row) {
+                    Some(_) => seeded.value(parent_row, referred).cloned(),
+                    None => seeded
+                        .columns
+                        .iter()
+                        .position(|name| name == referred)
+                        .map(|referred| values[referred].clone()),
+                },
+                Parent::Missing => None,
+            };
+            values[*index] = value.unwrap_or(SeedValue::Null);
+        }
+    }
+    Some(values)
+}
+
+/// The tables with their parents first. Where the relationships form a cycle, a table
+/// whose foreign keys into the cycle are all nullable goes first, with those keys null.
+fn table_order(diagram: &Diagram) -> Result<Vec<&Table>, Box<dyn std::error::Error>> {
+    let parents_of = |table: &Table| -> Vec<&Relationship> {
+        diagram
+            .relationships()
+            .filter(|relationship| {
+                relationship.target_table() == table.physical_name
+                    && relationship.source_table() != table.physical_name
+                    && diagram.find_table(relationship.source_table()).is_some()
+            })
+            .collect()
+    };
+    let mut remaining: Vec<&Table> = diagram.tables().iter().collect();
+    let mut ordered: Vec<&Table> = Vec::new();
+    while !remaining.is_empty() {
+        let is_done = |name: &str| ordered.iter().any(|table| table.physical_name == name);
+        let ready = remaining.iter().position(|table| {
+            parents_of(table)
+                .iter()
+                .all(|relationship| is_done(relationship.source_table()))
+        });
+        let breaks_cycle = || {
+            remaining.iter().position(|table| {
+                let columns = diagram.resolve_columns(table);
+                parents_of(table)
+                    .iter()
+                    .filter(|relationship| !is_done(relationship.source_table()))
+                    .all(|relationship| {
+                        relationship.fk_columns.fk_column.iter().all(|fk| {
+                            columns.iter().any(|column| {
+                                column.column.physical_name == fk.fk_column_name
+                                    && !column.is_not_null()
+                            })
+                        })
+                    })
+            })
+        };
+        let Some(next) = ready.or_else(breaks_cycle) else {
+            let names: Vec<&str> = remaining
+                .iter()
+                .map(|table| table.physical_name.as_str())
+                .collect();
+            return Err(format!(
+                "the foreign keys of {} form a cycle of not null columns",
+                names.join(", ")
+            )
+            .into());
+        };
+        ordered.push(remaining.remove(next));
+    }
+    Ok(ordered)
+}
+
+/// Generates the rows of every table, parents first.
+pub fn generate(
+    diagram: &Diagram,
+    options: &SeedOptions,
+) -> Result<Vec<SeedTable>, Box<dyn std::error::Error>> {
+    let mut generator = Generator {
+        diagram,
+        options,
+        tables: Vec::new(),
+    };
+    for table in table_order(diagram)? {
+        let seeded = generator.generate_table(table);
+        generator.tables.push(seeded);
+    }
+    Ok(generator.tables)
+}
+
+fn sql_value(value: &SeedValue, dialect: Dialect) -> String {
+    match value {
+        SeedValue::Null => "NULL".to_string(),
+        SeedValue::Number(number) => number.clone(),
+        SeedValue::Text(text) => quote_string(text),
+        SeedValue::Boolean(true) => "TRUE".to_string(),
+        SeedValue::Boolean(false) => "FALSE".to_string(),
+        SeedValue::Bytes(bytes) => match dialect {
+            Dialect::MySql => format!("X'{}'", hex(bytes)),
+            Dialect::PostgreSql => format!("'\\x{}'", hex(bytes)),
+        },
+    }
+}
+
+/// An INSERT statement per table, in the order of the tables. Key values are given
+/// explicitly, including auto-increment ones, so the sequences of PostgreSQL need to be
+/// reset afterwards.
+pub fn to_sql(tables: &[SeedTable], dialect: Dialect) -> String {
+    let mut out = String::new();
+    for table in tables.iter().filter(|table| !table.rows.is_empty()) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let columns: Vec<String> = table
+            .columns
+            .iter()
+            .map(|column| quote_identifier(dialect, column))
+            .collect();
+        let _ = writeln!(
+            out,
+            "INSERT INTO {} ({}) VALUES",
+            quote_identifier(dialect, &table.name),
+            columns.join(", ")
+        );
+        for (i, row) in table.rows.iter().enumerate() {
+            let values: Vec<String> = row.iter().map(|value| sql_value(value, dialect)).collect();
+            let end = if i + 1 == table.rows.len() { ";" } else { "," };
+            let _ = writeln!(out, "  ({}){end}", values.join(", "));
+        }
+    }
+    out
+}
+
+/// The rows of a table as CSV with a header row. Nulls are empty and bytes are written
+/// in the hex format of PostgreSQL, e.g. `\x0aff`.
+pub fn to_csv(table: &SeedTable) -> Result<String, Box<dyn std::error::Error>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(&table.columns)?;
+    for row in &table.rows {
+        writer.write_record(row.iter().map(|value| match value {
+            SeedValue::Null => String::new(),
+            SeedValue::Number(number) => number.clone(),
+            SeedValue::Text(text) => text.clone(),
+            SeedValue::Boolean(value) => value.to_string(),
+            SeedValue::Bytes(bytes) => format!("\\x{}", hex(bytes)),
+        }))?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+/// Writes `<table>.csv` per table into `directory`, creating it if needed.
+pub fn export_csv(tables: &[SeedTable], directory: &str) -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::create_dir_all(directory)?;
+    for table in tables {
+        std::fs::write(
+            Path::new(directory).join(format!("{}.csv", table.name)),
+            to_csv(table)?,
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,76 @@
+//! Summary figures of a diagram, such as the number of tables and columns.
+
+use crate::dtos::diagram::Diagram;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableStats {
+    pub physical_name: String,
+    pub logical_name: String,
+
+    /// Columns with column groups expanded.
+    pub columns: usize,
+
+    /// Relationships to parent tables, i.e. foreign keys of this table.
+    pub relationships: usize,
+
+    /// Relationships from child tables referring to this table.
+    pub referenced_by: usize,
+
+    pub indexes: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagramStats {
+    pub database: String,
+    pub tables: usize,
+    pub columns: usize,
+    pub relationships: usize,
+    pub indexes: usize,
+    pub column_groups: usize,
+
+    /// Tables without a primary key column.
+    pub tables_without_primary_key: Vec<String>,
+
+    pub table_stats: Vec<TableStats>,
+}
+
+pub fn stats(diagram: &Diagram) -> DiagramStats {
+    let table_stats: Vec<TableStats> = diagram
+        .tables()
+        .iter()
+        .map(|table| TableStats {
+            physical_name: table.physical_name.clone(),
+            logical_name: table.logical_name.clone(),
+            columns: diagram.resolve_columns(table).len(),
+            relationships: table.relationships().len(),
+            referenced_by: diagram
+                .relationships()
+                .filter(|relationship| relationship.source_table() == table.physical_name)
+                .count(),
+            indexes: table.index_list().len(),
+        })
+        .collect();
+    DiagramStats {
+        database: diagram.diagram_settings.database.clone(),
+        tables: table_stats.len(),
+        columns: table_stats.iter().map(|table| table.columns).sum(),
+        relationships: table_stats.iter().map(|table| table.relationships).sum(),
+        indexes: table_stats.iter().map(|table| table.indexes).sum(),
+        column_groups: diagram.groups().len(),
+        tables_without_primary_key: diagram
+            .tables()
+            .iter()
+            .filter(|table| {
+                !diagram
+                    .resolve_columns(table)
+                    .iter()
+                    .any(|column| column.is_primary_key())
+            })
+            .map(|table| table.physical_name.clone())
+            .collect(),
+        table_stats,
+    }
+}
@@ -0,0 +1,386 @@
+//! Rule-based checks of a diagram, reported as diagnostics for the editor and CI.
+//!
+//! Unlike [`crate::drift`], this looks at the diagram alone: missing keys, names the
+//! database would reject and references that lead nowhere.
+
+pub mod reserved_words;
+
+use crate::column_type::{ColumnType, format_column_type};
+use crate::dtos::column_groups;
+use crate::dtos::diagram::Diagram;
+use crate::dtos::diagram_walkers::{Column, NormalColumn, Table};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Rule {
+    MissingPrimaryKey,
+    DuplicateTableName,
+    DuplicateColumnName,
+    ReservedWord,
+    ForeignKeyTypeMismatch,
+    DanglingForeignKey,
+    MissingLength,
+    UnknownColumnGroup,
+    MissingRelationshipTable,
+    NameCase,
+    NamePrefix,
+    NameSuffix,
+    PluralName,
+    PrimaryKeyName,
+    ForeignKeyName,
+}
+
+impl Rule {
+    /// The name used to refer to the rule, e.g. `missing-primary-key`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Rule::MissingPrimaryKey => "missing-primary-key",
+            Rule::DuplicateTableName => "duplicate-table-name",
+            Rule::DuplicateColumnName => "duplicate-column-name",
+            Rule::ReservedWord => "reserved-word",
+            Rule::ForeignKeyTypeMismatch => "foreign-key-type-mismatch",
+            Rule::DanglingForeignKey => "dangling-foreign-key",
+            Rule::MissingLength => "missing-length",
+            Rule::UnknownColumnGroup => "unknown-column-group",
+            Rule::MissingRelationshipTable => "missing-relationship-table",
+            Rule::NameCase => "name-case",
+            Rule::NamePrefix => "name-prefix",
+            Rule::NameSuffix => "name-suffix",
+            Rule::PluralName => "plural-name",
+            Rule::PrimaryKeyName => "primary-key-name",
+            Rule::ForeignKeyName => "foreign-key-name",
+        }
+    }
+
+    pub fn severity(self) -> Severity {
+        match self {
+            Rule::DuplicateTableName
+            | Rule::DuplicateColumnName
+            | Rule::ForeignKeyTypeMismatch
+            | Rule::DanglingForeignKey
+            | Rule::MissingLength
+            | Rule::UnknownColumnGroup
+            | Rule::MissingRelationshipTable => Severity::Error,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+/// Where a diagnostic applies. A column is either in `table` or in `column_group`.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column_group: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relationship: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<String>,
+}
+
+impl Location {
+    pub fn table(table: &str) -> Self {
+        Self {
+            table: Some(table.to_string()),
+            ..Default::default()
+        }
+    }
+
+    pub fn column(table: &str, column: &str) -> Self {
+        Self {
+            column: Some(column.to_string()),
+            ..Self::table(table)
+        }
+    }
+
+    pub fn group_column(group: &str, column: &str) -> Self {
+        Self {
+            column_group: Some(group.to_string()),
+            column: Some(column.to_string()),
+            ..Default::default()
+        }
+    }
+
+    pub fn relationship(table: &str, relationship: &str) -> Self {
+        Self {
+            relationship: Some(relationship.to_string()),
+            ..Self::table(table)
+        }
+    }
+
+    pub fn index(table: &str, index: &str) -> Self {
+        Self {
+            index: Some(index.to_string()),
+            ..Self::table(table)
+        }
+    }
+}
+
+/// Formats as a path like the other reports, e.g. `MEMBERS.EMAIL`,
+/// `group.COMMON.CREATED_AT`, `POSTS.relationship.FK_POSTS_MEMBERS` or
+/// `MEMBERS.index.IX_MEMBERS_EMAIL`.
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(table) = &self.table {
+            parts.push(table.clone());
+        }
+        if let Some(group) = &self.column_group {
+            parts.push(format!("group.{group}"));
+        }
+        if let Some(column) = &self.column {
+            parts.push(column.clone());
+        }
+        if let Some(relationship) = &self.relationship {
+            parts.push(format!("relationship.{relationship}"));
+        }
+        if let Some(index) = &self.index {
+            parts.push(format!("index.{index}"));
+        }
+        f.write_str(&parts.join("."))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    pub rule: Rule,
+    pub severity: Severity,
+    pub location: Location,
+    pub message: String,
+
+    /// A replacement name that fixes the problem, when there is an obvious one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}[{}] {}: {}",
+            self.severity,
+            self.rule.name(),
+            self.location,
+            self.message
+        )?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (rename to {suggestion})")?;
+        }
+        Ok(())
+    }
+}
+
+struct Validator<'a> {
+    diagram: &'a Diagram,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Validator<'_> {
+    fn report(&mut self, rule: Rule, location: Location, message: String) {
+        self.diagnostics.push(Diagnostic {
+            rule,
+            severity: rule.severity(),
+            location,
+            message,
+            suggestion: None,
+        });
+    }
+
+    fn check_reserved(&mut self, name: &str, location: Location) {
+        let settings = &self.diagram.diagram_settings;
+        if settings.validate_physical_name != Some(false)
+            && reserved_words::is_reserved(&settings.database, name)
+        {
+            self.report(
+                Rule::ReservedWord,
+                location,
+                format!("{name} is a reserved word in {}", settings.database),
+            );
+        }
+    }
+
+    /// Only string and bit types need a length; numeric types such as `decimal(p,s)` or
+    /// `int(n)` fall back to the defaults of the database.
+    fn check_length(&mut self, column_type: &str, length: Option<u16>, location: Location) {
+        let Some(column_type) = ColumnType::parse(column_type) else {
+            return;
+        };
+        let needs_length = matches!(
+            column_type,
+            ColumnType::CharN
+                | ColumnType::VarCharN
+                | ColumnType::BinaryN
+                | ColumnType::VarBinaryN
+                | ColumnType::BitN
+        );
+        if length.is_none() && needs_length {
+            self.report(
+                Rule::MissingLength,
+                location,
+                format!("{} needs a length", column_type.config().label),
+            );
+        }
+    }
+
+    fn check_foreign_key(&mut self, table: &str, column: &NormalColumn) {
+        let Some((referred_table, referred_column)) = column.referred_table_and_column() else {
+            return;
+        };
+        let location = Location::column(table, &column.physical_name);
+        let Some(referred) = self
+            .diagram
+            .find_normal_column(referred_table, referred_column)
+        else {
+            self.report(
+                Rule::DanglingForeignKey,
+                location,
+                format!("refers to {referred_table}.{referred_column}, which does not exist"),
+            );
+            return;
+        };
+        // The referred column may itself take its type from another one.
+        let referred = self.diagram.resolve_column(&referred);
+        if column.column_type.is_some() && referred.column_type.is_some() {
+            let own = format_column_type(column);
+            let expected = format_column_type(&referred);
+            if own != expected {
+                self.report(
+                    Rule::ForeignKeyTypeMismatch,
+                    location,
+                    format!(
+                        "type {own} differs from {expected} of {referred_table}.{referred_column}"
+                    ),
+                );
+            }
+        }
+    }
+
+    fn check_table(&mut self, table: &Table) {
+        let name = &table.physical_name;
+        self.check_reserved(name, Location::table(name));
+
+        for item in table.column_items() {
+            match item {
+                Column::Normal(column) => {
+                    let location = Location::column(name, &column.physical_name);
+                    self.check_reserved(&column.physical_name, location.clone());
+                    if let Some(column_type) = &column.column_type {
+                        self.check_length(column_type, column.length, location);
+                    }
+                    self.check_foreign_key(name, column);
+                }
+                Column::Group(group) => {
+                    if self.diagram.find_column_group(group).is_none() {
+                        self.report(
+                            Rule::UnknownColumnGroup,
+                            Location::table(name),
+                            format!("column group {group} does not exist"),
+                        );
+                    }
+                }
+            }
+        }
+
+        let columns = self.diagram.resolve_columns(table);
+        for (i, column) in columns.iter().enumerate() {
+            let column_name = &column.column.physical_name;
+            if columns[..i]
+                .iter()
+                .any(|other| other.column.physical_name.eq_ignore_ascii_case(column_name))
+            {
+                let source = match &column.column_group {
+                    Some(group) => format!(" (from column group {group})"),
+                    None => String::new(),
+                };
+                self.report(
+                    Rule::DuplicateColumnName,
+                    Location::column(name, column_name),
+                    format!("column {column_name} is defined more than once{source}"),
+                );
+            }
+        }
+        if !columns.iter().any(|column| column.is_primary_key()) {
+            self.report(
+                Rule::MissingPrimaryKey,
+                Location::table(name),
+                "table has no primary key".to_string(),
+            );
+        }
+
+        for relationship in table.relationships() {
+            for end in [relationship.source_table(), relationship.target_table()] {
+                if self.diagram.find_table(end).is_none() {
+                    self.report(
+                        Rule::MissingRelationshipTable,
+                        Location::relationship(name, &relationship.name),
+                        format!("table {end} does not exist"),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_group_column(&mut self, group: &str, column: &column_groups::NormalColumn) {
+        let location = Location::group_column(group, &column.physical_name);
+        self.check_reserved(&column.physical_name, location.clone());
+        self.check_length(&column.column_type, column.length, location);
+    }
+}
+
+/// Checks the diagram against every rule. Physical names are checked against the
+/// reserved words of the database unless `validate_physical_name` is turned off.
+pub fn validate(diagram: &Diagram) -> Vec<Diagnostic> {
+    let mut validator = Validator {
+        diagram,
+        diagnostics: Vec::new(),
+    };
+    for (i, table) in diagram.tables().iter().enumerate() {
+        let name = &table.physical_name;
+        if diagram.tables()[..i]
+            .iter()
+            .any(|other| other.physical_name.eq_ignore_ascii_case(name))
+        {
+            validator.report(
+                Rule::DuplicateTableName,
+                Location::table(name),
+                format!("table {name} is defined more than once"),
+            );
+        }
+        validator.check_table(table);
+    }
+    for group in diagram.groups() {
+        for column in group.columns.normal_columns.iter().flatten() {
+            validator.check_group_column(&group.column_group_name, column);
+        }
+    }
+    validator.diagnostics
+}
@@ -0,0 +1,259 @@
+use super::entities::diagram::Diagram;
+use quick_xml::Reader;
+use quick_xml::escape::unescape;
+use quick_xml::events::Event;
+use quick_xml::se::Serializer;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+
+pub fn to_xml(diagram: &Diagram) -> Result<String, Box<dyn std::error::Error>> {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let mut serializer = Serializer::with_root(&mut xml, Some("diagram"))?;
+    serializer.indent('\t', 1);
+    diagram.serialize(serializer)?;
+    xml.push('\n');
+    Ok(xml)
+}
+
+pub fn write_file(filename: &str, diagram: &Diagram) -> Result<(), Box<dyn std::error::Error>> {
+    fs::write(filename, to_xml(diagram)?)?;
+    Ok(())
+}
+
+/// Writes the diagram over the file it was read from. The elements that `entities`
+/// models are replaced, and the others, e.g. `environment_settings` or `trigger_set`,
+/// are kept where they are.
+pub fn update_file(filename: &str, diagram: &Diagram) -> Result<(), Box<dyn std::error::Error>> {
+    let original_xml = fs::read_to_string(filename)?;
+    let original = parse(&original_xml)?;
+    // What the entities keep of the original tells which of its elements they model.
+    let modeled = parse(&to_xml(&quick_xml::de::from_str(&original_xml)?)?)?;
+    let written = parse(&to_xml(diagram)?)?;
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    write_node(&mut xml, &overlay(&original, Some(&modeled), &written), 0);
+    fs::write(filename, xml)?;
+    Ok(())
+}
+
+/// An element, with its attributes and text as they are written in the file.
+#[derive(Debug, Clone)]
+struct Node {
+    name: String,
+    attributes: String,
+    text: String,
+    children: Vec<Node>,
+    /// Whether an element without children is written on two lines, as ERFlute writes
+    /// empty lists, e.g. `<indexes>` and `</indexes>`.
+    multiline: bool,
+}
+
+impl Node {
+    fn value(&self) -> String {
+        unescape(&self.text).map_or_else(|_| self.text.clone(), |text| text.into_owned())
+    }
+
+    /// Identifies an element among its siblings of the same name: by the text of a
+    /// leaf, e.g. a `column_group` reference, or by the name or id of the element.
+    fn key(&self) -> String {
+        if self.children.is_empty() {
+            return self.value();
+        }
+        ["id", "physical_name", "name", "column_id", "fk_column_name"]
+            .iter()
+            .find_map(|field| self.children.iter().find(|child| child.name == *field))
+            .map(Node::value)
+            .unwrap_or_default()
+    }
+}
+
+fn parse(xml: &str) -> Result<Node, Box<dyn std::error::Error>> {
+    let mut reader = Reader::from_str(xml);
+    let mut stack: Vec<Node> = Vec::new();
+    let open = |start: &quick_xml::events::BytesStart| -> Result<Node, Box<dyn std::error::Error>> {
+        Ok(Node {
+            name: String::from_utf8(start.name().as_ref().to_vec())?,
+            attributes: String::from_utf8(start.attributes_raw().to_vec())?,
+            text: String::new(),
+            children: Vec::new(),
+            multiline: false,
+        })
+    };
+    loop {
+        match reader.read_event()? {
+            Event::Start(start) => stack.push(open(&start)?),
+            Event::Empty(start) => {
+                let node = open(&start)?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => return Ok(node),
+                }
+            }
+            Event::Text(text) => {
+                if let Some(node) = stack.last_mut() {
+                    node.text.push_str(std::str::from_utf8(&text)?);
+                }
+            }
+            Event::GeneralRef(reference) => {
+                if let Some(node) = stack.last_mut() {
+                    node.text.push('&');
+                    node.text.push_str(std::str::from_utf8(&reference)?);
+                    node.text.push(';');
+                }
+            }
+            Event::CData(data) => {
+                if let Some(node) = stack.last_mut() {
+                    node.text.push_str("<![CDATA[");
+                    node.text.push_str(std::str::from_utf8(&data)?);
+                    node.text.push_str("]]>");
+                }
+            }
+            Event::End(_) => {
+                let mut node = stack.pop().ok_or("unbalanced XML")?;
+                if !node.children.is_empty() || node.text.trim().is_empty() {
+                    node.multiline = !node.children.is_empty() || node.text.contains('\n');
+                    node.text.clear();
+                }
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => return Ok(node),
+                }
+            }
+            Event::Eof => return Err("no root element".into()),
+            _ => {}
+        }
+    }
+}
+
+/// Whether the children of an element are its fields rather than the items of a list:
+/// each of a different name, and not columns, whose order matters even when there is
+/// only one of each kind.
+fn is_record(node: &Node) -> bool {
+    let mut names = HashSet::new();
+    node.children.iter().all(|child| {
+        child.name != "normal_column" && child.name != "column_group" && names.insert(&child.name)
+    })
+}
+
+/// The position of the matching element of `children` for each of `nodes`, pairing
+/// elements of the same name and key in order.
+fn match_children(nodes: &[Node], children: &[Node]) -> Vec<Option<usize>> {
+    let mut taken = HashSet::new();
+    nodes
+        .iter()
+        .map(|node| {
+            let key = node.key();
+            let found = children.iter().enumerate().position(|(i, child)| {
+                !taken.contains(&i) && child.name == node.name && child.key() == key
+            });
+            if let Some(i) = found {
+                taken.insert(i);
+            }
+            found
+        })
+        .collect()
+}
+
+/// The written element, with the elements of the original that are not modeled kept in
+/// place and the unchanged ones kept as they were formatted.
+fn overlay(original: &Node, modeled: Option<&Node>, written: &Node) -> Node {
+    if original.children.is_empty() && written.children.is_empty() {
+        if original.value() == written.value() {
+            return original.clone();
+        }
+        return Node {
+            text: written.text.clone(),
+            multiline: written.text.is_empty() && original.multiline,
+            ..original.clone()
+        };
+    }
+
+    let modeled_children = modeled.map_or(&[][..], |modeled| modeled.children.as_slice());
+    let modeled_names: HashSet<&str> = modeled_children
+        .iter()
+        .chain(&written.children)
+        .map(|child| child.name.as_str())
+        .collect();
+    let originals = match_children(&written.children, &original.children);
+    let models = match_children(&original.children, modeled_children);
+
+    let merge = |w: usize| match originals[w] {
+        Some(i) => overlay(
+            &original.children[i],
+            models[i].map(|m| &modeled_children[m]),
+            &written.children[w],
+        ),
+        None => written.children[w].clone(),
+    };
+    let mut children = Vec::new();
+    if is_record(original) && is_record(written) {
+        // Fields keep their place in the original, and new ones follow the field
+        // written before them.
+        for (i, child) in original.children.iter().enumerate() {
+            if !modeled_names.contains(child.name.as_str()) {
+                children.push(child.clone());
+            } else if let Some(w) = originals.iter().position(|o| *o == Some(i)) {
+                children.push(merge(w));
+            }
+        }
+        for (w, original) in originals.iter().enumerate() {
+            if original.is_none() {
+                let at = match w.checked_sub(1) {
+                    Some(previous) => {
+                        let previous = &written.children[previous].name;
+                        children
+                            .iter()
+                            .position(|child| child.name == *previous)
+                            .map_or(children.len(), |p| p + 1)
+                    }
+                    None => 0,
+                };
+                children.insert(at, merge(w));
+            }
+        }
+    } else {
+        // The order of list items is significant, so they come in the written order,
+        // around the elements that are not modeled.
+        let mut next = 0;
+        for (i, child) in original.children.iter().enumerate() {
+            if !modeled_names.contains(child.name.as_str()) {
+                children.push(child.clone());
+            } else if let Some(w) = originals.iter().position(|o| *o == Some(i)) {
+                while next <= w {
+                    children.push(merge(next));
+                    next += 1;
+                }
+            }
+        }
+        while next < written.children.len() {
+            children.push(merge(next));
+            next += 1;
+        }
+    }
+
+    Node {
+        text: String::new(),
+        multiline: true,
+        children,
+        ..original.clone()
+    }
+}
+
+fn write_node(out: &mut String, node: &Node, depth: usize) {
+    let indent = "\t".repeat(depth);
+    out.push_str(&format!("{indent}<{}{}>", node.name, node.attributes));
+    if !node.children.is_empty() {
+        out.push('\n');
+        for child in &node.children {
+            write_node(out, child, depth + 1);
+        }
+        out.push_str(&indent);
+    } else if node.multiline {
+        out.push('\n');
+        out.push_str(&indent);
+    } else {
+        out.push_str(&node.text);
+    }
+    out.push_str(&format!("</{}>\n", node.name));
+}
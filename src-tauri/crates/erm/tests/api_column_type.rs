@@ -0,0 +1,131 @@
+use pretty_assertions::assert_eq;
+
+use erm::column_type::{
+    ColumnType, enum_values, format_column_type, format_group_column_type, format_sql_type,
+    parse_sql_type,
+};
+use erm::dtos::column_groups;
+use erm::dtos::diagram_walkers::NormalColumn;
+
+fn column(column_type: &str) -> NormalColumn {
+    NormalColumn {
+        physical_name: "COLUMN".to_string(),
+        column_type: Some(column_type.to_string()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_parse_column_type_from_key_or_label() {
+    assert_eq!(ColumnType::parse("integer"), Some(ColumnType::Int));
+    assert_eq!(ColumnType::parse("int"), Some(ColumnType::Int));
+    assert_eq!(
+        ColumnType::parse("double precision(m,d)"),
+        Some(ColumnType::DoubleMD)
+    );
+    assert_eq!(ColumnType::parse("unknown"), None);
+}
+
+#[test]
+fn test_format_column_type() {
+    assert_eq!(format_column_type(&NormalColumn::default()), "");
+    assert_eq!(
+        format_column_type(&NormalColumn {
+            length: Some(12),
+            decimal: Some(3),
+            ..column("decimal(p,s)")
+        }),
+        "decimal(12, 3)"
+    );
+    assert_eq!(format_column_type(&column("decimal(p,s)")), "decimal(0, 0)");
+    assert_eq!(
+        format_column_type(&NormalColumn {
+            length: Some(8),
+            decimal: Some(3),
+            ..column("decimal(p)")
+        }),
+        "decimal(8)"
+    );
+    assert_eq!(
+        format_column_type(&NormalColumn {
+            unsigned: Some(true),
+            ..column("bigint")
+        }),
+        "bigint unsigned"
+    );
+    assert_eq!(format_column_type(&column("integer")), "int");
+    assert_eq!(format_column_type(&column("uuid")), "uuid");
+    // As in the frontend, the values of an enum are left out.
+    let status = NormalColumn {
+        args: Some("'draft','it''s published'".to_string()),
+        ..column("enum")
+    };
+    assert_eq!(format_column_type(&status), "enum");
+}
+
+#[test]
+fn test_format_sql_type() {
+    assert_eq!(
+        format_sql_type(&NormalColumn {
+            args: Some("'draft','it''s published'".to_string()),
+            ..column("enum")
+        }),
+        "enum('draft', 'it''s published')"
+    );
+    assert_eq!(format_sql_type(&column("enum")), "enum");
+    assert_eq!(
+        format_sql_type(&NormalColumn {
+            length: Some(32),
+            args: Some("'ignored'".to_string()),
+            ..column("varchar(n)")
+        }),
+        "varchar(32)"
+    );
+}
+
+#[test]
+fn test_enum_values() {
+    assert_eq!(
+        enum_values("'a, b', 'it''s' ,c"),
+        vec!["a, b".to_string(), "it's".to_string(), "c".to_string()]
+    );
+    assert_eq!(enum_values(" "), Vec::<String>::new());
+}
+
+#[test]
+fn test_format_group_column_type() {
+    assert_eq!(
+        format_group_column_type(&column_groups::NormalColumn {
+            physical_name: "CODE".to_string(),
+            column_type: "varchar(n)".to_string(),
+            length: Some(16),
+            ..Default::default()
+        }),
+        "varchar(16)"
+    );
+}
+
+#[test]
+fn test_parse_sql_type() {
+    let parsed = parse_sql_type("VARCHAR(32)");
+    assert_eq!(parsed.column_type.as_deref(), Some("varchar(n)"));
+    assert_eq!(parsed.length, Some(32));
+
+    let parsed = parse_sql_type("decimal(10, 2)");
+    assert_eq!(parsed.column_type.as_deref(), Some("decimal(p,s)"));
+    assert_eq!((parsed.length, parsed.decimal), (Some(10), Some(2)));
+
+    let parsed = parse_sql_type("bigint unsigned");
+    assert_eq!(parsed.column_type.as_deref(), Some("bigint"));
+    assert!(parsed.unsigned);
+
+    let parsed = parse_sql_type("serial");
+    assert_eq!(parsed.column_type.as_deref(), Some("integer"));
+    assert!(parsed.auto_increment);
+
+    // The values keep their case, as `COLUMN_TYPE` of MySQL lists them.
+    let parsed = parse_sql_type("ENUM('Draft','Published')");
+    assert_eq!(parsed.column_type.as_deref(), Some("enum"));
+    assert_eq!(parsed.args.as_deref(), Some("'Draft', 'Published'"));
+    assert_eq!(parse_sql_type("int(11)").args, None);
+}
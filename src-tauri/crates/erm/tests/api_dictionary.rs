@@ -0,0 +1,122 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::open_fixture;
+use erm::dictionary::{
+    NameMismatch, collect_words, find_mismatches, import_erm, parse_csv, to_csv,
+};
+use erm::dtos::dictionary::Word;
+use erm::validation::Location;
+use erm::{open, save};
+
+const CSV: &str = "logical_name,physical_name,description
+会員,MEMBER,
+プロフィール,PROFILE,
+会員プロフィール,MEMBER_PROFILE,\"profile of a member, one per member\"
+画像,IMAGE,
+投稿,POST,
+";
+
+fn word(logical_name: &str, physical_name: &str) -> Word {
+    Word {
+        physical_name: physical_name.to_string(),
+        logical_name: logical_name.to_string(),
+        description: None,
+    }
+}
+
+#[test]
+fn test_dictionary_from_erm_section() {
+    let xml = std::fs::read_to_string("./tests/fixtures/testerd.erm").unwrap();
+    let xml = xml.replacen(
+        "</diagram_settings>",
+        "</diagram_settings>
+	<dictionary>
+		<word>
+			<physical_name>MEMBER</physical_name>
+			<logical_name>会員</logical_name>
+			<description></description>
+		</word>
+		<word>
+			<physical_name>POST</physical_name>
+			<logical_name>投稿</logical_name>
+			<description>記事</description>
+		</word>
+	</dictionary>",
+        1,
+    );
+    let filename = std::env::temp_dir().join("erm_api_dictionary.erm");
+    let filename = filename.to_str().unwrap();
+    std::fs::write(filename, xml).unwrap();
+
+    let dictionary = import_erm(filename).unwrap();
+    assert_eq!(dictionary.words[0].physical_name, "MEMBER");
+    assert_eq!(dictionary.words[1].description.as_deref(), Some("記事"));
+
+    save(filename, &open(filename).unwrap()).unwrap();
+    assert_eq!(import_erm(filename).unwrap(), dictionary);
+    std::fs::remove_file(filename).ok();
+}
+
+#[test]
+fn test_dictionary_suggests_physical_names() {
+    let dictionary = parse_csv(CSV).unwrap();
+    assert_eq!(dictionary.words.len(), 5);
+    assert_eq!(
+        dictionary.words[2].description.as_deref(),
+        Some("profile of a member, one per member")
+    );
+
+    assert_eq!(
+        dictionary.suggest_physical_name("会員").as_deref(),
+        Some("MEMBER")
+    );
+    // The longest word wins, and ASCII words are kept.
+    assert_eq!(
+        dictionary
+            .suggest_physical_name("会員プロフィールID")
+            .as_deref(),
+        Some("MEMBER_PROFILE_ID")
+    );
+    assert_eq!(
+        dictionary.suggest_physical_name("投稿 画像URL").as_deref(),
+        Some("POST_IMAGE_URL")
+    );
+    assert_eq!(dictionary.suggest_physical_name("投稿スレッド"), None);
+
+    assert_eq!(
+        parse_csv(&to_csv(&dictionary).unwrap()).unwrap(),
+        dictionary
+    );
+    assert!(parse_csv("会員\n").is_err());
+}
+
+#[test]
+fn test_dictionary_mismatches() {
+    let diagram = open_fixture();
+    let dictionary = parse_csv(CSV).unwrap();
+
+    assert_eq!(
+        find_mismatches(&diagram, &dictionary),
+        vec![
+            NameMismatch {
+                location: Location::column("MEMBER_PROFILES", "PROFILE_IMG_URL"),
+                logical_name: "プロフィール画像URL".to_string(),
+                physical_name: "PROFILE_IMG_URL".to_string(),
+                expected: "PROFILE_IMAGE_URL".to_string(),
+            },
+            NameMismatch {
+                location: Location::column("POSTS", "IMG_URL"),
+                logical_name: "画像URL".to_string(),
+                physical_name: "IMG_URL".to_string(),
+                expected: "IMAGE_URL".to_string(),
+            },
+        ]
+    );
+
+    // The words of the diagram agree with the diagram.
+    let words = collect_words(&diagram);
+    assert_eq!(words.words[0], word("会員ID", "MEMBER_ID"));
+    assert_eq!(find_mismatches(&diagram, &words), vec![]);
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::{FIXTURE, table_mut};
+use erm::diff::{ChangeKind, ColumnDiff, DiffOptions, FieldChange, TableDiff, diff};
+use erm::dtos::diagram_walkers;
+use erm::{diff_files, open};
+
+#[test]
+fn test_diff_same_file_is_empty() {
+    let diff = diff_files(FIXTURE, FIXTURE, &DiffOptions::default()).expect("failed to diff");
+    assert!(diff.is_empty());
+}
+
+#[test]
+fn test_diff_ignores_visual_changes_by_default() {
+    let old = open(FIXTURE).expect("failed to parse");
+    let mut new = old.clone();
+    let members = table_mut(&mut new, "MEMBERS");
+    members.x = 10;
+    members.width = 300;
+    members.color.r = 0;
+    members.font_size = 12;
+
+    assert!(diff(&old, &new, &DiffOptions::default()).is_empty());
+
+    let diff = diff(
+        &old,
+        &new,
+        &DiffOptions {
+            include_visual: true,
+        },
+    );
+    assert_eq!(diff.changed_tables(), vec!["MEMBERS"]);
+    let fields: Vec<&str> = diff.tables[0]
+        .changes
+        .iter()
+        .map(|change| change.field.as_str())
+        .collect();
+    assert_eq!(fields, vec!["x", "width", "color", "fontSize"]);
+}
+
+#[test]
+fn test_diff_reports_column_changes() {
+    let old = open(FIXTURE).expect("failed to parse");
+    let mut new = old.clone();
+    let members = table_mut(&mut new, "MEMBERS");
+    let items = members.columns.items.as_mut().unwrap();
+    if let diagram_walkers::Column::Normal(column) = &mut items[1] {
+        column.length = Some(64);
+    }
+    items.remove(2);
+    items.push(diagram_walkers::Column::Normal(
+        diagram_walkers::NormalColumn {
+            physical_name: "EMAIL".to_string(),
+            column_type: Some("varchar(n)".to_string()),
+            length: Some(256),
+            ..Default::default()
+        },
+    ));
+
+    let diff = diff(&old, &new, &DiffOptions::default());
+    assert_eq!(
+        diff.tables,
+        vec![TableDiff {
+            physical_name: "MEMBERS".to_string(),
+            kind: ChangeKind::Modified,
+            changes: vec![],
+            columns: vec![
+                ColumnDiff {
+                    physical_name: "LAST_NAME".to_string(),
+                    kind: ChangeKind::Modified,
+                    changes: vec![FieldChange {
+                        field: "length".to_string(),
+                        old_value: Some("32".to_string()),
+                        new_value: Some("64".to_string()),
+                    }],
+                },
+                ColumnDiff {
+                    physical_name: "FIRST_NAME".to_string(),
+                    kind: ChangeKind::Removed,
+                    changes: vec![],
+                },
+                ColumnDiff {
+                    physical_name: "EMAIL".to_string(),
+                    kind: ChangeKind::Added,
+                    changes: vec![],
+                },
+            ],
+            relationships: vec![],
+        }]
+    );
+    assert_eq!(
+        diff.to_string(),
+        "~ table MEMBERS
+    ~ column LAST_NAME
+        ~ length: 32 -> 64
+    - column FIRST_NAME
+    + column EMAIL
+"
+    );
+}
+
+#[test]
+fn test_diff_reports_tables_relationships_and_groups() {
+    let old = open(FIXTURE).expect("failed to parse");
+    let mut new = old.clone();
+    new.diagram_walkers
+        .tables
+        .as_mut()
+        .unwrap()
+        .retain(|table| table.physical_name != "POST_THREADS");
+    let profiles = table_mut(&mut new, "MEMBER_PROFILES");
+    profiles.connections.relationships.as_mut().unwrap()[0].on_delete_action =
+        "CASCADE".to_string();
+    new.column_groups.column_groups.as_mut().unwrap()[0]
+        .columns
+        .normal_columns
+        .as_mut()
+        .unwrap()
+        .pop();
+
+    let diff = diff(&old, &new, &DiffOptions::default());
+    assert_eq!(
+        diff.changed_tables(),
+        vec!["MEMBER_PROFILES", "POST_THREADS"]
+    );
+    assert_eq!(diff.tables[1].kind, ChangeKind::Removed);
+    assert_eq!(diff.tables[0].relationships.len(), 1);
+    assert_eq!(
+        diff.tables[0].relationships[0].changes,
+        vec![FieldChange {
+            field: "onDeleteAction".to_string(),
+            old_value: Some("RESTRICT".to_string()),
+            new_value: Some("CASCADE".to_string()),
+        }]
+    );
+    assert_eq!(diff.column_groups.len(), 1);
+    assert_eq!(diff.column_groups[0].column_group_name, "COMMON");
+    assert_eq!(diff.column_groups[0].columns[0].physical_name, "UPDATED_BY");
+    assert_eq!(diff.column_groups[0].columns[0].kind, ChangeKind::Removed);
+    assert!(diff.to_string().ends_with(
+        "- table POST_THREADS
+~ column group COMMON
+    - column UPDATED_BY
+"
+    ));
+}
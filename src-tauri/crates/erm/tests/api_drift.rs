@@ -0,0 +1,128 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::{column_mut, open_fixture, table_mut};
+use erm::drift::{Drift, DriftKind, DriftTarget, check, check_target};
+use erm::dtos::diagram_walkers::Column;
+use erm::importers::sqlite;
+use rusqlite::Connection;
+
+fn drift(kind: DriftKind, path: &str, expected: Option<&str>, actual: Option<&str>) -> Drift {
+    Drift {
+        kind,
+        path: path.to_string(),
+        expected: expected.map(str::to_string),
+        actual: actual.map(str::to_string),
+    }
+}
+
+#[test]
+fn test_drift_same_schema_is_empty() {
+    let diagram = open_fixture();
+
+    let mut actual = diagram.clone();
+    // Databases may fold the case of names.
+    table_mut(&mut actual, "POST_REPLIES").physical_name = "post_replies".to_string();
+
+    assert!(!check(&diagram, &actual).has_drift());
+}
+
+#[test]
+fn test_drift_reports_differences() {
+    let diagram = open_fixture();
+    let mut actual = diagram.clone();
+    actual
+        .diagram_walkers
+        .tables
+        .as_mut()
+        .unwrap()
+        .retain(|table| table.physical_name != "MEMBER_PROFILES");
+    let members = column_mut(&mut actual, "MEMBERS", "LAST_NAME");
+    members.length = Some(64);
+    members.not_null = None;
+    table_mut(&mut actual, "MST_GENDER")
+        .columns
+        .items
+        .as_mut()
+        .unwrap()
+        .retain(|item| !matches!(item, Column::Normal(column) if column.physical_name == "GENDER"));
+    column_mut(&mut actual, "POST_THREADS", "POST_ID").unique_key = None;
+    table_mut(&mut actual, "POST_REPLIES").physical_name = "ARTICLES".to_string();
+
+    let report = check(&diagram, &actual);
+
+    assert_eq!(
+        report.drifts,
+        vec![
+            drift(
+                DriftKind::TypeMismatch,
+                "MEMBERS.LAST_NAME",
+                Some("varchar(32)"),
+                Some("varchar(64)")
+            ),
+            drift(
+                DriftKind::NullabilityMismatch,
+                "MEMBERS.LAST_NAME",
+                Some("NOT NULL"),
+                Some("NULL")
+            ),
+            drift(DriftKind::MissingTable, "MEMBER_PROFILES", None, None),
+            drift(DriftKind::MissingColumn, "MST_GENDER.GENDER", None, None),
+            drift(DriftKind::MissingTable, "POST_REPLIES", None, None),
+            drift(
+                DriftKind::UniqueMismatch,
+                "POST_THREADS.POST_ID",
+                Some("true"),
+                Some("false")
+            ),
+            drift(DriftKind::ExtraTable, "ARTICLES", None, None),
+        ]
+    );
+    assert_eq!(
+        report.drifts[0].to_string(),
+        "MEMBERS.LAST_NAME: type differs (expected varchar(32), actual varchar(64))"
+    );
+}
+
+#[test]
+fn test_drift_against_sqlite_file() {
+    let filename = std::env::temp_dir().join("erm_api_drift.sqlite");
+    let filename = filename.to_str().unwrap();
+    std::fs::remove_file(filename).ok();
+    let connection = Connection::open(filename).unwrap();
+    connection
+        .execute_batch(
+            "CREATE TABLE MEMBERS (
+                MEMBER_ID INTEGER PRIMARY KEY,
+                EMAIL VARCHAR(256) NOT NULL
+            );
+            CREATE INDEX IX_MEMBERS_EMAIL ON MEMBERS (EMAIL);",
+        )
+        .unwrap();
+    let diagram = sqlite::import(filename).unwrap();
+
+    connection
+        .execute_batch(
+            "DROP INDEX IX_MEMBERS_EMAIL;
+            CREATE UNIQUE INDEX IX_MEMBERS_EMAIL ON MEMBERS (EMAIL);
+            ALTER TABLE MEMBERS ADD COLUMN NICKNAME VARCHAR(32);",
+        )
+        .unwrap();
+    let report = check_target(&diagram, DriftTarget::Sqlite(filename)).unwrap();
+    drop(connection);
+    std::fs::remove_file(filename).ok();
+
+    assert_eq!(
+        report.drifts,
+        vec![
+            drift(DriftKind::ExtraColumn, "MEMBERS.NICKNAME", None, None),
+            drift(
+                DriftKind::IndexMismatch,
+                "MEMBERS.index.IX_MEMBERS_EMAIL",
+                Some("(EMAIL)"),
+                Some("unique (EMAIL)")
+            ),
+        ]
+    );
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::open_fixture;
+use erm::dtos::diagram::Diagram;
+use erm::dtos::diagram_settings::{Category, CategorySettings};
+use erm::exporters::dbml::render;
+use erm::importers::dbml::import_script;
+
+/// The parts of a diagram that DBML keeps. Cardinalities are not, beyond one-to-one or
+/// one-to-many: they are derived from the columns again on import.
+fn schema(diagram: &Diagram) -> Vec<String> {
+    let mut lines = Vec::new();
+    for table in diagram.tables() {
+        lines.push(format!(
+            "{} {} {:?}",
+            table.physical_name, table.logical_name, table.description
+        ));
+        for column in diagram.resolve_columns(table) {
+            lines.push(format!(
+                "  {} {:?} {:?} {} pk={} nn={} uk={} ai={:?} default={:?} refers={:?}",
+                column.column.physical_name,
+                column.column.logical_name,
+                column.column.description,
+                erm::column_type::format_column_type(&column.column),
+                column.is_primary_key(),
+                column.is_not_null(),
+                column.is_unique(),
+                column.column.auto_increment,
+                column.column.default_value,
+                column.column.referred_column
+            ));
+        }
+    }
+    for relationship in diagram.relationships() {
+        lines.push(format!(
+            "{} {} -> {} {}/{}",
+            relationship.name,
+            relationship.target,
+            relationship.source,
+            relationship.on_delete_action,
+            relationship.on_update_action
+        ));
+    }
+    lines
+}
+
+#[test]
+fn test_render_dbml() {
+    let diagram = open_fixture();
+    let dbml = render(&diagram);
+
+    assert!(dbml.starts_with("Project diagram {\n  database_type: 'MySQL'\n}\n"));
+    assert!(dbml.contains(
+        "Table MEMBERS {
+  MEMBER_ID \"bigint unsigned\" [pk, increment, note: '会員ID']
+  LAST_NAME varchar(32) [not null, note: '苗字']
+"
+    ));
+    assert!(
+        dbml.contains("  GENDER char(2) [not null, note: '''性別\n「男性」または「女性」''']\n")
+    );
+    assert!(dbml.contains("  DELETED boolean [not null, default: FALSE, note: '削除済']\n"));
+    assert!(dbml.contains(
+        "Ref FK_MEMBER_POSTS_MEMBERS: POSTS.MEMBER_ID > MEMBERS.MEMBER_ID \
+         [delete: restrict, update: restrict]\n"
+    ));
+    assert!(dbml.contains(
+        "Ref FK_POST_THREADS_MEMBER_POSTS: POST_THREADS.POST_ID - POSTS.POST_ID \
+         [delete: restrict, update: restrict]\n"
+    ));
+}
+
+#[test]
+fn test_dbml_round_trip() {
+    let mut diagram = open_fixture();
+    diagram.diagram_settings.category_settings = Some(CategorySettings {
+        categories: vec![Category {
+            name: "Posting".to_string(),
+            node_elements: vec!["table.POSTS".to_string(), "table.POST_THREADS".to_string()],
+            selected: None,
+        }],
+    });
+    let result = import_script(&render(&diagram)).unwrap();
+
+    assert_eq!(result.warnings, vec![]);
+    assert_eq!(result.diagram.diagram_settings.database, "MySQL");
+    assert_eq!(schema(&result.diagram), schema(&diagram));
+    assert_eq!(result.diagram.categories(), diagram.categories());
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::open_fixture;
+use erm::drift::check;
+use erm::dtos::diagram_walkers::Column;
+use erm::exporters::ddl::generate;
+use erm::importers::ddl::{Dialect, import_script};
+
+#[test]
+fn test_generate_mysql_ddl() {
+    let diagram = open_fixture();
+    let ddl = generate(&diagram, Dialect::MySql);
+
+    assert!(ddl.starts_with(
+        "CREATE TABLE MEMBERS (
+  MEMBER_ID bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '会員ID',
+  LAST_NAME varchar(32) NOT NULL COMMENT '苗字',
+  FIRST_NAME varchar(32) NOT NULL COMMENT '名前',
+  CREATED_AT datetime NOT NULL COMMENT '作成時間',
+"
+    ));
+    assert!(ddl.contains(
+        "  GENDER char(2) NOT NULL COMMENT '性別
+「男性」または「女性」',
+  PRIMARY KEY (GENDER_ID)
+) COMMENT='マスター性別';
+"
+    ));
+    assert!(ddl.contains("  POST_ID bigint unsigned NOT NULL UNIQUE COMMENT '投稿ID',\n"));
+    assert!(ddl.ends_with(
+        "ALTER TABLE POST_THREADS ADD CONSTRAINT FK_POST_THREADS_MEMBER_POSTS \
+         FOREIGN KEY (POST_ID) REFERENCES POSTS (POST_ID) ON DELETE RESTRICT ON UPDATE RESTRICT;
+"
+    ));
+
+    // Reading the script back gives the same schema.
+    let imported = import_script(&ddl, Dialect::MySql);
+    assert_eq!(imported.warnings, vec![]);
+    assert_eq!(check(&diagram, &imported.diagram).drifts, vec![]);
+}
+
+#[test]
+fn test_generate_postgresql_ddl() {
+    let mut diagram = open_fixture();
+    let members = &mut diagram.diagram_walkers.tables.as_mut().unwrap()[0];
+    if let Some(Column::Normal(column)) = members.columns.items.as_mut().unwrap().get_mut(1) {
+        column.physical_name = "USER".to_string();
+    }
+    let ddl = generate(&diagram, Dialect::PostgreSql);
+
+    assert!(ddl.starts_with(
+        "CREATE TABLE MEMBERS (
+  MEMBER_ID bigint GENERATED BY DEFAULT AS IDENTITY NOT NULL,
+  \"USER\" varchar(32) NOT NULL,
+  FIRST_NAME varchar(32) NOT NULL,
+  CREATED_AT timestamp NOT NULL,
+"
+    ));
+    assert!(ddl.contains("  GENDER_ID integer,\n"));
+    assert!(ddl.contains("  DELETED boolean NOT NULL DEFAULT FALSE,\n"));
+    assert!(ddl.contains(
+        "COMMENT ON TABLE MEMBERS IS '会員';
+COMMENT ON COLUMN MEMBERS.MEMBER_ID IS '会員ID';
+COMMENT ON COLUMN MEMBERS.\"USER\" IS '苗字';
+"
+    ));
+
+    let imported = import_script(&ddl, Dialect::PostgreSql);
+    assert_eq!(imported.warnings, vec![]);
+    assert_eq!(imported.diagram.tables().len(), 6);
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::open_fixture;
+use erm::exporters::dot::{DotOptions, Neighbourhood, render};
+
+#[test]
+fn test_render_dot() {
+    let diagram = open_fixture();
+    let dot = render(&diagram, &DotOptions::default()).unwrap();
+
+    assert!(dot.starts_with("digraph schema {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert_eq!(dot.matches("[label=").count(), 6);
+    assert_eq!(dot.matches(" -> ").count(), 6);
+    assert!(dot.contains(
+        "  \"MST_GENDER\" [label=\"{MST_GENDER\\nマスター性別|\
+         GENDER_ID: int unsigned (PK)\\lGENDER: char(2)\\l}\"];\n"
+    ));
+    assert!(dot.contains(
+        "  \"MEMBER_PROFILES\" -> \"MEMBERS\" [taillabel=\"0..1\", headlabel=\"1\", \
+         tooltip=\"FK_MEMBER_PROFILES_MEMBERS\"];\n"
+    ));
+    assert!(!dot.contains("fillcolor"));
+}
+
+#[test]
+fn test_render_dot_neighbourhood_collapsed_and_colored() {
+    let mut diagram = open_fixture();
+    diagram.diagram_walkers.tables.as_mut().unwrap()[0].physical_name = "A|B".to_string();
+    let options = DotOptions {
+        collapse_columns: true,
+        use_table_colors: true,
+        neighbourhood: Some(Neighbourhood {
+            table: "MST_GENDER".to_string(),
+            hops: 1,
+        }),
+    };
+    let dot = render(&open_fixture(), &options).unwrap();
+    assert_eq!(
+        dot,
+        "digraph schema {
+  node [shape=record, fontsize=10];
+  edge [fontsize=9];
+  \"MEMBER_PROFILES\" [label=\"{MEMBER_PROFILES\\n会員プロフィール}\", style=filled, fillcolor=\"#8080c0\"];
+  \"MST_GENDER\" [label=\"{MST_GENDER\\nマスター性別}\", style=filled, fillcolor=\"#8080c0\"];
+  \"MEMBER_PROFILES\" -> \"MST_GENDER\" [taillabel=\"0..n\", headlabel=\"1\", tooltip=\"FK_MEMBER_PROFILES_MST_GENDER\"];
+}
+"
+    );
+
+    // Two hops reach the members through the profiles.
+    let options = DotOptions {
+        neighbourhood: Some(Neighbourhood {
+            table: "MST_GENDER".to_string(),
+            hops: 2,
+        }),
+        ..options
+    };
+    let dot = render(&open_fixture(), &options).unwrap();
+    assert_eq!(dot.matches("[label=").count(), 3);
+    assert!(dot.contains("\"MEMBER_PROFILES\" -> \"MEMBERS\""));
+
+    // Record delimiters in names are escaped.
+    let dot = render(&diagram, &DotOptions::default()).unwrap();
+    assert!(dot.contains("  \"A|B\" [label=\"{A\\|B"));
+
+    let error = render(
+        &diagram,
+        &DotOptions {
+            neighbourhood: Some(Neighbourhood {
+                table: "UNKNOWN".to_string(),
+                hops: 1,
+            }),
+            ..Default::default()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error.to_string(), "unknown table UNKNOWN");
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::open_fixture;
+use erm::dtos::diagram_walkers::Column;
+use erm::exporters::html::{SearchEntry, export, render, search_index, table_file_names};
+
+#[test]
+fn test_render_html_site() {
+    let diagram = open_fixture();
+    let pages = render(&diagram).unwrap();
+
+    assert_eq!(
+        pages
+            .iter()
+            .map(|page| page.path.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "index.html",
+            "tables/MEMBERS.html",
+            "tables/MEMBER_PROFILES.html",
+            "tables/MST_GENDER.html",
+            "tables/POSTS.html",
+            "tables/POST_REPLIES.html",
+            "tables/POST_THREADS.html",
+        ]
+    );
+
+    let index = &pages[0].content;
+    assert!(index.contains("<input id=\"search\""));
+    assert!(index.contains("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(index.contains(
+        "<tr><td><a href=\"tables/MEMBERS.html\">MEMBERS</a></td><td>会員</td><td>7</td></tr>"
+    ));
+    assert!(index.contains(
+        "const SEARCH_INDEX = [{\"physicalName\":\"MEMBERS\",\"logicalName\":\"会員\",\
+         \"table\":\"MEMBERS\",\"url\":\"tables/MEMBERS.html\"},"
+    ));
+    // Nothing is loaded from elsewhere.
+    for page in &pages {
+        assert!(!page.content.contains("<link"));
+        assert!(!page.content.contains(" src="));
+    }
+
+    let profiles = &pages[2].content;
+    assert!(
+        profiles
+            .contains("<h1>MEMBER_PROFILES <span class=\"logical\">会員プロフィール</span></h1>")
+    );
+    assert!(profiles.contains("<td><a href=\"MST_GENDER.html\">MST_GENDER</a>.GENDER_ID</td>"));
+    assert!(profiles.contains(
+        "<tr><td>FK_MEMBER_PROFILES_MEMBERS</td><td>MEMBER_ID</td>\
+         <td><a href=\"MEMBERS.html\">MEMBERS</a></td><td>1 : 0..1</td>\
+         <td>RESTRICT</td><td>RESTRICT</td></tr>"
+    ));
+    let members = &pages[1].content;
+    assert!(members.contains(
+        "<tr><td>FK_MEMBER_PROFILES_MEMBERS</td>\
+         <td><a href=\"MEMBER_PROFILES.html\">MEMBER_PROFILES</a></td><td>MEMBER_ID</td>\
+         <td>1 : 0..1</td></tr>"
+    ));
+}
+
+#[test]
+fn test_html_search_index() {
+    let mut diagram = open_fixture();
+    let table = &mut diagram.diagram_walkers.tables.as_mut().unwrap()[0];
+    table.logical_name = "会員</script>".to_string();
+    let entries = search_index(&diagram);
+
+    assert_eq!(entries.len(), 6 + 48);
+    assert_eq!(
+        entries[1],
+        SearchEntry {
+            physical_name: "MEMBER_ID".to_string(),
+            logical_name: "会員ID".to_string(),
+            table: "MEMBERS".to_string(),
+            url: "tables/MEMBERS.html#column-MEMBER_ID".to_string(),
+        }
+    );
+    let index = &render(&diagram).unwrap()[0].content;
+    assert!(index.contains("\"logicalName\":\"会員<\\/script>\""));
+}
+
+#[test]
+fn test_html_file_names_are_unique() {
+    let mut diagram = open_fixture();
+    let tables = diagram.diagram_walkers.tables.as_mut().unwrap();
+    for (table, name) in tables
+        .iter_mut()
+        .zip(["会員", "会議", "members", "MEMBERS"])
+    {
+        table.physical_name = name.to_string();
+    }
+    if let Column::Normal(column) = &mut tables[0].columns.items.as_mut().unwrap()[0] {
+        column.physical_name = "会員 ID".to_string();
+    }
+
+    let file_names = table_file_names(&diagram);
+    assert_eq!(file_names["会員"], "_E4_BC_9A_E5_93_A1.html");
+    assert_eq!(file_names["会議"], "_E4_BC_9A_E8_AD_B0.html");
+    assert_eq!(file_names["members"], "members-2.html");
+    assert_eq!(file_names["MEMBERS"], "MEMBERS.html");
+
+    let entries = search_index(&diagram);
+    let column = entries
+        .iter()
+        .find(|entry| entry.physical_name == "会員 ID")
+        .unwrap();
+    assert_eq!(
+        column.url,
+        "tables/_E4_BC_9A_E5_93_A1.html#column-%E4%BC%9A%E5%93%A1%20ID"
+    );
+
+    let directory = std::env::temp_dir().join("erm_api_export_html_unique");
+    std::fs::remove_dir_all(&directory).ok();
+    export(&diagram, directory.to_str().unwrap()).unwrap();
+    let count = std::fs::read_dir(directory.join("tables")).unwrap().count();
+    std::fs::remove_dir_all(&directory).ok();
+    assert_eq!(count, 6);
+}
+
+#[test]
+fn test_export_html_site() {
+    let diagram = open_fixture();
+    let directory = std::env::temp_dir().join("erm_api_export_html");
+    std::fs::remove_dir_all(&directory).ok();
+
+    export(&diagram, directory.to_str().unwrap()).unwrap();
+    assert!(directory.join("index.html").is_file());
+    assert!(directory.join("tables/POST_THREADS.html").is_file());
+    std::fs::remove_dir_all(&directory).ok();
+}
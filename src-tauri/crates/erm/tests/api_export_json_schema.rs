@@ -0,0 +1,129 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+use serde_json::{Value, json};
+
+use common::open_fixture;
+use erm::dtos::diagram::Diagram;
+use erm::dtos::diagram_walkers::{Column, NormalColumn};
+use erm::exporters::json_schema::{JsonSchemaOptions, PropertyNames, SchemaFormat, render};
+
+fn render_value(diagram: &Diagram, options: &JsonSchemaOptions) -> Value {
+    serde_json::from_str(&render(diagram, options)).expect("invalid JSON")
+}
+
+#[test]
+fn test_render_json_schema() {
+    let schema = render_value(&open_fixture(), &JsonSchemaOptions::default());
+    assert_eq!(
+        schema["$schema"],
+        "https://json-schema.org/draft/2020-12/schema"
+    );
+    assert_eq!(
+        schema["$defs"]["MST_GENDER"],
+        json!({
+            "title": "マスター性別",
+            "type": "object",
+            "properties": {
+                "GENDER_ID": {
+                    "description": "性別ID",
+                    "type": "integer",
+                    "format": "int32",
+                    "minimum": 0
+                },
+                "GENDER": {
+                    "description": "性別\n\n「男性」または「女性」",
+                    "type": "string",
+                    "maxLength": 2
+                }
+            },
+            "required": ["GENDER_ID", "GENDER"]
+        })
+    );
+    let profile = &schema["$defs"]["MEMBER_PROFILES"];
+    assert_eq!(
+        profile["properties"]["GENDER_ID"]["type"],
+        json!(["integer", "null"])
+    );
+    assert!(
+        !profile["required"]
+            .as_array()
+            .expect("no required")
+            .contains(&json!("GENDER_ID"))
+    );
+    // The properties keep the order of the columns.
+    let names: Vec<&String> = profile["properties"]
+        .as_object()
+        .expect("no properties")
+        .keys()
+        .collect();
+    assert_eq!(names[..2], ["MEMBER_PROFILE_ID", "MEMBER_ID"]);
+}
+
+#[test]
+fn test_render_openapi_with_logical_names() {
+    let options = JsonSchemaOptions {
+        format: SchemaFormat::OpenApi,
+        property_names: PropertyNames::Logical,
+    };
+    let document = render_value(&open_fixture(), &options);
+    assert_eq!(document["openapi"], "3.1.0");
+    assert!(document.get("$defs").is_none());
+    let gender = &document["components"]["schemas"]["MST_GENDER"];
+    assert_eq!(gender["required"], json!(["性別ID", "性別"]));
+    assert_eq!(gender["properties"]["性別"]["maxLength"], 2);
+}
+
+#[test]
+fn test_render_json_schema_enums_and_decimals() {
+    let mut diagram = open_fixture();
+    let tables = diagram.diagram_walkers.tables.as_mut().expect("no tables");
+    tables.truncate(1);
+    let columns = tables[0].columns.items.get_or_insert_default();
+    columns.clear();
+    columns.push(Column::Normal(NormalColumn {
+        physical_name: "STATUS".to_string(),
+        column_type: Some("enum".to_string()),
+        args: Some("'draft', 'published'".to_string()),
+        ..Default::default()
+    }));
+    columns.push(Column::Normal(NormalColumn {
+        physical_name: "PRICE".to_string(),
+        logical_name: Some("価格".to_string()),
+        column_type: Some("decimal(p,s)".to_string()),
+        length: Some(10),
+        decimal: Some(2),
+        unsigned: Some(true),
+        not_null: Some(true),
+        ..Default::default()
+    }));
+    columns.push(Column::Normal(NormalColumn {
+        physical_name: "LOCKED_PRICE".to_string(),
+        logical_name: Some("価格".to_string()),
+        column_type: Some("json".to_string()),
+        ..Default::default()
+    }));
+
+    let options = JsonSchemaOptions {
+        property_names: PropertyNames::Logical,
+        ..Default::default()
+    };
+    assert_eq!(
+        render_value(&diagram, &options)["$defs"]["MEMBERS"]["properties"],
+        json!({
+            "STATUS": {
+                "type": ["string", "null"],
+                "enum": ["draft", "published", null]
+            },
+            "価格": {
+                "description": "価格",
+                "type": "number",
+                "minimum": 0,
+                "multipleOf": 0.01
+            },
+            "LOCKED_PRICE": {
+                "description": "価格"
+            }
+        })
+    );
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::open_fixture;
+use erm::exporters::markdown::render;
+
+#[test]
+fn test_render_markdown() {
+    let diagram = open_fixture();
+    let markdown = render(&diagram);
+
+    assert!(markdown.starts_with(
+        "# Tables
+
+## 会員 (MEMBERS)
+
+- Physical name: `MEMBERS`
+- Logical name: 会員
+
+### Columns
+
+| Name | Logical name | Type | PK | FK | NOT NULL | UNIQUE | Default | Group | Description |
+| --- | --- | --- | --- | --- | --- | --- | --- | --- | --- |
+| MEMBER_ID | 会員ID | bigint unsigned | ✓ |  | ✓ |  |  |  |  |
+| LAST_NAME | 苗字 | varchar(32) |  |  | ✓ |  |  |  |  |
+| FIRST_NAME | 名前 | varchar(32) |  |  | ✓ |  |  |  |  |
+| CREATED_AT | 作成時間 | datetime |  |  | ✓ |  |  | COMMON |  |
+"
+    ));
+    assert!(markdown.contains(
+        "### Referenced by
+
+| Relationship | Table | Columns | Cardinality |
+| --- | --- | --- | --- |
+| FK_MEMBER_PROFILES_MEMBERS | MEMBER_PROFILES | MEMBER_ID | 1 : 0..1 |
+| FK_MEMBER_POSTS_MEMBERS | POSTS | MEMBER_ID | 0..1 : 0..n |
+| FK_POST_REPLIES_MEMBERS | POST_REPLIES | MEMBER_ID | 0..1 : 0..n |
+"
+    ));
+    assert!(markdown.contains(
+        "| GENDER_ID | 性別ID | int unsigned |  | MST_GENDER.GENDER_ID |  |  |  |  |  |
+"
+    ));
+    assert!(markdown.contains(
+        "### References
+
+| Relationship | Columns | Table | Cardinality | On delete | On update |
+| --- | --- | --- | --- | --- | --- |
+| FK_MEMBER_PROFILES_MEMBERS | MEMBER_ID | MEMBERS | 1 : 0..1 | RESTRICT | RESTRICT |
+| FK_MEMBER_PROFILES_MST_GENDER | GENDER_ID | MST_GENDER | 1 : 0..n | RESTRICT | RESTRICT |
+"
+    ));
+    assert!(markdown.contains("| DELETED | 削除済 | boolean |  |  | ✓ |  | FALSE |  |  |\n"));
+}
+
+#[test]
+fn test_render_markdown_escapes_cells() {
+    let mut diagram = open_fixture();
+    let table = &mut diagram.diagram_walkers.tables.as_mut().unwrap()[0];
+    table.description = "Registered members.\n".to_string();
+    if let Some(erm::dtos::diagram_walkers::Column::Normal(column)) =
+        table.columns.items.as_mut().unwrap().get_mut(1)
+    {
+        column.description = Some("family name\nor | surname".to_string());
+    }
+    let markdown = render(&diagram);
+
+    assert!(markdown.contains("- Logical name: 会員\n\nRegistered members.\n\n### Columns\n"));
+    assert_eq!(
+        markdown
+            .lines()
+            .find(|line| line.starts_with("| LAST_NAME ")),
+        Some(
+            "| LAST_NAME | 苗字 | varchar(32) |  |  | ✓ |  |  |  | family name<br>or \\| surname |"
+        )
+    );
+}
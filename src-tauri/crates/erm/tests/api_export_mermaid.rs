@@ -0,0 +1,96 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::{open_fixture, open_fixture_replaced};
+use erm::dtos::diagram_settings::{Category, CategorySettings};
+use erm::dtos::diagram_walkers::{Column, NormalColumn};
+use erm::exporters::mermaid::render;
+
+#[test]
+fn test_render_mermaid() {
+    let diagram = open_fixture();
+    let mermaid = render(&diagram);
+
+    assert!(mermaid.starts_with(
+        "erDiagram
+    MEMBERS {
+        bigint_unsigned MEMBER_ID PK \"会員ID\"
+        varchar(32) LAST_NAME \"苗字\"
+"
+    ));
+    assert!(mermaid.contains("        int_unsigned GENDER_ID FK \"性別ID\"\n"));
+    assert!(mermaid.ends_with(
+        "    MEMBERS |o..o{ POSTS : \"FK_MEMBER_POSTS_MEMBERS\"
+    MEMBERS ||..o| MEMBER_PROFILES : \"FK_MEMBER_PROFILES_MEMBERS\"
+    MST_GENDER ||..o{ MEMBER_PROFILES : \"FK_MEMBER_PROFILES_MST_GENDER\"
+    MEMBERS |o..o{ POST_REPLIES : \"FK_POST_REPLIES_MEMBERS\"
+    POST_THREADS ||..|{ POST_REPLIES : \"FK_POST_REPLIES_POST_THREADS\"
+    POSTS ||..o| POST_THREADS : \"FK_POST_THREADS_MEMBER_POSTS\"
+"
+    ));
+}
+
+#[test]
+fn test_render_mermaid_of_category() {
+    let mut diagram = open_fixture();
+    diagram.diagram_settings.category_settings = Some(CategorySettings {
+        categories: vec![Category {
+            name: "Posting".to_string(),
+            node_elements: vec!["table.POSTS".to_string(), "table.POST_THREADS".to_string()],
+            selected: None,
+        }],
+    });
+    assert!(diagram.extract("Unknown").is_none());
+    let category = diagram.extract("Posting").unwrap();
+
+    assert_eq!(
+        category
+            .tables()
+            .iter()
+            .map(|table| table.physical_name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["POSTS", "POST_THREADS"]
+    );
+    let mermaid = render(&category);
+    // The type of a foreign key column is kept although MEMBERS is left out.
+    assert!(mermaid.contains("        bigint_unsigned MEMBER_ID FK \"会員ID\"\n"));
+    assert!(
+        mermaid
+            .ends_with("    }\n    POSTS ||..o| POST_THREADS : \"FK_POST_THREADS_MEMBER_POSTS\"\n")
+    );
+}
+
+#[test]
+fn test_render_mermaid_enum_column() {
+    let mut diagram = open_fixture();
+    let columns = diagram.diagram_walkers.tables.as_mut().unwrap()[0]
+        .columns
+        .items
+        .get_or_insert_default();
+    columns.push(Column::Normal(NormalColumn {
+        physical_name: "STATUS".to_string(),
+        column_type: Some("enum".to_string()),
+        args: Some("'draft', 'published'".to_string()),
+        ..Default::default()
+    }));
+    let mermaid = render(&diagram);
+
+    // Only the type name, as the editor shows it.
+    assert!(mermaid.contains("        enum STATUS\n"));
+}
+
+#[test]
+fn test_render_mermaid_japanese_table_names() {
+    let diagram = open_fixture_replaced(
+        "api_export_mermaid_japanese",
+        &[("MEMBERS", "会員"), ("POSTS", "投稿")],
+    );
+    let mermaid = render(&diagram);
+
+    // Both names become underscores, yet stay apart, and show as aliases.
+    assert!(mermaid.contains("    __[\"会員\"] {\n"));
+    assert!(mermaid.contains("    ___2[\"投稿\"] {\n"));
+    assert!(mermaid.contains("    __ |o..o{ ___2 : \"FK_MEMBER_投稿_会員\"\n"));
+    assert!(mermaid.contains("    ___2 ||..o| POST_THREADS : \"FK_POST_THREADS_MEMBER_投稿\"\n"));
+}
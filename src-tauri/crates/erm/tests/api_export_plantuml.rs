@@ -0,0 +1,71 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::{open_fixture, open_fixture_replaced};
+use erm::exporters::plantuml::render;
+
+#[test]
+fn test_render_plantuml() {
+    let diagram = open_fixture();
+    let plantuml = render(&diagram);
+
+    assert!(plantuml.starts_with("@startuml\n"));
+    assert!(
+        plantuml.ends_with("POSTS ||..o| POST_THREADS : FK_POST_THREADS_MEMBER_POSTS\n@enduml\n")
+    );
+    assert!(plantuml.contains(
+        "entity \"MEMBER_PROFILES\\n会員プロフィール\" as MEMBER_PROFILES {
+  * MEMBER_PROFILE_ID : bigint unsigned <<PK>>
+  --
+  * MEMBER_ID : bigint unsigned <<FK>>
+  * SELF_INTRODUCTION : text
+  * PROFILE_IMG_URL : varchar(2048)
+  GENDER_ID : int unsigned <<FK>>
+"
+    ));
+    assert!(plantuml.contains("MEMBERS |o..o{ POSTS : FK_MEMBER_POSTS_MEMBERS\n"));
+}
+
+#[test]
+fn test_render_plantuml_of_vdiagram() {
+    let diagram = open_fixture();
+    let vdiagram = diagram.extract("sample").unwrap();
+
+    let members = &vdiagram.tables()[0];
+    assert_eq!(vdiagram.tables().len(), 1);
+    assert_eq!((members.x, members.y), (264, 182));
+    assert_eq!(
+        render(&vdiagram),
+        "@startuml
+hide circle
+skinparam linetype ortho
+
+entity \"MEMBERS\\n会員\" as MEMBERS {
+  * MEMBER_ID : bigint unsigned <<PK>>
+  --
+  * LAST_NAME : varchar(32)
+  * FIRST_NAME : varchar(32)
+  * CREATED_AT : datetime
+  * CREATED_BY : bigint
+  * UPDATED_AT : datetime
+  * UPDATED_BY : bigint
+}
+
+@enduml
+"
+    );
+}
+
+#[test]
+fn test_render_plantuml_japanese_table_names() {
+    let diagram = open_fixture_replaced(
+        "api_export_plantuml_japanese",
+        &[("MEMBERS", "会員"), ("POSTS", "投稿")],
+    );
+    let plantuml = render(&diagram);
+
+    assert!(plantuml.contains("entity \"会員\\n会員\" as __ {\n"));
+    assert!(plantuml.contains("entity \"投稿\\n投稿\" as ___2 {\n"));
+    assert!(plantuml.contains("__ |o..o{ ___2 : FK_MEMBER_投稿_会員\n"));
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::open_fixture;
+use erm::exporters::png::{PngOptions, export, render};
+
+/// Width and height from the IHDR chunk, which comes first.
+fn dimensions(png: &[u8]) -> (u32, u32) {
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    assert_eq!(&png[12..16], b"IHDR");
+    let number = |at: usize| u32::from_be_bytes(png[at..at + 4].try_into().unwrap());
+    (number(16), number(20))
+}
+
+#[test]
+fn test_render_png_scale() {
+    let mut diagram = open_fixture();
+    assert_eq!(PngOptions::default().scale_of(&diagram), 100);
+    let (width, height) = dimensions(&render(&diagram, &PngOptions::default()).unwrap());
+    assert!(width > 1000 && height > 600, "{width}x{height}");
+
+    let doubled = render(&diagram, &PngOptions { scale: Some(200) }).unwrap();
+    assert_eq!(dimensions(&doubled), (width * 2, height * 2));
+
+    // The page settings give the default.
+    diagram.page_settings.as_mut().unwrap().scale = Some(50);
+    let halved = render(&diagram, &PngOptions::default()).unwrap();
+    assert_eq!(dimensions(&halved), (width.div_ceil(2), height.div_ceil(2)));
+}
+
+#[test]
+fn test_export_png_vdiagram() {
+    let diagram = open_fixture();
+    let full = dimensions(&render(&diagram, &PngOptions::default()).unwrap());
+    let sample = diagram.extract("sample").unwrap();
+
+    let filename = std::env::temp_dir().join("erm_api_export_png_sample.png");
+    let filename = filename.to_str().unwrap();
+    export(&sample, &PngOptions::default(), filename).expect("failed to write");
+    let written = std::fs::read(filename).unwrap();
+    std::fs::remove_file(filename).ok();
+
+    let (width, height) = dimensions(&written);
+    assert!(width < full.0 && height < full.1);
+}
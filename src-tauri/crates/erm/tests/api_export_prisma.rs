@@ -0,0 +1,94 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::open_fixture;
+use erm::dtos::diagram::Diagram;
+use erm::exporters::prisma::render;
+use erm::importers::prisma::import_script;
+
+/// The parts of a diagram that a Prisma schema keeps. Cardinalities are not, beyond
+/// one-to-one or one-to-many: they are derived from the columns again on import.
+fn schema(diagram: &Diagram) -> Vec<String> {
+    let mut lines = Vec::new();
+    for table in diagram.tables() {
+        lines.push(format!(
+            "{} {} {:?}",
+            table.physical_name, table.logical_name, table.description
+        ));
+        for column in diagram.resolve_columns(table) {
+            lines.push(format!(
+                "  {} {:?} {:?} {} pk={} nn={} uk={} ai={:?} default={:?} refers={:?}",
+                column.column.physical_name,
+                column.column.logical_name,
+                column.column.description,
+                erm::column_type::format_column_type(&column.column),
+                column.is_primary_key(),
+                column.is_not_null(),
+                column.is_unique(),
+                column.column.auto_increment,
+                column.column.default_value,
+                column.column.referred_column
+            ));
+        }
+    }
+    for relationship in diagram.relationships() {
+        lines.push(format!(
+            "{} {} -> {} {}/{}",
+            relationship.name,
+            relationship.target,
+            relationship.source,
+            relationship.on_delete_action,
+            relationship.on_update_action
+        ));
+    }
+    lines
+}
+
+#[test]
+fn test_render_prisma() {
+    let diagram = open_fixture();
+    let prisma = render(&diagram);
+
+    assert!(prisma.contains("datasource db {\n  provider = \"mysql\"\n"));
+    assert!(prisma.contains(
+        r#"/// マスター性別
+model MstGender {
+  /// 性別ID
+  genderId Int    @id @default(autoincrement()) @map("GENDER_ID") @db.UnsignedInt
+  /// 性別
+  ///
+  /// 「男性」または「女性」
+  gender   String @map("GENDER") @db.Char(2)
+
+  memberProfiles MemberProfiles[]
+
+  @@map("MST_GENDER")
+}
+"#
+    ));
+    assert!(prisma.contains(
+        "  mstGender MstGender? @relation(fields: [genderId], references: [genderId], \
+         onDelete: Restrict, onUpdate: Restrict, map: \"FK_MEMBER_PROFILES_MST_GENDER\")\n"
+    ));
+    assert!(prisma.contains("  deleted       Boolean   @default(false) @map(\"DELETED\")\n"));
+    // POST_ID is unique in POST_THREADS, so posts have at most one thread.
+    assert!(prisma.contains("  postThreads PostThreads?\n"));
+}
+
+#[test]
+fn test_prisma_round_trip() {
+    let diagram = open_fixture();
+    let prisma = render(&diagram);
+    let result = import_script(&prisma).unwrap();
+
+    assert_eq!(result.warnings, vec![]);
+    assert_eq!(result.diagram.diagram_settings.database, "MySQL");
+    // Prisma writes booleans in lowercase.
+    let expected: Vec<String> = schema(&diagram)
+        .iter()
+        .map(|line| line.replace("Some(\"FALSE\")", "Some(\"false\")"))
+        .collect();
+    assert_eq!(schema(&result.diagram), expected);
+    assert_eq!(render(&result.diagram), prisma);
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::open_fixture;
+use erm::exporters::rust::{Orm, SourceFile, render};
+
+fn file<'a>(files: &'a [SourceFile], path: &str) -> &'a str {
+    &files
+        .iter()
+        .find(|file| file.path == path)
+        .unwrap_or_else(|| panic!("no {path}"))
+        .content
+}
+
+#[test]
+fn test_render_sea_orm() {
+    let files = render(&open_fixture(), Orm::SeaOrm);
+    assert_eq!(
+        files
+            .iter()
+            .map(|file| file.path.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "mod.rs",
+            "prelude.rs",
+            "members.rs",
+            "member_profiles.rs",
+            "mst_gender.rs",
+            "posts.rs",
+            "post_replies.rs",
+            "post_threads.rs",
+        ]
+    );
+    assert!(
+        file(&files, "prelude.rs").contains("pub use super::mst_gender::Entity as MstGender;\n")
+    );
+
+    assert_eq!(
+        file(&files, "mst_gender.rs"),
+        r#"//! `MST_GENDER`
+//!
+//! マスター性別
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "MST_GENDER")]
+pub struct Model {
+    /// 性別ID
+    #[sea_orm(column_name = "GENDER_ID", primary_key)]
+    pub gender_id: u32,
+    /// 性別
+    ///
+    /// 「男性」または「女性」
+    #[sea_orm(column_name = "GENDER")]
+    pub gender: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::member_profiles::Entity")]
+    MemberProfiles,
+}
+
+impl Related<super::member_profiles::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::MemberProfiles.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+"#
+    );
+
+    let profiles = file(&files, "member_profiles.rs");
+    assert!(profiles.contains("    pub gender_id: Option<u32>,\n"));
+    assert!(profiles.contains("    pub created_at: DateTime,\n"));
+    assert!(profiles.contains(
+        "    #[sea_orm(
+        belongs_to = \"super::members::Entity\",
+        from = \"Column::MemberId\",
+        to = \"super::members::Column::MemberId\",
+        on_update = \"Restrict\",
+        on_delete = \"Restrict\"
+    )]
+    Members,
+"
+    ));
+    // A post has at most one thread.
+    assert!(
+        file(&files, "posts.rs").contains("#[sea_orm(has_one = \"super::post_threads::Entity\")]")
+    );
+}
+
+#[test]
+fn test_render_sea_orm_without_foreign_key_columns() {
+    let mut diagram = open_fixture();
+    let posts = diagram
+        .diagram_walkers
+        .tables
+        .as_mut()
+        .unwrap()
+        .iter_mut()
+        .find(|table| table.physical_name == "POSTS")
+        .unwrap();
+    // The keys of a relationship into a missing table cannot be resolved.
+    posts.connections.relationships.as_mut().unwrap()[0].target = "table.GHOSTS".to_string();
+
+    let files = render(&diagram, Orm::SeaOrm);
+    let posts = file(&files, "posts.rs");
+    assert!(!posts.contains("Members,"));
+    assert!(!posts.contains("impl Related<super::members::Entity> for Entity"));
+    assert!(!file(&files, "members.rs").contains("has_many = \"super::posts::Entity\""));
+}
+
+#[test]
+fn test_render_diesel_postgresql() {
+    let mut diagram = open_fixture();
+    diagram.diagram_settings.database = "PostgreSQL".to_string();
+    let files = render(&diagram, Orm::Diesel);
+    let schema = file(&files, "schema.rs");
+    let models = file(&files, "models.rs");
+
+    // PostgreSQL has neither unsigned types nor `datetime`.
+    assert!(schema.contains(
+        "diesel::table! {
+    /// マスター性別
+    #[sql_name = \"MST_GENDER\"]
+    mst_gender (gender_id) {
+        /// 性別ID
+        #[sql_name = \"GENDER_ID\"]
+        gender_id -> Integer,
+"
+    ));
+    assert!(schema.contains("        public_end_at -> Nullable<Timestamp>,\n"));
+    assert!(schema.contains("diesel::joinable!(post_replies -> post_threads (post_thread_id));\n"));
+    assert_eq!(schema.matches("diesel::joinable!").count(), 6);
+    assert!(schema.ends_with("    post_threads,\n);\n"));
+
+    assert!(models.starts_with("use diesel::prelude::*;\n"));
+    assert!(models.contains(
+        "#[derive(Debug, Clone, PartialEq, Queryable, Selectable, Identifiable, Associations)]
+#[diesel(table_name = crate::schema::member_profiles, primary_key(member_profile_id))]
+#[diesel(belongs_to(Members, foreign_key = member_id))]
+#[diesel(belongs_to(MstGender, foreign_key = gender_id))]
+pub struct MemberProfiles {
+"
+    ));
+    assert!(models.contains("    pub gender_id: Option<i32>,\n"));
+    assert!(models.contains("    pub public_end_at: Option<chrono::NaiveDateTime>,\n"));
+}
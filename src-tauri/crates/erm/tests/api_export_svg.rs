@@ -0,0 +1,103 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::open_fixture;
+use erm::dtos::diagram_walkers::{Color, Note, WalkerGroup};
+use erm::exporters::svg::{Notation, SvgOptions, render, table_bounds};
+use erm::{open, save};
+
+#[test]
+fn test_render_svg_crows_foot() {
+    let diagram = open_fixture();
+    assert_eq!(Notation::of(&diagram), Notation::CrowsFoot);
+    let svg = render(&diagram, &SvgOptions::default());
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.ends_with("</svg>\n"));
+    assert_eq!(svg.matches("<g class=\"table\"").count(), 6);
+    assert_eq!(svg.matches("<g class=\"relationship\"").count(), 6);
+    assert!(svg.contains("<g class=\"relationship\" data-name=\"FK_MEMBER_POSTS_MEMBERS\">"));
+    assert!(svg.contains(">MEMBER_ID: bigint unsigned</text>"));
+    assert!(svg.contains(">MEMBER_PROFILES</text>"));
+    assert!(!svg.contains("stroke-dasharray=\"6 4\""));
+    assert!(!svg.contains("<a href="));
+
+    // Tables grow to fit their columns.
+    let table = diagram.find_table("MEMBER_PROFILES").unwrap();
+    let bounds = table_bounds(&diagram, table);
+    assert_eq!(
+        (bounds.x, bounds.y),
+        (f64::from(table.x), f64::from(table.y))
+    );
+    assert!(bounds.height >= 20.0 + 4.0 + 9.0 * 20.0);
+}
+
+#[test]
+fn test_render_svg_idef1x_with_links() {
+    let mut diagram = open_fixture();
+    diagram.diagram_settings.notation = Some("IDEF1X".to_string());
+    assert_eq!(Notation::of(&diagram), Notation::Idef1x);
+    let svg = render(
+        &diagram,
+        &SvgOptions {
+            table_link: Some(&|table| format!("#{}", table.physical_name)),
+        },
+    );
+
+    // None of the foreign keys of the fixture are in a primary key.
+    assert_eq!(svg.matches("stroke-dasharray=\"6 4\"").count(), 6);
+    assert!(svg.contains(">Z</text>"));
+    assert!(svg.contains("<a href=\"#POST_REPLIES\">"));
+}
+
+#[test]
+fn test_render_svg_notes_and_groups() {
+    let mut diagram = open_fixture();
+    diagram.diagram_walkers.notes = Some(vec![Note {
+        note_text: "Members & posts.\nReplies are threaded.".to_string(),
+        height: 60,
+        width: 180,
+        font_name: "Ubuntu".to_string(),
+        font_size: 9,
+        x: 560,
+        y: 330,
+        color: Color {
+            r: 255,
+            g: 255,
+            b: 206,
+        },
+    }]);
+    diagram.diagram_walkers.groups = Some(vec![WalkerGroup {
+        group_name: "Posting".to_string(),
+        height: 330,
+        width: 930,
+        font_name: "Ubuntu".to_string(),
+        font_size: 9,
+        x: 140,
+        y: 340,
+        color: Color {
+            r: 200,
+            g: 230,
+            b: 255,
+        },
+        walkers: vec!["table.POSTS".to_string(), "table.POST_THREADS".to_string()],
+    }]);
+
+    let filename = std::env::temp_dir().join("erm_api_export_svg_notes.erm");
+    let filename = filename.to_str().unwrap();
+    save(filename, &diagram).expect("failed to write");
+    let written = open(filename).expect("failed to parse written file");
+    std::fs::remove_file(filename).ok();
+    assert_eq!(written, diagram);
+
+    let svg = render(&written, &SvgOptions::default());
+    assert!(svg.contains(">Members &amp; posts.</text>"));
+    assert!(svg.contains(">Replies are threaded.</text>"));
+    assert!(svg.contains(">Posting</text>"));
+    // Groups stay behind the tables, and notes in front of them.
+    let group = svg.find("<g class=\"group\"").unwrap();
+    let table = svg.find("<g class=\"table\"").unwrap();
+    let note = svg.find("<g class=\"note\"").unwrap();
+    assert!(group < table && table < note);
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::open_fixture;
+use erm::exporters::text::render;
+
+#[test]
+fn test_render_text() {
+    let diagram = open_fixture();
+    let text = render(&diagram);
+
+    assert!(text.starts_with(
+        "database MySQL
+
+table MEMBERS \"会員\"
+  MEMBER_ID \"会員ID\" bigint unsigned PK NOT NULL AUTO_INCREMENT
+  LAST_NAME \"苗字\" varchar(32) NOT NULL
+  FIRST_NAME \"名前\" varchar(32) NOT NULL
+  column_group COMMON
+
+table MEMBER_PROFILES \"会員プロフィール\"
+  MEMBER_PROFILE_ID \"会員プロフィールID\" bigint unsigned PK NOT NULL AUTO_INCREMENT
+  MEMBER_ID bigint unsigned FK(MEMBERS.MEMBER_ID) NOT NULL
+"
+    ));
+    assert!(text.contains(
+        "relationship FK_MEMBER_PROFILES_MEMBERS
+  MEMBERS (1) -> MEMBER_PROFILES (0..1)
+  columns MEMBER_ID
+  references primary key
+  on delete RESTRICT, on update RESTRICT
+"
+    ));
+    assert!(text.ends_with(
+        "column_group COMMON
+  CREATED_AT \"作成時間\" datetime NOT NULL
+  CREATED_BY \"作成会員ID\" bigint NOT NULL
+  UPDATED_AT \"更新時間\" datetime NOT NULL
+  UPDATED_BY \"更新会員ID\" bigint NOT NULL
+"
+    ));
+}
+
+#[test]
+fn test_render_text_is_independent_of_table_order() {
+    let diagram = open_fixture();
+    let mut reversed = diagram.clone();
+    reversed.diagram_walkers.tables.as_mut().unwrap().reverse();
+    for table in reversed.diagram_walkers.tables.as_mut().unwrap() {
+        table.x += 10;
+    }
+
+    assert_eq!(render(&reversed), render(&diagram));
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::open_fixture;
+use erm::dtos::diagram_walkers::{Column, NormalColumn};
+use erm::exporters::typescript::render;
+use erm::importers::ddl::{Dialect, import_script};
+
+#[test]
+fn test_render_typescript() {
+    let typescript = render(&open_fixture());
+    assert!(typescript.starts_with("import { z } from \"zod\";\n\n/** 会員 */\n"));
+    assert!(typescript.contains(
+        r#"/** マスター性別 */
+export interface MstGender {
+  /** 性別ID */
+  GENDER_ID: number;
+  /**
+   * 性別
+   *
+   * 「男性」または「女性」
+   */
+  GENDER: string;
+}
+
+/** マスター性別 */
+export const MstGenderSchema = z.object({
+  GENDER_ID: z.number().int().nonnegative(),
+  GENDER: z.string().max(2),
+}) satisfies z.ZodType<MstGender>;
+"#
+    ));
+    assert!(typescript.contains("  GENDER_ID: number | null;\n"));
+    assert!(typescript.contains("  GENDER_ID: z.number().int().nonnegative().nullable(),\n"));
+    // 64-bit integers do not fit in a number.
+    assert!(typescript.contains("  MEMBER_ID: bigint;\n"));
+    assert!(typescript.contains("  MEMBER_ID: z.coerce.bigint().nonnegative(),\n"));
+}
+
+#[test]
+fn test_render_typescript_enums_and_decimals() {
+    let mut diagram = open_fixture();
+    let tables = diagram.diagram_walkers.tables.as_mut().expect("no tables");
+    tables.truncate(1);
+    let columns = tables[0].columns.items.get_or_insert_default();
+    columns.clear();
+    columns.push(Column::Normal(NormalColumn {
+        physical_name: "STATUS".to_string(),
+        column_type: Some("enum".to_string()),
+        args: Some("'draft', 'published'".to_string()),
+        not_null: Some(true),
+        ..Default::default()
+    }));
+    columns.push(Column::Normal(NormalColumn {
+        physical_name: "PRICE".to_string(),
+        column_type: Some("decimal(p,s)".to_string()),
+        length: Some(10),
+        decimal: Some(2),
+        unsigned: Some(true),
+        ..Default::default()
+    }));
+    columns.push(Column::Normal(NormalColumn {
+        physical_name: "unit-price".to_string(),
+        column_type: Some("numeric(p)".to_string()),
+        length: Some(6),
+        not_null: Some(true),
+        ..Default::default()
+    }));
+
+    assert_eq!(
+        render(&diagram),
+        r#"import { z } from "zod";
+
+/** 会員 */
+export interface Members {
+  STATUS: "draft" | "published";
+  PRICE: string | null;
+  "unit-price": string;
+}
+
+/** 会員 */
+export const MembersSchema = z.object({
+  STATUS: z.enum(["draft", "published"]),
+  PRICE: z.string().regex(/^\d{1,8}(\.\d{1,2})?$/).nullable(),
+  "unit-price": z.string().regex(/^-?\d{1,6}$/),
+}) satisfies z.ZodType<Members>;
+"#
+    );
+}
+
+#[test]
+fn test_render_typescript_of_imported_enum() {
+    let result = import_script(
+        "CREATE TABLE POSTS (
+  POST_ID int NOT NULL,
+  STATUS ENUM('Draft', 'it''s out') NOT NULL,
+  PRIMARY KEY (POST_ID)
+);",
+        Dialect::MySql,
+    );
+    let typescript = render(&result.diagram);
+
+    assert!(typescript.contains("  STATUS: \"Draft\" | \"it's out\";\n"));
+    assert!(typescript.contains("  STATUS: z.enum([\"Draft\", \"it's out\"]),\n"));
+}
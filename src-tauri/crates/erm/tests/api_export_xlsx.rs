@@ -0,0 +1,185 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use calamine::{Data, Reader, Xlsx};
+use common::open_fixture;
+use erm::dtos::diagram::Diagram;
+use erm::dtos::diagram_settings::ExportSettings;
+use erm::exporters::xlsx::render;
+use std::io::Cursor;
+
+// A 1x1 transparent PNG.
+const PNG: &[u8] = &[
+    0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1F, 0x15, 0xC4,
+    0x89, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9C, 0x63, 0x00, 0x01, 0x00, 0x00,
+    0x05, 0x00, 0x01, 0x0D, 0x0A, 0x2D, 0xB4, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4E, 0x44, 0xAE,
+    0x42, 0x60, 0x82,
+];
+
+fn read(bytes: Vec<u8>) -> Xlsx<Cursor<Vec<u8>>> {
+    Xlsx::new(Cursor::new(bytes)).expect("failed to read workbook")
+}
+
+fn cell(workbook: &mut Xlsx<Cursor<Vec<u8>>>, sheet: &str, row: u32, column: u32) -> String {
+    let range = workbook.worksheet_range(sheet).unwrap();
+    match range.get_value((row, column)) {
+        Some(Data::String(value)) => value.clone(),
+        Some(Data::Float(value)) => value.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn with_settings(settings: ExportSettings) -> Diagram {
+    let mut diagram = open_fixture();
+    diagram.diagram_settings.export_settings = Some(settings);
+    diagram
+}
+
+#[test]
+fn test_render_xlsx() {
+    let diagram = open_fixture();
+    let mut workbook = read(render(&diagram, Some(PNG)).unwrap());
+
+    assert_eq!(
+        workbook.sheet_names(),
+        vec![
+            "Index",
+            "MEMBERS",
+            "MEMBER_PROFILES",
+            "MST_GENDER",
+            "POSTS",
+            "POST_REPLIES",
+            "POST_THREADS",
+            "Relationships",
+        ]
+    );
+    assert_eq!(cell(&mut workbook, "Index", 1, 1), "MEMBERS");
+    assert_eq!(cell(&mut workbook, "Index", 1, 3), "会員");
+
+    let row = |workbook: &mut Xlsx<_>, row| {
+        (0..11)
+            .map(|column| cell(workbook, "MEMBER_PROFILES", row, column))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        row(&mut workbook, 6),
+        vec![
+            "No",
+            "Physical name",
+            "Logical name",
+            "Type",
+            "PK",
+            "FK",
+            "NOT NULL",
+            "UNIQUE",
+            "Default",
+            "Group",
+            "Description",
+        ]
+    );
+    assert_eq!(
+        row(&mut workbook, 8),
+        vec![
+            "2",
+            "MEMBER_ID",
+            "会員ID",
+            "bigint unsigned",
+            "",
+            "MEMBERS.MEMBER_ID",
+            "✓",
+            "",
+            "",
+            "",
+            "",
+        ]
+    );
+    assert_eq!(cell(&mut workbook, "MEMBER_PROFILES", 12, 9), "COMMON");
+
+    assert_eq!(
+        (0..8)
+            .map(|column| cell(&mut workbook, "Relationships", 1, column))
+            .collect::<Vec<_>>(),
+        vec![
+            "FK_MEMBER_POSTS_MEMBERS",
+            "MEMBERS",
+            "POSTS",
+            "MEMBER_ID",
+            "0..1",
+            "0..n",
+            "RESTRICT",
+            "RESTRICT",
+        ]
+    );
+}
+
+#[test]
+fn test_render_xlsx_honors_export_settings() {
+    let diagram = with_settings(ExportSettings {
+        put_diagram_on_excel: Some(true),
+        use_logical_name_as_sheet: Some(true),
+        ..Default::default()
+    });
+    let workbook = read(render(&diagram, Some(PNG)).unwrap());
+
+    assert_eq!(
+        workbook.sheet_names(),
+        vec![
+            "Index",
+            "Diagram",
+            "会員",
+            "会員プロフィール",
+            "マスター性別",
+            "投稿",
+            "投稿返信",
+            "投稿スレッド",
+            "Relationships",
+        ]
+    );
+}
+
+#[test]
+fn test_render_xlsx_renders_the_diagram_image() {
+    let diagram = with_settings(ExportSettings {
+        put_diagram_on_excel: Some(true),
+        ..Default::default()
+    });
+    let workbook = read(render(&diagram, None).unwrap());
+
+    assert_eq!(workbook.sheet_names()[1], "Diagram");
+    let pictures = workbook.pictures().expect("no pictures");
+    assert_eq!(pictures.len(), 1);
+    let (extension, image) = &pictures[0];
+    assert_eq!(extension, "png");
+    assert!(image.starts_with(&PNG[..8]));
+    assert!(image.len() > PNG.len());
+
+    // Without the setting, there is no diagram sheet even with an image.
+    let diagram = with_settings(ExportSettings::default());
+    let workbook = read(render(&diagram, Some(PNG)).unwrap());
+    assert!(!workbook.sheet_names().contains(&"Diagram".to_string()));
+    assert_eq!(workbook.pictures(), None);
+}
+
+#[test]
+fn test_render_xlsx_sheet_names_are_valid() {
+    let mut diagram = with_settings(ExportSettings {
+        use_logical_name_as_sheet: Some(true),
+        ..Default::default()
+    });
+    let tables = diagram.diagram_walkers.tables.as_mut().unwrap();
+    tables[0].logical_name = "Members: registered/active and verified".to_string();
+    tables[1].logical_name = "Members: registered/active and verified too".to_string();
+    tables[2].logical_name = "index".to_string();
+    let workbook = read(render(&diagram, None).unwrap());
+
+    assert_eq!(
+        &workbook.sheet_names()[1..4],
+        &[
+            "Members_ registered_active and",
+            "Members_ registered_active (2)",
+            "index (2)",
+        ]
+    );
+}
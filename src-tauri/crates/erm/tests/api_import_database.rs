@@ -0,0 +1,521 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::normal_column;
+use erm::dtos::diagram_walkers::NormalColumn;
+use erm::importers::import_database;
+use erm::importers::information_schema::{
+    Catalog, ColumnRow, ConstraintType, IndexColumnRow, KeyColumnRow, TableRow, import_catalog,
+};
+use erm::{open, save};
+
+fn column(table_name: &str, column_name: &str, data_type: &str, nullable: bool) -> ColumnRow {
+    ColumnRow {
+        table_name: table_name.to_string(),
+        column_name: column_name.to_string(),
+        data_type: data_type.to_string(),
+        nullable,
+        ..Default::default()
+    }
+}
+
+fn key_column(
+    table_name: &str,
+    constraint_name: &str,
+    constraint_type: ConstraintType,
+    column_name: &str,
+) -> KeyColumnRow {
+    KeyColumnRow {
+        table_name: table_name.to_string(),
+        constraint_name: constraint_name.to_string(),
+        constraint_type,
+        column_name: column_name.to_string(),
+        referenced_table: None,
+        referenced_column: None,
+        delete_rule: None,
+        update_rule: None,
+    }
+}
+
+/// The rows PostgreSQL returns for a small schema.
+fn catalog() -> Catalog {
+    Catalog {
+        tables: vec![
+            TableRow {
+                table_name: "members".to_string(),
+                comment: Some("会員\n登録済みの会員".to_string()),
+            },
+            TableRow {
+                table_name: "posts".to_string(),
+                comment: None,
+            },
+        ],
+        columns: vec![
+            ColumnRow {
+                auto_increment: true,
+                comment: Some("会員ID".to_string()),
+                ..column("members", "member_id", "integer", false)
+            },
+            ColumnRow {
+                comment: Some("メールアドレス;ログインに使う".to_string()),
+                ..column("members", "email", "character varying(256)", false)
+            },
+            column("posts", "post_id", "bigint", false),
+            column("posts", "member_id", "integer", false),
+            ColumnRow {
+                default_value: Some("'draft'".to_string()),
+                ..column("posts", "status", "character varying(16)", true)
+            },
+            column("posts", "posted_at", "timestamp without time zone", true),
+            column("post_view", "post_id", "bigint", true),
+        ],
+        key_columns: vec![
+            key_column(
+                "members",
+                "members_pkey",
+                ConstraintType::PrimaryKey,
+                "member_id",
+            ),
+            key_column(
+                "members",
+                "members_email_key",
+                ConstraintType::Unique,
+                "email",
+            ),
+            key_column("posts", "posts_pkey", ConstraintType::PrimaryKey, "post_id"),
+            KeyColumnRow {
+                referenced_table: Some("members".to_string()),
+                referenced_column: Some("member_id".to_string()),
+                delete_rule: Some("CASCADE".to_string()),
+                update_rule: Some("NO ACTION".to_string()),
+                ..key_column(
+                    "posts",
+                    "posts_member_id_fkey",
+                    ConstraintType::ForeignKey,
+                    "member_id",
+                )
+            },
+        ],
+        index_columns: vec![
+            IndexColumnRow {
+                table_name: "members".to_string(),
+                index_name: "members_pkey".to_string(),
+                column_name: Some("member_id".to_string()),
+                unique: true,
+                descending: false,
+            },
+            IndexColumnRow {
+                table_name: "posts".to_string(),
+                index_name: "posts_posted_at_idx".to_string(),
+                column_name: Some("posted_at".to_string()),
+                unique: false,
+                descending: true,
+            },
+            IndexColumnRow {
+                table_name: "posts".to_string(),
+                index_name: "posts_lower_status_idx".to_string(),
+                column_name: None,
+                unique: false,
+                descending: false,
+            },
+        ],
+    }
+}
+
+#[test]
+fn test_import_catalog_maps_comments_to_names() {
+    let diagram = import_catalog("PostgreSQL", &catalog());
+
+    assert_eq!(diagram.diagram_settings.database, "PostgreSQL");
+    let table_names: Vec<&str> = diagram
+        .tables()
+        .iter()
+        .map(|table| table.physical_name.as_str())
+        .collect();
+    assert_eq!(table_names, vec!["members", "posts"]);
+
+    let members = diagram.find_table("members").unwrap();
+    assert_eq!(members.logical_name, "会員");
+    assert_eq!(members.description, "登録済みの会員");
+    assert_eq!(diagram.find_table("posts").unwrap().logical_name, "posts");
+
+    assert_eq!(
+        normal_column(&diagram, "members", "member_id"),
+        &NormalColumn {
+            physical_name: "member_id".to_string(),
+            logical_name: Some("会員ID".to_string()),
+            column_type: Some("integer".to_string()),
+            not_null: Some(true),
+            primary_key: Some(true),
+            auto_increment: Some(true),
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        normal_column(&diagram, "members", "email"),
+        &NormalColumn {
+            physical_name: "email".to_string(),
+            logical_name: Some("メールアドレス".to_string()),
+            description: Some("ログインに使う".to_string()),
+            column_type: Some("varchar(n)".to_string()),
+            length: Some(256),
+            not_null: Some(true),
+            unique_key: Some(true),
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        normal_column(&diagram, "posts", "status").default_value,
+        Some("draft".to_string())
+    );
+}
+
+#[test]
+fn test_import_catalog_keys_and_indexes() {
+    let diagram = import_catalog("PostgreSQL", &catalog());
+
+    let posts = diagram.find_table("posts").unwrap();
+    let relationship = &posts.relationships()[0];
+    assert_eq!(relationship.name, "posts_member_id_fkey");
+    assert_eq!(relationship.source, "table.members");
+    assert_eq!(relationship.target, "table.posts");
+    assert_eq!(relationship.on_delete_action, "CASCADE");
+    assert!(relationship.reference_for_pk);
+    assert_eq!(
+        normal_column(&diagram, "posts", "member_id")
+            .referred_column
+            .as_deref(),
+        Some("table.members.member_id")
+    );
+
+    let index_names: Vec<&str> = posts
+        .index_list()
+        .iter()
+        .map(|index| index.name.as_str())
+        .collect();
+    assert_eq!(index_names, vec!["posts_posted_at_idx"]);
+    assert_eq!(posts.index_list()[0].columns.to_string(), "posted_at DESC");
+    assert!(
+        diagram
+            .find_table("members")
+            .unwrap()
+            .index_list()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_import_catalog_can_be_saved() {
+    let diagram = import_catalog("PostgreSQL", &catalog());
+    let filename = std::env::temp_dir().join("erm_api_import_database.erm");
+    let filename = filename.to_str().unwrap();
+
+    save(filename, &diagram).expect("failed to write");
+    let written = open(filename).expect("failed to parse written file");
+    std::fs::remove_file(filename).ok();
+
+    assert_eq!(written, diagram);
+}
+
+#[test]
+fn test_import_database_rejects_unknown_url() {
+    let error = import_database("oracle://localhost/db").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "unsupported database URL: oracle://localhost/db"
+    );
+}
+
+/// The rows MySQL returns for a small schema: full `COLUMN_TYPE`s, empty comments and
+/// `PRIMARY` and foreign key indexes named after their constraints.
+fn mysql_catalog() -> Catalog {
+    Catalog {
+        tables: vec![
+            TableRow {
+                table_name: "members".to_string(),
+                comment: Some("会員".to_string()),
+            },
+            TableRow {
+                table_name: "posts".to_string(),
+                comment: Some(String::new()),
+            },
+        ],
+        columns: vec![
+            ColumnRow {
+                auto_increment: true,
+                comment: Some(String::new()),
+                ..column("members", "member_id", "int unsigned", false)
+            },
+            ColumnRow {
+                comment: Some(String::new()),
+                ..column("members", "last_name", "varchar(32)", false)
+            },
+            ColumnRow {
+                comment: Some(String::new()),
+                ..column("members", "first_name", "varchar(32)", false)
+            },
+            ColumnRow {
+                auto_increment: true,
+                comment: Some(String::new()),
+                ..column("posts", "post_id", "bigint unsigned", false)
+            },
+            ColumnRow {
+                comment: Some(String::new()),
+                ..column("posts", "member_id", "int unsigned", false)
+            },
+            ColumnRow {
+                default_value: Some("draft".to_string()),
+                comment: Some("状態".to_string()),
+                ..column("posts", "status", "enum('draft','published')", false)
+            },
+        ],
+        key_columns: vec![
+            key_column(
+                "members",
+                "PRIMARY",
+                ConstraintType::PrimaryKey,
+                "member_id",
+            ),
+            key_column("posts", "PRIMARY", ConstraintType::PrimaryKey, "post_id"),
+            KeyColumnRow {
+                referenced_table: Some("members".to_string()),
+                referenced_column: Some("member_id".to_string()),
+                delete_rule: Some("RESTRICT".to_string()),
+                update_rule: Some("CASCADE".to_string()),
+                ..key_column(
+                    "posts",
+                    "fk_posts_members",
+                    ConstraintType::ForeignKey,
+                    "member_id",
+                )
+            },
+        ],
+        // Ordered by SEQ_IN_INDEX, not by the position of the columns in the table.
+        index_columns: vec![
+            IndexColumnRow {
+                table_name: "members".to_string(),
+                index_name: "PRIMARY".to_string(),
+                column_name: Some("member_id".to_string()),
+                unique: true,
+                descending: false,
+            },
+            IndexColumnRow {
+                table_name: "members".to_string(),
+                index_name: "idx_members_name".to_string(),
+                column_name: Some("first_name".to_string()),
+                unique: false,
+                descending: false,
+            },
+            IndexColumnRow {
+                table_name: "members".to_string(),
+                index_name: "idx_members_name".to_string(),
+                column_name: Some("last_name".to_string()),
+                unique: false,
+                descending: true,
+            },
+            IndexColumnRow {
+                table_name: "posts".to_string(),
+                index_name: "PRIMARY".to_string(),
+                column_name: Some("post_id".to_string()),
+                unique: true,
+                descending: false,
+            },
+            IndexColumnRow {
+                table_name: "posts".to_string(),
+                index_name: "fk_posts_members".to_string(),
+                column_name: Some("member_id".to_string()),
+                unique: false,
+                descending: false,
+            },
+        ],
+    }
+}
+
+#[test]
+fn test_import_catalog_of_mysql() {
+    let diagram = import_catalog("MySQL", &mysql_catalog());
+
+    let members = diagram.find_table("members").unwrap();
+    assert_eq!(members.logical_name, "会員");
+    assert_eq!(
+        normal_column(&diagram, "members", "member_id"),
+        &NormalColumn {
+            physical_name: "member_id".to_string(),
+            column_type: Some("integer".to_string()),
+            unsigned: Some(true),
+            not_null: Some(true),
+            primary_key: Some(true),
+            auto_increment: Some(true),
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        members
+            .index_list()
+            .iter()
+            .map(|index| (index.name.as_str(), index.columns.to_string()))
+            .collect::<Vec<_>>(),
+        vec![("idx_members_name", "first_name, last_name DESC".to_string())]
+    );
+
+    let posts = diagram.find_table("posts").unwrap();
+    // An empty comment gives the physical name as the logical one.
+    assert_eq!(posts.logical_name, "posts");
+    assert_eq!(
+        normal_column(&diagram, "posts", "status"),
+        &NormalColumn {
+            physical_name: "status".to_string(),
+            logical_name: Some("状態".to_string()),
+            column_type: Some("enum".to_string()),
+            args: Some("'draft', 'published'".to_string()),
+            not_null: Some(true),
+            default_value: Some("draft".to_string()),
+            ..Default::default()
+        }
+    );
+    let post_id = normal_column(&diagram, "posts", "post_id");
+    assert_eq!(
+        (post_id.column_type.as_deref(), post_id.unsigned),
+        (Some("bigint"), Some(true))
+    );
+    // The index backing the foreign key is part of the relationship.
+    assert!(posts.index_list().is_empty());
+    let relationship = &posts.relationships()[0];
+    assert_eq!(relationship.name, "fk_posts_members");
+    assert_eq!(
+        (
+            relationship.on_delete_action.as_str(),
+            relationship.on_update_action.as_str()
+        ),
+        ("RESTRICT", "CASCADE")
+    );
+}
+
+/// Runs against a real server at `ERM_TEST_POSTGRES_URL`, e.g.
+/// `postgres://postgres@localhost:5432/postgres`, with `cargo test -- --ignored`.
+#[test]
+#[ignore = "needs a PostgreSQL server at ERM_TEST_POSTGRES_URL"]
+fn test_import_postgresql() {
+    let url = std::env::var("ERM_TEST_POSTGRES_URL").expect("ERM_TEST_POSTGRES_URL is not set");
+    let mut client = postgres::Client::connect(&url, postgres::NoTls).unwrap();
+    client
+        .batch_execute(
+            "DROP SCHEMA IF EXISTS erm_import_test CASCADE;
+            CREATE SCHEMA erm_import_test;
+            SET search_path TO erm_import_test;
+            CREATE TABLE members (
+                member_id serial PRIMARY KEY,
+                email varchar(256) NOT NULL UNIQUE,
+                point numeric(10, 2) DEFAULT 0
+            );
+            COMMENT ON TABLE members IS '会員';
+            COMMENT ON COLUMN members.email IS 'メールアドレス';
+            CREATE TABLE posts (
+                post_id bigint GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+                member_id integer NOT NULL
+                    CONSTRAINT fk_parent REFERENCES members ON DELETE CASCADE,
+                status varchar(16) DEFAULT 'draft',
+                posted_at timestamp
+            );
+            CREATE INDEX posts_posted_at_idx ON posts (posted_at DESC);
+            -- Constraint names are unique per table only.
+            CREATE TABLE replies (
+                reply_id serial PRIMARY KEY,
+                post_id bigint CONSTRAINT fk_parent REFERENCES posts ON DELETE SET NULL
+            );",
+        )
+        .unwrap();
+
+    let catalog = erm::importers::postgresql::read_catalog(&mut client).unwrap();
+    client
+        .batch_execute("DROP SCHEMA erm_import_test CASCADE")
+        .unwrap();
+    let diagram = import_catalog("PostgreSQL", &catalog);
+
+    assert_eq!(diagram.find_table("members").unwrap().logical_name, "会員");
+    let member_id = normal_column(&diagram, "members", "member_id");
+    assert_eq!(member_id.auto_increment, Some(true));
+    assert_eq!(member_id.default_value, None);
+    assert_eq!(
+        normal_column(&diagram, "members", "email").logical_name,
+        Some("メールアドレス".to_string())
+    );
+    assert_eq!(normal_column(&diagram, "members", "point").decimal, Some(2));
+    assert_eq!(
+        normal_column(&diagram, "posts", "status").default_value,
+        Some("draft".to_string())
+    );
+    assert_eq!(
+        normal_column(&diagram, "posts", "post_id").auto_increment,
+        Some(true)
+    );
+    let posts = diagram.find_table("posts").unwrap();
+    assert_eq!(posts.relationships()[0].on_delete_action, "CASCADE");
+    assert_eq!(
+        posts.relationships()[0].fk_columns.fk_column[0].fk_column_name,
+        "member_id"
+    );
+    assert_eq!(posts.index_list().len(), 1);
+    assert_eq!(posts.index_list()[0].columns.to_string(), "posted_at DESC");
+    assert_eq!(posts.relationships().len(), 1);
+    let replies = diagram.find_table("replies").unwrap();
+    assert_eq!(replies.relationships().len(), 1);
+    assert_eq!(replies.relationships()[0].on_delete_action, "SET NULL");
+    assert_eq!(
+        normal_column(&diagram, "replies", "post_id").referred_column,
+        Some("table.posts.post_id".to_string())
+    );
+}
+
+/// Runs against a real server at `ERM_TEST_MYSQL_URL`, e.g.
+/// `mysql://root@localhost:3306/test`, with `cargo test -- --ignored`.
+#[test]
+#[ignore = "needs a MySQL server at ERM_TEST_MYSQL_URL"]
+fn test_import_mysql() {
+    use mysql::prelude::Queryable;
+
+    let url = std::env::var("ERM_TEST_MYSQL_URL").expect("ERM_TEST_MYSQL_URL is not set");
+    let mut conn = mysql::Conn::new(mysql::Opts::from_url(&url).unwrap()).unwrap();
+    for statement in [
+        "DROP TABLE IF EXISTS erm_import_posts, erm_import_members",
+        "CREATE TABLE erm_import_members (
+            member_id int unsigned AUTO_INCREMENT PRIMARY KEY,
+            last_name varchar(32) NOT NULL,
+            first_name varchar(32) NOT NULL,
+            INDEX idx_members_name (first_name, last_name)
+        ) COMMENT '会員'",
+        "CREATE TABLE erm_import_posts (
+            post_id bigint unsigned AUTO_INCREMENT PRIMARY KEY,
+            member_id int unsigned NOT NULL,
+            status enum('draft','published') NOT NULL DEFAULT 'draft' COMMENT '状態',
+            CONSTRAINT fk_posts_members FOREIGN KEY (member_id)
+                REFERENCES erm_import_members (member_id) ON UPDATE CASCADE
+        )",
+    ] {
+        conn.query_drop(statement).unwrap();
+    }
+
+    let catalog = erm::importers::mysql::read_catalog(&mut conn).unwrap();
+    conn.query_drop("DROP TABLE erm_import_posts, erm_import_members")
+        .unwrap();
+    let diagram = import_catalog("MySQL", &catalog);
+
+    let members = diagram.find_table("erm_import_members").unwrap();
+    assert_eq!(members.logical_name, "会員");
+    assert_eq!(
+        members.index_list()[0].columns.to_string(),
+        "first_name, last_name"
+    );
+    let member_id = normal_column(&diagram, "erm_import_members", "member_id");
+    assert_eq!(
+        (member_id.unsigned, member_id.auto_increment),
+        (Some(true), Some(true))
+    );
+    let status = normal_column(&diagram, "erm_import_posts", "status");
+    assert_eq!(status.args.as_deref(), Some("'draft', 'published'"));
+    assert_eq!(status.default_value.as_deref(), Some("draft"));
+    let posts = diagram.find_table("erm_import_posts").unwrap();
+    assert!(posts.index_list().is_empty());
+    assert_eq!(posts.relationships()[0].on_update_action, "CASCADE");
+}
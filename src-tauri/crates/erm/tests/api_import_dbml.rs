@@ -0,0 +1,201 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::normal_column;
+use erm::dtos::diagram_settings::Category;
+use erm::dtos::diagram_walkers::NormalColumn;
+use erm::importers::dbml::{DbmlWarning, import_script};
+
+const SCRIPT: &str = "
+// Members and their posts
+Project blog {
+  database_type: 'PostgreSQL'
+  Note: 'A blog'
+}
+
+Enum post_status {
+  draft
+  published [note: 'visible']
+}
+
+Table public.members as M [headercolor: #3498DB] {
+  id integer [pk, increment, note: '会員ID']
+  email \"character varying(256)\" [not null, unique, note: '''
+    メールアドレス
+    ログインに使う
+  ''']
+  point decimal(10, 2) [default: 0.5]
+  created_at timestamp [not null, default: `now()`]
+  Note: '会員'
+}
+
+Table posts {
+  id bigint [primary key]
+  member_id integer [not null, ref: > M.id]
+  status post_status [default: 'draft']
+  title varchar(100) /* no note */
+
+  indexes {
+    (member_id, title) [unique, name: 'UK_POSTS_TITLE']
+    title
+    `lower(title)`
+  }
+}
+
+Table post_tags {
+  post_id bigint
+  tag varchar(32)
+
+  indexes {
+    (post_id, tag) [pk]
+  }
+}
+
+Table post_details {
+  post_id bigint [pk]
+}
+
+Ref: post_tags.post_id > posts.id [delete: cascade, update: no action]
+Ref FK_DETAILS { post_details.post_id - posts.id [delete: set null] }
+Ref: members.id <> post_tags.tag
+
+TableGroup content {
+  posts
+  post_tags
+  comments
+}
+";
+
+#[test]
+fn test_import_dbml_tables() {
+    let result = import_script(SCRIPT).unwrap();
+    let diagram = &result.diagram;
+
+    assert_eq!(diagram.diagram_settings.database, "PostgreSQL");
+    assert_eq!(diagram.find_table("members").unwrap().logical_name, "会員");
+    assert_eq!(diagram.find_table("posts").unwrap().logical_name, "posts");
+    assert_eq!(
+        normal_column(diagram, "members", "id"),
+        &NormalColumn {
+            physical_name: "id".to_string(),
+            logical_name: Some("会員ID".to_string()),
+            column_type: Some("integer".to_string()),
+            not_null: Some(true),
+            primary_key: Some(true),
+            auto_increment: Some(true),
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        normal_column(diagram, "members", "email"),
+        &NormalColumn {
+            physical_name: "email".to_string(),
+            logical_name: Some("メールアドレス".to_string()),
+            description: Some("ログインに使う".to_string()),
+            column_type: Some("varchar(n)".to_string()),
+            length: Some(256),
+            not_null: Some(true),
+            unique_key: Some(true),
+            ..Default::default()
+        }
+    );
+    let point = normal_column(diagram, "members", "point");
+    assert_eq!((point.length, point.decimal), (Some(10), Some(2)));
+    assert_eq!(point.default_value.as_deref(), Some("0.5"));
+    assert_eq!(
+        normal_column(diagram, "members", "created_at")
+            .default_value
+            .as_deref(),
+        Some("now()")
+    );
+    let status = normal_column(diagram, "posts", "status");
+    assert_eq!(status.column_type.as_deref(), Some("enum"));
+    assert_eq!(status.args.as_deref(), Some("'draft', 'published'"));
+    assert_eq!(status.default_value.as_deref(), Some("draft"));
+
+    let posts = diagram.find_table("posts").unwrap();
+    assert_eq!(
+        posts
+            .index_list()
+            .iter()
+            .map(|index| (
+                index.name.as_str(),
+                index.columns.to_string(),
+                index.is_unique()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            ("UK_POSTS_TITLE", "member_id, title".to_string(), true),
+            ("IDX_posts_title", "title".to_string(), false),
+        ]
+    );
+    assert_eq!(
+        normal_column(diagram, "post_tags", "tag").primary_key,
+        Some(true)
+    );
+}
+
+#[test]
+fn test_import_dbml_refs_and_groups() {
+    let result = import_script(SCRIPT).unwrap();
+    let diagram = &result.diagram;
+
+    let posts = diagram.find_table("posts").unwrap();
+    let relationship = &posts.relationships()[0];
+    assert_eq!(relationship.source, "table.members");
+    assert_eq!(
+        (
+            relationship.parent_cardinality.as_str(),
+            relationship.child_cardinality.as_str()
+        ),
+        ("1", "0..n")
+    );
+    assert_eq!(
+        normal_column(diagram, "posts", "member_id")
+            .referred_column
+            .as_deref(),
+        Some("table.members.id")
+    );
+
+    let tags = &diagram.find_table("post_tags").unwrap().relationships()[0];
+    assert_eq!(tags.on_delete_action, "CASCADE");
+    assert_eq!(tags.on_update_action, "NO ACTION");
+    let details = &diagram.find_table("post_details").unwrap().relationships()[0];
+    assert_eq!(details.name, "FK_DETAILS");
+    assert_eq!(details.child_cardinality, "0..1");
+    assert_eq!(details.on_delete_action, "SET NULL");
+
+    assert_eq!(
+        diagram.categories(),
+        &[Category {
+            name: "content".to_string(),
+            node_elements: vec!["table.posts".to_string(), "table.post_tags".to_string()],
+            selected: None,
+        }]
+    );
+    assert_eq!(
+        result.warnings,
+        vec![
+            DbmlWarning {
+                line: 33,
+                message: "index on an expression of posts is not imported".to_string(),
+            },
+            DbmlWarning {
+                line: 52,
+                message: "many-to-many ref between members and post_tags is not imported"
+                    .to_string(),
+            },
+            DbmlWarning {
+                line: 57,
+                message: "table group content has unknown table comments".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_import_dbml_syntax_error() {
+    let error = import_script("Table members {\n  id integer [pk\n}\n").unwrap_err();
+    assert_eq!(error.to_string(), "line 3: expected `,` or `]`");
+}
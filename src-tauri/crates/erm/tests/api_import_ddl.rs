@@ -0,0 +1,219 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::normal_column;
+use erm::dtos::diagram_walkers::NormalColumn;
+use erm::exporters::svg::{Rect, table_bounds};
+use erm::importers::ddl::{DdlWarning, Dialect, import_script};
+
+const MYSQL_SCRIPT: &str = "
+-- Members
+CREATE TABLE `MEMBERS` (
+  `MEMBER_ID` int unsigned NOT NULL AUTO_INCREMENT COMMENT '会員ID',
+  `EMAIL` varchar(256) NOT NULL COMMENT 'メールアドレス\nログインに使う',
+  `POINT` decimal(10,2) DEFAULT '0.00',
+  `CREATED_AT` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (`MEMBER_ID`),
+  UNIQUE KEY `UK_MEMBERS_EMAIL` (`EMAIL`)
+) ENGINE=InnoDB COMMENT='会員';
+
+CREATE TABLE `POSTS` (
+  `POST_ID` bigint NOT NULL AUTO_INCREMENT,
+  `MEMBER_ID` int unsigned NOT NULL,
+  `TITLE` varchar(100) NOT NULL,
+  PRIMARY KEY (`POST_ID`),
+  KEY `IX_POSTS_TITLE` (`TITLE`),
+  CONSTRAINT `FK_POSTS_MEMBERS` FOREIGN KEY (`MEMBER_ID`) REFERENCES `MEMBERS` (`MEMBER_ID`) ON DELETE CASCADE
+);
+
+CREATE INDEX `IX_MEMBERS_CREATED_AT` ON `MEMBERS` (`CREATED_AT` DESC);
+CREATE TABLE BROKEN (;
+";
+
+const POSTGRESQL_SCRIPT: &str = "
+SET statement_timeout = 0;
+
+CREATE TABLE public.members (
+    member_id integer NOT NULL,
+    email character varying(256) NOT NULL,
+    status character varying(16) DEFAULT 'active'::character varying
+);
+
+CREATE SEQUENCE public.members_member_id_seq AS integer;
+ALTER TABLE ONLY public.members ALTER COLUMN member_id SET DEFAULT nextval('public.members_member_id_seq'::regclass);
+
+CREATE TABLE public.posts (
+    post_id bigserial PRIMARY KEY,
+    member_id integer NOT NULL REFERENCES public.members,
+    title text
+);
+
+ALTER TABLE ONLY public.members
+    ADD CONSTRAINT members_pkey PRIMARY KEY (member_id);
+ALTER TABLE ONLY public.members
+    ADD CONSTRAINT members_email_key UNIQUE (email);
+CREATE INDEX posts_lower_title_idx ON public.posts (lower(title));
+
+COMMENT ON TABLE public.members IS '会員';
+COMMENT ON COLUMN public.members.email IS 'メールアドレス';
+COMMENT ON COLUMN public.accounts.email IS 'unknown';
+";
+
+#[test]
+fn test_import_mysql_script() {
+    let result = import_script(MYSQL_SCRIPT, Dialect::MySql);
+    let diagram = &result.diagram;
+
+    assert_eq!(diagram.diagram_settings.database, "MySQL");
+    let members = diagram.find_table("MEMBERS").unwrap();
+    assert_eq!(members.logical_name, "会員");
+    assert_eq!(
+        normal_column(diagram, "MEMBERS", "MEMBER_ID"),
+        &NormalColumn {
+            physical_name: "MEMBER_ID".to_string(),
+            logical_name: Some("会員ID".to_string()),
+            column_type: Some("integer".to_string()),
+            unsigned: Some(true),
+            not_null: Some(true),
+            primary_key: Some(true),
+            auto_increment: Some(true),
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        normal_column(diagram, "MEMBERS", "EMAIL"),
+        &NormalColumn {
+            physical_name: "EMAIL".to_string(),
+            logical_name: Some("メールアドレス".to_string()),
+            description: Some("ログインに使う".to_string()),
+            column_type: Some("varchar(n)".to_string()),
+            length: Some(256),
+            not_null: Some(true),
+            unique_key: Some(true),
+            ..Default::default()
+        }
+    );
+    let point = normal_column(diagram, "MEMBERS", "POINT");
+    assert_eq!((point.length, point.decimal), (Some(10), Some(2)));
+    assert_eq!(point.default_value.as_deref(), Some("0.00"));
+    assert_eq!(
+        normal_column(diagram, "MEMBERS", "CREATED_AT")
+            .default_value
+            .as_deref(),
+        Some("CURRENT_TIMESTAMP")
+    );
+    assert_eq!(
+        members.index_list()[0].columns.to_string(),
+        "CREATED_AT DESC"
+    );
+
+    let posts = diagram.find_table("POSTS").unwrap();
+    let relationship = &posts.relationships()[0];
+    assert_eq!(relationship.name, "FK_POSTS_MEMBERS");
+    assert_eq!(relationship.source, "table.MEMBERS");
+    assert_eq!(relationship.on_delete_action, "CASCADE");
+    assert_eq!(relationship.on_update_action, "RESTRICT");
+    assert_eq!(
+        normal_column(diagram, "POSTS", "MEMBER_ID")
+            .referred_column
+            .as_deref(),
+        Some("table.MEMBERS.MEMBER_ID")
+    );
+    assert_eq!(posts.index_list()[0].name, "IX_POSTS_TITLE");
+
+    assert_eq!(result.warnings.len(), 1);
+    assert_eq!(result.warnings[0].line, 23);
+    assert_eq!(result.warnings[0].statement, "CREATE TABLE BROKEN (");
+}
+
+#[test]
+fn test_import_postgresql_dump() {
+    let result = import_script(POSTGRESQL_SCRIPT, Dialect::PostgreSql);
+    let diagram = &result.diagram;
+
+    assert_eq!(diagram.diagram_settings.database, "PostgreSQL");
+    assert_eq!(diagram.find_table("members").unwrap().logical_name, "会員");
+    assert_eq!(
+        normal_column(diagram, "members", "member_id"),
+        &NormalColumn {
+            physical_name: "member_id".to_string(),
+            column_type: Some("integer".to_string()),
+            not_null: Some(true),
+            primary_key: Some(true),
+            auto_increment: Some(true),
+            ..Default::default()
+        }
+    );
+    let email = normal_column(diagram, "members", "email");
+    assert_eq!(email.logical_name.as_deref(), Some("メールアドレス"));
+    assert_eq!(email.unique_key, Some(true));
+    assert_eq!(
+        normal_column(diagram, "members", "status")
+            .default_value
+            .as_deref(),
+        Some("active")
+    );
+    assert_eq!(
+        normal_column(diagram, "posts", "post_id").auto_increment,
+        Some(true)
+    );
+
+    let posts = diagram.find_table("posts").unwrap();
+    assert_eq!(posts.relationships()[0].name, "FK_posts_members");
+    assert!(posts.relationships()[0].reference_for_pk);
+    assert_eq!(
+        normal_column(diagram, "posts", "member_id")
+            .referred_column
+            .as_deref(),
+        Some("table.members.member_id")
+    );
+    assert!(posts.index_list().is_empty());
+
+    assert_eq!(
+        result.warnings,
+        vec![
+            DdlWarning {
+                line: 23,
+                statement: "CREATE INDEX posts_lower_title_idx ON public.posts (lower(title))"
+                    .to_string(),
+                message: "index on expression lower(title) is not supported".to_string(),
+            },
+            DdlWarning {
+                line: 27,
+                statement: "COMMENT ON COLUMN public.accounts.email IS 'unknown'".to_string(),
+                message: "unknown table accounts".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_import_script_arranges_tables() {
+    let diagram = import_script(MYSQL_SCRIPT, Dialect::MySql).diagram;
+    let bounds: Vec<Rect> = diagram
+        .tables()
+        .iter()
+        .map(|table| {
+            // Tables are as large as drawn.
+            let bounds = table_bounds(&diagram, table);
+            assert_eq!(
+                (table.width, table.height),
+                (bounds.width.ceil() as u16, bounds.height.ceil() as u16)
+            );
+            bounds
+        })
+        .collect();
+    assert!(bounds.len() > 1);
+    for (index, a) in bounds.iter().enumerate() {
+        for b in &bounds[index + 1..] {
+            assert!(
+                a.x + a.width <= b.x
+                    || b.x + b.width <= a.x
+                    || a.y + a.height <= b.y
+                    || b.y + b.height <= a.y,
+                "{a:?} overlaps {b:?}"
+            );
+        }
+    }
+}
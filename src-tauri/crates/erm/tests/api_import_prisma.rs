@@ -0,0 +1,173 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::normal_column;
+use erm::dtos::diagram_walkers::NormalColumn;
+use erm::importers::prisma::{PrismaWarning, import_script};
+
+const SCHEMA: &str = r#"
+// Members and their posts
+datasource db {
+  provider = "postgresql"
+  url      = env("DATABASE_URL")
+}
+
+generator client {
+  provider = "prisma-client-js"
+}
+
+/// 会員
+/// ログインする人
+model User {
+  id        Int      @id @default(autoincrement())
+  /// メールアドレス
+  email     String   @unique @db.VarChar(256)
+  role      Role     @default(SUPER_ADMIN)
+  balance   Decimal  @default(0) @db.Decimal(10, 2)
+  token     String   @default(uuid())
+  createdAt DateTime @default(now()) @map("created_at")
+  mentorId  Int?     @map("mentor_id")
+  mentor    User?    @relation("Mentoring", fields: [mentorId], references: [id])
+  mentees   User[]   @relation("Mentoring")
+  posts     Post[]
+  tags      Tag[]
+
+  @@map("users")
+}
+
+model Post {
+  id       BigInt @id
+  authorId Int    @map("author_id")
+  title    String @db.VarChar(100)
+  geom     Unsupported("geometry")?
+  author   User   @relation(fields: [authorId], references: [id], onDelete: Cascade, map: "fk_posts_author")
+
+  @@unique([authorId, title], map: "uk_posts_title")
+  @@index([title(sort: Desc)])
+  @@map("posts")
+}
+
+model Tag {
+  name  String @id
+  users User[]
+}
+
+enum Role {
+  USER
+  SUPER_ADMIN @map("super admin")
+}
+
+view Summary {
+  id Int @unique
+}
+"#;
+
+#[test]
+fn test_import_prisma_models() {
+    let result = import_script(SCHEMA).unwrap();
+    let diagram = &result.diagram;
+
+    assert_eq!(diagram.diagram_settings.database, "PostgreSQL");
+    let users = diagram.find_table("users").unwrap();
+    assert_eq!(users.logical_name, "会員");
+    assert_eq!(users.description, "ログインする人");
+    assert_eq!(
+        normal_column(diagram, "users", "email"),
+        &NormalColumn {
+            physical_name: "email".to_string(),
+            logical_name: Some("メールアドレス".to_string()),
+            column_type: Some("varchar(n)".to_string()),
+            length: Some(256),
+            not_null: Some(true),
+            unique_key: Some(true),
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        normal_column(diagram, "users", "id").auto_increment,
+        Some(true)
+    );
+    let role = normal_column(diagram, "users", "role");
+    assert_eq!(role.column_type.as_deref(), Some("enum"));
+    assert_eq!(role.args.as_deref(), Some("'USER', 'super admin'"));
+    assert_eq!(role.default_value.as_deref(), Some("super admin"));
+    let balance = normal_column(diagram, "users", "balance");
+    assert_eq!((balance.length, balance.decimal), (Some(10), Some(2)));
+    let created_at = normal_column(diagram, "users", "created_at");
+    assert_eq!(created_at.column_type.as_deref(), Some("timestamp"));
+    assert_eq!(
+        created_at.default_value.as_deref(),
+        Some("CURRENT_TIMESTAMP")
+    );
+    assert_eq!(normal_column(diagram, "users", "token").default_value, None);
+    assert_eq!(normal_column(diagram, "users", "mentor_id").not_null, None);
+    assert_eq!(
+        normal_column(diagram, "posts", "geom")
+            .column_type
+            .as_deref(),
+        Some("geometry")
+    );
+
+    let posts = diagram.find_table("posts").unwrap();
+    assert_eq!(
+        posts
+            .index_list()
+            .iter()
+            .map(|index| (index.name.as_str(), index.columns.to_string()))
+            .collect::<Vec<_>>(),
+        vec![("IDX_posts_title", "title DESC".to_string())]
+    );
+    assert_eq!(posts.compound_unique_keys()[0].name, "uk_posts_title");
+}
+
+#[test]
+fn test_import_prisma_relations() {
+    let result = import_script(SCHEMA).unwrap();
+    let diagram = &result.diagram;
+
+    let mentor = &diagram.find_table("users").unwrap().relationships()[0];
+    assert_eq!(mentor.source, "table.users");
+    assert_eq!(
+        (
+            mentor.parent_cardinality.as_str(),
+            mentor.on_delete_action.as_str(),
+            mentor.on_update_action.as_str()
+        ),
+        ("0..1", "SET NULL", "CASCADE")
+    );
+    let author = &diagram.find_table("posts").unwrap().relationships()[0];
+    assert_eq!(author.name, "fk_posts_author");
+    assert_eq!(author.on_delete_action, "CASCADE");
+    assert_eq!(
+        normal_column(diagram, "posts", "author_id")
+            .referred_column
+            .as_deref(),
+        Some("table.users.id")
+    );
+    assert_eq!(
+        result.warnings,
+        vec![
+            PrismaWarning {
+                line: 53,
+                message: "view Summary is not imported".to_string(),
+            },
+            PrismaWarning {
+                line: 20,
+                message:
+                    "default uuid() of field token is generated by Prisma Client and is not kept"
+                        .to_string(),
+            },
+            PrismaWarning {
+                line: 45,
+                message: "many-to-many relation between Tag and User is not imported".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_import_prisma_syntax_error() {
+    let error = import_script("model User {\n  id Int @id(\n}\n").unwrap_err();
+    assert_eq!(error.to_string(), "line 3: expected a value");
+}
@@ -0,0 +1,161 @@
+use pretty_assertions::assert_eq;
+
+use erm::dtos::diagram_walkers::{
+    Column, FkColumn, FkColumns, Index, IndexColumn, IndexColumns, NormalColumn, Relationship,
+};
+use erm::importers::sqlite::import_connection;
+use rusqlite::Connection;
+
+fn connection() -> Connection {
+    let connection = Connection::open_in_memory().unwrap();
+    connection
+        .execute_batch(
+            "CREATE TABLE MEMBERS (
+                MEMBER_ID INTEGER PRIMARY KEY AUTOINCREMENT,
+                EMAIL VARCHAR(256) NOT NULL UNIQUE,
+                NICKNAME VARCHAR(32),
+                POINT DECIMAL(10, 2) DEFAULT 0,
+                STATUS TEXT NOT NULL DEFAULT 'active'
+            );
+            CREATE TABLE POSTS (
+                POST_ID INTEGER PRIMARY KEY,
+                MEMBER_ID INTEGER NOT NULL REFERENCES MEMBERS ON DELETE CASCADE,
+                TITLE VARCHAR(100) NOT NULL,
+                POSTED_AT DATETIME,
+                UNIQUE (MEMBER_ID, TITLE)
+            );
+            CREATE INDEX IX_POSTS_POSTED_AT ON POSTS (POSTED_AT DESC, POST_ID);",
+        )
+        .unwrap();
+    connection
+}
+
+fn normal_columns(items: &[Column]) -> Vec<&NormalColumn> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            Column::Normal(column) => Some(column),
+            Column::Group(_) => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_import_sqlite_columns() {
+    let diagram = import_connection(&connection()).expect("failed to import");
+
+    assert_eq!(diagram.diagram_settings.database, "SQLite");
+    let members = diagram.find_table("MEMBERS").unwrap();
+    assert_eq!(members.logical_name, "MEMBERS");
+    assert_eq!(
+        normal_columns(members.column_items()),
+        vec![
+            &NormalColumn {
+                physical_name: "MEMBER_ID".to_string(),
+                column_type: Some("integer".to_string()),
+                not_null: Some(true),
+                primary_key: Some(true),
+                auto_increment: Some(true),
+                ..Default::default()
+            },
+            &NormalColumn {
+                physical_name: "EMAIL".to_string(),
+                column_type: Some("varchar(n)".to_string()),
+                length: Some(256),
+                not_null: Some(true),
+                unique_key: Some(true),
+                ..Default::default()
+            },
+            &NormalColumn {
+                physical_name: "NICKNAME".to_string(),
+                column_type: Some("varchar(n)".to_string()),
+                length: Some(32),
+                ..Default::default()
+            },
+            &NormalColumn {
+                physical_name: "POINT".to_string(),
+                column_type: Some("decimal(p,s)".to_string()),
+                length: Some(10),
+                decimal: Some(2),
+                default_value: Some("0".to_string()),
+                ..Default::default()
+            },
+            &NormalColumn {
+                physical_name: "STATUS".to_string(),
+                column_type: Some("text".to_string()),
+                not_null: Some(true),
+                default_value: Some("active".to_string()),
+                ..Default::default()
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_import_sqlite_relationships_and_indexes() {
+    let diagram = import_connection(&connection()).expect("failed to import");
+
+    let posts = diagram.find_table("POSTS").unwrap();
+    assert_eq!(
+        posts.relationships(),
+        &[Relationship {
+            name: "FK_POSTS_MEMBERS".to_string(),
+            source: "table.MEMBERS".to_string(),
+            target: "table.POSTS".to_string(),
+            fk_columns: FkColumns {
+                fk_column: vec![FkColumn {
+                    fk_column_name: "MEMBER_ID".to_string(),
+                }],
+            },
+            parent_cardinality: "1".to_string(),
+            child_cardinality: "0..n".to_string(),
+            reference_for_pk: true,
+            on_delete_action: "CASCADE".to_string(),
+            on_update_action: "NO ACTION".to_string(),
+        }]
+    );
+
+    let member_id = normal_columns(posts.column_items())[1];
+    assert_eq!(
+        member_id.referred_column.as_deref(),
+        Some("table.MEMBERS.MEMBER_ID")
+    );
+    assert_eq!(member_id.relationship.as_deref(), Some("FK_POSTS_MEMBERS"));
+
+    assert_eq!(
+        posts.index_list(),
+        &[Index {
+            name: "IX_POSTS_POSTED_AT".to_string(),
+            index_type: None,
+            description: None,
+            full_text: None,
+            non_unique: Some(true),
+            columns: IndexColumns {
+                column: vec![
+                    IndexColumn {
+                        column_id: "POSTED_AT".to_string(),
+                        desc: Some(true),
+                    },
+                    IndexColumn {
+                        column_id: "POST_ID".to_string(),
+                        desc: None,
+                    },
+                ],
+            },
+        }]
+    );
+    let unique_keys = posts.compound_unique_keys();
+    assert_eq!(unique_keys.len(), 1);
+    assert_eq!(unique_keys[0].column_names(), vec!["MEMBER_ID", "TITLE"]);
+}
+
+#[test]
+fn test_import_sqlite_places_tables_without_overlap() {
+    let diagram = import_connection(&connection()).expect("failed to import");
+
+    let members = diagram.find_table("MEMBERS").unwrap();
+    let posts = diagram.find_table("POSTS").unwrap();
+    assert_eq!((members.x, members.y), (50, 50));
+    assert!(posts.x >= members.x + members.width);
+    assert_eq!(posts.y, members.y);
+}
@@ -0,0 +1,249 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::open_fixture;
+use erm::dtos::diagram::Diagram;
+use erm::dtos::diagram_walkers::{Color, Note, WalkerGroup};
+use erm::exporters::svg::{Rect, table_bounds};
+use erm::layout::{LayoutOptions, Strategy, layout, layout_all};
+
+fn note(x: u16, y: u16) -> Note {
+    Note {
+        note_text: "Note".to_string(),
+        height: 60,
+        width: 180,
+        font_name: "Ubuntu".to_string(),
+        font_size: 9,
+        x,
+        y,
+        color: Color {
+            r: 255,
+            g: 255,
+            b: 206,
+        },
+    }
+}
+
+fn bounds(diagram: &Diagram) -> Vec<(String, Rect)> {
+    let tables = diagram
+        .tables()
+        .iter()
+        .map(|table| (table.physical_name.clone(), table_bounds(diagram, table)));
+    let notes = diagram.notes().iter().enumerate().map(|(index, note)| {
+        let rect = Rect {
+            x: f64::from(note.x),
+            y: f64::from(note.y),
+            width: f64::from(note.width),
+            height: f64::from(note.height),
+        };
+        (format!("note {index}"), rect)
+    });
+    tables.chain(notes).collect()
+}
+
+fn assert_no_overlaps(diagram: &Diagram) {
+    let bounds = bounds(diagram);
+    for (index, (a, ra)) in bounds.iter().enumerate() {
+        for (b, rb) in &bounds[index + 1..] {
+            let overlap = ra.x < rb.x + rb.width
+                && rb.x < ra.x + ra.width
+                && ra.y < rb.y + rb.height
+                && rb.y < ra.y + ra.height;
+            assert!(!overlap, "{a} overlaps {b}");
+        }
+    }
+}
+
+/// The fixture with copies of its tables, so that there is something to arrange.
+fn many_tables(copies: usize) -> Diagram {
+    let mut diagram = open_fixture();
+    let tables = diagram.diagram_walkers.tables.as_mut().unwrap();
+    let originals = tables.clone();
+    for copy in 1..=copies {
+        for original in &originals {
+            let mut table = original.clone();
+            table.physical_name = format!("{}_{copy}", original.physical_name);
+            tables.push(table);
+        }
+    }
+    diagram
+}
+
+#[test]
+fn test_layout_layered() {
+    let mut diagram = open_fixture();
+    diagram.diagram_walkers.notes = Some(vec![note(0, 0)]);
+    let sizes: Vec<(u16, u16)> = diagram
+        .tables()
+        .iter()
+        .map(|table| (table.width, table.height))
+        .collect();
+    layout(
+        &mut diagram,
+        &LayoutOptions {
+            include_notes: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert_no_overlaps(&diagram);
+    assert_eq!(
+        diagram
+            .tables()
+            .iter()
+            .map(|table| (table.width, table.height))
+            .collect::<Vec<_>>(),
+        sizes
+    );
+    // Parents above their children.
+    for relationship in diagram.relationships() {
+        let parent = diagram.find_table(relationship.source_table()).unwrap();
+        let child = diagram.find_table(relationship.target_table()).unwrap();
+        let parent_bottom = table_bounds(&diagram, parent);
+        assert!(
+            parent_bottom.y + parent_bottom.height < f64::from(child.y),
+            "{}",
+            relationship.name
+        );
+    }
+    let top = diagram.tables().iter().map(|table| table.y).min().unwrap();
+    assert_eq!(top, 50);
+    assert_eq!(diagram.find_table("MEMBERS").unwrap().y, 50);
+    assert_eq!(diagram.find_table("MST_GENDER").unwrap().y, 50);
+    // The note has no relationship, so it goes below the tables.
+    let lowest = diagram.tables().iter().map(|table| table.y).max().unwrap();
+    assert!(diagram.notes()[0].y > lowest);
+}
+
+#[test]
+fn test_layout_layered_with_cycle() {
+    let mut diagram = open_fixture();
+    let tables = diagram.diagram_walkers.tables.as_mut().unwrap();
+    let posts = tables
+        .iter()
+        .find(|table| table.physical_name == "POSTS")
+        .unwrap();
+    let mut relationship = posts.relationships()[0].clone();
+    relationship.name = "FK_MEMBERS_POST_REPLIES".to_string();
+    relationship.source = "table.POST_REPLIES".to_string();
+    relationship.target = "table.MEMBERS".to_string();
+    let members = tables
+        .iter_mut()
+        .find(|table| table.physical_name == "MEMBERS")
+        .unwrap();
+    members
+        .connections
+        .relationships
+        .get_or_insert_with(Vec::new)
+        .push(relationship);
+
+    layout(&mut diagram, &LayoutOptions::default()).unwrap();
+    assert_no_overlaps(&diagram);
+}
+
+#[test]
+fn test_layout_force_directed_and_grid() {
+    for strategy in [Strategy::ForceDirected, Strategy::Grid] {
+        let mut diagram = many_tables(4);
+        diagram.diagram_walkers.notes = Some(vec![note(100, 100), note(100, 100)]);
+        let options = LayoutOptions {
+            strategy,
+            include_notes: true,
+            spacing: 40,
+            ..Default::default()
+        };
+        layout(&mut diagram, &options).unwrap();
+        assert_no_overlaps(&diagram);
+
+        // Only depends on the diagram.
+        let mut again = many_tables(4);
+        again.diagram_walkers.notes = Some(vec![note(100, 100), note(100, 100)]);
+        layout(&mut again, &options).unwrap();
+        assert_eq!(again, diagram, "{strategy:?}");
+    }
+}
+
+#[test]
+fn test_layout_selection() {
+    let mut diagram = open_fixture();
+    diagram.diagram_walkers.notes = Some(vec![note(160, 700)]);
+    diagram.diagram_walkers.groups = Some(vec![WalkerGroup {
+        group_name: "Posting".to_string(),
+        height: 100,
+        width: 100,
+        font_name: "Ubuntu".to_string(),
+        font_size: 9,
+        x: 0,
+        y: 0,
+        color: Color {
+            r: 200,
+            g: 230,
+            b: 255,
+        },
+        walkers: vec!["table.POSTS".to_string(), "table.POST_THREADS".to_string()],
+    }]);
+    let before = diagram.clone();
+    let options = LayoutOptions {
+        strategy: Strategy::Grid,
+        selection: vec!["table.POSTS".to_string(), "POST_THREADS".to_string()],
+        include_notes: true,
+        ..Default::default()
+    };
+    layout(&mut diagram, &options).unwrap();
+
+    assert_no_overlaps(&diagram);
+    for table in diagram.tables() {
+        if !["POSTS", "POST_THREADS"].contains(&table.physical_name.as_str()) {
+            let old = before.find_table(&table.physical_name).unwrap();
+            assert_eq!(
+                (table.x, table.y),
+                (old.x, old.y),
+                "{}",
+                table.physical_name
+            );
+        }
+    }
+    // Side by side from where the selection started.
+    let posts = diagram.find_table("POSTS").unwrap();
+    let threads = diagram.find_table("POST_THREADS").unwrap();
+    assert_eq!((posts.x, posts.y), (159, 364));
+    assert_eq!(threads.y, posts.y);
+    assert!(threads.x > posts.x);
+    // Notes stay when arranging a selection.
+    assert_eq!(diagram.notes(), before.notes());
+    // The group encloses its tables again.
+    let group = &diagram.diagram_walkers.groups.as_ref().unwrap()[0];
+    for name in ["POSTS", "POST_THREADS"] {
+        let table = table_bounds(&diagram, diagram.find_table(name).unwrap());
+        assert!(
+            f64::from(group.x) < table.x
+                && table.x + table.width < f64::from(group.x + group.width)
+        );
+        assert!(
+            f64::from(group.y) < table.y
+                && table.y + table.height < f64::from(group.y + group.height)
+        );
+    }
+
+    let error = layout(
+        &mut diagram,
+        &LayoutOptions {
+            selection: vec!["UNKNOWN".to_string()],
+            ..Default::default()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error.to_string(), "unknown table UNKNOWN");
+
+    // Arranging every table ignores the selection, so it cannot fail.
+    let options = LayoutOptions {
+        selection: vec!["UNKNOWN".to_string()],
+        ..Default::default()
+    };
+    let mut expected = diagram.clone();
+    layout(&mut expected, &LayoutOptions::default()).unwrap();
+    layout_all(&mut diagram, &options);
+    assert_eq!(diagram, expected);
+}
@@ -0,0 +1,221 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::{open_fixture, table_mut};
+use erm::dtos::diagram_settings::Category;
+use erm::dtos::diagram_walkers;
+use erm::dtos::dictionary::Word;
+use erm::merge::{MergeConflict, merge};
+
+fn email_column() -> diagram_walkers::Column {
+    diagram_walkers::Column::Normal(diagram_walkers::NormalColumn {
+        physical_name: "EMAIL".to_string(),
+        column_type: Some("varchar(n)".to_string()),
+        length: Some(256),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_merge_independent_changes() {
+    let base = open_fixture();
+    let mut ours = base.clone();
+    table_mut(&mut ours, "MEMBERS").x = 400;
+    let mut theirs = base.clone();
+    table_mut(&mut theirs, "MEMBERS")
+        .columns
+        .items
+        .as_mut()
+        .unwrap()
+        .insert(3, email_column());
+    theirs
+        .diagram_walkers
+        .tables
+        .as_mut()
+        .unwrap()
+        .retain(|table| table.physical_name != "POST_REPLIES");
+
+    let result = merge(&base, &ours, &theirs);
+
+    assert!(!result.has_conflicts());
+    let mut expected = theirs.clone();
+    table_mut(&mut expected, "MEMBERS").x = 400;
+    assert_eq!(result.diagram, expected);
+}
+
+#[test]
+fn test_merge_same_change_on_both_sides() {
+    let base = open_fixture();
+    let mut ours = base.clone();
+    table_mut(&mut ours, "MEMBERS").logical_name = "メンバー".to_string();
+    let theirs = ours.clone();
+
+    let result = merge(&base, &ours, &theirs);
+
+    assert!(!result.has_conflicts());
+    assert_eq!(result.diagram, ours);
+}
+
+#[test]
+fn test_merge_conflicting_changes() {
+    let base = open_fixture();
+    let mut ours = base.clone();
+    table_mut(&mut ours, "MEMBERS").x = 400;
+    table_mut(&mut ours, "MST_GENDER").logical_name = "性別".to_string();
+    let mut theirs = base.clone();
+    table_mut(&mut theirs, "MEMBERS").x = 500;
+    theirs
+        .diagram_walkers
+        .tables
+        .as_mut()
+        .unwrap()
+        .retain(|table| table.physical_name != "MST_GENDER");
+
+    let result = merge(&base, &ours, &theirs);
+
+    assert_eq!(
+        result.conflicts,
+        vec![
+            MergeConflict {
+                path: "table.MEMBERS.x".to_string(),
+                message: "changed on both sides: ours 400, theirs 500".to_string(),
+            },
+            MergeConflict {
+                path: "table.MST_GENDER".to_string(),
+                message: "modified in ours, deleted in theirs".to_string(),
+            },
+        ]
+    );
+    assert_eq!(result.diagram, ours);
+}
+
+#[test]
+fn test_merge_column_changed_on_both_sides() {
+    let base = open_fixture();
+    let mut ours = base.clone();
+    let mut theirs = base.clone();
+    for (diagram, length) in [(&mut ours, 64), (&mut theirs, 128)] {
+        if let diagram_walkers::Column::Normal(column) = &mut table_mut(diagram, "MEMBERS")
+            .columns
+            .items
+            .as_mut()
+            .unwrap()[1]
+        {
+            column.length = Some(length);
+        }
+    }
+
+    let result = merge(&base, &ours, &theirs);
+
+    assert_eq!(
+        result.conflicts,
+        vec![MergeConflict {
+            path: "table.MEMBERS.column.LAST_NAME".to_string(),
+            message: "modified differently on both sides".to_string(),
+        }]
+    );
+}
+
+fn note(text: &str, x: u16) -> diagram_walkers::Note {
+    diagram_walkers::Note {
+        note_text: text.to_string(),
+        height: 40,
+        width: 120,
+        font_name: "Ubuntu".to_string(),
+        font_size: 9,
+        x,
+        y: 20,
+        color: diagram_walkers::Color {
+            r: 255,
+            g: 255,
+            b: 206,
+        },
+    }
+}
+
+#[test]
+fn test_merge_settings_notes_and_vdiagrams() {
+    let mut base = open_fixture();
+    base.diagram_walkers.notes = Some(vec![note("TODO", 10), note("TODO", 300)]);
+    let mut ours = base.clone();
+    ours.page_settings.as_mut().unwrap().scale = Some(80);
+    ours.diagram_settings.notation = Some("IDEF1X".to_string());
+    ours.dictionary.words.push(Word {
+        physical_name: "MEMBER".to_string(),
+        logical_name: "会員".to_string(),
+        description: None,
+    });
+    ours.vdiagrams[0].tables[0].x = 400;
+    ours.diagram_walkers.notes.as_mut().unwrap()[1].x = 500;
+    let mut theirs = base.clone();
+    theirs.page_settings.as_mut().unwrap().paper_size = Some("A3 297 x 420 mm".to_string());
+    theirs.diagram_settings.suspend_validator = Some(true);
+    theirs
+        .diagram_settings
+        .category_settings
+        .get_or_insert_default()
+        .categories
+        .push(Category {
+            name: "会員".to_string(),
+            node_elements: vec!["table.MEMBERS".to_string()],
+            selected: None,
+        });
+    theirs.vdiagrams[0].tables[0].y = 250;
+    theirs.diagram_walkers.notes.as_mut().unwrap()[1].y = 80;
+
+    let result = merge(&base, &ours, &theirs);
+
+    assert_eq!(result.conflicts, vec![]);
+    let mut expected = ours.clone();
+    expected.page_settings = theirs.page_settings.clone();
+    expected.page_settings.as_mut().unwrap().scale = Some(80);
+    expected.diagram_settings.suspend_validator = Some(true);
+    expected.diagram_settings.category_settings = theirs.diagram_settings.category_settings.clone();
+    expected.vdiagrams[0].tables[0].y = 250;
+    expected.diagram_walkers.notes.as_mut().unwrap()[1].y = 80;
+    assert_eq!(result.diagram, expected);
+}
+
+#[test]
+fn test_merge_settings_changed_on_both_sides() {
+    let base = open_fixture();
+    let mut ours = base.clone();
+    let mut theirs = base.clone();
+    ours.diagram_settings.notation = Some("IDEF1X".to_string());
+    theirs.diagram_settings.notation = Some("IE".to_string());
+    ours.vdiagrams[0].tables[0].x = 400;
+    theirs.vdiagrams[0].tables[0].x = 410;
+    ours.dictionary.words.push(Word {
+        physical_name: "MEMBER".to_string(),
+        logical_name: "会員".to_string(),
+        description: None,
+    });
+    theirs.dictionary.words.push(Word {
+        physical_name: "MEMBER".to_string(),
+        logical_name: "メンバー".to_string(),
+        description: None,
+    });
+
+    let result = merge(&base, &ours, &theirs);
+
+    assert_eq!(
+        result.conflicts,
+        vec![
+            MergeConflict {
+                path: "diagram_settings.notation".to_string(),
+                message: r#"changed on both sides: ours Some("IDEF1X"), theirs Some("IE")"#
+                    .to_string(),
+            },
+            MergeConflict {
+                path: "word.MEMBER".to_string(),
+                message: "added differently on both sides".to_string(),
+            },
+            MergeConflict {
+                path: "vdiagram.sample.table.table.MEMBERS.x".to_string(),
+                message: "changed on both sides: ours 400, theirs 410".to_string(),
+            },
+        ]
+    );
+    assert_eq!(result.diagram, ours);
+}
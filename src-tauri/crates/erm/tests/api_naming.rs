@@ -0,0 +1,208 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::{column_mut, open_fixture, table_mut};
+use erm::dtos::diagram_walkers::{Index, IndexColumn, IndexColumns};
+use erm::naming::{CONFIG_FILE_NAME, NamingConfig, check};
+use erm::validation::{Diagnostic, Location, Rule, Severity};
+
+const CONFIG: &str = r#"
+[tables]
+case = "upper_snake"
+plural = true
+
+[columns]
+case = "upper_snake"
+
+[primary_keys]
+pattern = "{singular}_ID"
+
+[foreign_keys]
+match_referenced = true
+
+[relationships]
+prefix = "FK_"
+
+[indexes]
+prefix = "IX_"
+unique_prefix = "UX_"
+"#;
+
+fn index(name: &str, column: &str, unique: bool) -> Index {
+    Index {
+        name: name.to_string(),
+        index_type: None,
+        description: None,
+        full_text: None,
+        non_unique: Some(!unique),
+        columns: IndexColumns {
+            column: vec![IndexColumn {
+                column_id: column.to_string(),
+                desc: None,
+            }],
+        },
+    }
+}
+
+fn diagnostic(rule: Rule, location: Location, message: &str, suggestion: &str) -> Diagnostic {
+    Diagnostic {
+        rule,
+        severity: Severity::Warning,
+        location,
+        message: message.to_string(),
+        suggestion: Some(suggestion.to_string()),
+    }
+}
+
+#[test]
+fn test_naming_fixture() {
+    let config = NamingConfig::parse(CONFIG).unwrap();
+
+    assert_eq!(
+        check(&open_fixture(), &config),
+        vec![
+            diagnostic(
+                Rule::PluralName,
+                Location::table("MST_GENDER"),
+                "MST_GENDER is not plural",
+                "MST_GENDERS"
+            ),
+            diagnostic(
+                Rule::PrimaryKeyName,
+                Location::column("MST_GENDER", "GENDER_ID"),
+                "primary key GENDER_ID is not named MST_GENDER_ID",
+                "MST_GENDER_ID"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_naming_suggestions() {
+    let config = NamingConfig::parse(CONFIG).unwrap();
+    let mut diagram = open_fixture();
+    table_mut(&mut diagram, "MST_GENDER").physical_name = "genderCategory".to_string();
+    column_mut(&mut diagram, "MEMBERS", "LAST_NAME").physical_name = "lastName".to_string();
+    column_mut(&mut diagram, "POSTS", "MEMBER_ID").physical_name = "AUTHOR".to_string();
+    column_mut(&mut diagram, "POST_REPLIES", "MEMBER_ID").physical_name =
+        "REPLIER_MEMBER_ID".to_string();
+    table_mut(&mut diagram, "MEMBERS").indexes.indexes = Some(vec![
+        index("MEMBERS_FIRST_NAME", "FIRST_NAME", false),
+        index("IX_MEMBERS_LAST_NAME", "lastName", true),
+    ]);
+
+    let diagnostics = check(&diagram, &config);
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            diagnostic(
+                Rule::NameCase,
+                Location::column("MEMBERS", "lastName"),
+                "lastName is not UPPER_SNAKE",
+                "LAST_NAME"
+            ),
+            diagnostic(
+                Rule::NamePrefix,
+                Location::index("MEMBERS", "MEMBERS_FIRST_NAME"),
+                "MEMBERS_FIRST_NAME does not start with IX_",
+                "IX_MEMBERS_FIRST_NAME"
+            ),
+            diagnostic(
+                Rule::NamePrefix,
+                Location::index("MEMBERS", "IX_MEMBERS_LAST_NAME"),
+                "IX_MEMBERS_LAST_NAME does not start with UX_",
+                "UX_MEMBERS_LAST_NAME"
+            ),
+            diagnostic(
+                Rule::NameCase,
+                Location::table("genderCategory"),
+                "genderCategory is not UPPER_SNAKE",
+                "GENDER_CATEGORIES"
+            ),
+            diagnostic(
+                Rule::PluralName,
+                Location::table("genderCategory"),
+                "genderCategory is not plural",
+                "GENDER_CATEGORIES"
+            ),
+            diagnostic(
+                Rule::PrimaryKeyName,
+                Location::column("genderCategory", "GENDER_ID"),
+                "primary key GENDER_ID is not named genderCategory_ID",
+                "genderCategory_ID"
+            ),
+            diagnostic(
+                Rule::ForeignKeyName,
+                Location::column("POSTS", "AUTHOR"),
+                "AUTHOR is not named after the referenced column MEMBER_ID",
+                "MEMBER_ID"
+            ),
+        ]
+    );
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "warning[name-case] MEMBERS.lastName: lastName is not UPPER_SNAKE (rename to LAST_NAME)"
+    );
+}
+
+#[test]
+fn test_naming_last_word_of_unusual_names() {
+    let config = NamingConfig::parse(
+        "[tables]\nplural = true\n\n[primary_keys]\npattern = \"{singular}_ID\"\n",
+    )
+    .unwrap();
+    let mut diagram = open_fixture();
+    // The last word does not end a name with a trailing separator.
+    table_mut(&mut diagram, "MEMBERS").physical_name = "MEMBER_NAMES_".to_string();
+    table_mut(&mut diagram, "MST_GENDER").physical_name = "性別_".to_string();
+
+    assert_eq!(
+        check(&diagram, &config),
+        vec![
+            diagnostic(
+                Rule::PrimaryKeyName,
+                Location::column("MEMBER_NAMES_", "MEMBER_ID"),
+                "primary key MEMBER_ID is not named MEMBER_NAME__ID",
+                "MEMBER_NAME__ID"
+            ),
+            diagnostic(
+                Rule::PluralName,
+                Location::table("性別_"),
+                "性別_ is not plural",
+                "性別S_"
+            ),
+            diagnostic(
+                Rule::PrimaryKeyName,
+                Location::column("性別_", "GENDER_ID"),
+                "primary key GENDER_ID is not named 性別__ID",
+                "性別__ID"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_naming_config_next_to_diagram() {
+    let dir = std::env::temp_dir().join("erm_api_naming");
+    std::fs::create_dir_all(&dir).unwrap();
+    let filename = dir.join("testerd.erm");
+    let filename = filename.to_str().unwrap();
+    std::fs::copy("./tests/fixtures/testerd.erm", filename).unwrap();
+    let config = dir.join(CONFIG_FILE_NAME);
+
+    std::fs::remove_file(&config).ok();
+    assert_eq!(erm::validate_file(filename).unwrap(), vec![]);
+
+    std::fs::write(&config, "severity = \"error\"\n[tables]\nplural = true\n").unwrap();
+    let diagnostics = erm::validate_file(filename).unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(diagnostics[0].suggestion.as_deref(), Some("MST_GENDERS"));
+
+    std::fs::write(&config, "[tables]\nplurals = true\n").unwrap();
+    assert!(erm::validate_file(filename).is_err());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
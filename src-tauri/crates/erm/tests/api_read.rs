@@ -4,6 +4,9 @@ use erm::dtos::column_groups;
 use erm::dtos::diagram;
 use erm::dtos::diagram_settings;
 use erm::dtos::diagram_walkers;
+use erm::dtos::dictionary;
+use erm::dtos::page_settings;
+use erm::dtos::vdiagrams;
 use erm::open;
 
 // TODO: Add test cases of detailed condition for each field in https://github.com/s-kai273/ersflute/issues/22
@@ -14,9 +17,31 @@ fn test_read_erm_file() {
     assert_eq!(
         diagram,
         diagram::Diagram {
+            page_settings: Some(page_settings::PageSettings {
+                direction_horizontal: Some(true),
+                scale: Some(100),
+                paper_size: Some("A4 210 x 297 mm".to_string()),
+                top_margin: Some(30),
+                left_margin: Some(30),
+                bottom_margin: Some(30),
+                right_margin: Some(30),
+            }),
             diagram_settings: diagram_settings::DiagramSettings {
                 database: "MySQL".to_string(),
+                notation: Some("".to_string()),
+                validate_physical_name: Some(true),
+                suspend_validator: Some(false),
+                export_settings: Some(diagram_settings::ExportSettings {
+                    category_name_to_export: Some("".to_string()),
+                    ddl_output: Some("".to_string()),
+                    excel_output: Some("".to_string()),
+                    image_output: Some("".to_string()),
+                    put_diagram_on_excel: Some(false),
+                    use_logical_name_as_sheet: Some(false),
+                }),
+                category_settings: Some(diagram_settings::CategorySettings { categories: vec![] }),
             },
+            dictionary: dictionary::Dictionary::default(),
             diagram_walkers: diagram_walkers::DiagramWalkers {
                 tables: Some(vec![
                     diagram_walkers::Table {
@@ -71,6 +96,10 @@ fn test_read_erm_file() {
                                 diagram_walkers::Column::Group("COMMON".to_string()),
                             ]),
                         },
+                        indexes: diagram_walkers::Indexes { indexes: None },
+                        compound_unique_key_list: diagram_walkers::CompoundUniqueKeyList {
+                            compound_unique_keys: None,
+                        },
                     },
                     diagram_walkers::Table {
                         physical_name: "MEMBER_PROFILES".to_string(),
@@ -166,7 +195,11 @@ fn test_read_erm_file() {
                                 }),
                                 diagram_walkers::Column::Group("COMMON".to_string()),
                             ]),
-                        }
+                        },
+                        indexes: diagram_walkers::Indexes { indexes: None },
+                        compound_unique_key_list: diagram_walkers::CompoundUniqueKeyList {
+                            compound_unique_keys: None,
+                        },
                     },
                     diagram_walkers::Table {
                         physical_name: "MST_GENDER".to_string(),
@@ -211,7 +244,11 @@ fn test_read_erm_file() {
                                     ..Default::default()
                                 }),
                             ]),
-                        }
+                        },
+                        indexes: diagram_walkers::Indexes { indexes: None },
+                        compound_unique_key_list: diagram_walkers::CompoundUniqueKeyList {
+                            compound_unique_keys: None,
+                        },
                     },
                     diagram_walkers::Table {
                         physical_name: "POSTS".to_string(),
@@ -328,6 +365,10 @@ fn test_read_erm_file() {
                                 diagram_walkers::Column::Group("COMMON".to_string()),
                             ]),
                         },
+                        indexes: diagram_walkers::Indexes { indexes: None },
+                        compound_unique_key_list: diagram_walkers::CompoundUniqueKeyList {
+                            compound_unique_keys: None,
+                        },
                     },
                     diagram_walkers::Table {
                         physical_name: "POST_REPLIES".to_string(),
@@ -435,6 +476,10 @@ fn test_read_erm_file() {
                                 diagram_walkers::Column::Group("COMMON".to_string()),
                             ]),
                         },
+                        indexes: diagram_walkers::Indexes { indexes: None },
+                        compound_unique_key_list: diagram_walkers::CompoundUniqueKeyList {
+                            compound_unique_keys: None,
+                        },
                     },
                     diagram_walkers::Table {
                         physical_name: "POST_THREADS".to_string(),
@@ -494,9 +539,27 @@ fn test_read_erm_file() {
                                 diagram_walkers::Column::Group("COMMON".to_string()),
                             ]),
                         },
+                        indexes: diagram_walkers::Indexes { indexes: None },
+                        compound_unique_key_list: diagram_walkers::CompoundUniqueKeyList {
+                            compound_unique_keys: None,
+                        },
                     }
                 ]),
+                notes: None,
+                groups: None,
             },
+            vdiagrams: vec![vdiagrams::VDiagram {
+                name: "sample".to_string(),
+                tables: vec![vdiagrams::VTable {
+                    table_id: "table.MEMBERS".to_string(),
+                    x: 264,
+                    y: 182,
+                    font_name: "Ubuntu".to_string(),
+                    font_size: 9,
+                }],
+                notes: vec![],
+                groups: vec![],
+            }],
             column_groups: column_groups::ColumnGroups {
                 column_groups: Some(vec![column_groups::ColumnGroup {
                     column_group_name: "COMMON".to_string(),
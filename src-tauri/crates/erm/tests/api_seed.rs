@@ -0,0 +1,200 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+use std::collections::HashSet;
+
+use common::open_fixture;
+use erm::dtos::diagram::Diagram;
+use erm::importers::ddl::{Dialect, import_script};
+use erm::seed::{SeedOptions, SeedTable, SeedValue, generate, to_csv, to_sql};
+
+fn find<'a>(tables: &'a [SeedTable], name: &str) -> &'a SeedTable {
+    tables
+        .iter()
+        .find(|table| table.name == name)
+        .expect("no such table")
+}
+
+fn column<'a>(table: &'a SeedTable, name: &str) -> Vec<&'a SeedValue> {
+    (0..table.rows.len())
+        .map(|row| table.value(row, name).expect("no such column"))
+        .collect()
+}
+
+fn import(sql: &str) -> Diagram {
+    let imported = import_script(sql, Dialect::MySql);
+    assert_eq!(imported.warnings, vec![]);
+    imported.diagram
+}
+
+#[test]
+fn test_generate_seed() {
+    let diagram = open_fixture();
+    let options = SeedOptions { rows: 20, seed: 42 };
+    let tables = generate(&diagram, &options).expect("failed to generate");
+
+    // Parents come first.
+    let names: Vec<&str> = tables.iter().map(|table| table.name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "MEMBERS",
+            "MST_GENDER",
+            "MEMBER_PROFILES",
+            "POSTS",
+            "POST_THREADS",
+            "POST_REPLIES"
+        ]
+    );
+    assert!(tables.iter().all(|table| table.rows.len() == 20));
+
+    let members = find(&tables, "MEMBERS");
+    let member_ids: HashSet<&SeedValue> = column(members, "MEMBER_ID").into_iter().collect();
+    assert_eq!(member_ids.len(), 20);
+    for child in ["MEMBER_PROFILES", "POSTS", "POST_REPLIES"] {
+        let child = find(&tables, child);
+        assert!(
+            column(child, "MEMBER_ID")
+                .iter()
+                .all(|id| member_ids.contains(id))
+        );
+    }
+    // The unique foreign key of the one-to-one relationship takes each post once.
+    let post_ids: HashSet<&SeedValue> = column(find(&tables, "POST_THREADS"), "POST_ID")
+        .into_iter()
+        .collect();
+    assert_eq!(post_ids.len(), 20);
+
+    // Values follow the logical names and the lengths.
+    for gender in column(find(&tables, "MST_GENDER"), "GENDER") {
+        assert!(matches!(gender, SeedValue::Text(text) if text == "男性" || text == "女性"));
+    }
+    assert!(column(members, "LAST_NAME").iter().all(|name| matches!(
+        name,
+        SeedValue::Text(text) if !text.is_ascii() && text.chars().count() <= 32
+    )));
+
+    // The same seed gives the same rows, another one other rows.
+    assert_eq!(generate(&diagram, &options).unwrap(), tables);
+    let other = SeedOptions {
+        seed: 43,
+        ..options
+    };
+    assert_ne!(generate(&diagram, &other).unwrap(), tables);
+}
+
+#[test]
+fn test_generate_seed_constraints() {
+    let diagram = import(
+        "CREATE TABLE CODES (
+  CODE_ID tinyint NOT NULL,
+  CODE varchar(3) NOT NULL,
+  PRICE decimal(5, 2) NOT NULL,
+  STATUS enum('draft', 'published') NOT NULL,
+  PRIMARY KEY (CODE_ID),
+  UNIQUE (CODE)
+);
+CREATE TABLE CATEGORIES (
+  CATEGORY_ID int NOT NULL,
+  PARENT_ID int,
+  SHOP_ID int NOT NULL,
+  SLOT int NOT NULL,
+  PRIMARY KEY (CATEGORY_ID),
+  CONSTRAINT UK_CATEGORIES UNIQUE (SHOP_ID, SLOT),
+  CONSTRAINT FK_CATEGORIES_PARENT FOREIGN KEY (PARENT_ID) REFERENCES CATEGORIES (CATEGORY_ID)
+);",
+    );
+    let options = SeedOptions {
+        rows: 200,
+        ..Default::default()
+    };
+    let tables = generate(&diagram, &options).expect("failed to generate");
+
+    // `tinyint` runs out after 127, and codes too long for `varchar(3)` become numbers.
+    let codes = find(&tables, "CODES");
+    assert_eq!(codes.rows.len(), 127);
+    let code_values: HashSet<&SeedValue> = column(codes, "CODE").into_iter().collect();
+    assert_eq!(code_values.len(), 127);
+    assert!(code_values.contains(&SeedValue::Text("127".to_string())));
+    for price in column(codes, "PRICE") {
+        let SeedValue::Number(price) = price else {
+            panic!("not a number: {price:?}");
+        };
+        let (integer, fraction) = price.split_once('.').expect("no fraction");
+        assert!(integer.parse::<u32>().unwrap() <= 999 && fraction.len() == 2);
+    }
+    for status in column(codes, "STATUS") {
+        assert!(matches!(status, SeedValue::Text(text) if text == "draft" || text == "published"));
+    }
+
+    let categories = find(&tables, "CATEGORIES");
+    assert_eq!(categories.rows.len(), 200);
+    let slots: HashSet<(&SeedValue, &SeedValue)> = column(categories, "SHOP_ID")
+        .into_iter()
+        .zip(column(categories, "SLOT"))
+        .collect();
+    assert_eq!(slots.len(), 200);
+    // A self-reference refers to an earlier row, or is null.
+    let ids = column(categories, "CATEGORY_ID");
+    for (row, parent) in column(categories, "PARENT_ID").iter().enumerate() {
+        assert!(**parent == SeedValue::Null || ids[..row].contains(parent));
+    }
+}
+
+#[test]
+fn test_generate_seed_cycle() {
+    let sql = "CREATE TABLE A (ID int NOT NULL, B_ID int NOT NULL, PRIMARY KEY (ID));
+CREATE TABLE B (ID int NOT NULL, A_ID int NOT NULL, PRIMARY KEY (ID));
+ALTER TABLE A ADD CONSTRAINT FK_A_B FOREIGN KEY (B_ID) REFERENCES B (ID);
+ALTER TABLE B ADD CONSTRAINT FK_B_A FOREIGN KEY (A_ID) REFERENCES A (ID);";
+    let error = generate(&import(sql), &SeedOptions::default()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "the foreign keys of A, B form a cycle of not null columns"
+    );
+
+    // A nullable foreign key breaks the cycle.
+    let tables = generate(
+        &import(&sql.replace("B_ID int NOT NULL", "B_ID int")),
+        &SeedOptions::default(),
+    )
+    .expect("failed to generate");
+    assert_eq!(tables[0].name, "A");
+    assert!(
+        column(&tables[0], "B_ID")
+            .iter()
+            .all(|id| **id == SeedValue::Null)
+    );
+    assert_eq!(tables[1].rows.len(), 10);
+}
+
+#[test]
+fn test_seed_to_sql_and_csv() {
+    let table = SeedTable {
+        name: "ORDER".to_string(),
+        columns: vec!["ID".to_string(), "NOTE".to_string(), "PAID".to_string()],
+        rows: vec![
+            vec![
+                SeedValue::Number("1".to_string()),
+                SeedValue::Text("it's, fine".to_string()),
+                SeedValue::Boolean(true),
+            ],
+            vec![
+                SeedValue::Number("2".to_string()),
+                SeedValue::Null,
+                SeedValue::Boolean(false),
+            ],
+        ],
+    };
+    assert_eq!(
+        to_sql(std::slice::from_ref(&table), Dialect::MySql),
+        "INSERT INTO `ORDER` (ID, NOTE, PAID) VALUES
+  (1, 'it''s, fine', TRUE),
+  (2, NULL, FALSE);
+"
+    );
+    assert_eq!(
+        to_csv(&table).unwrap(),
+        "ID,NOTE,PAID\n1,\"it's, fine\",true\n2,,false\n"
+    );
+}
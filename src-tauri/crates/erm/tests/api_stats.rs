@@ -0,0 +1,31 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::open_fixture;
+use erm::stats::{TableStats, stats};
+
+#[test]
+fn test_stats() {
+    let diagram = open_fixture();
+    let stats = stats(&diagram);
+
+    assert_eq!(stats.database, "MySQL");
+    assert_eq!(stats.tables, 6);
+    assert_eq!(stats.columns, 48);
+    assert_eq!(stats.relationships, 6);
+    assert_eq!(stats.indexes, 0);
+    assert_eq!(stats.column_groups, 1);
+    assert_eq!(stats.tables_without_primary_key, Vec::<String>::new());
+    assert_eq!(
+        stats.table_stats[0],
+        TableStats {
+            physical_name: "MEMBERS".to_string(),
+            logical_name: "会員".to_string(),
+            columns: 7,
+            relationships: 0,
+            referenced_by: 3,
+            indexes: 0,
+        }
+    );
+}
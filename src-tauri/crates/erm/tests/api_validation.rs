@@ -0,0 +1,162 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::{column_mut, open_fixture, table_mut};
+use erm::dtos::diagram_walkers::{self, Column};
+use erm::validation::{Diagnostic, Location, Rule, Severity, validate};
+
+fn diagnostic(rule: Rule, location: Location, message: &str) -> Diagnostic {
+    Diagnostic {
+        rule,
+        severity: rule.severity(),
+        location,
+        message: message.to_string(),
+        suggestion: None,
+    }
+}
+
+#[test]
+fn test_validate_fixture_is_clean() {
+    assert_eq!(validate(&open_fixture()), vec![]);
+}
+
+#[test]
+fn test_validate_reports_problems() {
+    let mut diagram = open_fixture();
+    let mut gender = table_mut(&mut diagram, "MST_GENDER").clone();
+    gender.physical_name = "mst_gender".to_string();
+    diagram
+        .diagram_walkers
+        .tables
+        .as_mut()
+        .unwrap()
+        .push(gender);
+    column_mut(&mut diagram, "MEMBERS", "LAST_NAME").length = None;
+    column_mut(&mut diagram, "MEMBERS", "FIRST_NAME").physical_name = "ORDER".to_string();
+    column_mut(&mut diagram, "POST_REPLIES", "TEXT").physical_name = "CREATED_AT".to_string();
+    let posts = table_mut(&mut diagram, "POSTS");
+    let items = posts.columns.items.as_mut().unwrap();
+    items
+        .retain(|item| !matches!(item, Column::Normal(column) if column.primary_key == Some(true)));
+    items.push(Column::Group("AUDIT".to_string()));
+    let member_id = column_mut(&mut diagram, "POSTS", "MEMBER_ID");
+    member_id.column_type = Some("varchar(n)".to_string());
+    member_id.length = Some(10);
+    column_mut(&mut diagram, "POST_THREADS", "POST_ID").referred_column =
+        Some("table.POSTS.ID".to_string());
+    let relationship = &mut table_mut(&mut diagram, "POST_REPLIES")
+        .connections
+        .relationships
+        .as_mut()
+        .unwrap()[0];
+    relationship.source = "table.THREADS".to_string();
+    let relationship_name = relationship.name.clone();
+
+    let diagnostics = validate(&diagram);
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            diagnostic(
+                Rule::MissingLength,
+                Location::column("MEMBERS", "LAST_NAME"),
+                "varchar(n) needs a length"
+            ),
+            diagnostic(
+                Rule::ReservedWord,
+                Location::column("MEMBERS", "ORDER"),
+                "ORDER is a reserved word in MySQL"
+            ),
+            diagnostic(
+                Rule::ForeignKeyTypeMismatch,
+                Location::column("POSTS", "MEMBER_ID"),
+                "type varchar(10) differs from bigint unsigned of MEMBERS.MEMBER_ID"
+            ),
+            diagnostic(
+                Rule::UnknownColumnGroup,
+                Location::table("POSTS"),
+                "column group AUDIT does not exist"
+            ),
+            diagnostic(
+                Rule::MissingPrimaryKey,
+                Location::table("POSTS"),
+                "table has no primary key"
+            ),
+            diagnostic(
+                Rule::DuplicateColumnName,
+                Location::column("POST_REPLIES", "CREATED_AT"),
+                "column CREATED_AT is defined more than once (from column group COMMON)"
+            ),
+            diagnostic(
+                Rule::MissingRelationshipTable,
+                Location::relationship("POST_REPLIES", &relationship_name),
+                "table THREADS does not exist"
+            ),
+            diagnostic(
+                Rule::DanglingForeignKey,
+                Location::column("POST_THREADS", "POST_ID"),
+                "refers to POSTS.ID, which does not exist"
+            ),
+            diagnostic(
+                Rule::DuplicateTableName,
+                Location::table("mst_gender"),
+                "table mst_gender is defined more than once"
+            ),
+        ]
+    );
+    assert_eq!(diagnostics[1].severity, Severity::Warning);
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "error[missing-length] MEMBERS.LAST_NAME: varchar(n) needs a length"
+    );
+}
+
+#[test]
+fn test_validate_length_of_string_and_bit_types_only() {
+    let mut diagram = open_fixture();
+    let items = table_mut(&mut diagram, "MEMBERS")
+        .columns
+        .items
+        .as_mut()
+        .unwrap();
+    for (name, column_type) in [
+        ("POINT", "decimal(p,s)"),
+        ("GRADE", "int(n)"),
+        ("RATE", "float(p)"),
+        ("FLAGS", "bit(n)"),
+    ] {
+        items.push(Column::Normal(diagram_walkers::NormalColumn {
+            physical_name: name.to_string(),
+            column_type: Some(column_type.to_string()),
+            ..Default::default()
+        }));
+    }
+
+    assert_eq!(
+        validate(&diagram),
+        vec![diagnostic(
+            Rule::MissingLength,
+            Location::column("MEMBERS", "FLAGS"),
+            "bit(n) needs a length"
+        )]
+    );
+}
+
+#[test]
+fn test_validate_foreign_key_to_column_without_type() {
+    let mut diagram = open_fixture();
+    // The referred column exists, so the keys are not dangling, only unchecked.
+    column_mut(&mut diagram, "MEMBERS", "MEMBER_ID").column_type = None;
+    assert_eq!(validate(&diagram), vec![]);
+}
+
+#[test]
+fn test_validate_physical_name_can_be_turned_off() {
+    let mut diagram = open_fixture();
+    column_mut(&mut diagram, "MEMBERS", "FIRST_NAME").physical_name = "SELECT".to_string();
+    assert_eq!(validate(&diagram).len(), 1);
+
+    diagram.diagram_settings.validate_physical_name = Some(false);
+    assert_eq!(validate(&diagram), vec![]);
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use common::FIXTURE;
+use erm::dtos::diagram_walkers::Column;
+use erm::{open, save, update};
+
+#[test]
+fn test_write_erm_file_round_trip() {
+    let diagram = open(FIXTURE).expect("failed to parse");
+    let filename = std::env::temp_dir().join("erm_api_write_round_trip.erm");
+    let filename = filename.to_str().unwrap();
+
+    save(filename, &diagram).expect("failed to write");
+    let written = open(filename).expect("failed to parse written file");
+    std::fs::remove_file(filename).ok();
+
+    assert_eq!(written, diagram);
+}
+
+#[test]
+fn test_update_erm_file_keeps_unmodeled_sections() {
+    let original = std::fs::read_to_string(FIXTURE).expect("failed to read");
+    let filename = std::env::temp_dir().join("erm_api_write_update.erm");
+    let filename = filename.to_str().unwrap();
+    std::fs::write(filename, &original).expect("failed to write");
+
+    // Nothing changes a file written over with what was read from it.
+    let mut diagram = open(FIXTURE).expect("failed to parse");
+    update(filename, &diagram).expect("failed to update");
+    assert_eq!(std::fs::read_to_string(filename).unwrap(), original);
+
+    // Changes are written, and removed fields are removed.
+    let tables = diagram.diagram_walkers.tables.as_mut().expect("no tables");
+    tables[0].x = 321;
+    let gender = tables
+        .iter_mut()
+        .find(|table| table.physical_name == "MST_GENDER")
+        .expect("no MST_GENDER");
+    for item in gender.columns.items.get_or_insert_default() {
+        if let Column::Normal(column) = item {
+            column.description = None;
+        }
+    }
+    update(filename, &diagram).expect("failed to update");
+    let written = std::fs::read_to_string(filename).unwrap();
+    let reopened = open(filename).expect("failed to parse written file");
+    std::fs::remove_file(filename).ok();
+
+    assert_eq!(reopened, diagram);
+    assert!(!written.contains("「男性」または「女性」"));
+    for section in [
+        "<presenter>ERFlute</presenter>",
+        "<model_properties>",
+        "<environment_settings>",
+        "<tablespace_set>",
+        "<sequence_set>",
+        "<trigger_set>",
+    ] {
+        assert!(written.contains(section), "{section} is lost");
+    }
+    assert_eq!(
+        written.lines().count(),
+        original.lines().count() - 1,
+        "only the description line goes"
+    );
+}
@@ -0,0 +1,64 @@
+//! Helpers shared by the integration tests. Each test crate uses only some of them.
+#![allow(dead_code)]
+
+use erm::dtos::diagram::Diagram;
+use erm::dtos::diagram_walkers::{Column, NormalColumn, Table};
+use erm::open;
+
+pub const FIXTURE: &str = "./tests/fixtures/testerd.erm";
+
+pub fn open_fixture() -> Diagram {
+    open(FIXTURE).expect("failed to parse")
+}
+
+/// The fixture with each `(from, to)` replaced throughout the file, e.g. to rename a
+/// table along with every reference to it. `name` keeps the temporary files apart.
+pub fn open_fixture_replaced(name: &str, replacements: &[(&str, &str)]) -> Diagram {
+    let mut content = std::fs::read_to_string(FIXTURE).expect("failed to read");
+    for (from, to) in replacements {
+        content = content.replace(from, to);
+    }
+    let filename = std::env::temp_dir().join(format!("erm_{name}.erm"));
+    std::fs::write(&filename, content).expect("failed to write");
+    let diagram = open(filename.to_str().unwrap()).expect("failed to parse");
+    std::fs::remove_file(&filename).ok();
+    diagram
+}
+
+pub fn table_mut<'a>(diagram: &'a mut Diagram, physical_name: &str) -> &'a mut Table {
+    diagram
+        .diagram_walkers
+        .tables
+        .as_mut()
+        .unwrap()
+        .iter_mut()
+        .find(|table| table.physical_name == physical_name)
+        .unwrap()
+}
+
+pub fn column_mut<'a>(diagram: &'a mut Diagram, table: &str, column: &str) -> &'a mut NormalColumn {
+    table_mut(diagram, table)
+        .columns
+        .items
+        .as_mut()
+        .unwrap()
+        .iter_mut()
+        .find_map(|item| match item {
+            Column::Normal(normal) if normal.physical_name == column => Some(normal),
+            _ => None,
+        })
+        .unwrap()
+}
+
+pub fn normal_column<'a>(diagram: &'a Diagram, table: &str, column: &str) -> &'a NormalColumn {
+    diagram
+        .find_table(table)
+        .unwrap()
+        .column_items()
+        .iter()
+        .find_map(|item| match item {
+            Column::Normal(normal) if normal.physical_name == column => Some(normal),
+            _ => None,
+        })
+        .unwrap()
+}
@@ -0,0 +1,388 @@
+//! Command-line interface to .erm files for CI and scripts.
+//!
+//! ```text
+//! erm validate <diagram.erm> [--strict] [--json]
+//! erm ddl <diagram.erm> [--dialect mysql|postgresql] [--output <file>]
+//! erm diff <old.erm> <new.erm> [--visual] [--json]
+//! erm export <diagram.erm> --format text|json|markdown|svg|mermaid|plantuml|dbml|prisma|typescript [--only <name>] [--output <file>]
+//! erm export <diagram.erm> --format json-schema|openapi [--logical-names] [--only <name>] [--output <file>]
+//! erm export <diagram.erm> --format dot [--collapse] [--colors] [--around <table> [--hops <n>]] [--only <name>] [--output <file>]
+//! erm export <diagram.erm> --format html [--only <name>] --output <directory>
+//! erm export <diagram.erm> --format sea-orm|diesel [--only <name>] --output <directory>
+//! erm export <diagram.erm> --format xlsx [--only <name>] [--output <file>]
+//! erm export <diagram.erm> --format png [--scale <percent>] [--only <name>] [--output <file>]
+//! erm stats <diagram.erm> [--json]
+//! erm seed <diagram.erm> [--rows <n>] [--seed <n>] [--dialect mysql|postgresql] [--output <file>]
+//! erm seed <diagram.erm> --csv [--rows <n>] [--seed <n>] --output <directory>
+//! erm drift <diagram.erm> --sqlite <file> [--json]
+//! erm drift <diagram.erm> --ddl <file> [--dialect mysql|postgresql] [--json]
+//! erm drift <diagram.erm> --database <url> [--json]
+//! ```
+//!
+//! `validate` checks the diagram and the naming conventions next to it, unless the
+//! validator is suspended in its settings, and exits with status 1 when there are
+//! errors, or any finding at all with `--strict`. `diff` and `drift` exit with status 1
+//! when there are differences, so they can fail a CI job. `--json` prints the result as
+//! JSON instead of text. `export --only` exports just the category or virtual diagram
+//! of that name. `--around` limits the DOT graph to the tables within `--hops` (1 by
+//! default) relationships of that table. `seed` generates `--rows` (10 by default) rows
+//! of test data per table, and the same `--seed` always gives the same rows.
+//!
+//! Errors, including usage errors, exit with status 2.
+
+use erm::diff::DiffOptions;
+use erm::drift::{self, DriftTarget};
+use erm::dtos::diagram::Diagram;
+use erm::dtos::diagram_settings::ExportSettings;
+use erm::exporters::dot::{DotOptions, Neighbourhood};
+use erm::exporters::json_schema::{JsonSchemaOptions, PropertyNames, SchemaFormat};
+use erm::exporters::png::PngOptions;
+use erm::exporters::rust::Orm;
+use erm::importers::ddl::Dialect;
+use erm::seed::SeedOptions;
+use erm::validation::Severity;
+use serde::Serialize;
+use std::env;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage:
+  erm validate <diagram.erm> [--strict] [--json]
+  erm ddl <diagram.erm> [--dialect mysql|postgresql] [--output <file>]
+  erm diff <old.erm> <new.erm> [--visual] [--json]
+  erm export <diagram.erm> --format text|json|markdown|svg|mermaid|plantuml|dbml|prisma|typescript [--only <name>] [--output <file>]
+  erm export <diagram.erm> --format json-schema|openapi [--logical-names] [--only <name>] [--output <file>]
+  erm export <diagram.erm> --format dot [--collapse] [--colors] [--around <table> [--hops <n>]] [--only <name>] [--output <file>]
+  erm export <diagram.erm> --format html [--only <name>] --output <directory>
+  erm export <diagram.erm> --format sea-orm|diesel [--only <name>] --output <directory>
+  erm export <diagram.erm> --format xlsx [--only <name>] [--output <file>]
+  erm export <diagram.erm> --format png [--scale <percent>] [--only <name>] [--output <file>]
+  erm stats <diagram.erm> [--json]
+  erm seed <diagram.erm> [--rows <n>] [--seed <n>] [--dialect mysql|postgresql] [--output <file>]
+  erm seed <diagram.erm> --csv [--rows <n>] [--seed <n>] --output <directory>
+  erm drift <diagram.erm> --sqlite <file> [--json]
+  erm drift <diagram.erm> --ddl <file> [--dialect mysql|postgresql] [--json]
+  erm drift <diagram.erm> --database <url> [--json]";
+
+type Error = Box<dyn std::error::Error>;
+
+/// Printed as the usage text alone rather than as an error message.
+#[derive(Debug)]
+struct UsageError;
+
+impl std::fmt::Display for UsageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(USAGE)
+    }
+}
+
+impl std::error::Error for UsageError {}
+
+/// Returns the value following `name`, e.g. the file of `--sqlite <file>`.
+fn option<'a>(args: &'a [String], name: &str) -> Result<Option<&'a str>, Error> {
+    match args.iter().position(|arg| arg == name) {
+        Some(position) => match args.get(position + 1) {
+            Some(value) => Ok(Some(value)),
+            None => Err(format!("{name} needs a value").into()),
+        },
+        None => Ok(None),
+    }
+}
+
+fn flag(args: &[String], name: &str) -> bool {
+    args.iter().any(|arg| arg == name)
+}
+
+/// Splits the leading `N` arguments, e.g. the diagram files, from the options.
+fn positional<const N: usize>(args: &[String]) -> Result<(&[String; N], &[String]), Error> {
+    match args.split_first_chunk::<N>() {
+        Some((files, options)) if !files.iter().any(|arg| arg.starts_with("--")) => {
+            Ok((files, options))
+        }
+        _ => Err(UsageError.into()),
+    }
+}
+
+fn dialect(options: &[String], diagram: &Diagram) -> Result<Dialect, Error> {
+    match option(options, "--dialect")? {
+        Some(name) => Ok(Dialect::parse(name).ok_or_else(|| format!("unknown dialect {name}"))?),
+        None => Ok(
+            Dialect::from_database(&diagram.diagram_settings.database).unwrap_or(Dialect::MySql)
+        ),
+    }
+}
+
+fn print_json(value: &impl Serialize) -> Result<(), Error> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Writes `content` to the file of `--output`, or to stdout.
+fn write_output(options: &[String], content: &str) -> Result<(), Error> {
+    match option(options, "--output")? {
+        Some(filename) => std::fs::write(filename, content)?,
+        None => print!("{content}"),
+    }
+    Ok(())
+}
+
+fn validate(args: &[String]) -> Result<bool, Error> {
+    let ([filename], options) = positional(args)?;
+    let diagnostics = erm::validate_file(filename)?;
+    if flag(options, "--json") {
+        print_json(&diagnostics)?;
+    } else {
+        for diagnostic in &diagnostics {
+            println!("{diagnostic}");
+        }
+    }
+    let strict = flag(options, "--strict");
+    Ok(!diagnostics
+        .iter()
+        .any(|diagnostic| strict || diagnostic.severity == Severity::Error))
+}
+
+fn ddl(args: &[String]) -> Result<bool, Error> {
+    let ([filename], options) = positional(args)?;
+    let diagram = erm::open(filename)?;
+    let dialect = dialect(options, &diagram)?;
+    write_output(options, &erm::exporters::ddl::generate(&diagram, dialect))?;
+    Ok(true)
+}
+
+fn diff(args: &[String]) -> Result<bool, Error> {
+    let ([old, new], options) = positional(args)?;
+    let diff_options = DiffOptions {
+        include_visual: flag(options, "--visual"),
+    };
+    let diff = erm::diff_files(old, new, &diff_options)?;
+    if flag(options, "--json") {
+        print_json(&diff)?;
+    } else {
+        print!("{diff}");
+    }
+    Ok(diff.is_empty())
+}
+
+/// The file of `--output`, or the one configured in the export settings.
+fn binary_output<'a>(
+    options: &'a [String],
+    diagram: &'a Diagram,
+    format: &str,
+    setting: &str,
+    configured: fn(&ExportSettings) -> Option<&str>,
+) -> Result<&'a str, Error> {
+    let configured = diagram
+        .diagram_settings
+        .export_settings
+        .as_ref()
+        .and_then(configured)
+        .filter(|filename| !filename.is_empty());
+    Ok(option(options, "--output")?
+        .or(configured)
+        .ok_or_else(|| format!("{format} needs --output or export_settings.{setting}"))?)
+}
+
+fn dot_options(options: &[String]) -> Result<DotOptions, Error> {
+    let neighbourhood = match option(options, "--around")? {
+        Some(table) => Some(Neighbourhood {
+            table: table.to_string(),
+            hops: match option(options, "--hops")? {
+                Some(hops) => hops.parse().map_err(|_| format!("invalid --hops {hops}"))?,
+                None => 1,
+            },
+        }),
+        None => None,
+    };
+    Ok(DotOptions {
+        collapse_columns: flag(options, "--collapse"),
+        use_table_colors: flag(options, "--colors"),
+        neighbourhood,
+    })
+}
+
+fn export(args: &[String]) -> Result<bool, Error> {
+    let ([filename], options) = positional(args)?;
+    let mut diagram = erm::open(filename)?;
+    if let Some(name) = option(options, "--only")? {
+        diagram = diagram
+            .extract(name)
+            .ok_or_else(|| format!("no category or virtual diagram named {name}"))?;
+    }
+    let content = match option(options, "--format")? {
+        Some("text") => erm::exporters::text::render(&diagram),
+        Some("json") => serde_json::to_string_pretty(&diagram)? + "\n",
+        Some("markdown") => erm::exporters::markdown::render(&diagram),
+        Some("svg") => erm::exporters::svg::render(&diagram, &Default::default()),
+        Some("mermaid") => erm::exporters::mermaid::render(&diagram),
+        Some("plantuml") => erm::exporters::plantuml::render(&diagram),
+        Some("dbml") => erm::exporters::dbml::render(&diagram),
+        Some("prisma") => erm::exporters::prisma::render(&diagram),
+        Some("typescript") => erm::exporters::typescript::render(&diagram),
+        Some(format @ ("json-schema" | "openapi")) => {
+            let options = JsonSchemaOptions {
+                format: match format {
+                    "openapi" => SchemaFormat::OpenApi,
+                    _ => SchemaFormat::JsonSchema,
+                },
+                property_names: if flag(options, "--logical-names") {
+                    PropertyNames::Logical
+                } else {
+                    PropertyNames::Physical
+                },
+            };
+            erm::exporters::json_schema::render(&diagram, &options)
+        }
+        Some("dot") => erm::exporters::dot::render(&diagram, &dot_options(options)?)?,
+        // A site of several files, so the output is a directory.
+        Some("html") => {
+            let directory = option(options, "--output")?.ok_or(UsageError)?;
+            erm::exporters::html::export(&diagram, directory)?;
+            return Ok(true);
+        }
+        // Several source files, so the output is a directory too.
+        Some(format @ ("sea-orm" | "diesel")) => {
+            let directory = option(options, "--output")?.ok_or(UsageError)?;
+            let orm = Orm::parse(format).ok_or(UsageError)?;
+            erm::exporters::rust::export(&diagram, orm, directory)?;
+            return Ok(true);
+        }
+        // Binary, so these need a file.
+        Some("xlsx") => {
+            let filename = binary_output(options, &diagram, "xlsx", "excel_output", |settings| {
+                settings.excel_output.as_deref()
+            })?;
+            erm::exporters::xlsx::export(&diagram, None, filename)?;
+            return Ok(true);
+        }
+        Some("png") => {
+            let filename = binary_output(options, &diagram, "png", "image_output", |settings| {
+                settings.image_output.as_deref()
+            })?;
+            let png_options = PngOptions {
+                scale: match option(options, "--scale")? {
+                    Some(scale) => Some(
+                        scale
+                            .parse()
+                            .map_err(|_| format!("invalid --scale {scale}"))?,
+                    ),
+                    None => None,
+                },
+            };
+            erm::exporters::png::export(&diagram, &png_options, filename)?;
+            return Ok(true);
+        }
+        Some(format) => return Err(format!("unknown format {format}").into()),
+        None => return Err(UsageError.into()),
+    };
+    write_output(options, &content)?;
+    Ok(true)
+}
+
+fn stats(args: &[String]) -> Result<bool, Error> {
+    let ([filename], options) = positional(args)?;
+    let stats = erm::stats::stats(&erm::open(filename)?);
+    if flag(options, "--json") {
+        return print_json(&stats).map(|_| true);
+    }
+    println!("database       {}", stats.database);
+    println!("tables         {}", stats.tables);
+    println!("columns        {}", stats.columns);
+    println!("relationships  {}", stats.relationships);
+    println!("indexes        {}", stats.indexes);
+    println!("column groups  {}", stats.column_groups);
+    if !stats.tables_without_primary_key.is_empty() {
+        println!(
+            "without primary key  {}",
+            stats.tables_without_primary_key.join(", ")
+        );
+    }
+    println!();
+    for table in &stats.table_stats {
+        println!(
+            "{} \"{}\": {} columns, {} relationships, referenced by {}, {} indexes",
+            table.physical_name,
+            table.logical_name,
+            table.columns,
+            table.relationships,
+            table.referenced_by,
+            table.indexes
+        );
+    }
+    Ok(true)
+}
+
+fn seed(args: &[String]) -> Result<bool, Error> {
+    let ([filename], options) = positional(args)?;
+    let diagram = erm::open(filename)?;
+    let mut seed_options = SeedOptions::default();
+    if let Some(rows) = option(options, "--rows")? {
+        seed_options.rows = rows.parse().map_err(|_| format!("invalid --rows {rows}"))?;
+    }
+    if let Some(seed) = option(options, "--seed")? {
+        seed_options.seed = seed.parse().map_err(|_| format!("invalid --seed {seed}"))?;
+    }
+    let tables = erm::seed::generate(&diagram, &seed_options)?;
+    if flag(options, "--csv") {
+        // A file per table, so the output is a directory.
+        let directory = option(options, "--output")?.ok_or(UsageError)?;
+        erm::seed::export_csv(&tables, directory)?;
+    } else {
+        let dialect = dialect(options, &diagram)?;
+        write_output(options, &erm::seed::to_sql(&tables, dialect))?;
+    }
+    Ok(true)
+}
+
+fn drift(args: &[String]) -> Result<bool, Error> {
+    let ([filename], options) = positional(args)?;
+    let diagram = erm::open(filename)?;
+
+    let target = if let Some(sqlite) = option(options, "--sqlite")? {
+        DriftTarget::Sqlite(sqlite)
+    } else if let Some(ddl) = option(options, "--ddl")? {
+        DriftTarget::Ddl(ddl, dialect(options, &diagram)?)
+    } else if let Some(url) = option(options, "--database")? {
+        DriftTarget::Database(url)
+    } else {
+        return Err(UsageError.into());
+    };
+
+    let report = drift::check_target(&diagram, target)?;
+    if flag(options, "--json") {
+        print_json(&report)?;
+        return Ok(!report.has_drift());
+    }
+    for warning in &report.warnings {
+        eprintln!("warning: {warning}");
+    }
+    for drift in &report.drifts {
+        println!("{drift}");
+    }
+    Ok(!report.has_drift())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command: fn(&[String]) -> Result<bool, Error> = match args.first().map(String::as_str) {
+        Some("validate") => validate,
+        Some("ddl") => ddl,
+        Some("diff") => diff,
+        Some("export") => export,
+        Some("stats") => stats,
+        Some("seed") => seed,
+        Some("drift") => drift,
+        _ => |_| Err(UsageError.into()),
+    };
+    match command(args.get(1..).unwrap_or_default()) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) if e.is::<UsageError>() => {
+            eprintln!("{e}");
+            ExitCode::from(2)
+        }
+        Err(e) => {
+            eprintln!("erm: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use erm::diff::{DiagramDiff, DiffOptions};
+use erm::drift::{DriftReport, DriftTarget};
+use erm::importers::ddl::Dialect;
+use erm::validation::{Diagnostic, Rule, Severity};
+
+const FIXTURE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../crates/erm/tests/fixtures/testerd.erm"
+);
+
+/// A directory of its own per test, so no naming config is picked up by accident.
+fn temp_file(name: &str, file_name: &str, content: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("erm_cli_{name}"));
+    std::fs::create_dir_all(&dir).expect("failed to create");
+    let path = dir.join(file_name);
+    std::fs::write(&path, content).expect("failed to write");
+    path
+}
+
+fn fixture() -> String {
+    std::fs::read_to_string(FIXTURE).expect("failed to read")
+}
+
+/// Runs `erm` and returns its exit code and stdout.
+fn erm(args: &[&str]) -> (i32, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_erm"))
+        .args(args)
+        .output()
+        .expect("failed to run erm");
+    (
+        output.status.code().expect("killed by a signal"),
+        String::from_utf8(output.stdout).expect("not UTF-8"),
+    )
+}
+
+#[test]
+fn test_validate_clean() {
+    assert_eq!(erm(&["validate", FIXTURE]), (0, String::new()));
+    assert_eq!(erm(&["validate", FIXTURE, "--strict"]), (0, String::new()));
+}
+
+#[test]
+fn test_validate_warnings() {
+    let path = temp_file(
+        "validate_warnings",
+        "testerd.erm",
+        &fixture().replacen(
+            "<physical_name>FIRST_NAME</physical_name>",
+            "<physical_name>ORDER</physical_name>",
+            1,
+        ),
+    );
+    let path = path.to_str().unwrap();
+
+    let (code, stdout) = erm(&["validate", path]);
+    assert_eq!(code, 0);
+    assert_eq!(
+        stdout,
+        "warning[reserved-word] MEMBERS.ORDER: ORDER is a reserved word in MySQL\n"
+    );
+    // Any finding fails with --strict.
+    assert_eq!(erm(&["validate", path, "--strict"]).0, 1);
+}
+
+#[test]
+fn test_validate_errors() {
+    let content = fixture().replacen("<length>32</length>", "", 1);
+    let path = temp_file("validate_errors", "testerd.erm", &content);
+    let path = path.to_str().unwrap();
+
+    let (code, stdout) = erm(&["validate", path, "--json"]);
+    assert_eq!(code, 1);
+    let diagnostics: Vec<Diagnostic> = serde_json::from_str(&stdout).expect("not JSON");
+    assert_eq!(diagnostics, erm::validate_file(path).unwrap());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, Rule::MissingLength);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json[0]["rule"], "missingLength");
+    assert_eq!(json[0]["location"]["column"], "LAST_NAME");
+
+    // Nothing is reported while the validator is suspended.
+    let suspended = temp_file(
+        "validate_errors",
+        "suspended.erm",
+        &content.replacen(
+            "<suspend_validator>false</suspend_validator>",
+            "<suspend_validator>true</suspend_validator>",
+            1,
+        ),
+    );
+    assert_eq!(
+        erm(&["validate", suspended.to_str().unwrap(), "--json"]),
+        (0, "[]\n".to_string())
+    );
+}
+
+#[test]
+fn test_diff() {
+    assert_eq!(erm(&["diff", FIXTURE, FIXTURE]), (0, String::new()));
+
+    let new = temp_file(
+        "diff",
+        "new.erm",
+        &fixture().replacen(
+            "<physical_name>FIRST_NAME</physical_name>",
+            "<physical_name>GIVEN_NAME</physical_name>",
+            1,
+        ),
+    );
+    let new = new.to_str().unwrap();
+    let (code, stdout) = erm(&["diff", FIXTURE, new, "--json"]);
+    assert_eq!(code, 1);
+    let diff: DiagramDiff = serde_json::from_str(&stdout).expect("not JSON");
+    assert_eq!(
+        diff,
+        erm::diff_files(FIXTURE, new, &DiffOptions::default()).unwrap()
+    );
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["tables"][0]["physicalName"], "MEMBERS");
+}
+
+#[test]
+fn test_drift() {
+    let ddl = temp_file("drift", "schema.sql", "");
+    let ddl = ddl.to_str().unwrap();
+    assert_eq!(erm(&["ddl", FIXTURE, "--output", ddl]).0, 0);
+    assert_eq!(erm(&["drift", FIXTURE, "--ddl", ddl]), (0, String::new()));
+
+    let schema = std::fs::read_to_string(ddl).unwrap();
+    std::fs::write(ddl, schema.replace("FIRST_NAME", "GIVEN_NAME")).unwrap();
+    let (code, stdout) = erm(&["drift", FIXTURE, "--ddl", ddl, "--json"]);
+    assert_eq!(code, 1);
+    let report: DriftReport = serde_json::from_str(&stdout).expect("not JSON");
+    assert_eq!(
+        report,
+        erm::check_drift(FIXTURE, DriftTarget::Ddl(ddl, Dialect::MySql)).unwrap()
+    );
+    assert!(report.has_drift());
+}
+
+#[test]
+fn test_errors_exit_with_status_2() {
+    assert_eq!(erm(&[]).0, 2);
+    assert_eq!(erm(&["validate"]).0, 2);
+    assert_eq!(erm(&["validate", "missing.erm"]).0, 2);
+    assert_eq!(erm(&["drift", FIXTURE]).0, 2);
+}
@@ -0,0 +1,43 @@
+//! Git merge driver for .erm files.
+//!
+//! Register it in `.gitattributes` and the git config:
+//!
+//! ```text
+//! *.erm merge=erm
+//!
+//! [merge "erm"]
+//!     name = ersflute diagram merge
+//!     driver = erm-merge %O %A %B
+//! ```
+//!
+//! The merged diagram is written to `%A`, keeping the sections of ours that ersflute
+//! does not read. Conflicts are printed to stderr and make the driver exit with a
+//! non-zero status so git keeps the file marked as conflicted.
+
+use std::env;
+use std::process::ExitCode;
+
+fn run(base: &str, ours: &str, theirs: &str) -> Result<bool, Box<dyn std::error::Error>> {
+    let result = erm::merge_files(base, ours, theirs)?;
+    erm::update(ours, &result.diagram)?;
+    for conflict in &result.conflicts {
+        eprintln!("CONFLICT {}: {}", conflict.path, conflict.message);
+    }
+    Ok(!result.has_conflicts())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+    let [_, base, ours, theirs] = args.as_slice() else {
+        eprintln!("usage: erm-merge <base> <ours> <theirs>");
+        return ExitCode::from(2);
+    };
+    match run(base, ours, theirs) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("erm-merge: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
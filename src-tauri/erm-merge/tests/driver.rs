@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+const FIXTURE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../crates/erm/tests/fixtures/testerd.erm"
+);
+
+fn temp_file(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("erm_merge_driver_{name}.erm"));
+    std::fs::write(&path, content).expect("failed to write");
+    path
+}
+
+fn merge(name: &str, base: &str, ours: &str, theirs: &str) -> (bool, String) {
+    let paths = [
+        temp_file(&format!("{name}_base"), base),
+        temp_file(&format!("{name}_ours"), ours),
+        temp_file(&format!("{name}_theirs"), theirs),
+    ];
+    let status = Command::new(env!("CARGO_BIN_EXE_erm-merge"))
+        .args(&paths)
+        .status()
+        .expect("failed to run erm-merge");
+    let merged = std::fs::read_to_string(&paths[1]).expect("failed to read");
+    for path in &paths {
+        std::fs::remove_file(path).ok();
+    }
+    (status.success(), merged)
+}
+
+#[test]
+fn test_merge_unchanged_keeps_the_file() {
+    let fixture = std::fs::read_to_string(FIXTURE).expect("failed to read");
+    let (success, merged) = merge("unchanged", &fixture, &fixture, &fixture);
+    assert!(success);
+    assert_eq!(merged, fixture);
+}
+
+#[test]
+fn test_merge_takes_their_changes_and_keeps_the_rest() {
+    let fixture = std::fs::read_to_string(FIXTURE).expect("failed to read");
+    let theirs =
+        fixture
+            .replacen("<x>160</x>", "<x>170</x>", 1)
+            .replacen("<y>106</y>", "<y>116</y>", 1);
+    assert_ne!(theirs, fixture);
+    let (success, merged) = merge("theirs", &fixture, &fixture, &theirs);
+    assert!(success);
+    // With ours unchanged, the merge is theirs, unmodeled sections included.
+    assert_eq!(merged, theirs);
+}
@@ -0,0 +1,31 @@
+//! Git textconv driver printing a readable rendering of an .erm file.
+//!
+//! Register it in `.gitattributes` and the git config:
+//!
+//! ```text
+//! *.erm diff=erm
+//!
+//! [diff "erm"]
+//!     textconv = erm-textconv
+//! ```
+
+use std::env;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+    let [_, filename] = args.as_slice() else {
+        eprintln!("usage: erm-textconv <file>");
+        return ExitCode::from(2);
+    };
+    match erm::open(filename) {
+        Ok(diagram) => {
+            print!("{}", erm::exporters::text::render(&diagram));
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("erm-textconv: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -1,10 +1,15 @@
 type NormalColumn = {
   physicalName: string;
   logicalName?: string;
+  description?: string;
   columnType: string;
+  length?: number;
+  decimal?: number;
   notNull?: boolean;
   uniqueKey?: boolean;
   unsigned?: boolean;
+  args?: string;
+  defaultValue?: string;
 };
 
 type Columns = {
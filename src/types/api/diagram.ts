@@ -1,7 +1,15 @@
 import type { ColumnGroupsResponse } from "./columnGroups";
+import type { DiagramSettingsResponse } from "./diagramSettings";
 import type { DiagramWalkersResponse } from "./diagramWalkers";
+import type { DictionaryResponse } from "./dictionary";
+import type { PageSettingsResponse } from "./pageSettings";
+import type { VDiagramResponse } from "./vdiagrams";
 
 export type DiagramResponse = {
+  pageSettings?: PageSettingsResponse;
+  diagramSettings: DiagramSettingsResponse;
+  dictionary: DictionaryResponse;
   diagramWalkers: DiagramWalkersResponse;
+  vdiagrams: VDiagramResponse[];
   columnGroups: ColumnGroupsResponse;
 };
@@ -0,0 +1,27 @@
+type ExportSettings = {
+  categoryNameToExport?: string;
+  ddlOutput?: string;
+  excelOutput?: string;
+  imageOutput?: string;
+  putDiagramOnExcel?: boolean;
+  useLogicalNameAsSheet?: boolean;
+};
+
+type Category = {
+  name: string;
+  nodeElements: string[];
+  selected?: boolean;
+};
+
+type CategorySettings = {
+  categories: Category[];
+};
+
+export type DiagramSettingsResponse = {
+  database: string;
+  notation?: string;
+  validatePhysicalName?: boolean;
+  suspendValidator?: boolean;
+  exportSettings?: ExportSettings;
+  categorySettings?: CategorySettings;
+};
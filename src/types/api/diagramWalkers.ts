@@ -38,6 +38,7 @@ type NormalColumn = {
   length?: number;
   decimal?: number;
   unsigned?: boolean;
+  args?: string;
   notNull?: boolean;
   uniqueKey?: boolean;
   defaultValue?: string;
@@ -51,6 +52,37 @@ type Columns = {
   items?: (NormalColumn | string)[];
 };
 
+type IndexColumn = {
+  columnId: string;
+  desc?: boolean;
+};
+
+type IndexColumns = {
+  column: IndexColumn[];
+};
+
+type Index = {
+  name: string;
+  indexType?: string;
+  description?: string;
+  fullText?: boolean;
+  nonUnique?: boolean;
+  columns: IndexColumns;
+};
+
+type Indexes = {
+  indexes?: Index[];
+};
+
+type CompoundUniqueKey = {
+  name: string;
+  columns: IndexColumns;
+};
+
+type CompoundUniqueKeyList = {
+  compoundUniqueKeys?: CompoundUniqueKey[];
+};
+
 export type TableResponse = {
   physicalName: string;
   logicalName: string;
@@ -67,8 +99,35 @@ export type TableResponse = {
   primaryKeyName?: string;
   option?: string;
   columns: Columns;
+  indexes?: Indexes;
+  compoundUniqueKeyList?: CompoundUniqueKeyList;
+};
+
+export type NoteResponse = {
+  noteText: string;
+  height: number;
+  width: number;
+  fontName: string;
+  fontSize: number;
+  x: number;
+  y: number;
+  color: Color;
+};
+
+export type WalkerGroupResponse = {
+  groupName: string;
+  height: number;
+  width: number;
+  fontName: string;
+  fontSize: number;
+  x: number;
+  y: number;
+  color: Color;
+  walkers: string[];
 };
 
 export type DiagramWalkersResponse = {
   tables?: TableResponse[];
+  notes?: NoteResponse[];
+  groups?: WalkerGroupResponse[];
 };
@@ -0,0 +1,9 @@
+type Word = {
+  physicalName: string;
+  logicalName: string;
+  description?: string;
+};
+
+export type DictionaryResponse = {
+  words: Word[];
+};
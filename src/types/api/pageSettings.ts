@@ -0,0 +1,9 @@
+export type PageSettingsResponse = {
+  directionHorizontal?: boolean;
+  scale?: number;
+  paperSize?: string;
+  topMargin?: number;
+  leftMargin?: number;
+  bottomMargin?: number;
+  rightMargin?: number;
+};
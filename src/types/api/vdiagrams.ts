@@ -0,0 +1,16 @@
+import type { NoteResponse, WalkerGroupResponse } from "./diagramWalkers";
+
+type VTable = {
+  tableId: string;
+  x: number;
+  y: number;
+  fontName: string;
+  fontSize: number;
+};
+
+export type VDiagramResponse = {
+  name: string;
+  tables: VTable[];
+  notes: NoteResponse[];
+  groups: WalkerGroupResponse[];
+};